@@ -453,6 +453,62 @@ fn bench_memory_usage(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_large_file(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large_file");
+    group.sample_size(20);
+    
+    // Roughly 30k lines, the size of an aggregated API definition
+    let aggregated_proto = generate_aggregated_proto(1000);
+    
+    group.bench_function("aggregated_proto_parsing", |b| {
+        let parser = NomProtoParser::new();
+        b.iter(|| {
+            let result = parser.parse_content(black_box(&aggregated_proto));
+            black_box(result.unwrap())
+        })
+    });
+    
+    group.finish();
+}
+
+fn generate_aggregated_proto(num_blocks: usize) -> String {
+    let mut proto = String::from("syntax = \"proto3\";\n\npackage aggregated.v1;\n\nimport \"google/api/annotations.proto\";\n");
+    
+    for i in 0..num_blocks {
+        proto.push_str(&format!(r#"
+// Service {i} handles items.
+service Service{i} {{
+    // Get an item.
+    rpc GetItem{i}(GetItem{i}Request) returns (Item{i}) {{
+        option (google.api.http) = {{
+            get: "/v1/service{i}/items/{{item_id}}"
+        }};
+    }}
+}}
+
+/* Request for an item. */
+message GetItem{i}Request {{
+    // The id.
+    string item_id = 1;
+    repeated string tags = 2 [deprecated = true];
+    map<string, int32> counts = 3;
+}}
+
+message Item{i} {{
+    string id = 1;
+    string name = 2;
+    Status status = 3;
+    enum Status {{
+        STATUS_UNSPECIFIED = 0;
+        STATUS_OK = 1;
+    }}
+}}
+"#));
+    }
+    
+    proto
+}
+
 fn generate_large_proto(num_services: usize) -> String {
     let mut proto = String::new();
     proto.push_str(r#"
@@ -531,7 +587,8 @@ criterion_group!(
         bench_http_extraction,
         bench_code_generation,
         bench_end_to_end,
-        bench_memory_usage
+        bench_memory_usage,
+        bench_large_file
 );
 
 criterion_main!(benches);
\ No newline at end of file
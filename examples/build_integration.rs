@@ -1,7 +1,6 @@
 //! Build.rs integration examples
 
 use proto_http_parser::*;
-use std::path::PathBuf;
 
 fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     println!("Proto HTTP Parser v2 - Build Integration Examples");
@@ -83,7 +82,7 @@ infer_query_params = true
     
     let result3 = BuildIntegration::new()
         .with_env_config()
-        .add_proto_glob(&format!("{}/*.proto", proto_dir.display()))?
+        .add_proto_glob(format!("{}/*.proto", proto_dir.display()))?
         .output_dir(&output_dir3)
         .verbose(true)
         .generate()?;
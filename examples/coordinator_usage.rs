@@ -127,7 +127,7 @@ enum Status {
     }
     
     // Display generated files
-    for (filename, _) in &result.generated_files {
+    for filename in result.generated_files.keys() {
         println!("  Generated: {}", filename);
     }
     
@@ -1,7 +1,6 @@
 //! Custom configuration example demonstrating advanced configuration options
 
 use proto_http_parser::*;
-use std::collections::HashMap;
 
 fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     println!("Proto HTTP Parser v2 - Custom Configuration Example");
@@ -199,7 +198,7 @@ enum ResourceType {
     println!("\n=== Example 5: Template Customization ===");
     
     // Create custom template content
-    let custom_controller_template = r#"
+    let _custom_controller_template = r#"
 //! Custom controller template for {{service_name}}
 //! Generated with custom formatting
 
@@ -308,7 +308,7 @@ enum OrderStatus {
     for route in all_routes {
         routes_by_service
             .entry(route.service_name.clone())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(route);
     }
     
@@ -35,7 +35,7 @@ fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     
     let start = Instant::now();
     let coordinator = ProtoHttpCoordinator::with_config(speed_config);
-    let result = coordinator.process_content(&proto_content)?;
+    let _result = coordinator.process_content(&proto_content)?;
     let optimized_duration = start.elapsed();
     
     println!("Optimized processing time: {:?}", optimized_duration);
@@ -61,7 +61,7 @@ fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     // Example 4: Batch processing performance
     println!("\n=== Example 4: Batch Processing Performance ===");
     
-    let small_protos = (0..10).map(|i| generate_test_proto(5)).collect::<Vec<_>>();
+    let small_protos = (0..10).map(|_i| generate_test_proto(5)).collect::<Vec<_>>();
     
     // Sequential processing
     let start = Instant::now();
@@ -106,7 +106,7 @@ fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     let extract_duration = start.elapsed();
     
     // Code generation only
-    let start = Instant::now();
+    let _start = Instant::now();
     let generator = PoemOpenApiGenerator::new();
     let mut generation_duration = Duration::ZERO;
     
@@ -195,7 +195,7 @@ fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     println!("6. Process multiple files in parallel when possible");
     
     // Performance configuration example
-    let production_config = ConfigBuilder::new()
+    let _production_config = ConfigBuilder::new()
         .preserve_comments(true)       // Keep for documentation
         .use_rustfmt(true)            // Format for production
         .strict_validation(true)       // Validate thoroughly
@@ -203,7 +203,7 @@ fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
         .use_dependency_injection(true)
         .build()?;
     
-    let development_config = ConfigBuilder::new()
+    let _development_config = ConfigBuilder::new()
         .preserve_comments(false)      // Skip for speed
         .use_rustfmt(false)           // Skip for speed
         .strict_validation(false)      // Skip for speed
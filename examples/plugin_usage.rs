@@ -236,6 +236,8 @@ fn create_mock_proto_file() -> ProtoFile {
                             body: None,
                             additional_bindings: vec![],
                         }),
+                        idempotency_level: None,
+                        method_signature: vec![],
                     },
                     RpcMethod {
                         name: "badMethodName".to_string(), // Bad naming convention
@@ -257,10 +259,13 @@ fn create_mock_proto_file() -> ProtoFile {
                             body: Some("*".to_string()),
                             additional_bindings: vec![],
                         }),
+                        idempotency_level: None,
+                        method_signature: vec![],
                     },
                 ],
                 options: vec![],
                 comments: vec![],
+                source: None,
             },
         ],
         messages: vec![
@@ -271,6 +276,10 @@ fn create_mock_proto_file() -> ProtoFile {
                 nested_enums: vec![],
                 options: vec![],
                 comments: vec![],
+                is_group: false,
+                reserved_ranges: vec![],
+                reserved_names: vec![],
+                source: None,
             },
             Message {
                 name: "badMessageName".to_string(), // Bad naming convention
@@ -279,9 +288,14 @@ fn create_mock_proto_file() -> ProtoFile {
                 nested_enums: vec![],
                 options: vec![],
                 comments: vec![],
+                is_group: false,
+                reserved_ranges: vec![],
+                reserved_names: vec![],
+                source: None,
             },
         ],
         enums: vec![],
+        unknown_statements: vec![],
     }
 }
 
@@ -314,6 +328,12 @@ fn create_mock_http_routes() -> Vec<HttpRoute> {
                 package: None,
                 is_stream: false,
             },
+            idempotency_level: None,
+            is_paginated: false,
+            deprecation: None,
+            segments: Vec::new(),
+            rate_limit: None,
+            security: None,
         },
         HttpRoute {
             service_name: "UserService".to_string(),
@@ -333,6 +353,12 @@ fn create_mock_http_routes() -> Vec<HttpRoute> {
                 package: None,
                 is_stream: false,
             },
+            idempotency_level: None,
+            is_paginated: false,
+            deprecation: None,
+            segments: Vec::new(),
+            rate_limit: None,
+            security: None,
         },
     ]
 }
\ No newline at end of file
@@ -3,6 +3,9 @@
 //! This file shows various ways to integrate proto-http-parser-v2 into your build process.
 //! Copy the relevant sections to your own build.rs file.
 
+// The feature flags referenced below belong to the consuming crate, not this one.
+#![allow(unexpected_cfgs)]
+
 use proto_http_parser::*;
 
 fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
@@ -0,0 +1,89 @@
+//! Streaming example demonstrating server-sent event endpoints for server-streaming methods
+
+use proto_http_parser::*;
+
+fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    println!("Proto HTTP Parser v2 - Streaming Example");
+
+    // A service with one unary and one server-streaming method
+    let proto_content = r#"
+syntax = "proto3";
+
+package events.v1;
+
+import "google/api/annotations.proto";
+
+// Event notification service
+service EventService {
+    // Get a single event
+    rpc GetEvent(GetEventRequest) returns (Event) {
+        option (google.api.http) = {
+            get: "/v1/events/{event_id}"
+        };
+    }
+
+    // Watch events on a topic as they happen
+    rpc WatchEvents(WatchEventsRequest) returns (stream Event) {
+        option (google.api.http) = {
+            get: "/v1/topics/{topic}/events:watch"
+        };
+    }
+}
+
+message GetEventRequest {
+    string event_id = 1;
+}
+
+message WatchEventsRequest {
+    string topic = 1;
+}
+
+message Event {
+    string id = 1;
+    string topic = 2;
+    string payload = 3;
+}
+"#;
+
+    // Step 1: Process the proto content end-to-end
+    println!("\n=== Step 1: Processing Protocol Buffer Content ===");
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content)?;
+
+    for route in &result.routes {
+        println!("  {} {} -> {}::{}{}",
+            route.http_method.as_str(),
+            route.path_template,
+            route.service_name,
+            route.method_name,
+            if route.response_type.is_stream { " (server-sent events)" } else { "" }
+        );
+    }
+
+    // Step 2: Show the streaming endpoint in the generated controller
+    println!("\n=== Step 2: Generated Streaming Controller Method ===");
+    let controller = &result.generated_files["event_service_controller.rs"];
+    print_method(&controller.content, "async fn watch_events");
+    println!("  Dependencies: {:?}", controller.dependencies);
+
+    // Step 3: Show the streaming method in the generated service trait
+    println!("\n=== Step 3: Generated Streaming Service Trait Method ===");
+    let service_trait = &result.generated_files["event_service_service.rs"];
+    print_method(&service_trait.content, "async fn watch_events");
+
+    println!("\nImplement the trait method by returning a boxed stream, for example:");
+    println!("  Ok(futures::StreamExt::boxed(futures::stream::iter(events.into_iter().map(Ok))))");
+    println!("The item type must implement poem-openapi's `Type` and `ToJSON` traits.");
+
+    Ok(())
+}
+
+/// Print the lines of a generated method starting at the given signature
+fn print_method(content: &str, signature: &str) {
+    let lines: Vec<&str> = content.lines().collect();
+    if let Some(start) = lines.iter().position(|line| line.contains(signature)) {
+        for line in lines.iter().skip(start).take_while(|line| !line.trim().is_empty()) {
+            println!("  {}", line);
+        }
+    }
+}
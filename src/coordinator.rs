@@ -6,12 +6,12 @@
 use crate::core::*;
 use crate::parser::NomProtoParser;
 use crate::extractor::GoogleApiHttpExtractor;
-use crate::generator::PoemOpenApiGenerator;
-use crate::validation::ValidationEngine;
-use crate::error_reporter::ErrorReporter;
-use crate::plugins::PluginManager;
+use crate::generator::{DynCodeGenerator, GeneratorRegistry};
+use crate::validation::{ValidationConfig, ValidationEngine};
+use crate::plugins::{Plugin, PluginManager};
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 /// Main coordinator for the proto-http-parser-v2 library
 /// 
@@ -22,9 +22,8 @@ pub struct ProtoHttpCoordinator {
     config: ProtoHttpParserConfig,
     parser: NomProtoParser,
     extractor: GoogleApiHttpExtractor,
-    generator: PoemOpenApiGenerator,
+    generators: GeneratorRegistry,
     validator: ValidationEngine,
-    error_reporter: ErrorReporter,
     plugin_manager: PluginManager,
 }
 
@@ -39,18 +38,16 @@ impl ProtoHttpCoordinator {
     pub fn with_config(config: ProtoHttpParserConfig) -> Self {
         let parser = NomProtoParser::with_config(config.parser.clone());
         let extractor = GoogleApiHttpExtractor::with_config(config.extractor.clone());
-        let generator = PoemOpenApiGenerator::with_config(config.generator.clone());
-        let validator = ValidationEngine::new();
-        let error_reporter = ErrorReporter::new();
+        let generators = GeneratorRegistry::with_builtin(&config.generator);
+        let validator = ValidationEngine::with_config(with_external_types(ValidationConfig::default(), &config.generator));
         let plugin_manager = PluginManager::new();
         
         Self {
             config,
             parser,
             extractor,
-            generator,
+            generators,
             validator,
-            error_reporter,
             plugin_manager,
         }
     }
@@ -60,23 +57,112 @@ impl ProtoHttpCoordinator {
         let proto_path = proto_path.as_ref();
         
         // Step 1: Parse the proto file
+        let parse_start = Instant::now();
         let proto_file = self.parser.parse_file(proto_path)
-            .map_err(|e| ProtoHttpParserError::Parse(e))?;
+            .map_err(ProtoHttpParserError::Parse)?;
+        self.check_recovered_errors()?;
         
-        // Step 2: Validate the parsed proto file
-        self.validator.validate_proto_file(&proto_file)
-            .map_err(|e| ProtoHttpParserError::Validation(e))?;
+        self.process_parsed(Some(proto_path), proto_file, parse_start)
+    }
+    
+    /// Process proto content from a string and generate code
+    pub fn process_content(&self, content: &str) -> Result<ProcessResult, ProtoHttpParserError> {
+        // Step 1: Parse the proto content
+        let parse_start = Instant::now();
+        let proto_file = self.parser.parse_content(content)
+            .map_err(ProtoHttpParserError::Parse)?;
+        self.check_recovered_errors()?;
+        
+        self.process_parsed(None, proto_file, parse_start)
+    }
+    
+    /// Process proto content from a string as if it had been read from `virtual_path`
+    ///
+    /// Useful for editors processing unsaved buffers: the result is associated with
+    /// `virtual_path` exactly as [`Self::process_file`] would associate it with a real
+    /// file, but nothing is read from disk. Generated files are named after the virtual
+    /// path, placed in a directory named after its file stem as
+    /// [`Self::write_batch_results`] does, e.g. `orders/user_service_controller.rs` for
+    /// `orders.proto`.
+    pub fn process_content_named<P: AsRef<Path>>(&self, content: &str, virtual_path: P) -> Result<ProcessResult, ProtoHttpParserError> {
+        let virtual_path = virtual_path.as_ref();
+        
+        // Step 1: Parse the proto content
+        let parse_start = Instant::now();
+        let proto_file = self.parser.parse_content(content)
+            .map_err(ProtoHttpParserError::Parse)?;
+        self.check_recovered_errors()?;
+        
+        let mut result = self.process_parsed(Some(virtual_path), proto_file, parse_start)?;
+        if let Some(proto_name) = virtual_path.file_stem().and_then(|stem| stem.to_str()) {
+            result.generated_files = result.generated_files.into_iter()
+                .map(|(filename, code)| (format!("{}/{}", proto_name, filename), code))
+                .collect();
+        }
+        
+        Ok(result)
+    }
+    
+    /// Fail with the first declaration skipped by a recovering parse
+    ///
+    /// Generating code from the remaining declarations would silently drop the skipped
+    /// ones; [`Self::validate_directory`] reports all of them instead.
+    fn check_recovered_errors(&self) -> Result<(), ProtoHttpParserError> {
+        match self.parser.take_recovered_errors().into_iter().next() {
+            Some(error) => Err(ProtoHttpParserError::Parse(error)),
+            None => Ok(()),
+        }
+    }
+    
+    /// Run validation, route extraction and code generation on a parsed proto file
+    ///
+    /// `parse_start` marks when parsing began; import resolution is counted
+    /// towards the parse phase.
+    fn process_parsed(&self, source_path: Option<&Path>, proto_file: ProtoFile, parse_start: Instant) -> Result<ProcessResult, ProtoHttpParserError> {
+        let mut metrics = ProcessMetrics::default();
+        let file_name = source_path
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "<content>".to_string());
+        
+        // Step 1.1: Collect imported files so cross-file type references resolve
+        let imported_files = self.parser.parse_imported_files_from(&proto_file, source_path)
+            .map_err(ProtoHttpParserError::Parse)?;
+        
+        let mut type_registry = TypeRegistry::new();
+        type_registry.register_file(&file_name, &proto_file);
+        for (import_path, imported_file) in &imported_files {
+            type_registry.register_file(&import_path.to_string_lossy(), imported_file);
+        }
+        
+        let resolution_file = proto_file.with_imported_definitions(
+            imported_files.iter().map(|(_, imported_file)| imported_file)
+        );
+        let imports: Vec<ProtoFile> = imported_files.into_iter().map(|(_, imported_file)| imported_file).collect();
+        
+        metrics.parse = parse_start.elapsed();
+        
+        // Step 2: Validate the parsed proto file; its imports only resolve type references
+        let validate_start = Instant::now();
+        let report = self.validator.validate_proto_file_with_imports(&proto_file, &imports)
+            .map_err(ProtoHttpParserError::Validation)?;
+        
+        if !report.errors.is_empty() {
+            return Err(self.validation_failure(report.errors));
+        }
         
         // Step 2.1: Run plugin validators
         let plugin_validation_errors = self.plugin_manager.validate_proto_file(&proto_file)
-            .map_err(|e| ProtoHttpParserError::Plugin(e))?;
+            .map_err(ProtoHttpParserError::Plugin)?;
         
         if !plugin_validation_errors.is_empty() {
-            return Err(ProtoHttpParserError::Validation(plugin_validation_errors[0].clone()));
+            return Err(self.validation_failure(plugin_validation_errors));
         }
         
+        metrics.validate = validate_start.elapsed();
+        
         // Step 3: Extract HTTP routes
-        let routes = self.extractor.extract_routes(&proto_file)
+        let extract_start = Instant::now();
+        let routes = self.extractor.extract_routes(&resolution_file)
             .map_err(|e| ProtoHttpParserError::Validation(ValidationError::HttpAnnotationError {
                 message: format!("Failed to extract HTTP routes: {}", e),
             }))?;
@@ -89,13 +175,19 @@ impl ProtoHttpCoordinator {
         
         // Step 4.1: Run plugin HTTP validators
         let plugin_http_errors = self.plugin_manager.validate_http_routes(&routes)
-            .map_err(|e| ProtoHttpParserError::Plugin(e))?;
+            .map_err(ProtoHttpParserError::Plugin)?;
         
         if !plugin_http_errors.is_empty() {
             return Err(ProtoHttpParserError::Validation(plugin_http_errors[0].clone()));
         }
         
+        metrics.extract = extract_start.elapsed();
+        
         // Step 5: Generate code for each service
+        let generate_start = Instant::now();
+        let generator = self.active_generator()?;
+        generator.check_models(&resolution_file, &routes)
+            .map_err(ProtoHttpParserError::CodeGeneration)?;
         let mut generated_files = HashMap::new();
         
         for service in &proto_file.services {
@@ -106,111 +198,257 @@ impl ProtoHttpCoordinator {
                 .collect();
             
             // Generate controller code
-            let controller_code = self.generator.generate_controller(service, &service_routes)
-                .map_err(|e| ProtoHttpParserError::CodeGeneration(e))?;
+            let controller_code = generator.generate_controller(service, &service_routes)
+                .map_err(ProtoHttpParserError::CodeGeneration)?;
             
-            let controller_filename = format!("{}_controller.rs", to_snake_case(&service.name));
+            let controller_filename = format!("{}_{}.rs", self.snake_case(&service.name), self.config.generator.naming.controller_module_suffix);
             generated_files.insert(controller_filename, controller_code.clone());
             
+            // Generate the parts of a controller split across files
+            let parts = generator.generate_controller_parts(service, &service_routes)
+                .map_err(ProtoHttpParserError::CodeGeneration)?;
+            for (index, part_code) in parts.into_iter().enumerate() {
+                let part_filename = format!("{}_{}_{}.rs", self.snake_case(&service.name), self.config.generator.naming.controller_module_suffix, index + 1);
+                generated_files.insert(part_filename, part_code);
+            }
+            
             // Generate service trait if configured
             if self.config.generator.generate_service_traits {
-                let service_trait_code = self.generator.generate_service_trait(service, &service_routes)
-                    .map_err(|e| ProtoHttpParserError::CodeGeneration(e))?;
+                let service_trait_code = generator.generate_service_trait(service, &service_routes)
+                    .map_err(ProtoHttpParserError::CodeGeneration)?;
                 
-                let trait_filename = format!("{}_service.rs", to_snake_case(&service.name));
+                let trait_filename = format!("{}_{}.rs", self.snake_case(&service.name), self.config.generator.naming.trait_module_suffix);
                 generated_files.insert(trait_filename, service_trait_code);
             }
+            
+            // Generate a mock service implementation if the generator emits one
+            if let Some(mock_code) = generator.generate_mock(service, &service_routes)
+                .map_err(ProtoHttpParserError::CodeGeneration)?
+            {
+                let mock_filename = format!("{}_mock.rs", self.snake_case(&service.name));
+                generated_files.insert(mock_filename, mock_code);
+            }
+            
+            // Generate integration test scaffolding if the generator emits it
+            if let Some(tests_code) = generator.generate_tests(service, &service_routes, &resolution_file)
+                .map_err(ProtoHttpParserError::CodeGeneration)?
+            {
+                let tests_filename = format!("{}_tests.rs", self.snake_case(&service.name));
+                generated_files.insert(tests_filename, tests_code);
+            }
         }
         
-        Ok(ProcessResult {
-            proto_file,
-            routes,
-            generated_files,
-        })
-    }
-    
-    /// Process proto content from a string and generate code
-    pub fn process_content(&self, content: &str) -> Result<ProcessResult, ProtoHttpParserError> {
-        // Step 1: Parse the proto content
-        let proto_file = self.parser.parse_content(content)
-            .map_err(|e| ProtoHttpParserError::Parse(e))?;
+        // Generate the shared tags enum if the generator emits one
+        if let Some(tags_code) = generator.generate_tags_enum(&proto_file.services)
+            .map_err(ProtoHttpParserError::CodeGeneration)?
+        {
+            generated_files.insert(TAGS_FILENAME.to_string(), tags_code);
+        }
         
-        // Step 2: Validate the parsed proto file
-        self.validator.validate_proto_file(&proto_file)
-            .map_err(|e| ProtoHttpParserError::Validation(e))?;
+        // Generate the controller registration helper if the generator emits one
+        if let Some(registration_code) = generator.generate_registration_helper(&proto_file.services)
+            .map_err(ProtoHttpParserError::CodeGeneration)?
+        {
+            generated_files.insert(REGISTRATION_FILENAME.to_string(), registration_code);
+        }
         
-        // Step 2.1: Run plugin validators
-        let plugin_validation_errors = self.plugin_manager.validate_proto_file(&proto_file)
-            .map_err(|e| ProtoHttpParserError::Plugin(e))?;
+        // Generate the API metadata module if the generator emits one
+        let api_metadata = proto_file.api_metadata();
+        if let Some(metadata_code) = generator.generate_api_metadata(&api_metadata)
+            .map_err(ProtoHttpParserError::CodeGeneration)?
+        {
+            generated_files.insert(API_METADATA_FILENAME.to_string(), metadata_code);
+        }
         
-        if !plugin_validation_errors.is_empty() {
-            return Err(ProtoHttpParserError::Validation(plugin_validation_errors[0].clone()));
+        // Generate the shared errors module if the generator emits one
+        if let Some(errors_code) = generator.generate_error_module(&proto_file.services)
+            .map_err(ProtoHttpParserError::CodeGeneration)?
+        {
+            generated_files.insert(ERRORS_FILENAME.to_string(), errors_code);
         }
         
-        // Step 3: Extract HTTP routes
-        let routes = self.extractor.extract_routes(&proto_file)
-            .map_err(|e| ProtoHttpParserError::Validation(ValidationError::HttpAnnotationError {
-                message: format!("Failed to extract HTTP routes: {}", e),
-            }))?;
+        // Generate the route path builders if the generator emits them
+        if let Some(paths_code) = generator.generate_path_builders(&routes)
+            .map_err(ProtoHttpParserError::CodeGeneration)?
+        {
+            generated_files.insert(PATHS_FILENAME.to_string(), paths_code);
+        }
         
-        // Step 4: Validate HTTP annotations
-        self.extractor.validate_annotations(&routes)
-            .map_err(|e| ProtoHttpParserError::Validation(ValidationError::HttpAnnotationError {
-                message: format!("HTTP annotation validation failed: {}", e),
-            }))?;
+        // Generate the per-path CORS metadata if the generator emits it
+        if let Some(cors_code) = generator.generate_cors_config(&routes)
+            .map_err(ProtoHttpParserError::CodeGeneration)?
+        {
+            generated_files.insert(CORS_FILENAME.to_string(), cors_code);
+        }
         
-        // Step 4.1: Run plugin HTTP validators
-        let plugin_http_errors = self.plugin_manager.validate_http_routes(&routes)
-            .map_err(|e| ProtoHttpParserError::Plugin(e))?;
+        // Generate the route inventory entry type if the generator emits it
+        if let Some(route_info_code) = generator.generate_route_info(&routes)
+            .map_err(ProtoHttpParserError::CodeGeneration)?
+        {
+            generated_files.insert(ROUTE_INFO_FILENAME.to_string(), route_info_code);
+        }
         
-        if !plugin_http_errors.is_empty() {
-            return Err(ProtoHttpParserError::Validation(plugin_http_errors[0].clone()));
+        // Generate the prelude last so it re-exports every other generated file
+        if let Some(prelude_code) = generator.generate_prelude(&proto_file, &generated_files)
+            .map_err(ProtoHttpParserError::CodeGeneration)?
+        {
+            generated_files.insert(PRELUDE_FILENAME.to_string(), prelude_code);
         }
         
-        // Step 5: Generate code for each service
-        let mut generated_files = HashMap::new();
+        if let Some(package) = proto_file.package.as_deref().filter(|_| self.config.generator.package_to_module) {
+            generated_files = nest_in_package_module(generated_files, package, &self.config.generator.models_module_path);
+        }
         
-        for service in &proto_file.services {
-            // Filter routes for this service
-            let service_routes: Vec<HttpRoute> = routes.iter()
-                .filter(|route| route.service_name == service.name)
-                .cloned()
+        metrics.generate = generate_start.elapsed();
+        
+        Ok(ProcessResult {
+            source_path: source_path.map(Path::to_path_buf),
+            proto_file,
+            routes,
+            generated_files,
+            type_registry,
+            api_metadata,
+            metrics: self.config.collect_metrics.then_some(metrics),
+        })
+    }
+    
+    /// Generate the service-wide files once for the files of a batch written together
+    ///
+    /// The tags enum, errors module, registration helper and prelude are generated per
+    /// proto file, so protos written into one directory would each bring their own
+    /// `tags.rs`, `errors.rs`, `apis.rs` and `prelude.rs`. Files sharing a package module
+    /// directory (or all files, without `package_to_module`) get one of each covering all
+    /// their services and generated files instead, keyed by the file name they replace.
+    fn batch_shared_files(&self, batch_result: &BatchProcessResult) -> Result<HashMap<String, GeneratedCode>, ProtoHttpParserError> {
+        let generator = self.active_generator()?;
+        let mut proto_paths: Vec<&PathBuf> = batch_result.results.keys().collect();
+        proto_paths.sort();
+        
+        // Results grouped by the package whose module directory they are written to
+        let mut groups: Vec<(Option<&str>, Vec<&ProcessResult>)> = Vec::new();
+        for proto_path in proto_paths {
+            let result = &batch_result.results[proto_path];
+            let package = result.proto_file.package.as_deref().filter(|_| self.config.generator.package_to_module);
+            match groups.iter_mut().find(|(group_package, _)| *group_package == package) {
+                Some((_, results)) => results.push(result),
+                None => groups.push((package, vec![result])),
+            }
+        }
+        
+        let mut shared_files = HashMap::new();
+        for (package, results) in groups {
+            let prefix = package
+                .map(|package| format!("{}/", package.split('.').map(to_snake_case).collect::<Vec<_>>().join("/")))
+                .unwrap_or_default();
+            let services: Vec<Service> = results.iter()
+                .flat_map(|result| result.proto_file.services.iter().cloned())
                 .collect();
             
-            // Generate controller code
-            let controller_code = self.generator.generate_controller(service, &service_routes)
-                .map_err(|e| ProtoHttpParserError::CodeGeneration(e))?;
+            // The group's other generated files, named as before package nesting
+            let mut generated_files: HashMap<String, GeneratedCode> = results.iter()
+                .flat_map(|result| &result.generated_files)
+                .filter_map(|(filename, generated_code)| {
+                    let filename = filename.strip_prefix(prefix.as_str())?;
+                    (!BATCH_SHARED_FILENAMES.contains(&filename))
+                        .then(|| (filename.to_string(), generated_code.clone()))
+                })
+                .collect();
             
-            let controller_filename = format!("{}_controller.rs", to_snake_case(&service.name));
-            generated_files.insert(controller_filename, controller_code.clone());
+            let mut group_files = HashMap::new();
+            let service_files = [
+                (TAGS_FILENAME, generator.generate_tags_enum(&services)),
+                (ERRORS_FILENAME, generator.generate_error_module(&services)),
+                (REGISTRATION_FILENAME, generator.generate_registration_helper(&services)),
+            ];
+            for (filename, code) in service_files {
+                if let Some(code) = code.map_err(ProtoHttpParserError::CodeGeneration)? {
+                    generated_files.insert(filename.to_string(), code.clone());
+                    group_files.insert(filename.to_string(), code);
+                }
+            }
             
-            // Generate service trait if configured
-            if self.config.generator.generate_service_traits {
-                let service_trait_code = self.generator.generate_service_trait(service, &service_routes)
-                    .map_err(|e| ProtoHttpParserError::CodeGeneration(e))?;
-                
-                let trait_filename = format!("{}_service.rs", to_snake_case(&service.name));
-                generated_files.insert(trait_filename, service_trait_code);
+            let models_file = results[0].proto_file.with_imported_definitions(
+                results[1..].iter().map(|result| &result.proto_file)
+            );
+            if let Some(prelude_code) = generator.generate_prelude(&models_file, &generated_files)
+                .map_err(ProtoHttpParserError::CodeGeneration)?
+            {
+                group_files.insert(PRELUDE_FILENAME.to_string(), prelude_code);
             }
+            
+            if let Some(package) = package {
+                group_files = nest_in_package_module(group_files, package, &self.config.generator.models_module_path);
+            }
+            shared_files.extend(group_files);
         }
         
-        Ok(ProcessResult {
-            proto_file,
-            routes,
-            generated_files,
-        })
+        Ok(shared_files)
+    }
+    
+    /// Turn a non-empty list of validation errors into the error reported to the caller
+    ///
+    /// Only the first error is reported unless `collect_all_errors` is enabled.
+    fn validation_failure(&self, errors: Vec<ValidationError>) -> ProtoHttpParserError {
+        let mut errors: Vec<ProtoHttpParserError> = errors.into_iter()
+            .map(ProtoHttpParserError::Validation)
+            .collect();
+        
+        if self.config.collect_all_errors && errors.len() > 1 {
+            ProtoHttpParserError::Multiple(errors)
+        } else {
+            errors.swap_remove(0)
+        }
+    }
+    
+    /// Convert a name to snake_case for generated file names, honoring `casing_overrides`
+    fn snake_case(&self, name: &str) -> String {
+        crate::utils::to_snake_case_with_overrides(name, &self.config.generator.casing_overrides, to_snake_case)
     }
     
     /// Process multiple proto files in batch
     pub fn process_files<P: AsRef<Path>>(&self, proto_paths: &[P]) -> Result<BatchProcessResult, ProtoHttpParserError> {
+        self.process_files_with_progress(proto_paths, &mut |_, _, _| {})
+    }
+    
+    /// Process multiple proto files in batch, reporting progress per file
+    ///
+    /// `progress` is called with `(current, total, file)` before each file is
+    /// processed, where `current` counts from 1 up to `total`. CLIs can use it to
+    /// render a progress bar.
+    pub fn process_files_with_progress<P: AsRef<Path>>(
+        &self,
+        proto_paths: &[P],
+        progress: &mut dyn FnMut(usize, usize, &Path),
+    ) -> Result<BatchProcessResult, ProtoHttpParserError> {
         let mut results = HashMap::new();
         let mut errors = Vec::new();
+        let mut type_registry = TypeRegistry::new();
+        let mut metrics = ProcessMetrics::default();
         
-        for proto_path in proto_paths {
+        // Routes seen so far across the batch (conflict signature -> route description)
+        let mut route_signatures: HashMap<String, String> = HashMap::new();
+        
+        let total = proto_paths.len();
+        for (index, proto_path) in proto_paths.iter().enumerate() {
             let path = proto_path.as_ref();
+            progress(index + 1, total, path);
             match self.process_file(path) {
                 Ok(result) => {
-                    results.insert(path.to_path_buf(), result);
+                    match check_batch_route_conflicts(&result.routes, &route_signatures) {
+                        Ok(()) => {
+                            for route in &result.routes {
+                                route_signatures.insert(route.conflict_signature(), route.describe());
+                            }
+                            type_registry.merge(result.type_registry.clone());
+                            if let Some(result_metrics) = &result.metrics {
+                                metrics.accumulate(result_metrics);
+                            }
+                            results.insert(path.to_path_buf(), result);
+                        }
+                        Err(error) => {
+                            errors.push((path.to_path_buf(), ProtoHttpParserError::Validation(error)));
+                        }
+                    }
                 }
                 Err(error) => {
                     errors.push((path.to_path_buf(), error));
@@ -221,6 +459,8 @@ impl ProtoHttpCoordinator {
         Ok(BatchProcessResult {
             results,
             errors,
+            type_registry,
+            metrics: self.config.collect_metrics.then_some(metrics),
         })
     }
     
@@ -230,33 +470,145 @@ impl ProtoHttpCoordinator {
         
         // Find all .proto files in the directory
         let proto_files = find_proto_files(dir_path)
-            .map_err(|e| ProtoHttpParserError::Io(e))?;
+            .map_err(ProtoHttpParserError::Io)?;
         
         self.process_files(&proto_files)
     }
     
+    /// Validate all proto files in a directory without generating code
+    ///
+    /// Returns whether every file passed along with a report per file, so CLI
+    /// wrappers can map the flag to an exit code and print the reports.
+    pub fn validate_directory<P: AsRef<Path>>(&self, dir_path: P) -> Result<(bool, Vec<(PathBuf, ValidationReport)>), ProtoHttpParserError> {
+        let mut proto_files = find_proto_files(dir_path.as_ref())
+            .map_err(ProtoHttpParserError::Io)?;
+        proto_files.sort();
+        
+        let reports: Vec<(PathBuf, ValidationReport)> = proto_files.into_iter()
+            .map(|path| {
+                let report = self.validate_file_report(&path);
+                (path, report)
+            })
+            .collect();
+        
+        let all_valid = reports.iter().all(|(_, report)| report.is_valid);
+        Ok((all_valid, reports))
+    }
+    
+    /// Validate all proto files in a directory, merging their reports into one
+    ///
+    /// The merged report is valid only if every file passed.
+    pub fn validate_directory_merged<P: AsRef<Path>>(&self, dir_path: P) -> Result<ValidationReport, ProtoHttpParserError> {
+        let (_, reports) = self.validate_directory(dir_path)?;
+        
+        let mut merged = ValidationReport::new();
+        for (_, report) in reports {
+            merged.merge(report);
+        }
+        Ok(merged)
+    }
+    
+    /// Run every validation step of the pipeline on a single file, collecting errors into a report
+    fn validate_file_report(&self, proto_path: &Path) -> ValidationReport {
+        let proto_file = match self.parser.parse_file(proto_path) {
+            Ok(proto_file) => proto_file,
+            Err(e) => return failed_report(ValidationError::ProcessingFailed {
+                message: format!("Failed to parse {}: {}", proto_path.display(), e),
+            }),
+        };
+        let recovered_errors = self.parser.take_recovered_errors();
+        
+        let imported_files = match self.parser.parse_imported_files_from(&proto_file, Some(proto_path)) {
+            Ok(imported_files) => imported_files,
+            Err(e) => return failed_report(ValidationError::ProcessingFailed {
+                message: format!("Failed to parse {}: {}", proto_path.display(), e),
+            }),
+        };
+        let resolution_file = proto_file.with_imported_definitions(
+            imported_files.iter().map(|(_, imported_file)| imported_file)
+        );
+        
+        let imports: Vec<ProtoFile> = imported_files.into_iter().map(|(_, imported_file)| imported_file).collect();
+        let mut report = match self.validator.validate_proto_file_with_imports(&proto_file, &imports) {
+            Ok(report) => report,
+            Err(e) => failed_report(e),
+        };
+        report.errors.extend(recovered_errors.into_iter().map(|e| ValidationError::ProcessingFailed {
+            message: format!("Failed to parse {}: {}", proto_path.display(), e),
+        }));
+        
+        if self.config.parser.unknown_option_policy == UnknownOptionPolicy::Warn {
+            report.warnings.extend(self.parser.unknown_options(&proto_file).into_iter().map(|option| ValidationWarning {
+                message: format!("Unknown option '{}' on {}", option.name, option.location),
+                location: None,
+                category: WarningCategory::Style,
+            }));
+        }
+        
+        if self.config.parser.missing_import_policy == MissingImportPolicy::Warn {
+            report.warnings.extend(self.parser.missing_imports(&proto_file, Some(proto_path)).into_iter().map(|import_path| ValidationWarning {
+                message: format!("Import '{}' not found on the include paths", import_path),
+                location: None,
+                category: WarningCategory::Style,
+            }));
+        }
+        
+        match self.plugin_manager.validate_proto_file(&proto_file) {
+            Ok(errors) => report.errors.extend(errors),
+            Err(e) => report.errors.push(ValidationError::ProcessingFailed { message: e.to_string() }),
+        }
+        
+        match self.extractor.extract_routes(&resolution_file) {
+            Ok(routes) => {
+                if let Err(e) = self.extractor.validate_annotations(&routes) {
+                    report.errors.push(ValidationError::HttpAnnotationError {
+                        message: format!("HTTP annotation validation failed: {}", e),
+                    });
+                }
+                
+                match self.validator.validate_http_routes(&routes) {
+                    Ok(route_report) => {
+                        report.errors.extend(route_report.errors);
+                        report.warnings.extend(route_report.warnings);
+                        report.suggestions.extend(route_report.suggestions);
+                    }
+                    Err(e) => report.errors.push(e),
+                }
+                
+                match self.plugin_manager.validate_http_routes(&routes) {
+                    Ok(errors) => report.errors.extend(errors),
+                    Err(e) => report.errors.push(ValidationError::ProcessingFailed { message: e.to_string() }),
+                }
+            }
+            Err(e) => report.errors.push(ValidationError::HttpAnnotationError {
+                message: format!("Failed to extract HTTP routes: {}", e),
+            }),
+        }
+        
+        report.is_valid = report.errors.is_empty();
+        report
+    }
+    
     /// Write generated code to files in the specified output directory
     pub fn write_generated_code<P: AsRef<Path>>(&self, result: &ProcessResult, output_dir: P) -> Result<(), ProtoHttpParserError> {
         let output_dir = output_dir.as_ref();
         
         // Create output directory if it doesn't exist
         std::fs::create_dir_all(output_dir)
-            .map_err(|e| ProtoHttpParserError::Io(e))?;
+            .map_err(ProtoHttpParserError::Io)?;
         
         // Write each generated file
         for (filename, generated_code) in &result.generated_files {
             let file_path = output_dir.join(filename);
+            if let Some(parent) = file_path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(ProtoHttpParserError::Io)?;
+            }
             
-            // Format the code if rustfmt is enabled
-            let content = if self.config.generator.formatting.use_rustfmt {
-                format_rust_code(&generated_code.content)
-                    .unwrap_or_else(|_| generated_code.content.clone())
-            } else {
-                generated_code.content.clone()
-            };
+            let content = render_output_file(&self.config.generator, generated_code);
             
             std::fs::write(&file_path, content)
-                .map_err(|e| ProtoHttpParserError::Io(e))?;
+                .map_err(ProtoHttpParserError::Io)?;
         }
         
         Ok(())
@@ -285,11 +637,44 @@ impl ProtoHttpCoordinator {
     }
     
     /// Update the configuration
+    ///
+    /// The built-in generators are rebuilt from the new generator settings;
+    /// generators registered with [`Self::register_generator`] are kept.
     pub fn update_config(&mut self, config: ProtoHttpParserConfig) {
         self.config = config.clone();
         self.parser = NomProtoParser::with_config(config.parser.clone());
         self.extractor = GoogleApiHttpExtractor::with_config(config.extractor.clone());
-        self.generator = PoemOpenApiGenerator::with_config(config.generator.clone());
+        self.validator = ValidationEngine::with_config(with_external_types(self.validator.config().clone(), &config.generator));
+        self.generators.reconfigure_builtin(&config.generator);
+    }
+    
+    /// Replace the validation engine configuration
+    pub fn set_validation_config(&mut self, config: ValidationConfig) {
+        self.validator = ValidationEngine::with_config(with_external_types(config, &self.config.generator));
+    }
+    
+    /// Get access to the generator registry
+    pub fn generator_registry(&self) -> &GeneratorRegistry {
+        &self.generators
+    }
+    
+    /// Get mutable access to the generator registry
+    pub fn generator_registry_mut(&mut self) -> &mut GeneratorRegistry {
+        &mut self.generators
+    }
+    
+    /// Register a code generator for a target framework
+    pub fn register_generator(&mut self, framework: TargetFramework, generator: Box<DynCodeGenerator>) {
+        self.generators.register(framework, generator);
+    }
+    
+    /// Get the generator for the configured target framework
+    fn active_generator(&self) -> Result<&DynCodeGenerator, ProtoHttpParserError> {
+        let framework = &self.config.generator.target_framework;
+        self.generators.get(framework)
+            .ok_or_else(|| ProtoHttpParserError::CodeGeneration(CodeGenerationError::GeneratorNotRegistered {
+                framework: format!("{:?}", framework),
+            }))
     }
     
     /// Get access to the plugin manager
@@ -302,10 +687,16 @@ impl ProtoHttpCoordinator {
         &mut self.plugin_manager
     }
     
+    /// Register an in-process plugin instance with a default configuration
+    pub fn register_plugin(&mut self, plugin: Box<dyn Plugin>) -> Result<(), ProtoHttpParserError> {
+        self.plugin_manager.register(plugin)
+            .map_err(ProtoHttpParserError::Plugin)
+    }
+    
     /// Load plugins from a configuration file
     pub fn load_plugins_from_config<P: AsRef<Path>>(&mut self, config_path: P) -> Result<(), ProtoHttpParserError> {
         self.plugin_manager.load_from_config(config_path)
-            .map_err(|e| ProtoHttpParserError::Plugin(e))
+            .map_err(ProtoHttpParserError::Plugin)
     }
 }
 
@@ -315,15 +706,297 @@ impl Default for ProtoHttpCoordinator {
     }
 }
 
+/// Declare the types given an external type mapping as defined outside the validated files
+fn with_external_types(mut config: ValidationConfig, generator: &GeneratorConfig) -> ValidationConfig {
+    config.external_types.extend(generator.external_type_mappings.keys()
+        .map(|proto_type| proto_type.trim_start_matches('.').to_string()));
+    config
+}
+
+/// File name of the generated tags enum module
+pub const TAGS_FILENAME: &str = "tags.rs";
+
+/// File name of the generated controller registration helper
+pub const REGISTRATION_FILENAME: &str = "apis.rs";
+
+/// File name of the generated shared errors module
+pub const ERRORS_FILENAME: &str = "errors.rs";
+
+/// File name of the generated API metadata module
+pub const API_METADATA_FILENAME: &str = "api_metadata.rs";
+
+/// File name of the generated route path builders module
+pub const PATHS_FILENAME: &str = "paths.rs";
+
+/// File name of the generated CORS metadata module
+pub const CORS_FILENAME: &str = "cors.rs";
+
+/// File name of the generated route inventory entry module
+pub const ROUTE_INFO_FILENAME: &str = "route_info.rs";
+
+/// File name of the generated prelude module re-exporting the other generated modules
+pub const PRELUDE_FILENAME: &str = "prelude.rs";
+
+/// Files generated once per output directory when a batch is written together
+const BATCH_SHARED_FILENAMES: [&str; 4] = [TAGS_FILENAME, ERRORS_FILENAME, REGISTRATION_FILENAME, PRELUDE_FILENAME];
+
 /// Result of processing a single proto file
 #[derive(Debug, Clone)]
 pub struct ProcessResult {
+    /// Path the proto file was read from, or its virtual path; `None` for anonymous content
+    pub source_path: Option<PathBuf>,
     /// The parsed proto file
     pub proto_file: ProtoFile,
     /// Extracted HTTP routes
     pub routes: Vec<HttpRoute>,
     /// Generated code files (filename -> generated code)
     pub generated_files: HashMap<String, GeneratedCode>,
+    /// Types defined in this file and the files it imports
+    pub type_registry: TypeRegistry,
+    /// API title, version and description read from file options
+    pub api_metadata: ApiMetadata,
+    /// Per-phase timings, present when metrics collection is enabled
+    pub metrics: Option<ProcessMetrics>,
+}
+
+impl ProcessResult {
+    /// Get every path and query parameter across all routes, in route order
+    pub fn all_parameters(&self) -> Vec<ParameterInfo> {
+        let mut parameters = Vec::new();
+        
+        for route in &self.routes {
+            let info = |kind, name: &String, param_type: &ParameterType, required| ParameterInfo {
+                service_name: route.service_name.clone(),
+                method_name: route.method_name.clone(),
+                kind,
+                name: name.clone(),
+                param_type: param_type.clone(),
+                required,
+            };
+            
+            for param in &route.path_parameters {
+                parameters.push(info(ParameterKind::Path, &param.name, &param.param_type, param.required));
+            }
+            for param in &route.query_parameters {
+                parameters.push(info(ParameterKind::Query, &param.name, &param.param_type, param.required));
+            }
+        }
+        
+        parameters
+    }
+    
+    /// Compare the generated files of this result against `other`
+    ///
+    /// Files only in `other` are reported as added, files only in `self` as removed,
+    /// and files whose content differs as changed, with a line-level diff from
+    /// `self` to `other`. File lists are sorted by name.
+    pub fn diff(&self, other: &ProcessResult) -> GenerationDiff {
+        let mut diff = GenerationDiff::default();
+        
+        for (filename, code) in &self.generated_files {
+            match other.generated_files.get(filename) {
+                Some(other_code) if other_code.content != code.content => {
+                    diff.changed.push(FileDiff {
+                        filename: filename.clone(),
+                        lines: diff_lines(&code.content, &other_code.content),
+                    });
+                }
+                Some(_) => {}
+                None => diff.removed.push(filename.clone()),
+            }
+        }
+        
+        diff.added = other.generated_files.keys()
+            .filter(|filename| !self.generated_files.contains_key(*filename))
+            .cloned()
+            .collect();
+        
+        diff.added.sort();
+        diff.removed.sort();
+        diff.changed.sort_by(|a, b| a.filename.cmp(&b.filename));
+        diff
+    }
+}
+
+/// Differences between the generated files of two process results
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GenerationDiff {
+    /// Files generated only by the newer result
+    pub added: Vec<String>,
+    /// Files generated only by the older result
+    pub removed: Vec<String>,
+    /// Files generated by both with different content
+    pub changed: Vec<FileDiff>,
+}
+
+impl GenerationDiff {
+    /// Check whether both results generated identical files
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Line-level difference of one generated file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileDiff {
+    /// Name of the generated file
+    pub filename: String,
+    /// Every line of both versions, in order, marked as kept, removed or added
+    pub lines: Vec<DiffLine>,
+}
+
+impl FileDiff {
+    /// Iterate over the removed and added lines only
+    pub fn changes(&self) -> impl Iterator<Item = &DiffLine> {
+        self.lines.iter().filter(|line| !matches!(line, DiffLine::Unchanged(_)))
+    }
+}
+
+/// A line in a file diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Unchanged(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Diff two texts line by line using a longest common subsequence
+///
+/// The common prefix and suffix are matched up front, so the quadratic table
+/// only covers the region that actually changed.
+/// Move generated files into the module directory of a proto package
+///
+/// `foo.v1` files go under `foo/v1/`. Sibling `super::` imports keep working, and so
+/// do relative model imports, which now resolve from the package's module; absolute
+/// model imports are pointed at the package's module under `models_module_path`.
+fn nest_in_package_module(
+    generated_files: HashMap<String, GeneratedCode>,
+    package: &str,
+    models_module_path: &str,
+) -> HashMap<String, GeneratedCode> {
+    let module_path = package.split('.').map(to_snake_case).collect::<Vec<_>>();
+    let is_relative = ["self", "super"].iter()
+        .any(|keyword| models_module_path.split("::").next() == Some(*keyword));
+    let models_import = format!("use {}::", models_module_path);
+    let package_models_import = format!("use {}::{}::", models_module_path, module_path.join("::"));
+    
+    generated_files.into_iter()
+        .map(|(filename, mut generated_code)| {
+            generated_code.content = generated_code.content.split_inclusive('\n')
+                .map(|line| {
+                    let (visibility, statement) = match line.strip_prefix("pub ") {
+                        Some(statement) => ("pub ", statement),
+                        None => ("", line),
+                    };
+                    match statement.strip_prefix(models_import.as_str()).filter(|_| !is_relative) {
+                        Some(items) => format!("{}{}{}", visibility, package_models_import, items),
+                        None => line.to_string(),
+                    }
+                })
+                .collect();
+            (format!("{}/{}", module_path.join("/"), filename), generated_code)
+        })
+        .collect()
+}
+
+fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    
+    let prefix = old_lines.iter().zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..].iter().rev().zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old_lines[prefix..old_lines.len() - suffix];
+    let new_middle = &new_lines[prefix..new_lines.len() - suffix];
+    
+    // lcs[i][j] is the LCS length of old_middle[i..] and new_middle[j..]
+    let mut lcs = vec![vec![0usize; new_middle.len() + 1]; old_middle.len() + 1];
+    for i in (0..old_middle.len()).rev() {
+        for j in (0..new_middle.len()).rev() {
+            lcs[i][j] = if old_middle[i] == new_middle[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    
+    let mut lines: Vec<DiffLine> = old_lines[..prefix].iter()
+        .map(|line| DiffLine::Unchanged(line.to_string()))
+        .collect();
+    
+    let (mut i, mut j) = (0, 0);
+    while i < old_middle.len() || j < new_middle.len() {
+        if i < old_middle.len() && j < new_middle.len() && old_middle[i] == new_middle[j] {
+            lines.push(DiffLine::Unchanged(old_middle[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < old_middle.len() && (j == new_middle.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(DiffLine::Removed(old_middle[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new_middle[j].to_string()));
+            j += 1;
+        }
+    }
+    
+    lines.extend(old_lines[old_lines.len() - suffix..].iter().map(|line| DiffLine::Unchanged(line.to_string())));
+    lines
+}
+
+/// A path or query parameter of a route, flattened for cataloguing
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterInfo {
+    /// Service the route belongs to
+    pub service_name: String,
+    /// RPC method the route is bound to
+    pub method_name: String,
+    /// Where the parameter is taken from
+    pub kind: ParameterKind,
+    /// Parameter name
+    pub name: String,
+    /// Parameter type
+    pub param_type: ParameterType,
+    /// Whether the parameter is required
+    pub required: bool,
+}
+
+/// Location of a route parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterKind {
+    Path,
+    Query,
+}
+
+/// Time spent in each processing phase
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessMetrics {
+    /// Parsing, including import resolution
+    pub parse: Duration,
+    /// Proto file validation, including plugin validators
+    pub validate: Duration,
+    /// HTTP route extraction and annotation validation
+    pub extract: Duration,
+    /// Code generation
+    pub generate: Duration,
+}
+
+impl ProcessMetrics {
+    /// Get the total time across all phases
+    pub fn total(&self) -> Duration {
+        self.parse + self.validate + self.extract + self.generate
+    }
+    
+    /// Add the timings from another run to this one
+    pub fn accumulate(&mut self, other: &ProcessMetrics) {
+        self.parse += other.parse;
+        self.validate += other.validate;
+        self.extract += other.extract;
+        self.generate += other.generate;
+    }
 }
 
 /// Result of batch processing multiple proto files
@@ -333,6 +1006,11 @@ pub struct BatchProcessResult {
     pub results: HashMap<PathBuf, ProcessResult>,
     /// Errors encountered (proto path -> error)
     pub errors: Vec<(PathBuf, ProtoHttpParserError)>,
+    /// Types defined across all successfully processed files and their imports
+    pub type_registry: TypeRegistry,
+    /// Per-phase timings summed over successfully processed files, present
+    /// when metrics collection is enabled
+    pub metrics: Option<ProcessMetrics>,
 }
 
 impl BatchProcessResult {
@@ -369,7 +1047,6 @@ impl BatchProcessResult {
 pub mod build_integration {
     use super::*;
     use crate::core::config::ConfigBuilder;
-    use crate::core::errors::ConfigError;
     
     /// Builder for build.rs integration with enhanced configuration support
     pub struct BuildIntegration {
@@ -471,7 +1148,7 @@ pub mod build_integration {
         /// Add all proto files from a directory
         pub fn add_proto_directory<P: AsRef<Path>>(mut self, dir: P) -> Result<Self, ProtoHttpParserError> {
             let proto_files = find_proto_files(dir.as_ref())
-                .map_err(|e| ProtoHttpParserError::Io(e))?;
+                .map_err(ProtoHttpParserError::Io)?;
             
             for file in proto_files {
                 self.proto_files.push(file);
@@ -511,6 +1188,51 @@ pub mod build_integration {
         
         /// Generate code for all configured proto files
         pub fn generate(mut self) -> Result<BuildResult, Box<dyn std::error::Error>> {
+            let batch_result = self.process_all()?;
+            
+            // Write generated code to a flat directory structure for build integration
+            let generated_files = self.write_flat_batch_results(&batch_result)?;
+            
+            // Print cargo rerun-if-changed directives
+            for proto_file in &self.proto_files {
+                println!("cargo:rerun-if-changed={}", proto_file.display());
+            }
+            
+            // Also watch config files
+            for source in &self.config_sources {
+                if let ConfigSource::File(path) = source {
+                    println!("cargo:rerun-if-changed={}", path.display());
+                }
+            }
+            
+            if self.verbose {
+                println!("cargo:warning=Generated {} files in {}", 
+                    generated_files.len(), 
+                    self.output_dir.display()
+                );
+            }
+            
+            Ok(BuildResult {
+                generated_files,
+                output_dir: self.output_dir,
+                processed_files: self.proto_files,
+            })
+        }
+        
+        /// Generate code for all configured proto files without writing it
+        ///
+        /// Returns the content [`Self::generate`] would write, keyed by file name.
+        /// Nothing is written to the output directory and no cargo directives are
+        /// printed, even in verbose mode.
+        pub fn generate_to_memory(mut self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+            self.verbose = false;
+            let batch_result = self.process_all()?;
+            
+            Ok(self.render_flat_batch_results(&batch_result)?.into_iter().collect())
+        }
+        
+        /// Apply the configuration sources and process every configured proto file
+        fn process_all(&mut self) -> Result<BatchProcessResult, Box<dyn std::error::Error>> {
             // Apply configuration sources in order
             let mut config = self.coordinator.config().clone();
             
@@ -563,61 +1285,80 @@ pub mod build_integration {
                 ).into());
             }
             
-            // Write generated code to a flat directory structure for build integration
-            let generated_files = self.write_flat_batch_results(&batch_result)?;
+            Ok(batch_result)
+        }
+        
+        /// Render batch results as flat (file name, content) pairs, in the order they are written
+        ///
+        /// The registration helper and prelude are generated once for the whole batch.
+        /// Other protos that produce the same file name with identical content are
+        /// written once; differing content is reported as an
+        /// [`CodeGenerationError::OutputCollision`] instead of silently overwriting.
+        fn render_flat_batch_results(&self, batch_result: &BatchProcessResult) -> Result<Vec<(String, String)>, ProtoHttpParserError> {
+            let mut proto_paths: Vec<&PathBuf> = batch_result.results.keys().collect();
+            proto_paths.sort();
+            let shared_files = self.coordinator.batch_shared_files(batch_result)?;
             
-            // Print cargo rerun-if-changed directives
-            for proto_file in &self.proto_files {
-                println!("cargo:rerun-if-changed={}", proto_file.display());
-            }
+            let mut rendered: Vec<(String, String)> = Vec::new();
+            let mut owners: HashMap<String, (usize, &PathBuf)> = HashMap::new();
             
-            // Also watch config files
-            for source in &self.config_sources {
-                if let ConfigSource::File(path) = source {
-                    println!("cargo:rerun-if-changed={}", path.display());
+            for proto_path in proto_paths {
+                let mut filenames: Vec<&String> = batch_result.results[proto_path].generated_files.keys()
+                    .filter(|filename| !shared_files.contains_key(*filename))
+                    .collect();
+                filenames.sort();
+                
+                for filename in filenames {
+                    let generated_code = &batch_result.results[proto_path].generated_files[filename];
+                    let content = render_output_file(&self.coordinator.config().generator, generated_code);
+                    
+                    match owners.get(filename) {
+                        Some(&(index, _)) if rendered[index].1 == content => {}
+                        Some(&(_, first)) => {
+                            return Err(ProtoHttpParserError::CodeGeneration(CodeGenerationError::OutputCollision {
+                                filename: filename.clone(),
+                                first: first.clone(),
+                                second: proto_path.clone(),
+                            }));
+                        }
+                        None => {
+                            owners.insert(filename.clone(), (rendered.len(), proto_path));
+                            rendered.push((filename.clone(), content));
+                        }
+                    }
                 }
             }
             
-            if self.verbose {
-                println!("cargo:warning=Generated {} files in {}", 
-                    generated_files.len(), 
-                    self.output_dir.display()
-                );
+            let mut shared_filenames: Vec<&String> = shared_files.keys().collect();
+            shared_filenames.sort();
+            for filename in shared_filenames {
+                let content = render_output_file(&self.coordinator.config().generator, &shared_files[filename]);
+                rendered.push((filename.clone(), content));
             }
             
-            Ok(BuildResult {
-                generated_files,
-                output_dir: self.output_dir,
-                processed_files: self.proto_files,
-            })
+            Ok(rendered)
         }
         
         /// Write batch results to a flat directory structure (all files in the same directory)
         fn write_flat_batch_results(&self, batch_result: &BatchProcessResult) -> Result<Vec<PathBuf>, ProtoHttpParserError> {
             // Create output directory if it doesn't exist
             std::fs::create_dir_all(&self.output_dir)
-                .map_err(|e| ProtoHttpParserError::Io(e))?;
+                .map_err(ProtoHttpParserError::Io)?;
             
             let mut generated_files = Vec::new();
             
             // Write all generated files to the same directory
-            for result in batch_result.results.values() {
-                for (filename, generated_code) in &result.generated_files {
-                    let file_path = self.output_dir.join(filename);
-                    
-                    // Format the code if rustfmt is enabled
-                    let content = if self.coordinator.config().generator.formatting.use_rustfmt {
-                        format_rust_code(&generated_code.content)
-                            .unwrap_or_else(|_| generated_code.content.clone())
-                    } else {
-                        generated_code.content.clone()
-                    };
-                    
-                    std::fs::write(&file_path, content)
-                        .map_err(|e| ProtoHttpParserError::Io(e))?;
-                    
-                    generated_files.push(file_path);
+            for (filename, content) in self.render_flat_batch_results(batch_result)? {
+                let file_path = self.output_dir.join(filename);
+                if let Some(parent) = file_path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .map_err(ProtoHttpParserError::Io)?;
                 }
+                
+                std::fs::write(&file_path, content)
+                    .map_err(ProtoHttpParserError::Io)?;
+                
+                generated_files.push(file_path);
             }
             
             Ok(generated_files)
@@ -703,6 +1444,51 @@ fn find_proto_files<P: AsRef<Path>>(dir: P) -> std::io::Result<Vec<PathBuf>> {
     Ok(proto_files)
 }
 
+/// Build a failing validation report holding a single error
+fn failed_report(error: ValidationError) -> ValidationReport {
+    ValidationReport {
+        is_valid: false,
+        errors: vec![error],
+        warnings: Vec::new(),
+        suggestions: Vec::new(),
+    }
+}
+
+/// Check a file's routes against routes already accepted from other files in a batch
+fn check_batch_route_conflicts(
+    routes: &[HttpRoute],
+    route_signatures: &HashMap<String, String>,
+) -> Result<(), ValidationError> {
+    for route in routes {
+        if let Some(existing) = route_signatures.get(&route.conflict_signature()) {
+            return Err(ValidationError::ConflictingRoutes {
+                route1: existing.clone(),
+                route2: route.describe(),
+            });
+        }
+    }
+    
+    Ok(())
+}
+
+/// Render the final file contents for generated code: optionally formatted
+/// with rustfmt and prefixed with the configured file header
+fn render_output_file(config: &GeneratorConfig, generated_code: &GeneratedCode) -> String {
+    // Format the code if rustfmt is enabled
+    let content = if config.formatting.use_rustfmt {
+        format_rust_code(&generated_code.content)
+            .unwrap_or_else(|_| generated_code.content.clone())
+    } else {
+        generated_code.content.clone()
+    };
+    
+    match &config.file_header {
+        Some(header) if header.ends_with('\n') => format!("{}{}", header, content),
+        Some(header) => format!("{}\n{}", header, content),
+        None => content,
+    }
+}
+
 /// Format Rust code using rustfmt
 fn format_rust_code(code: &str) -> Result<String, std::io::Error> {
     use std::process::{Command, Stdio};
@@ -725,8 +1511,7 @@ fn format_rust_code(code: &str) -> Result<String, std::io::Error> {
     if output.status.success() {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     } else {
-        Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
+        Err(std::io::Error::other(
             format!("rustfmt failed: {}", String::from_utf8_lossy(&output.stderr))
         ))
     }
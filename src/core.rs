@@ -86,6 +86,34 @@ pub struct ValidationReport {
     pub suggestions: Vec<ValidationSuggestion>,
 }
 
+impl ValidationReport {
+    /// Create an empty, passing report
+    pub fn new() -> Self {
+        Self {
+            is_valid: true,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            suggestions: Vec::new(),
+        }
+    }
+    
+    /// Append the entries of another report to this one
+    /// 
+    /// The merged report is valid only if both reports are.
+    pub fn merge(&mut self, other: ValidationReport) {
+        self.is_valid &= other.is_valid;
+        self.errors.extend(other.errors);
+        self.warnings.extend(other.warnings);
+        self.suggestions.extend(other.suggestions);
+    }
+}
+
+impl Default for ValidationReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Validation warning (non-fatal issue)
 #[derive(Debug, Clone)]
 pub struct ValidationWarning {
@@ -145,11 +173,112 @@ pub struct SourceLocation {
 pub trait CodeGenerator {
     type Error: Error + Send + Sync + 'static;
     
+    /// Check that the models of a proto file support the code generated for its routes
+    /// 
+    /// Runs before any code is generated. Accepts every proto file by default.
+    fn check_models(&self, _proto_file: &ProtoFile, _routes: &[HttpRoute]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    
     /// Generate controller code for a service
     fn generate_controller(&self, service: &Service, routes: &[HttpRoute]) -> Result<GeneratedCode, Self::Error>;
     
+    /// Generate the parts a controller is split into, one file each
+    /// 
+    /// Part `n` (counting from 1) is written next to the controller with an `_n`
+    /// suffix. Returns no parts when the controller is generated as a single file,
+    /// which is the default.
+    fn generate_controller_parts(&self, _service: &Service, _routes: &[HttpRoute]) -> Result<Vec<GeneratedCode>, Self::Error> {
+        Ok(Vec::new())
+    }
+    
     /// Generate service trait interface for dependency injection
     fn generate_service_trait(&self, service: &Service, routes: &[HttpRoute]) -> Result<GeneratedCode, Self::Error>;
+    
+    /// Generate a shared tags enum covering all services in a proto file
+    /// 
+    /// Returns `None` when the generator does not emit a tags enum, which is
+    /// the default.
+    fn generate_tags_enum(&self, _services: &[Service]) -> Result<Option<GeneratedCode>, Self::Error> {
+        Ok(None)
+    }
+    
+    /// Generate a helper assembling the controllers of all services in a proto file
+    /// 
+    /// Returns `None` when the generator does not emit a registration helper,
+    /// which is the default.
+    fn generate_registration_helper(&self, _services: &[Service]) -> Result<Option<GeneratedCode>, Self::Error> {
+        Ok(None)
+    }
+    
+    /// Generate constants describing the API for mounting the controllers
+    /// 
+    /// Returns `None` when the generator does not emit API metadata, which is
+    /// the default.
+    fn generate_api_metadata(&self, _metadata: &ApiMetadata) -> Result<Option<GeneratedCode>, Self::Error> {
+        Ok(None)
+    }
+    
+    /// Generate a shared error type that controllers map service errors into
+    /// 
+    /// Returns `None` when the generator does not emit an errors module, which
+    /// is the default.
+    fn generate_error_module(&self, _services: &[Service]) -> Result<Option<GeneratedCode>, Self::Error> {
+        Ok(None)
+    }
+    
+    /// Generate functions building the concrete path of each route from its parameters
+    /// 
+    /// Returns `None` when the generator does not emit path builders, which is
+    /// the default.
+    fn generate_path_builders(&self, _routes: &[HttpRoute]) -> Result<Option<GeneratedCode>, Self::Error> {
+        Ok(None)
+    }
+    
+    /// Generate a helper listing the HTTP methods and request headers used on each route path
+    /// 
+    /// Returns `None` when the generator does not emit CORS metadata, which is
+    /// the default.
+    fn generate_cors_config(&self, _routes: &[HttpRoute]) -> Result<Option<GeneratedCode>, Self::Error> {
+        Ok(None)
+    }
+    
+    /// Generate the type describing the entries of the controllers' route inventories
+    /// 
+    /// Returns `None` when the generator does not emit route inventories, which is
+    /// the default.
+    fn generate_route_info(&self, _routes: &[HttpRoute]) -> Result<Option<GeneratedCode>, Self::Error> {
+        Ok(None)
+    }
+    
+    /// Generate a module re-exporting the public items of the other generated files
+    /// and the models of a proto file
+    /// 
+    /// `generated_files` maps file names to the code generated for them. Returns
+    /// `None` when the generator does not emit a prelude, which is the default.
+    fn generate_prelude(
+        &self,
+        _proto_file: &ProtoFile,
+        _generated_files: &std::collections::HashMap<String, GeneratedCode>,
+    ) -> Result<Option<GeneratedCode>, Self::Error> {
+        Ok(None)
+    }
+    
+    /// Generate a mock implementation of a service trait for testing controllers
+    /// 
+    /// Returns `None` when the generator does not emit mocks, which is the default.
+    fn generate_mock(&self, _service: &Service, _routes: &[HttpRoute]) -> Result<Option<GeneratedCode>, Self::Error> {
+        Ok(None)
+    }
+    
+    /// Generate integration test scaffolding exercising each route of a service
+    /// 
+    /// `proto_file` declares the messages the routes refer to, from which canned
+    /// responses and sample request bodies are built. Returns `None` when the
+    /// generator does not emit tests, which is the default.
+    fn generate_tests(&self, _service: &Service, _routes: &[HttpRoute], _proto_file: &ProtoFile) -> Result<Option<GeneratedCode>, Self::Error> {
+        Ok(None)
+    }
 }
 
 /// Trait for template-based code generation
@@ -178,6 +307,27 @@ pub trait TemplateHelper: Send + Sync {
     fn call(&self, args: &[TemplateValue]) -> Result<TemplateValue, Box<dyn Error>>;
 }
 
+/// Trait for walking the definitions of a parsed proto file
+/// 
+/// Drive a traversal with [`ProtoFile::accept`]; every method defaults to a
+/// no-op, so implementors only override the definitions they care about.
+pub trait Visitor {
+    /// Visit a service, before its methods
+    fn visit_service(&mut self, _service: &Service) {}
+    
+    /// Visit an RPC method of `service`
+    fn visit_method(&mut self, _service: &Service, _method: &RpcMethod) {}
+    
+    /// Visit a message, top-level or nested, before its fields and nested definitions
+    fn visit_message(&mut self, _message: &Message) {}
+    
+    /// Visit a field of `message`
+    fn visit_field(&mut self, _message: &Message, _field: &Field) {}
+    
+    /// Visit an enum, top-level or nested
+    fn visit_enum(&mut self, _enum_def: &Enum) {}
+}
+
 /// Template context data for rendering
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemplateContext {
@@ -233,4 +383,59 @@ impl GeneratedCode {
         self.dependencies.push(dependency);
         self
     }
+    
+    /// Merge another code fragment into this one
+    /// 
+    /// Content is concatenated with inner attributes (`#![...]`) hoisted to
+    /// the top, and duplicate single-line `use` statements and inner
+    /// attributes are kept only once. Import and dependency metadata are
+    /// unioned.
+    pub fn combine(self, other: GeneratedCode) -> GeneratedCode {
+        let mut seen = std::collections::HashSet::new();
+        let mut inner_attributes = Vec::new();
+        let mut body = Vec::new();
+        
+        for (index, content) in [&self.content, &other.content].into_iter().enumerate() {
+            if index > 0 && !body.is_empty() {
+                body.push(String::new());
+            }
+            
+            for line in content.lines() {
+                let trimmed = line.trim();
+                let is_inner_attribute = trimmed.starts_with("#![");
+                let is_use = trimmed.starts_with("use ") && trimmed.ends_with(';');
+                
+                if (is_inner_attribute || is_use) && !seen.insert(trimmed.to_string()) {
+                    continue;
+                }
+                
+                if is_inner_attribute {
+                    inner_attributes.push(line.to_string());
+                } else {
+                    body.push(line.to_string());
+                }
+            }
+        }
+        
+        let mut lines = inner_attributes;
+        lines.extend(body);
+        
+        let mut imports = self.imports;
+        imports.extend(other.imports);
+        imports.sort();
+        imports.dedup();
+        
+        let mut dependencies = self.dependencies;
+        for dependency in other.dependencies {
+            if !dependencies.contains(&dependency) {
+                dependencies.push(dependency);
+            }
+        }
+        
+        GeneratedCode {
+            content: lines.join("\n") + "\n",
+            imports,
+            dependencies,
+        }
+    }
 }
\ No newline at end of file
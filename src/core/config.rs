@@ -16,6 +16,28 @@ pub struct ParserConfig {
     pub strict_validation: bool,
     /// Maximum recursion depth for imports
     pub max_import_depth: usize,
+    /// Maximum nesting depth of message and enum definitions
+    #[serde(default = "default_max_nesting_depth")]
+    pub max_nesting_depth: usize,
+    /// How options outside the known set are treated
+    #[serde(default)]
+    pub unknown_option_policy: UnknownOptionPolicy,
+    /// Whether unrecognized top-level statements (e.g. `edition = "2023";`) are skipped
+    /// and kept in [`ProtoFile::unknown_statements`](crate::ProtoFile) instead of failing the parse
+    #[serde(default)]
+    pub allow_unknown_statements: bool,
+    /// How normal and public imports that cannot be found are treated; missing
+    /// `import weak` files are always ignored
+    #[serde(default)]
+    pub missing_import_policy: MissingImportPolicy,
+    /// Whether the exact source text of each top-level service, message and enum is
+    /// kept in its `source` field
+    #[serde(default)]
+    pub keep_source_spans: bool,
+    /// Whether a top-level declaration that fails to parse is skipped up to the next
+    /// balanced `}` or `;`, recording an error, instead of failing the whole file
+    #[serde(default)]
+    pub error_recovery: bool,
 }
 
 impl Default for ParserConfig {
@@ -25,10 +47,52 @@ impl Default for ParserConfig {
             preserve_comments: true,
             strict_validation: true,
             max_import_depth: 10,
+            max_nesting_depth: default_max_nesting_depth(),
+            unknown_option_policy: UnknownOptionPolicy::default(),
+            allow_unknown_statements: false,
+            missing_import_policy: MissingImportPolicy::default(),
+            keep_source_spans: false,
+            error_recovery: false,
         }
     }
 }
 
+fn default_max_nesting_depth() -> usize {
+    32
+}
+
+/// Policy for options whose names are not recognized
+///
+/// Options in the `google.` namespace must name a known option, which catches
+/// typos such as `(google.api.htttp)`; options of other namespaces are custom
+/// extensions and always accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UnknownOptionPolicy {
+    /// Keep unknown options without reporting them
+    #[default]
+    Ignore,
+    /// Report unknown options as validation warnings
+    Warn,
+    /// Fail parsing on the first unknown option
+    Error,
+}
+
+/// Policy for imports whose file cannot be found
+///
+/// Applies to normal and public imports only. A missing `import weak` file is
+/// expected by protobuf semantics and never reported. `google/api/` and
+/// `google/protobuf/` imports are built in and never looked up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MissingImportPolicy {
+    /// Skip missing imports without reporting them
+    Ignore,
+    /// Report missing imports as validation warnings
+    #[default]
+    Warn,
+    /// Fail parsing on the first missing import
+    Error,
+}
+
 /// Configuration for HTTP annotation extraction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtractorConfig {
@@ -36,6 +100,15 @@ pub struct ExtractorConfig {
     pub infer_query_params: bool,
     /// List of common query parameter names to infer
     pub common_query_params: Vec<String>,
+    /// Which methods receive the inferred common query parameters
+    #[serde(default)]
+    pub query_param_inference: QueryParamInference,
+    /// Method name prefixes identifying list-style methods
+    #[serde(default = "default_list_method_prefixes")]
+    pub list_method_prefixes: Vec<String>,
+    /// Longest path template, in bytes, accepted before any parsing work is done
+    #[serde(default = "default_max_path_template_length")]
+    pub max_path_template_length: usize,
     /// Whether to validate HTTP method compatibility
     pub validate_http_methods: bool,
     /// Whether to allow custom HTTP methods
@@ -55,12 +128,34 @@ impl Default for ExtractorConfig {
                 "filter".to_string(),
                 "search".to_string(),
             ],
+            query_param_inference: QueryParamInference::ListMethods,
+            list_method_prefixes: default_list_method_prefixes(),
+            max_path_template_length: default_max_path_template_length(),
             validate_http_methods: true,
             allow_custom_methods: false,
         }
     }
 }
 
+fn default_max_path_template_length() -> usize {
+    2048
+}
+
+fn default_list_method_prefixes() -> Vec<String> {
+    vec!["List".to_string(), "Search".to_string()]
+}
+
+/// Policy selecting the methods that receive inferred common query parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum QueryParamInference {
+    /// Every method with an HTTP binding
+    AllMethods,
+    /// Only list-style methods: names starting with one of `list_method_prefixes`,
+    /// or responses whose first field is a repeated collection
+    #[default]
+    ListMethods,
+}
+
 /// Configuration for code generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GeneratorConfig {
@@ -76,6 +171,242 @@ pub struct GeneratorConfig {
     pub additional_imports: Vec<String>,
     /// Code formatting options
     pub formatting: FormattingConfig,
+    /// Header prepended verbatim to every generated file (e.g. an SPDX license banner)
+    pub file_header: Option<String>,
+    /// Whether to generate a `Tags` enum with one variant per service
+    #[serde(default)]
+    pub generate_tags_enum: bool,
+    /// Whether to generate a `register_apis` helper assembling every service's controller,
+    /// along with the API metadata constants used to mount them
+    #[serde(default)]
+    pub generate_registration_helper: bool,
+    /// Largest tuple of controllers the registration helper builds; beyond it controllers
+    /// are grouped into nested tuples, as poem-openapi implements `OpenApi` for tuples of
+    /// at most 16 elements
+    #[serde(default = "default_max_controller_tuple_arity")]
+    pub max_controller_tuple_arity: usize,
+    /// Most operations generated into one controller file; a service with more routes
+    /// gets one controller that serves parts split across `<controller module>_<n>.rs`
+    /// files, which compile faster incrementally. `None` keeps every service in one file
+    #[serde(default)]
+    pub max_operations_per_file: Option<usize>,
+    /// Whether to generate a shared `ApiError` response enum that controllers map service errors into
+    #[serde(default)]
+    pub generate_error_module: bool,
+    /// Whether handlers take numeric, boolean, timestamp and enum path/query parameters as
+    /// strings and parse them, answering `ApiError::BadRequest` when parsing fails
+    #[serde(default)]
+    pub validate_parameters: bool,
+    /// Whether to generate a `Mock<Service>` implementation of each service trait
+    /// with settable canned responses, written to `<service>_mock.rs`
+    #[serde(default)]
+    pub generate_mocks: bool,
+    /// Whether to generate `#[tokio::test]` scaffolding exercising each route of a
+    /// service through its mock, written to `<service>_tests.rs`; tests whose response
+    /// model has neither a generated `Default` nor an `@example` are marked `#[ignore]`
+    #[serde(default)]
+    pub generate_tests: bool,
+    /// Whether to generate page-collecting helpers for AIP-style paginated list methods
+    #[serde(default)]
+    pub generate_pagination_helpers: bool,
+    /// Whether to generate a fluent builder for each model struct
+    #[serde(default)]
+    pub generate_builders: bool,
+    /// Whether to implement `Default` for models whose fields can all default,
+    /// honouring proto2 `[default = ...]` values
+    #[serde(default)]
+    pub generate_defaults: bool,
+    /// Whether to mark generated model structs and enums `#[non_exhaustive]`, so adding
+    /// a field or variant later is not a breaking change downstream
+    #[serde(default)]
+    pub non_exhaustive: bool,
+    /// Whether to generate a `paths` module with a function building the path of each
+    /// route, written to `paths.rs`
+    #[serde(default)]
+    pub generate_path_builders: bool,
+    /// Whether to generate a `cors_config()` helper listing the methods and headers used
+    /// on each route path, written to `cors.rs`
+    #[serde(default)]
+    pub generate_cors_config: bool,
+    /// Whether to generate a `routes()` function on each controller listing the method,
+    /// path and operation id of its routes, with the `RouteInfo` type in `route_info.rs`
+    #[serde(default)]
+    pub generate_route_inventory: bool,
+    /// Whether to generate a `prelude` module re-exporting every generated public item
+    /// along with the models, written to `prelude.rs`
+    #[serde(default)]
+    pub generate_prelude: bool,
+    /// Generic envelope type (e.g. `ApiResponse`) wrapping every unary JSON response;
+    /// the path must be resolvable from the generated modules. Service traits keep
+    /// returning the bare type, which controllers wrap through `From`, so
+    /// `Wrapper<T>: From<T>` must hold for each response type
+    pub response_wrapper: Option<String>,
+    /// Whether service traits declare their methods natively instead of through the
+    /// `#[async_trait]` macro, avoiding its boxed futures; the returned futures are
+    /// `Send` so handlers can await them
+    #[serde(default)]
+    pub use_native_async_trait: bool,
+    /// Whether the files generated for a proto package go into a nested module directory
+    /// (`foo.v1` → `foo/v1/`), so several versions of a package can be generated side by
+    /// side; an absolute `models_module_path` then points at the matching module below
+    /// it (`crate::models::foo::v1`)
+    #[serde(default)]
+    pub package_to_module: bool,
+    /// Error type (e.g. `crate::Error`) returned by service trait methods instead of
+    /// `Box<dyn std::error::Error>`; it must implement `Debug`, and `From<&str>` when
+    /// generated tests fall back to a placeholder error response
+    pub service_error_type: Option<String>,
+    /// Whether handlers extract the shared state with `poem::web::Data` and pass it
+    /// to the service trait methods
+    #[serde(default)]
+    pub generate_shared_state: bool,
+    /// Shared state type (e.g. `crate::AppState`) passed to the service trait methods;
+    /// the path must be resolvable from the generated modules
+    #[serde(default = "default_shared_state_type")]
+    pub shared_state_type: String,
+    /// Lints suppressed by a `#![allow(...)]` attribute at the top of each generated file
+    #[serde(default = "default_lint_allows")]
+    pub lint_allows: Vec<String>,
+    /// Casing applied to generated controller and service trait method names
+    #[serde(default)]
+    pub method_naming: MethodNaming,
+    /// Module path the generated code imports message types from (e.g. `crate::models`)
+    #[serde(default = "default_models_module_path")]
+    pub models_module_path: String,
+    /// Method option (e.g. `(debug_only)`) that gates an operation behind `#[cfg(...)]`;
+    /// `true` maps to `debug_assertions` and a string value is used as the cfg predicate
+    pub route_cfg_option: Option<String>,
+    /// `(pattern, replacement)` regex rules applied in order to every route path at generation
+    /// time, e.g. `("^/v1/", "/v2/")`; replacements may use `$1`-style capture references
+    #[serde(default)]
+    pub path_rewrites: Vec<(String, String)>,
+    /// Prefix (e.g. `/api`) prepended to every route path at generation time, after
+    /// the path rewrites, to match the mount point of the API
+    #[serde(default)]
+    pub path_prefix: Option<String>,
+    /// Payload used for responses of methods without a `(response_content_type)` option
+    #[serde(default)]
+    pub default_response_content_type: ResponseContentType,
+    /// Whether methods annotated with `option (content_negotiation) = true` respond with
+    /// JSON or protobuf depending on the `Accept` header
+    #[serde(default)]
+    pub content_negotiation: bool,
+    /// Exact snake_case forms (e.g. `gRPC` -> `grpc`) used for words in generated
+    /// identifiers and file names instead of the automatic conversion
+    #[serde(default)]
+    pub casing_overrides: HashMap<String, String>,
+    /// Existing Rust paths (e.g. `crate::external::Thing`) used for fully-qualified proto
+    /// types (e.g. `third.party.Thing`) instead of a generated model of the same name
+    #[serde(default)]
+    pub external_type_mappings: HashMap<String, String>,
+    /// Rules for generating create/update variants of resource models; `None` disables them
+    pub model_variants: Option<ModelVariantConfig>,
+    /// How field constraints such as `(maximum)` or `(buf.validate.field)` rules are enforced
+    /// by generated models
+    #[serde(default)]
+    pub constraint_validation: ConstraintValidation,
+    /// Visibility of the fields of generated model structs
+    #[serde(default)]
+    pub field_visibility: FieldVisibility,
+    /// Suffixes naming the controller, service trait and their modules generated per service
+    #[serde(default)]
+    pub naming: NamingConfig,
+    /// Extra attributes (e.g. `#[tracing::instrument]`) emitted on generated handler functions
+    #[serde(default)]
+    pub handler_attributes: Vec<HandlerAttribute>,
+}
+
+/// An attribute emitted on generated controller handler functions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandlerAttribute {
+    /// Attribute to emit, with or without the surrounding `#[...]`
+    pub attribute: String,
+    /// Regex the RPC method name must match; `None` applies the attribute to every handler
+    #[serde(default)]
+    pub method_pattern: Option<String>,
+}
+
+/// Suffixes appended to a service name to name the items generated for it
+///
+/// With the defaults, `UserService` yields a `UserServiceController` in
+/// `user_service_controller.rs` and a `UserServiceService` trait in
+/// `user_service_service.rs`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NamingConfig {
+    /// Suffix of controller type names (`Handler` yields `UserServiceHandler`)
+    pub controller_suffix: String,
+    /// Suffix of service trait names (`Api` yields `UserServiceApi`)
+    pub trait_suffix: String,
+    /// Suffix of controller module and file names (`handler` yields `user_service_handler.rs`)
+    pub controller_module_suffix: String,
+    /// Suffix of service trait module and file names (`api` yields `user_service_api.rs`)
+    pub trait_module_suffix: String,
+}
+
+impl Default for NamingConfig {
+    fn default() -> Self {
+        Self {
+            controller_suffix: "Controller".to_string(),
+            trait_suffix: "Service".to_string(),
+            controller_module_suffix: "controller".to_string(),
+            trait_module_suffix: "service".to_string(),
+        }
+    }
+}
+
+/// Naming and field rules for the create/update variants of resource models
+///
+/// A resource is a message with a `google.api.resource` option or at least one
+/// read-only field. Its create variant leaves out read-only and excluded fields;
+/// its update variant also leaves out immutable fields, makes the remaining fields
+/// optional and adds a field mask.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelVariantConfig {
+    /// Prefix of the create variant name (`Create` yields `CreateUser`)
+    pub create_prefix: String,
+    /// Prefix of the update variant name (`Update` yields `UpdateUser`)
+    pub update_prefix: String,
+    /// Name of the field mask field on the update variant
+    pub update_mask_field: String,
+    /// Field names left out of both variants in addition to read-only fields
+    #[serde(default)]
+    pub excluded_fields: Vec<String>,
+}
+
+impl Default for ModelVariantConfig {
+    fn default() -> Self {
+        Self {
+            create_prefix: "Create".to_string(),
+            update_prefix: "Update".to_string(),
+            update_mask_field: "update_mask".to_string(),
+            excluded_fields: Vec::new(),
+        }
+    }
+}
+
+/// Lints allowed in generated code by default
+///
+/// Only lints the templates are known to trigger: message types and poem extractors
+/// are imported for every route, and some handlers ignore their parameters.
+fn default_lint_allows() -> Vec<String> {
+    vec![
+        "unused_imports".to_string(),
+        "unused_variables".to_string(),
+    ]
+}
+
+/// Module path message types are imported from by default
+fn default_models_module_path() -> String {
+    "super".to_string()
+}
+
+fn default_max_controller_tuple_arity() -> usize {
+    16
+}
+
+fn default_shared_state_type() -> String {
+    "AppState".to_string()
 }
 
 impl Default for GeneratorConfig {
@@ -87,15 +418,132 @@ impl Default for GeneratorConfig {
             type_mappings: HashMap::new(),
             additional_imports: Vec::new(),
             formatting: FormattingConfig::default(),
+            file_header: None,
+            generate_tags_enum: false,
+            generate_registration_helper: false,
+            max_controller_tuple_arity: default_max_controller_tuple_arity(),
+            max_operations_per_file: None,
+            generate_error_module: false,
+            validate_parameters: false,
+            generate_mocks: false,
+            generate_tests: false,
+            generate_pagination_helpers: false,
+            generate_builders: false,
+            generate_defaults: false,
+            non_exhaustive: false,
+            generate_path_builders: false,
+            generate_cors_config: false,
+            generate_route_inventory: false,
+            generate_prelude: false,
+            response_wrapper: None,
+            service_error_type: None,
+            use_native_async_trait: false,
+            package_to_module: false,
+            generate_shared_state: false,
+            shared_state_type: default_shared_state_type(),
+            lint_allows: default_lint_allows(),
+            method_naming: MethodNaming::SnakeCase,
+            models_module_path: default_models_module_path(),
+            route_cfg_option: None,
+            path_rewrites: Vec::new(),
+            path_prefix: None,
+            default_response_content_type: ResponseContentType::Json,
+            content_negotiation: false,
+            casing_overrides: HashMap::new(),
+            external_type_mappings: HashMap::new(),
+            model_variants: None,
+            constraint_validation: ConstraintValidation::Attributes,
+            field_visibility: FieldVisibility::Public,
+            naming: NamingConfig::default(),
+            handler_attributes: Vec::new(),
         }
     }
 }
 
 /// Target framework for code generation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TargetFramework {
     PoemOpenApi,
-    // Future: Axum, Warp, etc.
+    /// Third-party framework whose generator is registered at runtime
+    Custom(String),
+}
+
+/// Casing convention for generated method names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MethodNaming {
+    /// `get_user`
+    #[default]
+    SnakeCase,
+    /// `getUser`
+    CamelCase,
+    /// Keep the proto method name as written (`GetUser`)
+    Preserve,
+}
+
+impl MethodNaming {
+    /// Name used to select the convention from templates
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MethodNaming::SnakeCase => "snake_case",
+            MethodNaming::CamelCase => "camel_case",
+            MethodNaming::Preserve => "preserve",
+        }
+    }
+}
+
+/// How generated models enforce field constraints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ConstraintValidation {
+    /// poem-openapi `#[oai(validator(...))]` attributes, checked on deserialization
+    #[default]
+    Attributes,
+    /// A `validate(&self)` method on each model; `pattern` constraints are not checked
+    Method,
+}
+
+/// Visibility of generated model fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FieldVisibility {
+    /// `pub` fields
+    #[default]
+    Public,
+    /// `pub(crate)` fields
+    Crate,
+    /// Private fields, read and written through generated getter and setter methods
+    PrivateWithAccessors,
+}
+
+/// poem-openapi payload used for generated responses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ResponseContentType {
+    /// `application/json` via `payload::Json`
+    #[default]
+    Json,
+    /// `text/plain` via `payload::PlainText`
+    PlainText,
+    /// `application/octet-stream` via `payload::Binary`
+    Binary,
+}
+
+impl ResponseContentType {
+    /// Parse a content type hint, either a MIME type (`text/plain`) or a payload name (`plain_text`)
+    pub fn from_hint(hint: &str) -> Option<Self> {
+        match hint.trim().to_ascii_lowercase().as_str() {
+            "json" | "application/json" => Some(ResponseContentType::Json),
+            "plain_text" | "plaintext" | "text" | "text/plain" => Some(ResponseContentType::PlainText),
+            "binary" | "bytes" | "application/octet-stream" => Some(ResponseContentType::Binary),
+            _ => None,
+        }
+    }
+    
+    /// Name of the poem-openapi payload type
+    pub fn payload_type(&self) -> &'static str {
+        match self {
+            ResponseContentType::Json => "Json",
+            ResponseContentType::PlainText => "PlainText",
+            ResponseContentType::Binary => "Binary",
+        }
+    }
 }
 
 /// Code formatting configuration
@@ -186,6 +634,13 @@ pub struct ProtoHttpParserConfig {
     pub generator: GeneratorConfig,
     /// Template configuration
     pub template: TemplateConfig,
+    /// Whether to collect per-phase timing metrics while processing
+    #[serde(default)]
+    pub collect_metrics: bool,
+    /// Whether to report every validation error of a file as
+    /// [`ProtoHttpParserError::Multiple`](crate::ProtoHttpParserError::Multiple) instead of only the first
+    #[serde(default)]
+    pub collect_all_errors: bool,
 }
 
 
@@ -368,6 +823,20 @@ impl ProtoHttpParserConfig {
             });
         }
         
+        if self.parser.max_nesting_depth == 0 {
+            return Err(ConfigError::ValidationError {
+                field: "parser.max_nesting_depth".to_string(),
+                message: "must be greater than 0".to_string(),
+            });
+        }
+        
+        if self.extractor.max_path_template_length == 0 {
+            return Err(ConfigError::ValidationError {
+                field: "extractor.max_path_template_length".to_string(),
+                message: "must be greater than 0".to_string(),
+            });
+        }
+        
         // Validate generator configuration
         if self.generator.formatting.indent_size == 0 {
             return Err(ConfigError::ValidationError {
@@ -390,6 +859,101 @@ impl ProtoHttpParserConfig {
             });
         }
         
+        if self.generator.generate_mocks && !self.generator.generate_service_traits {
+            return Err(ConfigError::ValidationError {
+                field: "generator.generate_mocks".to_string(),
+                message: "mocks implement the service traits, so generate_service_traits must be enabled".to_string(),
+            });
+        }
+        
+        if !(2..=16).contains(&self.generator.max_controller_tuple_arity) {
+            return Err(ConfigError::ValidationError {
+                field: "generator.max_controller_tuple_arity".to_string(),
+                message: "must be between 2 and 16, the tuple sizes poem-openapi supports".to_string(),
+            });
+        }
+        
+        if self.generator.max_operations_per_file == Some(0) {
+            return Err(ConfigError::ValidationError {
+                field: "generator.max_operations_per_file".to_string(),
+                message: "must allow at least one operation per file".to_string(),
+            });
+        }
+        
+        if self.generator.generate_shared_state && self.generator.shared_state_type.trim().is_empty() {
+            return Err(ConfigError::ValidationError {
+                field: "generator.shared_state_type".to_string(),
+                message: "must name a type when generate_shared_state is enabled".to_string(),
+            });
+        }
+        
+        if self.generator.validate_parameters && !self.generator.generate_error_module {
+            return Err(ConfigError::ValidationError {
+                field: "generator.validate_parameters".to_string(),
+                message: "parameter parse failures are answered with ApiError::BadRequest, so generate_error_module must be enabled".to_string(),
+            });
+        }
+        
+        if self.generator.generate_tests && !self.generator.generate_mocks {
+            return Err(ConfigError::ValidationError {
+                field: "generator.generate_tests".to_string(),
+                message: "the generated tests serve routes through the mocks, so generate_mocks must be enabled".to_string(),
+            });
+        }
+        
+        if let Some(variants) = &self.generator.model_variants {
+            if variants.create_prefix.is_empty() || variants.update_prefix.is_empty()
+                || variants.create_prefix == variants.update_prefix
+            {
+                return Err(ConfigError::ValidationError {
+                    field: "generator.model_variants".to_string(),
+                    message: "create and update prefixes must be non-empty and distinct".to_string(),
+                });
+            }
+            
+            if variants.update_mask_field.is_empty() {
+                return Err(ConfigError::ValidationError {
+                    field: "generator.model_variants.update_mask_field".to_string(),
+                    message: "cannot be empty".to_string(),
+                });
+            }
+        }
+        
+        let naming = &self.generator.naming;
+        if naming.controller_suffix == naming.trait_suffix {
+            return Err(ConfigError::ValidationError {
+                field: "generator.naming".to_string(),
+                message: "controller and trait suffixes must be distinct".to_string(),
+            });
+        }
+        
+        if naming.controller_module_suffix.is_empty() || naming.controller_module_suffix == naming.trait_module_suffix {
+            return Err(ConfigError::ValidationError {
+                field: "generator.naming".to_string(),
+                message: "controller and trait module suffixes must be non-empty and distinct".to_string(),
+            });
+        }
+        
+        for handler_attribute in &self.generator.handler_attributes {
+            if let Some(pattern) = &handler_attribute.method_pattern {
+                if let Err(e) = regex::Regex::new(pattern) {
+                    return Err(ConfigError::ValidationError {
+                        field: "generator.handler_attributes".to_string(),
+                        message: format!("invalid method pattern '{}': {}", pattern, e),
+                    });
+                }
+            }
+        }
+        
+        for (pattern, _) in &self.generator.path_rewrites {
+            if let Err(e) = regex::Regex::new(pattern) {
+                return Err(ConfigError::ValidationError {
+                    field: "generator.path_rewrites".to_string(),
+                    message: format!("invalid pattern '{}': {}", pattern, e),
+                });
+            }
+        }
+        
         // Validate include paths exist
         for path in &self.parser.include_paths {
             if !path.exists() {
@@ -515,6 +1079,42 @@ impl ConfigBuilder {
         self
     }
     
+    /// Set maximum nesting depth of message and enum definitions
+    pub fn max_nesting_depth(mut self, depth: usize) -> Self {
+        self.config.parser.max_nesting_depth = depth;
+        self
+    }
+    
+    /// Set how options outside the known set are treated
+    pub fn unknown_option_policy(mut self, policy: UnknownOptionPolicy) -> Self {
+        self.config.parser.unknown_option_policy = policy;
+        self
+    }
+    
+    /// Skip unrecognized top-level statements instead of failing the parse
+    pub fn allow_unknown_statements(mut self, allow: bool) -> Self {
+        self.config.parser.allow_unknown_statements = allow;
+        self
+    }
+    
+    /// Set how normal imports that cannot be found are treated
+    pub fn missing_import_policy(mut self, policy: MissingImportPolicy) -> Self {
+        self.config.parser.missing_import_policy = policy;
+        self
+    }
+    
+    /// Keep the exact source text of each top-level declaration
+    pub fn keep_source_spans(mut self, keep: bool) -> Self {
+        self.config.parser.keep_source_spans = keep;
+        self
+    }
+    
+    /// Enable or disable skipping top-level declarations that fail to parse
+    pub fn error_recovery(mut self, recover: bool) -> Self {
+        self.config.parser.error_recovery = recover;
+        self
+    }
+    
     /// Add include path for proto imports
     pub fn add_include_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
         self.config.parser.include_paths.push(path.into());
@@ -539,12 +1139,252 @@ impl ConfigBuilder {
         self
     }
     
+    /// Enable or disable generation of the `Tags` enum
+    pub fn generate_tags_enum(mut self, generate: bool) -> Self {
+        self.config.generator.generate_tags_enum = generate;
+        self
+    }
+    
+    /// Enable or disable generation of the `register_apis` helper and API metadata module
+    pub fn generate_registration_helper(mut self, generate: bool) -> Self {
+        self.config.generator.generate_registration_helper = generate;
+        self
+    }
+    
+    /// Set the largest tuple of controllers built by the registration helper
+    pub fn max_controller_tuple_arity(mut self, arity: usize) -> Self {
+        self.config.generator.max_controller_tuple_arity = arity;
+        self
+    }
+    
+    /// Split controllers with more operations than `max` across several files
+    pub fn max_operations_per_file(mut self, max: usize) -> Self {
+        self.config.generator.max_operations_per_file = Some(max);
+        self
+    }
+    
+    /// Enable or disable generation of the shared `ApiError` errors module
+    pub fn generate_error_module(mut self, generate: bool) -> Self {
+        self.config.generator.generate_error_module = generate;
+        self
+    }
+    
+    /// Enable or disable parsing parameters in the handlers, answering `400` on failure
+    pub fn validate_parameters(mut self, validate: bool) -> Self {
+        self.config.generator.validate_parameters = validate;
+        self
+    }
+    
+    /// Enable or disable generation of the `paths` module of route path builders
+    pub fn generate_path_builders(mut self, generate: bool) -> Self {
+        self.config.generator.generate_path_builders = generate;
+        self
+    }
+    
+    /// Enable or disable generation of the per-path `cors_config()` helper
+    pub fn generate_cors_config(mut self, generate: bool) -> Self {
+        self.config.generator.generate_cors_config = generate;
+        self
+    }
+    
+    /// Enable or disable the `routes()` inventory on generated controllers
+    pub fn generate_route_inventory(mut self, generate: bool) -> Self {
+        self.config.generator.generate_route_inventory = generate;
+        self
+    }
+    
+    /// Enable or disable generation of the `prelude` re-export module
+    pub fn generate_prelude(mut self, generate: bool) -> Self {
+        self.config.generator.generate_prelude = generate;
+        self
+    }
+    
+    /// Enable or disable generation of mock service implementations
+    pub fn generate_mocks(mut self, generate: bool) -> Self {
+        self.config.generator.generate_mocks = generate;
+        self
+    }
+    
+    /// Enable or disable generation of per-service integration test scaffolding
+    pub fn generate_tests(mut self, generate: bool) -> Self {
+        self.config.generator.generate_tests = generate;
+        self
+    }
+    
+    /// Enable or disable generation of pagination helpers for list methods
+    pub fn generate_pagination_helpers(mut self, generate: bool) -> Self {
+        self.config.generator.generate_pagination_helpers = generate;
+        self
+    }
+    
+    /// Enable or disable generation of model builders
+    pub fn generate_builders(mut self, generate: bool) -> Self {
+        self.config.generator.generate_builders = generate;
+        self
+    }
+    
+    /// Enable or disable `Default` implementations for models
+    pub fn generate_defaults(mut self, generate: bool) -> Self {
+        self.config.generator.generate_defaults = generate;
+        self
+    }
+    
+    /// Enable or disable `#[non_exhaustive]` on generated structs and enums
+    pub fn non_exhaustive(mut self, non_exhaustive: bool) -> Self {
+        self.config.generator.non_exhaustive = non_exhaustive;
+        self
+    }
+    
+    /// Set the casing convention for generated method names
+    pub fn method_naming(mut self, naming: MethodNaming) -> Self {
+        self.config.generator.method_naming = naming;
+        self
+    }
+    
+    /// Set the module path generated code imports message types from
+    pub fn models_module_path<S: Into<String>>(mut self, path: S) -> Self {
+        self.config.generator.models_module_path = path.into();
+        self
+    }
+    
+    /// Set the method option used to gate operations behind `#[cfg(...)]`
+    pub fn route_cfg_option<S: Into<String>>(mut self, option_name: S) -> Self {
+        self.config.generator.route_cfg_option = Some(option_name.into());
+        self
+    }
+    
+    /// Enable or disable native async service traits without `#[async_trait]`
+    pub fn use_native_async_trait(mut self, native: bool) -> Self {
+        self.config.generator.use_native_async_trait = native;
+        self
+    }
+    
+    /// Enable or disable nesting generated files in per-package module directories
+    pub fn package_to_module(mut self, nest: bool) -> Self {
+        self.config.generator.package_to_module = nest;
+        self
+    }
+    
+    /// Set the error type returned by service trait methods
+    pub fn service_error_type<S: Into<String>>(mut self, error_type: S) -> Self {
+        self.config.generator.service_error_type = Some(error_type.into());
+        self
+    }
+    
+    /// Enable or disable passing shared state to the service trait methods
+    pub fn generate_shared_state(mut self, generate: bool) -> Self {
+        self.config.generator.generate_shared_state = generate;
+        self
+    }
+    
+    /// Set the shared state type passed to the service trait methods
+    pub fn shared_state_type<S: Into<String>>(mut self, state_type: S) -> Self {
+        self.config.generator.shared_state_type = state_type.into();
+        self
+    }
+    
+    /// Pin the snake_case form of a word in generated identifiers and file names
+    pub fn casing_override<S: Into<String>>(mut self, word: S, snake_case: S) -> Self {
+        self.config.generator.casing_overrides.insert(word.into(), snake_case.into());
+        self
+    }
+    
+    /// Map a fully-qualified proto type to an existing Rust path
+    pub fn external_type_mapping<S: Into<String>>(mut self, proto_type: S, rust_path: S) -> Self {
+        self.config.generator.external_type_mappings.insert(proto_type.into(), rust_path.into());
+        self
+    }
+    
+    /// Set the suffixes naming each service's controller, service trait and their modules
+    pub fn naming(mut self, naming: NamingConfig) -> Self {
+        self.config.generator.naming = naming;
+        self
+    }
+    
+    /// Generate create/update variants of resource models following the given rules
+    pub fn model_variants(mut self, variants: ModelVariantConfig) -> Self {
+        self.config.generator.model_variants = Some(variants);
+        self
+    }
+    
+    /// Set how generated models enforce field constraints
+    pub fn constraint_validation(mut self, validation: ConstraintValidation) -> Self {
+        self.config.generator.constraint_validation = validation;
+        self
+    }
+    
+    /// Set the visibility of generated model fields
+    pub fn field_visibility(mut self, visibility: FieldVisibility) -> Self {
+        self.config.generator.field_visibility = visibility;
+        self
+    }
+    
+    /// Add an attribute emitted on every generated handler function
+    pub fn handler_attribute<S: Into<String>>(mut self, attribute: S) -> Self {
+        self.config.generator.handler_attributes.push(HandlerAttribute {
+            attribute: attribute.into(),
+            method_pattern: None,
+        });
+        self
+    }
+    
+    /// Add an attribute emitted on the handlers of methods whose name matches a regex
+    pub fn handler_attribute_for<P: Into<String>, S: Into<String>>(mut self, method_pattern: P, attribute: S) -> Self {
+        self.config.generator.handler_attributes.push(HandlerAttribute {
+            attribute: attribute.into(),
+            method_pattern: Some(method_pattern.into()),
+        });
+        self
+    }
+    
+    /// Add a regex rule rewriting route paths at generation time
+    pub fn path_rewrite<P: Into<String>, R: Into<String>>(mut self, pattern: P, replacement: R) -> Self {
+        self.config.generator.path_rewrites.push((pattern.into(), replacement.into()));
+        self
+    }
+    
+    /// Set the prefix prepended to every route path at generation time
+    pub fn path_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.config.generator.path_prefix = Some(prefix.into());
+        self
+    }
+    
+    /// Set the payload used for responses of methods without a content type option
+    pub fn default_response_content_type(mut self, content_type: ResponseContentType) -> Self {
+        self.config.generator.default_response_content_type = content_type;
+        self
+    }
+    
+    /// Enable or disable JSON/protobuf content negotiation for annotated methods
+    pub fn content_negotiation(mut self, enable: bool) -> Self {
+        self.config.generator.content_negotiation = enable;
+        self
+    }
+    
     /// Enable or disable query parameter inference
     pub fn infer_query_params(mut self, infer: bool) -> Self {
         self.config.extractor.infer_query_params = infer;
         self
     }
     
+    /// Set which methods receive inferred common query parameters
+    pub fn query_param_inference(mut self, inference: QueryParamInference) -> Self {
+        self.config.extractor.query_param_inference = inference;
+        self
+    }
+    
+    /// Set the longest path template, in bytes, the extractor accepts
+    pub fn max_path_template_length(mut self, length: usize) -> Self {
+        self.config.extractor.max_path_template_length = length;
+        self
+    }
+    
+    /// Set the method name prefixes identifying list-style methods
+    pub fn list_method_prefixes<S: Into<String>>(mut self, prefixes: Vec<S>) -> Self {
+        self.config.extractor.list_method_prefixes = prefixes.into_iter().map(|p| p.into()).collect();
+        self
+    }
+    
     /// Enable or disable rustfmt formatting
     pub fn use_rustfmt(mut self, use_fmt: bool) -> Self {
         self.config.generator.formatting.use_rustfmt = use_fmt;
@@ -581,6 +1421,42 @@ impl ConfigBuilder {
         self
     }
     
+    /// Add a lint to the `#![allow(...)]` attribute of generated files
+    pub fn add_lint_allow<S: Into<String>>(mut self, lint: S) -> Self {
+        self.config.generator.lint_allows.push(lint.into());
+        self
+    }
+    
+    /// Set the lints allowed in generated files, replacing the defaults
+    pub fn lint_allows<S: Into<String>>(mut self, lints: Vec<S>) -> Self {
+        self.config.generator.lint_allows = lints.into_iter().map(|l| l.into()).collect();
+        self
+    }
+    
+    /// Enable or disable per-phase timing metrics
+    pub fn collect_metrics(mut self, collect: bool) -> Self {
+        self.config.collect_metrics = collect;
+        self
+    }
+    
+    /// Enable or disable reporting every validation error of a file at once
+    pub fn collect_all_errors(mut self, collect: bool) -> Self {
+        self.config.collect_all_errors = collect;
+        self
+    }
+    
+    /// Set the envelope type wrapping every unary response
+    pub fn response_wrapper<S: Into<String>>(mut self, wrapper: S) -> Self {
+        self.config.generator.response_wrapper = Some(wrapper.into());
+        self
+    }
+    
+    /// Set the header prepended to every generated file
+    pub fn file_header<S: Into<String>>(mut self, header: S) -> Self {
+        self.config.generator.file_header = Some(header.into());
+        self
+    }
+    
     /// Set template directory
     pub fn template_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
         self.config.template.template_dir = Some(dir.into());
@@ -668,6 +1544,49 @@ mod tests {
             .build();
         
         assert!(config.is_err());
+        
+        let config = ConfigBuilder::new()
+            .path_rewrite("^/v1/(", "/v2/")
+            .build();
+        
+        assert!(config.is_err());
+        
+        let config = ConfigBuilder::new()
+            .path_rewrite("^/v1/", "/v2/")
+            .build();
+        
+        assert!(config.is_ok());
+        
+        let config = ConfigBuilder::new()
+            .model_variants(ModelVariantConfig {
+                update_prefix: "Create".to_string(),
+                ..ModelVariantConfig::default()
+            })
+            .build();
+        
+        assert!(config.is_err());
+        
+        let config = ConfigBuilder::new()
+            .model_variants(ModelVariantConfig {
+                update_prefix: String::new(),
+                ..ModelVariantConfig::default()
+            })
+            .build();
+        
+        assert!(config.is_err());
+        
+        let config = ConfigBuilder::new()
+            .generate_service_traits(false)
+            .generate_mocks(true)
+            .build();
+        
+        assert!(config.is_err());
+        
+        let config = ConfigBuilder::new()
+            .generate_tests(true)
+            .build();
+        
+        assert!(config.is_err());
     }
     
     #[test]
@@ -687,7 +1606,7 @@ mod tests {
         // Load configuration
         let loaded_config = ProtoHttpParserConfig::from_file(&config_path).unwrap();
         
-        assert_eq!(loaded_config.parser.preserve_comments, false);
+        assert!(!loaded_config.parser.preserve_comments);
         assert_eq!(loaded_config.parser.max_import_depth, 15);
     }
     
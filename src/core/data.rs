@@ -2,12 +2,16 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use super::Visitor;
 
 /// Protocol Buffer version
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ProtocolVersion {
     Proto2,
     Proto3,
+    /// Editions file, e.g. `edition = "2023";`; features are not interpreted, so
+    /// fields are generated as in proto3
+    Edition(String),
 }
 
 /// Represents a complete Protocol Buffer file
@@ -27,6 +31,84 @@ pub struct ProtoFile {
     pub messages: Vec<Message>,
     /// Enum definitions
     pub enums: Vec<Enum>,
+    /// Unrecognized top-level statements skipped by a lenient parse, as written
+    #[serde(default)]
+    pub unknown_statements: Vec<String>,
+}
+
+/// Metadata describing a whole API, as passed to `poem_openapi::OpenApiService::new`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiMetadata {
+    /// API title
+    pub title: String,
+    /// API version
+    pub version: String,
+    /// Longer description of the API
+    pub description: std::option::Option<String>,
+    /// Servers the API is reachable at, in declaration order
+    #[serde(default)]
+    pub servers: Vec<ApiServer>,
+}
+
+/// Server an API is reachable at, declared by a `server` file option
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiServer {
+    /// URL template, e.g. `https://{region}.api.example.com`
+    pub url: String,
+    /// Description of the server
+    pub description: std::option::Option<String>,
+    /// Variables substituted into `{name}` placeholders of the URL
+    pub variables: Vec<ServerVariable>,
+}
+
+/// Variable of a templated server URL
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerVariable {
+    /// Name of the `{name}` placeholder
+    pub name: String,
+    /// Value used when the client does not provide one
+    pub default: String,
+    /// Description of the variable
+    pub description: std::option::Option<String>,
+}
+
+impl ApiServer {
+    /// Parse a `server` option value
+    ///
+    /// A string value is an untemplated URL. An aggregate value holds the `url`, an
+    /// optional `description` and a `variables` entry per template variable, e.g.
+    /// `{ url: "https://{region}.api.example.com" variables: { name: "region" default: "us-east" } }`.
+    pub fn from_option_value(value: &OptionValue) -> std::option::Option<Self> {
+        if let std::option::Option::Some(url) = value.as_str() {
+            return std::option::Option::Some(Self {
+                url: url.to_string(),
+                description: std::option::Option::None,
+                variables: Vec::new(),
+            });
+        }
+        
+        let text = |value: &OptionValue, key: &str| value.get(key)
+            .and_then(OptionValue::as_str)
+            .map(str::to_string);
+        std::option::Option::Some(Self {
+            url: text(value, "url")?,
+            description: text(value, "description"),
+            variables: value.get_all("variables")
+                .filter_map(|variable| std::option::Option::Some(ServerVariable {
+                    name: text(variable, "name")?,
+                    default: text(variable, "default")?,
+                    description: text(variable, "description"),
+                }))
+                .collect(),
+        })
+    }
+    
+    /// Get the URL with every declared variable replaced by its default
+    pub fn default_url(&self) -> String {
+        self.variables.iter().fold(self.url.clone(), |url, variable| {
+            url.replace(&format!("{{{}}}", variable.name), &variable.default)
+        })
+    }
 }
 
 /// Import statement in a proto file
@@ -62,7 +144,9 @@ pub enum OptionValue {
     Number(f64),
     Boolean(bool),
     Identifier(String),
-    MessageLiteral(HashMap<String, OptionValue>),
+    /// Aggregate `{ key: value ... }` value; entries keep their source order and
+    /// a key may repeat
+    MessageLiteral(Vec<(String, OptionValue)>),
 }
 
 /// Service definition
@@ -76,6 +160,10 @@ pub struct Service {
     pub options: Vec<ProtoOption>,
     /// Documentation comments
     pub comments: Vec<Comment>,
+    /// Exact source text of the top-level declaration, without leading comments; only
+    /// recorded when [`ParserConfig::keep_source_spans`](crate::ParserConfig) is set
+    #[serde(default)]
+    pub source: std::option::Option<String>,
 }
 
 /// RPC method definition
@@ -93,10 +181,24 @@ pub struct RpcMethod {
     pub comments: Vec<Comment>,
     /// HTTP annotation if present
     pub http_annotation: std::option::Option<HttpAnnotation>,
+    /// Idempotency level declared via `option idempotency_level`
+    #[serde(default)]
+    pub idempotency_level: std::option::Option<IdempotencyLevel>,
+    /// Preferred argument ordering declared via `option (google.api.method_signature)`
+    #[serde(default)]
+    pub method_signature: Vec<String>,
+}
+
+/// Method idempotency level (`google.protobuf.MethodOptions.IdempotencyLevel`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum IdempotencyLevel {
+    IdempotencyUnknown,
+    NoSideEffects,
+    Idempotent,
 }
 
 /// Type reference (can be simple or fully qualified)
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct TypeReference {
     /// Type name
     pub name: String,
@@ -106,6 +208,26 @@ pub struct TypeReference {
     pub is_stream: bool,
 }
 
+/// Wrapper types (`google/protobuf/wrappers.proto`) and the scalar type each one holds
+const WRAPPER_TYPES: &[(&str, &str)] = &[
+    ("google.protobuf.DoubleValue", "double"),
+    ("google.protobuf.FloatValue", "float"),
+    ("google.protobuf.Int64Value", "int64"),
+    ("google.protobuf.UInt64Value", "uint64"),
+    ("google.protobuf.Int32Value", "int32"),
+    ("google.protobuf.UInt32Value", "uint32"),
+    ("google.protobuf.BoolValue", "bool"),
+    ("google.protobuf.StringValue", "string"),
+    ("google.protobuf.BytesValue", "bytes"),
+];
+
+/// Get the scalar type held by the wrapper type with the given fully-qualified name
+pub(crate) fn wrapped_scalar(qualified_name: &str) -> std::option::Option<&'static str> {
+    WRAPPER_TYPES.iter()
+        .find(|(wrapper, _)| *wrapper == qualified_name)
+        .map(|(_, scalar)| *scalar)
+}
+
 /// HTTP annotation from google.api.http
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HttpAnnotation {
@@ -119,8 +241,12 @@ pub struct HttpAnnotation {
     pub additional_bindings: Vec<HttpBinding>,
 }
 
+/// HTTP rules supplied outside the proto file, keyed by fully-qualified
+/// method name (`package.Service.Method`, a leading `.` is allowed)
+pub type HttpRuleOverrides = HashMap<String, HttpAnnotation>;
+
 /// HTTP method types
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum HttpMethod {
     Get,
     Post,
@@ -142,7 +268,7 @@ pub struct HttpBinding {
 }
 
 /// Structured HTTP route information
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct HttpRoute {
     /// Service name
     pub service_name: String,
@@ -162,10 +288,81 @@ pub struct HttpRoute {
     pub input_type: TypeReference,
     /// Response type
     pub response_type: TypeReference,
+    /// Idempotency level of the underlying RPC method
+    #[serde(default)]
+    pub idempotency_level: std::option::Option<IdempotencyLevel>,
+    /// Whether the method follows the AIP list pagination convention
+    /// (`page_size`/`page_token` request fields, `next_page_token` in the response)
+    #[serde(default)]
+    pub is_paginated: bool,
+    /// Deprecation of the underlying RPC method, if any
+    #[serde(default)]
+    pub deprecation: std::option::Option<Deprecation>,
+    /// `path_template` split into literal text and parameters
+    #[serde(default)]
+    pub segments: Vec<PathSegment>,
+    /// Rate limit declared on the underlying RPC method, if any
+    #[serde(default)]
+    pub rate_limit: std::option::Option<RateLimit>,
+    /// Authentication scheme protecting the operation, declared on the method, its
+    /// service or the file
+    #[serde(default)]
+    pub security: std::option::Option<SecurityScheme>,
+}
+
+/// Authentication scheme declared by a `security` option, e.g. `(openapi.security) = "bearer"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityScheme {
+    /// `Authorization: Bearer <token>`
+    Bearer,
+    /// `Authorization: Basic <credentials>`
+    Basic,
+    /// API key passed in the `X-API-Key` header
+    ApiKey,
+}
+
+/// Piece of a path template, as produced by [`PathSegment::parse`]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PathSegment {
+    /// Literal text between parameters, e.g. `/v1/users/`
+    Literal(String),
+    /// `{name}` or `{name=pattern}` parameter; `name` is the field path as written
+    /// (e.g. `book.id`) and `pattern` the segment pattern (e.g. `shelves/*`), if any
+    Param {
+        name: String,
+        pattern: std::option::Option<String>,
+    },
+}
+
+/// Deprecation metadata of an operation
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct Deprecation {
+    /// Date (e.g. `2025-06-01`) after which the operation is removed
+    pub sunset: std::option::Option<String>,
+}
+
+/// Rate limit of an operation, declared as `"100/min"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Number of requests allowed per period
+    pub requests: u64,
+    /// Period the requests are counted over
+    pub period: RateLimitPeriod,
+}
+
+/// Period of a [`RateLimit`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitPeriod {
+    Second,
+    Minute,
+    Hour,
+    Day,
 }
 
 /// Path parameter definition
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct PathParameter {
     /// Parameter name
     pub name: String,
@@ -176,7 +373,7 @@ pub struct PathParameter {
 }
 
 /// Query parameter definition
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct QueryParameter {
     /// Parameter name
     pub name: String,
@@ -184,23 +381,45 @@ pub struct QueryParameter {
     pub param_type: ParameterType,
     /// Whether the parameter is required
     pub required: bool,
+    /// How the parameter is serialized in the query string
+    #[serde(default)]
+    pub style: QueryParameterStyle,
+}
+
+/// OpenAPI serialization style of a query parameter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum QueryParameterStyle {
+    /// `?name=value`, the OpenAPI default for query parameters
+    #[default]
+    Form,
+    /// `?name[field]=value`, used for parameters backed by nested messages
+    DeepObject,
 }
 
 /// Parameter type information
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ParameterType {
     String,
     Integer,
     Float,
     Boolean,
+    /// `google.protobuf.Timestamp`, accepted as an RFC3339 string
+    Timestamp,
+    /// Enum type, carrying the enum type name
+    Enum(String),
     Custom(String),
 }
 
 /// Request body configuration
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct RequestBody {
     /// Field name in the request message
     pub field: std::option::Option<String>,
+    /// Resolved type of the body field, when `field` names a field of the request message
+    pub field_type: std::option::Option<TypeReference>,
+    /// Whether the body field is repeated, making the body a list of `field_type`
+    #[serde(default)]
+    pub is_repeated: bool,
     /// Content type
     pub content_type: String,
     /// Whether the entire message is the body
@@ -222,6 +441,28 @@ pub struct Message {
     pub options: Vec<ProtoOption>,
     /// Documentation comments
     pub comments: Vec<Comment>,
+    /// Whether the message was declared by a proto2 `group` field
+    #[serde(default)]
+    pub is_group: bool,
+    /// Field number ranges declared by `reserved` statements
+    #[serde(default)]
+    pub reserved_ranges: Vec<ReservedRange>,
+    /// Field names declared by `reserved` statements
+    #[serde(default)]
+    pub reserved_names: Vec<String>,
+    /// Exact source text of the top-level declaration, without leading comments; only
+    /// recorded when [`ParserConfig::keep_source_spans`](crate::ParserConfig) is set
+    #[serde(default)]
+    pub source: std::option::Option<String>,
+}
+
+/// A field number range declared by a `reserved` statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReservedRange {
+    /// First reserved number
+    pub start: u32,
+    /// Last reserved number (inclusive), or `None` for `to max`
+    pub end: std::option::Option<u32>,
 }
 
 /// Message field definition
@@ -239,6 +480,58 @@ pub struct Field {
     pub options: Vec<ProtoOption>,
     /// Documentation comments
     pub comments: Vec<Comment>,
+    /// Whether the field declares `[packed = true]`, asking for the packed wire
+    /// encoding of a repeated scalar field
+    #[serde(default)]
+    pub packed: bool,
+    /// Behaviors declared by `(google.api.field_behavior)` options, in source order
+    #[serde(default)]
+    pub behaviors: Vec<FieldBehavior>,
+}
+
+/// AIP field behavior, as declared by `(google.api.field_behavior)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FieldBehavior {
+    /// `OPTIONAL`: the field may be omitted
+    Optional,
+    /// `REQUIRED`: the field must be set by the client
+    Required,
+    /// `OUTPUT_ONLY`: the field is set by the server only
+    OutputOnly,
+    /// `INPUT_ONLY`: the field is set by the client only and never returned
+    InputOnly,
+    /// `IMMUTABLE`: the field can be set on creation but never changed
+    Immutable,
+    /// `UNORDERED_LIST`: the order of a repeated field is not preserved
+    UnorderedList,
+    /// `NON_EMPTY_DEFAULT`: the server fills in a default when the field is unset
+    NonEmptyDefault,
+    /// `IDENTIFIER`: the field holds the resource name
+    Identifier,
+}
+
+/// Value constraint declared on a field, e.g. by `[(maximum) = 100]` or
+/// `[(buf.validate.field).int32 = {lte: 100}]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FieldConstraint {
+    /// Largest allowed number, inclusive
+    Maximum(f64),
+    /// Largest allowed number, exclusive
+    ExclusiveMaximum(f64),
+    /// Smallest allowed number, inclusive
+    Minimum(f64),
+    /// Smallest allowed number, exclusive
+    ExclusiveMinimum(f64),
+    /// Largest allowed string length, in characters
+    MaxLength(u64),
+    /// Smallest allowed string length, in characters
+    MinLength(u64),
+    /// Regular expression strings must match
+    Pattern(String),
+    /// Largest allowed number of items in a repeated field
+    MaxItems(u64),
+    /// Smallest allowed number of items in a repeated field
+    MinItems(u64),
 }
 
 /// Field type
@@ -262,6 +555,11 @@ pub enum FieldType {
     Bytes,
     /// Message or enum type
     MessageOrEnum(TypeReference),
+    /// Map type (`map<K, V>`)
+    Map {
+        key: Box<FieldType>,
+        value: Box<FieldType>,
+    },
 }
 
 /// Field label
@@ -283,6 +581,10 @@ pub struct Enum {
     pub options: Vec<ProtoOption>,
     /// Documentation comments
     pub comments: Vec<Comment>,
+    /// Exact source text of the top-level declaration, without leading comments; only
+    /// recorded when [`ParserConfig::keep_source_spans`](crate::ParserConfig) is set
+    #[serde(default)]
+    pub source: std::option::Option<String>,
 }
 
 /// Enum value definition
@@ -491,7 +793,7 @@ impl TypeRegistry {
             "google.protobuf.NullValue",
         ];
         
-        if well_known_types.contains(&well_known_name.as_str()) {
+        if well_known_types.contains(&well_known_name.as_str()) || wrapped_scalar(&well_known_name).is_some() {
             return std::option::Option::Some(ResolvedType {
                 original_reference: type_ref.clone(),
                 resolved_name: well_known_name,
@@ -504,6 +806,12 @@ impl TypeRegistry {
         std::option::Option::None
     }
     
+    /// Merge all types and dependencies from another registry into this one
+    pub fn merge(&mut self, other: TypeRegistry) {
+        self.types.extend(other.types);
+        self.dependencies.extend(other.dependencies);
+    }
+    
     /// Get all unresolved types across all registered files
     pub fn get_unresolved_types(&self) -> Vec<String> {
         // This would require keeping track of all type references
@@ -530,6 +838,65 @@ impl ProtoFile {
             services: Vec::new(),
             messages: Vec::new(),
             enums: Vec::new(),
+            unknown_statements: Vec::new(),
+        }
+    }
+    
+    /// Walk every service, method, message, field and enum of this file with `visitor`
+    /// 
+    /// Services and their methods are visited first, then messages depth-first
+    /// (fields, nested messages, nested enums), then top-level enums.
+    pub fn accept(&self, visitor: &mut dyn Visitor) {
+        for service in &self.services {
+            visitor.visit_service(service);
+            for method in &service.methods {
+                visitor.visit_method(service, method);
+            }
+        }
+        
+        for message in &self.messages {
+            message.accept(visitor);
+        }
+        
+        for enum_def in &self.enums {
+            visitor.visit_enum(enum_def);
+        }
+    }
+    
+    /// Get the value of a file-level option, e.g. `my.file_opt` for `option (my.file_opt) = ...;`
+    pub fn option(&self, name: &str) -> std::option::Option<&OptionValue> {
+        let name = name.trim_start_matches('(').trim_end_matches(')');
+        self.options.iter()
+            .find(|option| option.name == name)
+            .map(|option| &option.value)
+    }
+    
+    /// Get the API metadata declared by file options
+    ///
+    /// `title`, `version` and `description` options of any namespace are read, e.g.
+    /// `option (myapi.title) = "User API";`; an `api_` prefix (`api_title`) is also
+    /// accepted. The title defaults to the package name and the version to `1.0.0`.
+    /// Each `server` option declares a server, see [`ApiServer::from_option_value`].
+    pub fn api_metadata(&self) -> ApiMetadata {
+        let options = |key: &'static str| self.options.iter()
+            .filter(move |option| {
+                let name = option.name.trim_start_matches('(').trim_end_matches(')');
+                let name = name.rsplit('.').next().unwrap_or(name);
+                name.strip_prefix("api_").unwrap_or(name) == key
+            });
+        let option = |key: &'static str| options(key).next()
+            .and_then(|option| option.value.as_str())
+            .map(str::to_string);
+        
+        ApiMetadata {
+            title: option("title")
+                .or_else(|| self.package.clone())
+                .unwrap_or_else(|| "API".to_string()),
+            version: option("version").unwrap_or_else(|| "1.0.0".to_string()),
+            description: option("description"),
+            servers: options("server")
+                .filter_map(|option| ApiServer::from_option_value(&option.value))
+                .collect(),
         }
     }
     
@@ -608,6 +975,190 @@ impl ProtoFile {
         }
     }
     
+    /// Create a copy of this file whose messages and enums also include the
+    /// definitions from imported files, for resolving cross-file type lookups
+    pub fn with_imported_definitions<'a>(&self, imports: impl IntoIterator<Item = &'a ProtoFile>) -> ProtoFile {
+        let mut merged = self.clone();
+        for imported in imports {
+            merged.messages.extend(imported.messages.iter().cloned());
+            merged.enums.extend(imported.enums.iter().cloned());
+        }
+        merged
+    }
+    
+    /// Get the authentication scheme protecting a method of a service
+    ///
+    /// A scheme declared on the method takes precedence over one declared on the
+    /// service, which takes precedence over the file. Unknown schemes are ignored.
+    pub fn security_scheme(&self, service: &Service, method: &RpcMethod) -> std::option::Option<SecurityScheme> {
+        [&method.options, &service.options, &self.options].into_iter()
+            .filter_map(|options| SecurityScheme::from_options(options))
+            .find_map(Result::ok)
+    }
+    
+    /// Find a message definition by name, searching nested messages as well
+    ///
+    /// The name is matched as a nesting path (`Outer.Inner`), with or without this
+    /// file's package, so a top-level `Book` wins over a nested `Outer.Book`. Names
+    /// that match no path, such as types merged in from other packages, fall back to
+    /// the last segment, but only when a single message has that name.
+    pub fn find_message(&self, name: &str) -> std::option::Option<&Message> {
+        let by_path = self.definition_paths(name).into_iter().find_map(|path| {
+            let (last, parents) = path.split_last()?;
+            self.scope_at_path(parents)?.0.iter().find(|m| m.name == *last)
+        });
+        if by_path.is_some() {
+            return by_path;
+        }
+        
+        let simple_name = name.rsplit('.').next().unwrap_or(name);
+        let mut matches = Vec::new();
+        let mut pending: Vec<&Message> = self.messages.iter().collect();
+        while let std::option::Option::Some(message) = pending.pop() {
+            if message.name == simple_name {
+                matches.push(message);
+            }
+            pending.extend(message.nested_messages.iter());
+        }
+        
+        match matches.as_slice() {
+            [message] => std::option::Option::Some(message),
+            _ => std::option::Option::None,
+        }
+    }
+    
+    /// Find the message a type reference written inside a message nesting scope refers to
+    ///
+    /// The reference is resolved like [`TypeReference::resolve_within`], so a nested
+    /// type shadows a top-level type of the same name. The message is returned with
+    /// its nesting path, the scope of the type references in its own fields.
+    pub fn find_message_within(&self, type_ref: &TypeReference, scope: &[&str]) -> std::option::Option<(&Message, Vec<String>)> {
+        let message = self.find_message(&self.resolved_name_within(type_ref, scope))?;
+        let path = self.type_path_within(type_ref, scope)
+            .unwrap_or_else(|| vec![message.name.clone()]);
+        std::option::Option::Some((message, path))
+    }
+    
+    /// Nesting path (e.g. `["Outer", "Inner"]`) of the message or enum a type reference
+    /// written inside a message nesting scope refers to
+    ///
+    /// Resolved like [`Self::find_message_within`], falling back to the only definition
+    /// with the reference's simple name.
+    pub fn type_path_within(&self, type_ref: &TypeReference, scope: &[&str]) -> std::option::Option<Vec<String>> {
+        let resolved_name = self.resolved_name_within(type_ref, scope);
+        if let std::option::Option::Some(path) = self.definition_paths(&resolved_name).into_iter()
+            .find(|path| self.has_type_at_path(path))
+        {
+            return std::option::Option::Some(path.into_iter().map(str::to_string).collect());
+        }
+        
+        let simple_name = resolved_name.rsplit('.').next().unwrap_or(&resolved_name);
+        let mut matches = Vec::new();
+        let mut pending: Vec<(Vec<String>, &Message)> = self.messages.iter()
+            .map(|message| (Vec::new(), message))
+            .collect();
+        matches.extend(self.enums.iter().filter(|e| e.name == simple_name).map(|e| vec![e.name.clone()]));
+        while let std::option::Option::Some((mut path, message)) = pending.pop() {
+            path.push(message.name.clone());
+            if message.name == simple_name {
+                matches.push(path.clone());
+            }
+            for enum_def in message.nested_enums.iter().filter(|e| e.name == simple_name) {
+                let mut enum_path = path.clone();
+                enum_path.push(enum_def.name.clone());
+                matches.push(enum_path);
+            }
+            pending.extend(message.nested_messages.iter().map(|nested| (path.clone(), nested)));
+        }
+        
+        match matches.len() {
+            1 => matches.pop(),
+            _ => std::option::Option::None,
+        }
+    }
+    
+    /// Find the enum a type reference written inside a message nesting scope refers to
+    ///
+    /// The reference is resolved like [`Self::find_message_within`].
+    pub fn find_enum_within(&self, type_ref: &TypeReference, scope: &[&str]) -> std::option::Option<&Enum> {
+        self.find_enum(&self.resolved_name_within(type_ref, scope))
+    }
+    
+    /// Fully qualified name of the type a reference written inside a nesting scope refers to
+    fn resolved_name_within(&self, type_ref: &TypeReference, scope: &[&str]) -> String {
+        type_ref.resolve_within(self, scope)
+            .map(|resolved| resolved.resolved_name)
+            .unwrap_or_else(|| type_ref.fully_qualified_name())
+    }
+    
+    /// Nesting paths a type name may refer to: as written, and relative to this file's package
+    fn definition_paths<'a>(&self, name: &'a str) -> Vec<Vec<&'a str>> {
+        let name = name.trim_start_matches('.');
+        let mut paths = vec![name.split('.').collect::<Vec<_>>()];
+        if let std::option::Option::Some(relative) = self.package.as_deref()
+            .and_then(|package| name.strip_prefix(package))
+            .and_then(|name| name.strip_prefix('.'))
+        {
+            paths.insert(0, relative.split('.').collect());
+        }
+        paths
+    }
+    
+    /// Get the messages and enums declared inside the message at a nesting path
+    ///
+    /// An empty path is the top level of the file.
+    fn scope_at_path(&self, path: &[&str]) -> std::option::Option<(&Vec<Message>, &Vec<Enum>)> {
+        let mut messages = &self.messages;
+        let mut enums = &self.enums;
+        for segment in path {
+            let message = messages.iter().find(|m| m.name == *segment)?;
+            messages = &message.nested_messages;
+            enums = &message.nested_enums;
+        }
+        std::option::Option::Some((messages, enums))
+    }
+    
+    /// Check whether a message or enum is defined at a nesting path (e.g. `["Outer", "Inner"]`)
+    fn has_type_at_path(&self, path: &[&str]) -> bool {
+        let std::option::Option::Some((last, parents)) = path.split_last() else {
+            return false;
+        };
+        
+        match self.scope_at_path(parents) {
+            std::option::Option::Some((messages, enums)) => {
+                messages.iter().any(|m| m.name == *last) || enums.iter().any(|e| e.name == *last)
+            }
+            std::option::Option::None => false,
+        }
+    }
+    
+    /// Find an enum definition by name, searching enums nested in messages as well
+    ///
+    /// Names are matched like [`Self::find_message`]: as a nesting path first, then
+    /// by the last segment when a single enum has that name.
+    pub fn find_enum(&self, name: &str) -> std::option::Option<&Enum> {
+        let by_path = self.definition_paths(name).into_iter().find_map(|path| {
+            let (last, parents) = path.split_last()?;
+            self.scope_at_path(parents)?.1.iter().find(|e| e.name == *last)
+        });
+        if by_path.is_some() {
+            return by_path;
+        }
+        
+        let simple_name = name.rsplit('.').next().unwrap_or(name);
+        let mut matches: Vec<&Enum> = self.enums.iter().filter(|e| e.name == simple_name).collect();
+        let mut pending: Vec<&Message> = self.messages.iter().collect();
+        while let std::option::Option::Some(message) = pending.pop() {
+            matches.extend(message.nested_enums.iter().filter(|e| e.name == simple_name));
+            pending.extend(message.nested_messages.iter());
+        }
+        
+        match matches.as_slice() {
+            [enum_def] => std::option::Option::Some(enum_def),
+            _ => std::option::Option::None,
+        }
+    }
+    
     /// Resolve a type reference within this file's context
     pub fn resolve_type(&self, type_ref: &TypeReference) -> std::option::Option<ResolvedType> {
         // If it's already fully qualified, use as-is
@@ -741,6 +1292,281 @@ impl ProtoFile {
     }
 }
 
+impl OptionValue {
+    /// Get a canonical form of the value for comparison
+    ///
+    /// Aggregate entries are ordered by key, recursively; repeated keys keep their
+    /// relative source order. Negative zero becomes zero.
+    pub fn normalize(&self) -> OptionValue {
+        match self {
+            OptionValue::MessageLiteral(entries) => {
+                let mut entries: Vec<(String, OptionValue)> = entries.iter()
+                    .map(|(key, value)| (key.clone(), value.normalize()))
+                    .collect();
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                OptionValue::MessageLiteral(entries)
+            }
+            OptionValue::Number(number) if *number == 0.0 => OptionValue::Number(0.0),
+            value => value.clone(),
+        }
+    }
+    
+    /// Get the text of a string or identifier value
+    pub fn as_str(&self) -> std::option::Option<&str> {
+        match self {
+            OptionValue::String(value) | OptionValue::Identifier(value) => std::option::Option::Some(value),
+            _ => std::option::Option::None,
+        }
+    }
+    
+    /// Get a number value that is a whole number within the range of `i64`
+    pub fn as_i64(&self) -> std::option::Option<i64> {
+        match self {
+            OptionValue::Number(number)
+                if number.fract() == 0.0 && *number >= i64::MIN as f64 && *number < i64::MAX as f64 =>
+            {
+                std::option::Option::Some(*number as i64)
+            }
+            _ => std::option::Option::None,
+        }
+    }
+    
+    /// Get the value of a boolean
+    pub fn as_bool(&self) -> std::option::Option<bool> {
+        match self {
+            OptionValue::Boolean(value) => std::option::Option::Some(*value),
+            _ => std::option::Option::None,
+        }
+    }
+    
+    /// Get the first value of `key` in an aggregate value
+    pub fn get(&self, key: &str) -> std::option::Option<&OptionValue> {
+        match self {
+            OptionValue::MessageLiteral(entries) => entries.iter()
+                .find(|(entry_key, _)| entry_key == key)
+                .map(|(_, value)| value),
+            _ => std::option::Option::None,
+        }
+    }
+    
+    /// Get every value of `key` in an aggregate value, in source order
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a OptionValue> + 'a {
+        let entries = match self {
+            OptionValue::MessageLiteral(entries) => entries.as_slice(),
+            _ => &[],
+        };
+        entries.iter()
+            .filter(move |(entry_key, _)| entry_key == key)
+            .map(|(_, value)| value)
+    }
+}
+
+impl ReservedRange {
+    /// Check whether `number` falls inside the range
+    pub fn contains(&self, number: u32) -> bool {
+        number >= self.start && self.end.is_none_or(|end| number <= end)
+    }
+}
+
+impl std::fmt::Display for ReservedRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.end {
+            Some(end) if end == self.start => write!(f, "{}", self.start),
+            Some(end) => write!(f, "{} to {}", self.start, end),
+            None => write!(f, "{} to max", self.start),
+        }
+    }
+}
+
+impl Message {
+    /// Find the reserved range, if any, that covers field number `number`
+    pub fn reserved_range_for(&self, number: u32) -> std::option::Option<&ReservedRange> {
+        self.reserved_ranges.iter().find(|range| range.contains(number))
+    }
+    
+    /// Walk this message, its fields and its nested definitions with `visitor`
+    pub fn accept(&self, visitor: &mut dyn Visitor) {
+        visitor.visit_message(self);
+        for field in &self.fields {
+            visitor.visit_field(self, field);
+        }
+        for nested_message in &self.nested_messages {
+            nested_message.accept(visitor);
+        }
+        for nested_enum in &self.nested_enums {
+            visitor.visit_enum(nested_enum);
+        }
+    }
+    
+    /// Get the raw JSON of an `@example: {...}` directive in the message's comments
+    pub fn example_directive(&self) -> std::option::Option<&str> {
+        self.comments.iter()
+            .flat_map(|comment| comment.text.lines())
+            .find_map(|line| line.trim().strip_prefix("@example:"))
+            .map(str::trim)
+    }
+    
+    /// Check whether the message describes an API resource, either by a
+    /// `google.api.resource` option or by having server-set (read-only) fields
+    pub fn is_resource(&self) -> bool {
+        self.options.iter().any(|option| option.name.trim_start_matches('(').trim_end_matches(')') == "google.api.resource")
+            || self.fields.iter().any(Field::is_read_only)
+    }
+}
+
+impl Field {
+    /// Check whether the field is required, either by a proto2 `required` label or
+    /// by `(google.api.field_behavior) = REQUIRED`
+    pub fn is_required(&self) -> bool {
+        self.label == FieldLabel::Required || self.behaviors.contains(&FieldBehavior::Required)
+    }
+    
+    /// Check whether the field is set by the server only, either by an `@readonly`
+    /// comment directive or by `(google.api.field_behavior) = OUTPUT_ONLY`
+    pub fn is_read_only(&self) -> bool {
+        self.has_directive("@readonly") || self.behaviors.contains(&FieldBehavior::OutputOnly)
+    }
+    
+    /// Check whether the field is set by the client only, either by a `@writeonly`
+    /// comment directive or by `(google.api.field_behavior) = INPUT_ONLY`
+    pub fn is_write_only(&self) -> bool {
+        self.has_directive("@writeonly") || self.behaviors.contains(&FieldBehavior::InputOnly)
+    }
+    
+    /// Check whether a comment line of the field consists of the given directive
+    fn has_directive(&self, directive: &str) -> bool {
+        self.comments.iter()
+            .flat_map(|comment| comment.text.lines())
+            .any(|line| line.trim() == directive)
+    }
+    
+    /// Check whether the field can only be set on creation, by
+    /// `(google.api.field_behavior) = IMMUTABLE`
+    pub fn is_immutable(&self) -> bool {
+        self.behaviors.contains(&FieldBehavior::Immutable)
+    }
+    
+    /// Collect the value constraints declared by the field options
+    pub fn constraints(&self) -> Vec<FieldConstraint> {
+        FieldConstraint::from_options(&self.options)
+    }
+}
+
+impl FieldConstraint {
+    /// Collect the constraints declared among `options`, skipping unrecognized rules
+    ///
+    /// Options named after a constraint (`maximum`, `min_length`, `pattern`, ...) are
+    /// recognized under any prefix. Rules of `buf.validate.field` and `validate.rules`
+    /// options (`lte`, `gt`, `min_len`, ...) are recognized both in option names, as in
+    /// `(validate.rules).string.min_len = 1`, and in aggregate values.
+    pub fn from_options(options: &[ProtoOption]) -> Vec<FieldConstraint> {
+        let mut constraints = Vec::new();
+        for option in options {
+            let name = option.name.trim_start_matches('(').replace(')', "");
+            let validate_rules = name.starts_with("buf.validate.") || name.starts_with("validate.");
+            collect_constraints(&name, &option.value, validate_rules, &mut constraints);
+        }
+        constraints
+    }
+    
+    /// Parse a single rule, given the last segment of its name
+    fn from_rule(rule: &str, value: &OptionValue, validate_rules: bool) -> std::option::Option<FieldConstraint> {
+        let number = || match value {
+            OptionValue::Number(number) => std::option::Option::Some(*number),
+            _ => std::option::Option::None,
+        };
+        let count = || value.as_i64().and_then(|count| u64::try_from(count).ok());
+        
+        let constraint = match rule {
+            "maximum" => FieldConstraint::Maximum(number()?),
+            "exclusive_maximum" => FieldConstraint::ExclusiveMaximum(number()?),
+            "minimum" => FieldConstraint::Minimum(number()?),
+            "exclusive_minimum" => FieldConstraint::ExclusiveMinimum(number()?),
+            "max_length" => FieldConstraint::MaxLength(count()?),
+            "min_length" => FieldConstraint::MinLength(count()?),
+            "pattern" => FieldConstraint::Pattern(value.as_str()?.to_string()),
+            "max_items" => FieldConstraint::MaxItems(count()?),
+            "min_items" => FieldConstraint::MinItems(count()?),
+            "lte" if validate_rules => FieldConstraint::Maximum(number()?),
+            "lt" if validate_rules => FieldConstraint::ExclusiveMaximum(number()?),
+            "gte" if validate_rules => FieldConstraint::Minimum(number()?),
+            "gt" if validate_rules => FieldConstraint::ExclusiveMinimum(number()?),
+            "max_len" if validate_rules => FieldConstraint::MaxLength(count()?),
+            "min_len" if validate_rules => FieldConstraint::MinLength(count()?),
+            _ => return std::option::Option::None,
+        };
+        std::option::Option::Some(constraint)
+    }
+}
+
+/// Collect the constraints of an option value, descending into aggregate values
+fn collect_constraints(name: &str, value: &OptionValue, validate_rules: bool, constraints: &mut Vec<FieldConstraint>) {
+    match value {
+        OptionValue::MessageLiteral(entries) => {
+            for (key, value) in entries {
+                collect_constraints(key, value, validate_rules, constraints);
+            }
+        }
+        _ => {
+            let rule = name.rsplit('.').next().unwrap_or(name);
+            constraints.extend(FieldConstraint::from_rule(rule, value, validate_rules));
+        }
+    }
+}
+
+impl FieldBehavior {
+    /// Parse a `google.api.FieldBehavior` value name such as `OUTPUT_ONLY`
+    pub fn from_name(name: &str) -> std::option::Option<FieldBehavior> {
+        match name {
+            "OPTIONAL" => std::option::Option::Some(FieldBehavior::Optional),
+            "REQUIRED" => std::option::Option::Some(FieldBehavior::Required),
+            "OUTPUT_ONLY" => std::option::Option::Some(FieldBehavior::OutputOnly),
+            "INPUT_ONLY" => std::option::Option::Some(FieldBehavior::InputOnly),
+            "IMMUTABLE" => std::option::Option::Some(FieldBehavior::Immutable),
+            "UNORDERED_LIST" => std::option::Option::Some(FieldBehavior::UnorderedList),
+            "NON_EMPTY_DEFAULT" => std::option::Option::Some(FieldBehavior::NonEmptyDefault),
+            "IDENTIFIER" => std::option::Option::Some(FieldBehavior::Identifier),
+            _ => std::option::Option::None,
+        }
+    }
+    
+    /// Collect the behaviors declared by the `(google.api.field_behavior)` options
+    /// among `options`, skipping unrecognized values
+    pub fn from_options(options: &[ProtoOption]) -> Vec<FieldBehavior> {
+        options.iter()
+            .filter(|option| option.name.trim_start_matches('(').trim_end_matches(')') == "google.api.field_behavior")
+            .filter_map(|option| option.value.as_str())
+            .filter_map(FieldBehavior::from_name)
+            .collect()
+    }
+}
+
+impl FieldType {
+    /// Get the Protocol Buffer type reference for this field type
+    pub fn to_type_reference(&self) -> TypeReference {
+        let name = match self {
+            FieldType::Double => "double",
+            FieldType::Float => "float",
+            FieldType::Int32 => "int32",
+            FieldType::Int64 => "int64",
+            FieldType::Uint32 => "uint32",
+            FieldType::Uint64 => "uint64",
+            FieldType::Sint32 => "sint32",
+            FieldType::Sint64 => "sint64",
+            FieldType::Fixed32 => "fixed32",
+            FieldType::Fixed64 => "fixed64",
+            FieldType::Sfixed32 => "sfixed32",
+            FieldType::Sfixed64 => "sfixed64",
+            FieldType::Bool => "bool",
+            FieldType::String => "string",
+            FieldType::Bytes => "bytes",
+            FieldType::MessageOrEnum(type_ref) => return type_ref.clone(),
+            FieldType::Map { .. } => "map",
+        };
+        TypeReference::new(name.to_string())
+    }
+}
+
 impl Default for ProtoFile {
     fn default() -> Self {
         Self::new()
@@ -761,6 +1587,27 @@ impl HttpMethod {
     }
 }
 
+impl IdempotencyLevel {
+    /// Parse the proto enum value name (e.g. `NO_SIDE_EFFECTS`)
+    pub fn from_proto_name(name: &str) -> std::option::Option<Self> {
+        match name {
+            "IDEMPOTENCY_UNKNOWN" => Some(IdempotencyLevel::IdempotencyUnknown),
+            "NO_SIDE_EFFECTS" => Some(IdempotencyLevel::NoSideEffects),
+            "IDEMPOTENT" => Some(IdempotencyLevel::Idempotent),
+            _ => None,
+        }
+    }
+
+    /// Get the proto enum value name
+    pub fn as_proto_name(&self) -> &'static str {
+        match self {
+            IdempotencyLevel::IdempotencyUnknown => "IDEMPOTENCY_UNKNOWN",
+            IdempotencyLevel::NoSideEffects => "NO_SIDE_EFFECTS",
+            IdempotencyLevel::Idempotent => "IDEMPOTENT",
+        }
+    }
+}
+
 impl std::fmt::Display for HttpMethod {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_str())
@@ -775,6 +1622,7 @@ impl HttpRoute {
         http_method: HttpMethod,
         path_template: String,
     ) -> Self {
+        let segments = PathSegment::parse(&path_template);
         Self {
             service_name,
             method_name,
@@ -785,9 +1633,33 @@ impl HttpRoute {
             request_body: std::option::Option::None,
             input_type: TypeReference::new("Empty".to_string()),
             response_type: TypeReference::new("Empty".to_string()),
+            idempotency_level: std::option::Option::None,
+            is_paginated: false,
+            deprecation: std::option::Option::None,
+            segments,
+            rate_limit: std::option::Option::None,
+            security: std::option::Option::None,
         }
     }
     
+    /// Set the idempotency level
+    pub fn with_idempotency_level(mut self, level: IdempotencyLevel) -> Self {
+        self.idempotency_level = Some(level);
+        self
+    }
+    
+    /// Mark the route as a paginated list method
+    pub fn with_pagination(mut self) -> Self {
+        self.is_paginated = true;
+        self
+    }
+    
+    /// Mark the route as deprecated
+    pub fn with_deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.deprecation = Some(deprecation);
+        self
+    }
+    
     /// Add a path parameter
     pub fn with_path_parameter(mut self, param: PathParameter) -> Self {
         self.path_parameters.push(param);
@@ -831,6 +1703,192 @@ impl HttpRoute {
     pub fn has_request_body(&self) -> bool {
         self.request_body.is_some()
     }
+    
+    /// Get the signature used to detect conflicting routes
+    ///
+    /// Bare path parameters are normalized to `{}` so that templates which only
+    /// differ in parameter names (e.g. `/users/{id}` and `/users/{user_id}`)
+    /// produce the same signature. Parameters with a segment pattern are expanded
+    /// to it, with `*` normalized to `{}`, so `/v1/{name=shelves/*}` matches two
+    /// segments and does not conflict with `/v1/{id}`.
+    pub fn conflict_signature(&self) -> String {
+        let parsed;
+        let segments = if self.segments.is_empty() {
+            parsed = PathSegment::parse(&self.path_template);
+            &parsed
+        } else {
+            &self.segments
+        };
+        
+        let normalized: String = segments.iter()
+            .map(|segment| match segment {
+                PathSegment::Literal(text) => text.clone(),
+                PathSegment::Param { pattern: std::option::Option::None, .. } => "{}".to_string(),
+                PathSegment::Param { pattern: std::option::Option::Some(pattern), .. } => pattern.split('/')
+                    .map(|part| match part {
+                        "*" => "{}",
+                        "**" => "{**}",
+                        part => part,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("/"),
+            })
+            .collect();
+        
+        format!("{} {}", self.http_method.as_str(), normalized)
+    }
+    
+    /// Describe this route for diagnostics, e.g. `GET /users/{id} (UserService.GetUser)`
+    pub fn describe(&self) -> String {
+        format!("{} {} ({}.{})", self.http_method.as_str(), self.path_template, self.service_name, self.method_name)
+    }
+}
+
+impl Ord for HttpRoute {
+    /// Order routes by `(path_template, http_method, method_name)`
+    ///
+    /// Ties are broken by service name and then by the remaining fields, so the
+    /// ordering stays consistent with `Eq` and routes can live in a `BTreeSet`.
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.path_template.cmp(&other.path_template)
+            .then_with(|| self.http_method.as_str().cmp(other.http_method.as_str()))
+            .then_with(|| self.method_name.cmp(&other.method_name))
+            .then_with(|| self.service_name.cmp(&other.service_name))
+            .then_with(|| self.http_method.cmp(&other.http_method))
+            .then_with(|| self.path_parameters.cmp(&other.path_parameters))
+            .then_with(|| self.query_parameters.cmp(&other.query_parameters))
+            .then_with(|| self.request_body.cmp(&other.request_body))
+            .then_with(|| self.input_type.cmp(&other.input_type))
+            .then_with(|| self.response_type.cmp(&other.response_type))
+            .then_with(|| self.idempotency_level.cmp(&other.idempotency_level))
+            .then_with(|| self.is_paginated.cmp(&other.is_paginated))
+            .then_with(|| self.deprecation.cmp(&other.deprecation))
+            .then_with(|| self.segments.cmp(&other.segments))
+            .then_with(|| self.rate_limit.cmp(&other.rate_limit))
+            .then_with(|| self.security.cmp(&other.security))
+    }
+}
+
+impl PartialOrd for HttpRoute {
+    fn partial_cmp(&self, other: &Self) -> std::option::Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PathSegment {
+    /// Split a path template into alternating literal and parameter segments
+    ///
+    /// `/v1/users/{id}/posts` yields `Literal("/v1/users/")`, `Param { name: "id" }`
+    /// and `Literal("/posts")`. An unclosed `{` is kept as literal text.
+    pub fn parse(path_template: &str) -> Vec<PathSegment> {
+        let mut segments = Vec::new();
+        let mut rest = path_template;
+        
+        while let std::option::Option::Some(start) = rest.find('{') {
+            let end = match rest[start..].find('}') {
+                std::option::Option::Some(end) => start + end,
+                std::option::Option::None => break,
+            };
+            if start > 0 {
+                segments.push(PathSegment::Literal(rest[..start].to_string()));
+            }
+            
+            let variable = &rest[start + 1..end];
+            let (name, pattern) = match variable.split_once('=') {
+                std::option::Option::Some((name, pattern)) => (name, std::option::Option::Some(pattern.to_string())),
+                std::option::Option::None => (variable, std::option::Option::None),
+            };
+            segments.push(PathSegment::Param { name: name.to_string(), pattern });
+            rest = &rest[end + 1..];
+        }
+        
+        if !rest.is_empty() {
+            segments.push(PathSegment::Literal(rest.to_string()));
+        }
+        
+        segments
+    }
+}
+
+impl RateLimit {
+    /// Parse a `<requests>/<period>` declaration such as `100/min` or `5000/day`
+    ///
+    /// The period may be written as `s`, `sec` or `second`, `m`, `min` or `minute`,
+    /// `h` or `hour`, and `d` or `day`, the words optionally pluralized. Any other
+    /// unit, such as `ms`, is rejected.
+    pub fn parse(value: &str) -> std::option::Option<RateLimit> {
+        let (requests, period) = value.split_once('/')?;
+        let requests = requests.trim().parse().ok()?;
+        let period = match period.trim().to_lowercase().as_str() {
+            "s" | "sec" | "secs" | "second" | "seconds" => RateLimitPeriod::Second,
+            "m" | "min" | "mins" | "minute" | "minutes" => RateLimitPeriod::Minute,
+            "h" | "hour" | "hours" => RateLimitPeriod::Hour,
+            "d" | "day" | "days" => RateLimitPeriod::Day,
+            _ => return std::option::Option::None,
+        };
+        std::option::Option::Some(RateLimit { requests, period })
+    }
+}
+
+impl SecurityScheme {
+    /// Parse a scheme name: `bearer`, `basic` or `api_key`
+    pub fn from_name(name: &str) -> std::option::Option<SecurityScheme> {
+        match name.trim().to_lowercase().replace('-', "_").as_str() {
+            "bearer" => std::option::Option::Some(SecurityScheme::Bearer),
+            "basic" => std::option::Option::Some(SecurityScheme::Basic),
+            "api_key" | "apikey" => std::option::Option::Some(SecurityScheme::ApiKey),
+            _ => std::option::Option::None,
+        }
+    }
+    
+    /// Get the scheme declared by a `security` option of any namespace among `options`
+    ///
+    /// Returns `None` when no scheme is declared and `Some(Err(raw))` when the
+    /// declared value is not a known scheme.
+    pub fn from_options(options: &[ProtoOption]) -> std::option::Option<Result<SecurityScheme, String>> {
+        let option = options.iter().find(|option| {
+            let name = option.name.trim_start_matches('(').trim_end_matches(')');
+            name.rsplit('.').next() == std::option::Option::Some("security")
+        })?;
+        let raw = option.value.as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{:?}", option.value));
+        
+        std::option::Option::Some(SecurityScheme::from_name(&raw).ok_or(raw))
+    }
+    
+    /// Name of the scheme, as used in generated type names
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecurityScheme::Bearer => "bearer",
+            SecurityScheme::Basic => "basic",
+            SecurityScheme::ApiKey => "api_key",
+        }
+    }
+}
+
+impl RateLimitPeriod {
+    /// Get the length of the period in seconds
+    pub fn seconds(&self) -> u64 {
+        match self {
+            RateLimitPeriod::Second => 1,
+            RateLimitPeriod::Minute => 60,
+            RateLimitPeriod::Hour => 60 * 60,
+            RateLimitPeriod::Day => 24 * 60 * 60,
+        }
+    }
+}
+
+impl std::fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let period = match self.period {
+            RateLimitPeriod::Second => "sec",
+            RateLimitPeriod::Minute => "min",
+            RateLimitPeriod::Hour => "hour",
+            RateLimitPeriod::Day => "day",
+        };
+        write!(f, "{}/{}", self.requests, period)
+    }
 }
 
 impl PathParameter {
@@ -851,9 +1909,16 @@ impl QueryParameter {
             name,
             param_type,
             required,
+            style: QueryParameterStyle::Form,
         }
     }
     
+    /// Set the serialization style of the parameter
+    pub fn with_style(mut self, style: QueryParameterStyle) -> Self {
+        self.style = style;
+        self
+    }
+    
     /// Create an optional query parameter
     pub fn optional(name: String, param_type: ParameterType) -> Self {
         Self::new(name, param_type, false)
@@ -870,6 +1935,8 @@ impl RequestBody {
     pub fn entire_message() -> Self {
         Self {
             field: std::option::Option::None,
+            field_type: std::option::Option::None,
+            is_repeated: false,
             content_type: "application/json".to_string(),
             is_entire_message: true,
         }
@@ -879,11 +1946,25 @@ impl RequestBody {
     pub fn field(field_name: String) -> Self {
         Self {
             field: std::option::Option::Some(field_name),
+            field_type: std::option::Option::None,
+            is_repeated: false,
             content_type: "application/json".to_string(),
             is_entire_message: false,
         }
     }
     
+    /// Set the resolved type of the body field
+    pub fn with_field_type(mut self, field_type: TypeReference) -> Self {
+        self.field_type = std::option::Option::Some(field_type);
+        self
+    }
+    
+    /// Mark the body field as repeated
+    pub fn repeated(mut self) -> Self {
+        self.is_repeated = true;
+        self
+    }
+    
     /// Set the content type
     pub fn with_content_type(mut self, content_type: String) -> Self {
         self.content_type = content_type;
@@ -899,6 +1980,7 @@ impl Service {
             methods: Vec::new(),
             options: Vec::new(),
             comments: Vec::new(),
+            source: None,
         }
     }
     
@@ -914,6 +1996,31 @@ impl Service {
             .filter(|method| method.http_annotation.is_some())
             .collect()
     }
+    
+    /// Get the base path declared with a service-level `google.api.http` option
+    ///
+    /// `option (google.api.http) = { base_path: "/v1/library" };` on a service
+    /// prefixes the path of every HTTP rule of its methods.
+    pub fn http_base_path(&self) -> std::option::Option<&str> {
+        self.options.iter()
+            .filter(|option| option.name.trim_start_matches('(').trim_end_matches(')') == "google.api.http")
+            .find_map(|option| match &option.value {
+                OptionValue::MessageLiteral(fields) => fields.iter()
+                    .find_map(|(key, value)| match (key.as_str(), value) {
+                        ("base_path", OptionValue::String(path)) => std::option::Option::Some(path.as_str()),
+                        _ => std::option::Option::None,
+                    }),
+                _ => std::option::Option::None,
+            })
+    }
+    
+    /// Prepend the service's HTTP base path, if any, to a method's path template
+    pub fn http_path(&self, path: &str) -> String {
+        match self.http_base_path() {
+            std::option::Option::Some(base) => format!("{}/{}", base.trim_end_matches('/'), path.trim_start_matches('/')),
+            std::option::Option::None => path.to_string(),
+        }
+    }
 }
 
 impl RpcMethod {
@@ -930,6 +2037,8 @@ impl RpcMethod {
             options: Vec::new(),
             comments: Vec::new(),
             http_annotation: std::option::Option::None,
+            idempotency_level: std::option::Option::None,
+            method_signature: Vec::new(),
         }
     }
     
@@ -939,10 +2048,129 @@ impl RpcMethod {
         self
     }
     
+    /// Set the idempotency level
+    pub fn with_idempotency_level(mut self, level: IdempotencyLevel) -> Self {
+        self.idempotency_level = std::option::Option::Some(level);
+        self
+    }
+    
+    /// Set the preferred argument ordering
+    pub fn with_method_signature(mut self, fields: Vec<String>) -> Self {
+        self.method_signature = fields;
+        self
+    }
+    
     /// Check if this method has HTTP annotation
     pub fn is_http_enabled(&self) -> bool {
         self.http_annotation.is_some()
     }
+
+    /// Get every HTTP binding of this method: the primary annotation followed by its
+    /// `additional_bindings`, in declaration order; empty without an annotation
+    pub fn all_http_bindings(&self) -> Vec<HttpBinding> {
+        self.http_annotation.iter()
+            .flat_map(|annotation| {
+                std::iter::once(HttpBinding {
+                    method: annotation.method.clone(),
+                    path: annotation.path.clone(),
+                    body: annotation.body.clone(),
+                })
+                .chain(annotation.additional_bindings.iter().cloned())
+            })
+            .collect()
+    }
+
+    /// Get the deprecation of this method, from an `@deprecated` (or
+    /// `@deprecated: <sunset date>`) comment directive or a `deprecated = true` option
+    pub fn deprecation(&self) -> std::option::Option<Deprecation> {
+        let directive = self.comments.iter()
+            .flat_map(|comment| comment.text.lines())
+            .find_map(|line| line.trim().strip_prefix("@deprecated"))
+            .filter(|rest| rest.is_empty() || rest.starts_with(':'));
+        if let std::option::Option::Some(rest) = directive {
+            let sunset = rest.trim_start_matches(':').trim();
+            return std::option::Option::Some(Deprecation {
+                sunset: (!sunset.is_empty()).then(|| sunset.to_string()),
+            });
+        }
+        
+        self.options.iter()
+            .any(|option| option.name == "deprecated" && option.value == OptionValue::Boolean(true))
+            .then(Deprecation::default)
+    }
+    
+    /// Check whether the method is intentionally left off the HTTP surface, through a
+    /// `@no_http` comment directive or an `http_excluded = true` option of any namespace
+    pub fn is_http_excluded(&self) -> bool {
+        let directive = self.comments.iter()
+            .flat_map(|comment| comment.text.lines())
+            .any(|line| line.trim() == "@no_http");
+        
+        directive || self.options.iter().any(|option| {
+            let name = option.name.trim_start_matches('(').trim_end_matches(')');
+            name.rsplit('.').next() == std::option::Option::Some("http_excluded")
+                && option.value == OptionValue::Boolean(true)
+        })
+    }
+    
+    /// Get the success status code declared by an `@status: 201` comment directive
+    /// or a `(response_status) = 201` option
+    ///
+    /// Returns `None` when no status is declared and `Some(Err(raw))` when the
+    /// declared value is not a 2xx status code.
+    pub fn status_code(&self) -> std::option::Option<Result<u16, String>> {
+        let directive = self.comments.iter()
+            .flat_map(|comment| comment.text.lines())
+            .find_map(|line| line.trim().strip_prefix("@status:"))
+            .map(|value| value.trim().to_string());
+        let raw = directive.or_else(|| self.options.iter()
+            .find(|option| option.name.trim_start_matches('(').trim_end_matches(')') == "response_status")
+            .map(|option| match (option.value.as_i64(), option.value.as_str()) {
+                (Some(number), _) => number.to_string(),
+                (_, Some(value)) => value.to_string(),
+                _ => format!("{:?}", option.value),
+            }))?;
+        
+        std::option::Option::Some(match raw.parse::<u16>() {
+            Ok(code) if (200..300).contains(&code) => Ok(code),
+            _ => Err(raw),
+        })
+    }
+    
+    /// Get the query parameters declared by a `query_params` option of any namespace,
+    /// e.g. `option (myapi.query_params) = "status, limit";`
+    ///
+    /// Returns `None` when the method does not declare its query parameters.
+    pub fn declared_query_params(&self) -> std::option::Option<Vec<String>> {
+        let option = self.options.iter().find(|option| {
+            let name = option.name.trim_start_matches('(').trim_end_matches(')');
+            name.rsplit('.').next() == std::option::Option::Some("query_params")
+        })?;
+        
+        std::option::Option::Some(option.value.as_str()?
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+    
+    /// Get the rate limit declared by a `rate_limit` option of any namespace,
+    /// e.g. `option (myapi.rate_limit) = "100/min";`
+    ///
+    /// Returns `None` when no rate limit is declared and `Some(Err(raw))` when the
+    /// declared value cannot be parsed by [`RateLimit::parse`].
+    pub fn rate_limit(&self) -> std::option::Option<Result<RateLimit, String>> {
+        let option = self.options.iter().find(|option| {
+            let name = option.name.trim_start_matches('(').trim_end_matches(')');
+            name.rsplit('.').next() == std::option::Option::Some("rate_limit")
+        })?;
+        let raw = option.value.as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{:?}", option.value));
+        
+        std::option::Option::Some(RateLimit::parse(&raw).ok_or(raw))
+    }
 }
 
 impl TypeReference {
@@ -973,6 +2201,16 @@ impl TypeReference {
         }
     }
     
+    /// Create a type reference from a possibly dotted name (e.g. `foo.bar.Baz`)
+    ///
+    /// The last segment becomes the name and any preceding segments the package.
+    pub fn from_qualified_name(qualified_name: &str) -> Self {
+        match qualified_name.rsplit_once('.') {
+            std::option::Option::Some((package, name)) => Self::with_package(name.to_string(), package.to_string()),
+            std::option::Option::None => Self::new(qualified_name.to_string()),
+        }
+    }
+    
     /// Get the fully qualified name
     pub fn fully_qualified_name(&self) -> String {
         match &self.package {
@@ -981,6 +2219,43 @@ impl TypeReference {
         }
     }
     
+    /// Resolve this reference from inside a message nesting scope, as protoc does
+    ///
+    /// `scope` is the path of the message containing the reference (e.g.
+    /// `["Outer", "Inner"]`). The name is looked up in the innermost scope first,
+    /// then in each enclosing scope, then at the top level of the file, so a nested
+    /// type shadows a top-level type of the same name. Names not defined in the
+    /// file fall back to [`ProtoFile::resolve_type`].
+    pub fn resolve_within(&self, proto_file: &ProtoFile, scope: &[&str]) -> std::option::Option<ResolvedType> {
+        if self.is_scalar() {
+            return proto_file.resolve_type(self);
+        }
+        
+        let qualified_name = self.fully_qualified_name();
+        let relative_name = proto_file.package.as_deref()
+            .and_then(|package| qualified_name.strip_prefix(package))
+            .and_then(|name| name.strip_prefix('.'));
+        let segments: Vec<&str> = qualified_name.split('.').collect();
+        
+        let candidates = (0..=scope.len()).rev()
+            .map(|depth| scope[..depth].iter().copied().chain(segments.iter().copied()).collect::<Vec<_>>())
+            .chain(relative_name.map(|name| name.split('.').collect()));
+        
+        for path in candidates {
+            if proto_file.has_type_at_path(&path) {
+                return std::option::Option::Some(ResolvedType {
+                    original_reference: self.clone(),
+                    resolved_name: proto_file.qualify_type_name(&path.join(".")),
+                    definition_location: TypeLocation::Local,
+                    is_scalar: false,
+                    is_well_known: false,
+                });
+            }
+        }
+        
+        proto_file.resolve_type(self)
+    }
+    
     /// Check if this is a scalar type
     pub fn is_scalar(&self) -> bool {
         matches!(self.name.as_str(), 
@@ -997,6 +2272,16 @@ impl TypeReference {
             "google.protobuf.Empty" | "google.protobuf.Any" |
             "google.protobuf.Struct" | "google.protobuf.Value" |
             "google.protobuf.ListValue" | "google.protobuf.NullValue"
-        )
+        ) || self.wrapped_scalar().is_some()
+    }
+    
+    /// Get the scalar type held by a wrapper type, e.g. `int32` for `google.protobuf.Int32Value`
+    pub fn wrapped_scalar(&self) -> std::option::Option<&'static str> {
+        wrapped_scalar(&self.fully_qualified_name())
+    }
+    
+    /// Check if this is `google.protobuf.Empty`
+    pub fn is_empty_message(&self) -> bool {
+        self.fully_qualified_name() == "google.protobuf.Empty"
     }
 }
\ No newline at end of file
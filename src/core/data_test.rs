@@ -134,6 +134,36 @@ mod tests {
         assert!(service.methods[0].is_http_enabled());
     }
 
+    #[test]
+    fn test_all_http_bindings() {
+        let method = RpcMethod::new(
+            "GetUser".to_string(),
+            TypeReference::new("GetUserRequest".to_string()),
+            TypeReference::new("User".to_string()),
+        );
+        assert!(method.all_http_bindings().is_empty());
+
+        let method = method.with_http_annotation(HttpAnnotation {
+            method: HttpMethod::Get,
+            path: "/v1/users/{id}".to_string(),
+            body: None,
+            additional_bindings: vec![HttpBinding {
+                method: HttpMethod::Post,
+                path: "/v1/users/{id}:get".to_string(),
+                body: Some("*".to_string()),
+            }],
+        });
+
+        let bindings = method.all_http_bindings();
+        assert_eq!(bindings.len(), 2);
+        assert_eq!(bindings[0].method, HttpMethod::Get);
+        assert_eq!(bindings[0].path, "/v1/users/{id}");
+        assert_eq!(bindings[0].body, None);
+        assert_eq!(bindings[1].method, HttpMethod::Post);
+        assert_eq!(bindings[1].path, "/v1/users/{id}:get");
+        assert_eq!(bindings[1].body.as_deref(), Some("*"));
+    }
+
     #[test]
     fn test_type_registry() {
         let mut registry = TypeRegistry::new();
@@ -148,6 +178,10 @@ mod tests {
             nested_enums: Vec::new(),
             options: Vec::new(),
             comments: Vec::new(),
+            is_group: false,
+            reserved_ranges: Vec::new(),
+            reserved_names: Vec::new(),
+            source: None,
         });
 
         // Register the file
@@ -174,6 +208,10 @@ mod tests {
             nested_enums: Vec::new(),
             options: Vec::new(),
             comments: Vec::new(),
+            is_group: false,
+            reserved_ranges: Vec::new(),
+            reserved_names: Vec::new(),
+            source: None,
         });
 
         // Test local type resolution
@@ -196,6 +234,119 @@ mod tests {
         assert!(resolved_scalar.is_scalar);
     }
 
+    #[test]
+    fn test_type_resolution_within_nested_scope() {
+        let message = |name: &str, nested_messages: Vec<Message>| Message {
+            name: name.to_string(),
+            fields: Vec::new(),
+            nested_messages,
+            nested_enums: Vec::new(),
+            options: Vec::new(),
+            comments: Vec::new(),
+            is_group: false,
+            reserved_ranges: Vec::new(),
+            reserved_names: Vec::new(),
+            source: None,
+        };
+
+        let mut proto_file = ProtoFile::new();
+        proto_file.package = Some("com.example".to_string());
+        proto_file.messages.push(message("Result", Vec::new()));
+        proto_file.messages.push(message("Search", vec![
+            message("Result", Vec::new()),
+            message("Page", Vec::new()),
+        ]));
+
+        let result_ref = TypeReference::new("Result".to_string());
+
+        // The nested definition shadows the top-level one inside its scope
+        let resolved = result_ref.resolve_within(&proto_file, &["Search", "Page"]).unwrap();
+        assert_eq!(resolved.resolved_name, "com.example.Search.Result");
+        assert_eq!(resolved.definition_location, TypeLocation::Local);
+
+        // Outside the scope the top-level definition wins
+        let resolved = result_ref.resolve_within(&proto_file, &[]).unwrap();
+        assert_eq!(resolved.resolved_name, "com.example.Result");
+
+        // Dotted and package-qualified names resolve from the enclosing scopes too
+        let page_ref = TypeReference::from_qualified_name("Search.Page");
+        let resolved = page_ref.resolve_within(&proto_file, &["Result"]).unwrap();
+        assert_eq!(resolved.resolved_name, "com.example.Search.Page");
+        let qualified_ref = TypeReference::from_qualified_name("com.example.Result");
+        let resolved = qualified_ref.resolve_within(&proto_file, &["Search"]).unwrap();
+        assert_eq!(resolved.resolved_name, "com.example.Result");
+
+        // Scalars and unknown names fall back to file-level resolution
+        let resolved = TypeReference::new("string".to_string()).resolve_within(&proto_file, &["Search"]).unwrap();
+        assert_eq!(resolved.definition_location, TypeLocation::Builtin);
+        assert!(TypeReference::new("Missing".to_string()).resolve_within(&proto_file, &["Search"]).is_none());
+    }
+
+    #[test]
+    fn test_find_definitions_by_nesting_path() {
+        let field = |name: &str| Field {
+            name: name.to_string(),
+            field_type: FieldType::Int32,
+            number: 1,
+            label: FieldLabel::Optional,
+            options: Vec::new(),
+            comments: Vec::new(),
+            packed: false,
+            behaviors: Vec::new(),
+        };
+        let message = |name: &str, fields: Vec<Field>, nested_messages: Vec<Message>, nested_enums: Vec<Enum>| Message {
+            name: name.to_string(),
+            fields,
+            nested_messages,
+            nested_enums,
+            options: Vec::new(),
+            comments: Vec::new(),
+            is_group: false,
+            reserved_ranges: Vec::new(),
+            reserved_names: Vec::new(),
+            source: None,
+        };
+        let enum_def = |name: &str| Enum {
+            name: name.to_string(),
+            values: Vec::new(),
+            options: Vec::new(),
+            comments: Vec::new(),
+            source: None,
+        };
+
+        let mut proto_file = ProtoFile::new();
+        proto_file.package = Some("a.v1".to_string());
+        proto_file.messages.push(message("Book", vec![field("name")], Vec::new(), Vec::new()));
+        proto_file.messages.push(message("Outer", Vec::new(), vec![
+            message("Book", vec![field("y")], Vec::new(), Vec::new()),
+            message("Inner", Vec::new(), Vec::new(), Vec::new()),
+        ], vec![enum_def("Status")]));
+        proto_file.enums.push(enum_def("Status"));
+
+        // A nested message never shadows the top-level one of the same name
+        assert_eq!(proto_file.find_message("Book").unwrap().fields[0].name, "name");
+        assert_eq!(proto_file.find_message("a.v1.Book").unwrap().fields[0].name, "name");
+        assert_eq!(proto_file.find_message("Outer.Book").unwrap().fields[0].name, "y");
+        assert_eq!(proto_file.find_message(".a.v1.Outer.Book").unwrap().fields[0].name, "y");
+
+        // Unambiguous simple names still find nested definitions
+        assert_eq!(proto_file.find_message("other.v1.Inner").unwrap().name, "Inner");
+        assert!(proto_file.find_message("other.v1.Book").is_none());
+
+        // Enums follow the same rules
+        assert!(std::ptr::eq(proto_file.find_enum("Status").unwrap(), &proto_file.enums[0]));
+        assert!(std::ptr::eq(proto_file.find_enum("Outer.Status").unwrap(), &proto_file.messages[1].nested_enums[0]));
+        assert!(proto_file.find_enum("other.v1.Status").is_none());
+
+        // References inside a message resolve from its scope
+        let (book, path) = proto_file.find_message_within(&TypeReference::new("Book".to_string()), &["Outer"]).unwrap();
+        assert_eq!(book.fields[0].name, "y");
+        assert_eq!(path, vec!["Outer", "Book"]);
+        let (book, path) = proto_file.find_message_within(&TypeReference::new("Book".to_string()), &[]).unwrap();
+        assert_eq!(book.fields[0].name, "name");
+        assert_eq!(path, vec!["Book"]);
+    }
+
     #[test]
     fn test_request_body_creation() {
         let entire_body = RequestBody::entire_message();
@@ -224,4 +375,96 @@ mod tests {
         assert_eq!(required_query.name, "filter");
         assert!(required_query.required);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_http_route_ordering() {
+        let routes = vec![
+            HttpRoute::new("UserService".to_string(), "UpdateUser".to_string(), HttpMethod::Put, "/users/{id}".to_string()),
+            HttpRoute::new("UserService".to_string(), "ListUsers".to_string(), HttpMethod::Get, "/users".to_string()),
+            HttpRoute::new("UserService".to_string(), "GetUser".to_string(), HttpMethod::Get, "/users/{id}".to_string()),
+            HttpRoute::new("UserService".to_string(), "CreateUser".to_string(), HttpMethod::Post, "/users".to_string()),
+            HttpRoute::new("UserService".to_string(), "DeleteUser".to_string(), HttpMethod::Delete, "/users/{id}".to_string()),
+        ];
+
+        let mut sorted = routes.clone();
+        sorted.sort();
+        let names: Vec<&str> = sorted.iter().map(|r| r.method_name.as_str()).collect();
+        assert_eq!(names, vec!["ListUsers", "CreateUser", "DeleteUser", "GetUser", "UpdateUser"]);
+
+        let mut reversed = routes.clone();
+        reversed.reverse();
+        reversed.sort();
+        assert_eq!(reversed, sorted);
+
+        let set: std::collections::BTreeSet<HttpRoute> = routes.iter().chain(routes.iter()).cloned().collect();
+        assert_eq!(set.len(), routes.len());
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), sorted);
+
+        // Routes agreeing on every sort key are ordered by their remaining fields
+        let plain = HttpRoute::new("UserService".to_string(), "GetUser".to_string(), HttpMethod::Get, "/users/{id}".to_string());
+        let mut paged = plain.clone();
+        paged.query_parameters.push(QueryParameter::optional("page".to_string(), ParameterType::Integer));
+        let mut deprecated = plain.clone();
+        deprecated.deprecation = Some(Deprecation::default());
+        assert_eq!(plain.cmp(&paged), std::cmp::Ordering::Less);
+        assert_eq!(paged.cmp(&plain), std::cmp::Ordering::Greater);
+        assert_eq!(plain.cmp(&deprecated), std::cmp::Ordering::Less);
+        assert_eq!(plain.cmp(&plain.clone()), std::cmp::Ordering::Equal);
+
+        // A custom method spelled like a standard one is still a different route
+        let custom = HttpRoute::new("UserService".to_string(), "GetUser".to_string(), HttpMethod::Custom("GET".to_string()), "/users/{id}".to_string());
+        assert_ne!(plain.cmp(&custom), std::cmp::Ordering::Equal);
+        let set: std::collections::BTreeSet<HttpRoute> = [plain, paged, deprecated, custom].into_iter().collect();
+        assert_eq!(set.len(), 4);
+    }
+
+    #[test]
+    fn test_rate_limit_parsing() {
+        let limit = |requests, period| Some(RateLimit { requests, period });
+        assert_eq!(RateLimit::parse("100/min"), limit(100, RateLimitPeriod::Minute));
+        assert_eq!(RateLimit::parse("10 / Seconds"), limit(10, RateLimitPeriod::Second));
+        assert_eq!(RateLimit::parse("5/s"), limit(5, RateLimitPeriod::Second));
+        assert_eq!(RateLimit::parse("60/mins"), limit(60, RateLimitPeriod::Minute));
+        assert_eq!(RateLimit::parse("1000/h"), limit(1000, RateLimitPeriod::Hour));
+        assert_eq!(RateLimit::parse("5000/days"), limit(5000, RateLimitPeriod::Day));
+
+        // Units are matched exactly rather than by stripping a trailing `s`
+        assert_eq!(RateLimit::parse("100/ms"), None);
+        assert_eq!(RateLimit::parse("100/hs"), None);
+        assert_eq!(RateLimit::parse("100/secss"), None);
+        assert_eq!(RateLimit::parse("100/"), None);
+        assert_eq!(RateLimit::parse("100/week"), None);
+        assert_eq!(RateLimit::parse("lots"), None);
+    }
+
+    #[test]
+    fn test_option_value_normalization() {
+        let first = OptionValue::MessageLiteral(vec![
+            ("post".to_string(), OptionValue::String("/v1/users".to_string())),
+            ("body".to_string(), OptionValue::String("*".to_string())),
+            ("limits".to_string(), OptionValue::MessageLiteral(vec![
+                ("max".to_string(), OptionValue::Number(100.0)),
+                ("enabled".to_string(), OptionValue::Boolean(true)),
+            ])),
+        ]);
+        let second = OptionValue::MessageLiteral(vec![
+            ("limits".to_string(), OptionValue::MessageLiteral(vec![
+                ("enabled".to_string(), OptionValue::Boolean(true)),
+                ("max".to_string(), OptionValue::Number(100.0)),
+            ])),
+            ("body".to_string(), OptionValue::String("*".to_string())),
+            ("post".to_string(), OptionValue::String("/v1/users".to_string())),
+        ]);
+        assert_ne!(first, second);
+        assert_eq!(first.normalize(), second.normalize());
+
+        let normalized = first.normalize();
+        assert_eq!(normalized.get("post").and_then(OptionValue::as_str), Some("/v1/users"));
+        let limits = normalized.get("limits").unwrap();
+        assert_eq!(limits.get("max").and_then(OptionValue::as_i64), Some(100));
+        assert_eq!(limits.get("enabled").and_then(OptionValue::as_bool), Some(true));
+        assert_eq!(OptionValue::Identifier("OUTPUT_ONLY".to_string()).as_str(), Some("OUTPUT_ONLY"));
+        assert_eq!(OptionValue::Number(1.5).as_i64(), None);
+        assert_eq!(OptionValue::String("true".to_string()).as_bool(), None);
+    }
+}
@@ -1,6 +1,7 @@
 //! Error types for the Proto HTTP Parser library
 
 use thiserror::Error;
+use std::ops::Range;
 use std::path::PathBuf;
 
 /// Main error type for the Proto HTTP Parser library
@@ -26,6 +27,17 @@ pub enum ProtoHttpParserError {
     
     #[error("Plugin error: {0}")]
     Plugin(#[from] PluginError),
+    
+    #[error("{} errors occurred: {}", .0.len(), join_errors(.0))]
+    Multiple(Vec<ProtoHttpParserError>),
+}
+
+/// Join the messages of several errors into one line
+fn join_errors(errors: &[ProtoHttpParserError]) -> String {
+    errors.iter()
+        .map(|error| error.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 /// Errors that occur during Protocol Buffer parsing
@@ -55,9 +67,29 @@ pub enum ParseError {
         cycle: Vec<String>,
     },
     
+    #[error("Import depth exceeds maximum of {max_depth}: {chain:?}")]
+    ImportDepthExceeded {
+        max_depth: usize,
+        chain: Vec<String>,
+    },
+    
+    #[error("Definition '{name}' exceeds maximum nesting depth of {max_depth}")]
+    NestingDepthExceeded {
+        name: String,
+        max_depth: usize,
+    },
+    
     #[error("Invalid Protocol Buffer syntax: {message}")]
     InvalidSyntax {
         message: String,
+        /// Byte offsets into the source of the offending text, when known
+        span: Option<Range<usize>>,
+    },
+    
+    #[error("Unknown option '{name}' on {location}")]
+    UnknownOption {
+        name: String,
+        location: String,
     },
     
     #[error("Unsupported feature: {feature}")]
@@ -74,6 +106,11 @@ pub enum ParseError {
     InvalidEncoding {
         path: PathBuf,
     },
+    
+    #[error("Invalid FileDescriptorSet: {message}")]
+    InvalidDescriptorSet {
+        message: String,
+    },
 }
 
 /// Errors that occur during validation
@@ -131,6 +168,40 @@ pub enum ValidationError {
     HttpAnnotationError {
         message: String,
     },
+    
+    #[error("Invalid enum {enum_name}: {message}")]
+    InvalidEnum {
+        enum_name: String,
+        message: String,
+    },
+    
+    #[error("Invalid field number {number} for {message_name}.{field_name}: {reason}")]
+    InvalidFieldNumber {
+        message_name: String,
+        field_name: String,
+        number: u32,
+        reason: String,
+    },
+    
+    #[error("Invalid value {value} for option {option} on {location}")]
+    InvalidOptionValue {
+        option: String,
+        value: String,
+        location: String,
+    },
+    
+    #[error("Warning treated as error: {message}")]
+    PromotedWarning {
+        message: String,
+    },
+    
+    #[error("Validation could not complete: {message}")]
+    ProcessingFailed {
+        message: String,
+    },
+    
+    #[error("{} validation errors: {}", .0.len(), .0.iter().map(|error| error.to_string()).collect::<Vec<_>>().join("; "))]
+    Multiple(Vec<ValidationError>),
 }
 
 /// Errors that occur during code generation
@@ -157,6 +228,12 @@ pub enum CodeGenerationError {
         to_type: String,
     },
     
+    #[error("Client-streaming method {service_name}.{method_name} has an HTTP binding, but only server streaming can be served over HTTP")]
+    UnsupportedClientStreaming {
+        service_name: String,
+        method_name: String,
+    },
+    
     #[error("Missing dependency: {dependency}")]
     MissingDependency {
         dependency: String,
@@ -171,6 +248,28 @@ pub enum CodeGenerationError {
     ContextError {
         message: String,
     },
+    
+    #[error("No code generator registered for target framework: {framework}")]
+    GeneratorNotRegistered {
+        framework: String,
+    },
+    
+    #[error("Failed to render template '{template_name}' for service {service_name}{}: {message} (context: {context_snippet})",
+        .method_name.as_ref().map(|method| format!(", method {}", method)).unwrap_or_default())]
+    RenderFailed {
+        template_name: String,
+        service_name: String,
+        method_name: Option<String>,
+        context_snippet: String,
+        message: String,
+    },
+    
+    #[error("Output file {filename} is generated by both {} and {} with different content", .first.display(), .second.display())]
+    OutputCollision {
+        filename: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
 }
 
 /// Errors that occur in the template engine
@@ -0,0 +1,582 @@
+//! `FileDescriptorSet` decoding, for pipelines that already run `protoc`
+//!
+//! A descriptor set written by `protoc --descriptor_set_out` (optionally with
+//! `--include_imports`) is decoded with `prost-types` into the same [`ProtoFile`]
+//! model the text parser produces. Extension options are not part of the
+//! `prost-types` descriptors, so `google.api.http`, `google.api.method_signature`
+//! and `google.api.field_behavior` are decoded from a second pass over the same
+//! bytes with minimal mirror messages that only declare those extensions.
+
+use crate::core::*;
+use crate::parser::parse_method_signature;
+use prost::Message as _;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::method_options::IdempotencyLevel as DescriptorIdempotencyLevel;
+use prost_types::{
+    DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorProto,
+    FileDescriptorSet, MethodDescriptorProto, ServiceDescriptorProto,
+};
+use std::path::{Path, PathBuf};
+
+/// Parser turning a binary `FileDescriptorSet` into [`ProtoFile`]s
+///
+/// Source comments are only available when the set was produced with
+/// `--include_source_info` and are not decoded; definitions come without comments.
+#[derive(Debug, Clone, Default)]
+pub struct DescriptorSetParser;
+
+impl DescriptorSetParser {
+    /// Create a new descriptor set parser
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Decode the descriptor set stored at `path`
+    pub fn parse_file(&self, path: &Path) -> Result<Vec<(PathBuf, ProtoFile)>, ParseError> {
+        let bytes = std::fs::read(path)
+            .map_err(|_| ParseError::FileNotFound { path: path.to_path_buf() })?;
+        self.parse_bytes(&bytes)
+    }
+
+    /// Decode an encoded descriptor set into one [`ProtoFile`] per file it describes,
+    /// paired with the file's import path (e.g. `user/v1/user.proto`)
+    pub fn parse_bytes(&self, bytes: &[u8]) -> Result<Vec<(PathBuf, ProtoFile)>, ParseError> {
+        let descriptor_set = FileDescriptorSet::decode(bytes).map_err(invalid_descriptor_set)?;
+        let extensions = ExtensionSet::decode(bytes).map_err(invalid_descriptor_set)?;
+
+        Ok(descriptor_set.file.iter()
+            .enumerate()
+            .map(|(index, file)| {
+                let file_extensions = extensions.file.get(index).cloned().unwrap_or_default();
+                (PathBuf::from(file.name()), proto_file(file, &file_extensions))
+            })
+            .collect())
+    }
+}
+
+fn invalid_descriptor_set(error: prost::DecodeError) -> ParseError {
+    ParseError::InvalidDescriptorSet {
+        message: error.to_string(),
+    }
+}
+
+/// Convert one file descriptor
+fn proto_file(file: &FileDescriptorProto, extensions: &FileExtensions) -> ProtoFile {
+    let import_type = |index: usize| {
+        let index = index as i32;
+        if file.public_dependency.contains(&index) {
+            ImportType::Public
+        } else if file.weak_dependency.contains(&index) {
+            ImportType::Weak
+        } else {
+            ImportType::Normal
+        }
+    };
+    let package = file.package.clone().filter(|package| !package.is_empty());
+    let scope = TypeScope { package: package.as_deref() };
+
+    ProtoFile {
+        syntax: match file.syntax() {
+            "proto3" => ProtocolVersion::Proto3,
+            "editions" => ProtocolVersion::Edition(edition_name(extensions.edition)),
+            _ => ProtocolVersion::Proto2,
+        },
+        imports: file.dependency.iter()
+            .enumerate()
+            .map(|(index, path)| Import { import_type: import_type(index), path: path.clone() })
+            .collect(),
+        options: Vec::new(),
+        services: file.service.iter()
+            .enumerate()
+            .map(|(index, service)| {
+                let service_extensions = extensions.service.get(index).cloned().unwrap_or_default();
+                self::service(service, &service_extensions, &scope)
+            })
+            .collect(),
+        messages: file.message_type.iter()
+            .enumerate()
+            .map(|(index, message)| {
+                let message_extensions = extensions.message_type.get(index).cloned().unwrap_or_default();
+                self::message(message, &message_extensions, &scope)
+            })
+            .collect(),
+        enums: file.enum_type.iter().map(enum_def).collect(),
+        unknown_statements: Vec::new(),
+        package,
+    }
+}
+
+/// Edition named by a `google.protobuf.Edition` value, e.g. `2023` for `EDITION_2023`
+fn edition_name(edition: Option<i32>) -> String {
+    match edition {
+        Some(1000) => "2023".to_string(),
+        Some(1001) => "2024".to_string(),
+        Some(other) => other.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Package of the file being converted, used to shorten type names declared in it
+struct TypeScope<'a> {
+    package: Option<&'a str>,
+}
+
+impl TypeScope<'_> {
+    /// Reference to a type named `.package.Type`, written relative to the file's
+    /// package as the text parser would see it
+    fn type_reference(&self, type_name: &str) -> TypeReference {
+        let qualified_name = type_name.trim_start_matches('.');
+        let relative_name = self.package
+            .and_then(|package| qualified_name.strip_prefix(package)?.strip_prefix('.'))
+            .unwrap_or(qualified_name);
+        TypeReference::from_qualified_name(relative_name)
+    }
+}
+
+fn service(service: &ServiceDescriptorProto, extensions: &ServiceExtensions, scope: &TypeScope) -> Service {
+    Service {
+        name: service.name().to_string(),
+        methods: service.method.iter()
+            .enumerate()
+            .map(|(index, method)| {
+                let method_options = extensions.method.get(index)
+                    .and_then(|method| method.options.clone())
+                    .unwrap_or_default();
+                self::method(method, &method_options, scope)
+            })
+            .collect(),
+        options: Vec::new(),
+        comments: Vec::new(),
+        source: None,
+    }
+}
+
+fn method(method: &MethodDescriptorProto, extensions: &MethodOptionExtensions, scope: &TypeScope) -> RpcMethod {
+    let mut options = Vec::new();
+    let idempotency_level = match method.options.as_ref().map(|options| options.idempotency_level()) {
+        Some(DescriptorIdempotencyLevel::NoSideEffects) => Some(IdempotencyLevel::NoSideEffects),
+        Some(DescriptorIdempotencyLevel::Idempotent) => Some(IdempotencyLevel::Idempotent),
+        _ => None,
+    };
+    if let Some(level) = idempotency_level {
+        options.push(ProtoOption {
+            name: "idempotency_level".to_string(),
+            value: OptionValue::Identifier(level.as_proto_name().to_string()),
+        });
+    }
+    for signature in &extensions.method_signature {
+        options.push(ProtoOption {
+            name: "(google.api.method_signature)".to_string(),
+            value: OptionValue::String(signature.clone()),
+        });
+    }
+
+    RpcMethod {
+        name: method.name().to_string(),
+        input_type: TypeReference {
+            is_stream: method.client_streaming(),
+            ..scope.type_reference(method.input_type())
+        },
+        output_type: TypeReference {
+            is_stream: method.server_streaming(),
+            ..scope.type_reference(method.output_type())
+        },
+        options,
+        comments: Vec::new(),
+        http_annotation: extensions.http.as_ref().and_then(HttpRule::to_annotation),
+        idempotency_level,
+        method_signature: extensions.method_signature.first()
+            .map(|signature| parse_method_signature(signature))
+            .unwrap_or_default(),
+    }
+}
+
+fn message(message: &DescriptorProto, extensions: &MessageExtensions, scope: &TypeScope) -> Message {
+    // Map fields refer to a synthesized `<Name>Entry` message flagged as a map entry
+    let is_map_entry = |nested: &DescriptorProto| nested.options.as_ref().is_some_and(|options| options.map_entry());
+    let map_entry = |type_name: &str| message.nested_type.iter()
+        .filter(|nested| is_map_entry(nested))
+        .find(|nested| type_name.rsplit('.').next() == Some(nested.name()));
+
+    let fields = message.field.iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let field_extensions = extensions.field.get(index)
+                .and_then(|field| field.options.clone())
+                .unwrap_or_default();
+            let entry = (field.r#type() == Type::Message)
+                .then(|| map_entry(field.type_name()))
+                .flatten();
+            self::field(field, entry, &field_extensions, scope)
+        })
+        .collect();
+
+    let nested_messages = message.nested_type.iter()
+        .enumerate()
+        .filter(|(_, nested)| !is_map_entry(nested))
+        .map(|(index, nested)| {
+            let nested_extensions = extensions.nested_type.get(index).cloned().unwrap_or_default();
+            self::message(nested, &nested_extensions, scope)
+        })
+        .collect();
+
+    Message {
+        name: message.name().to_string(),
+        fields,
+        nested_messages,
+        nested_enums: message.enum_type.iter().map(enum_def).collect(),
+        options: Vec::new(),
+        comments: Vec::new(),
+        is_group: false,
+        // Descriptor ranges are end-exclusive
+        reserved_ranges: message.reserved_range.iter()
+            .map(|range| ReservedRange {
+                start: range.start() as u32,
+                end: (range.end() <= MAX_FIELD_NUMBER_EXCLUSIVE).then(|| range.end() as u32 - 1),
+            })
+            .collect(),
+        reserved_names: message.reserved_name.clone(),
+        source: None,
+    }
+}
+
+/// End of a `reserved ... to max` range in a descriptor (`2^29`)
+const MAX_FIELD_NUMBER_EXCLUSIVE: i32 = 536_870_912;
+
+fn field(field: &FieldDescriptorProto, map_entry: Option<&DescriptorProto>, extensions: &FieldOptionExtensions, scope: &TypeScope) -> Field {
+    let mut options: Vec<ProtoOption> = extensions.field_behavior.iter()
+        .filter_map(|&behavior| FIELD_BEHAVIOR_NAMES.get(behavior as usize))
+        .map(|name| ProtoOption {
+            name: "(google.api.field_behavior)".to_string(),
+            value: OptionValue::Identifier(name.to_string()),
+        })
+        .collect();
+    let packed = field.options.as_ref().is_some_and(|options| options.packed());
+    if packed {
+        options.push(ProtoOption { name: "packed".to_string(), value: OptionValue::Boolean(true) });
+    }
+
+    let (field_type, label) = match map_entry {
+        Some(entry) => {
+            let entry_type = |number: i32| entry.field.iter()
+                .find(|field| field.number() == number)
+                .map(|field| field_type(field, scope))
+                .unwrap_or(FieldType::String);
+            let map_type = FieldType::Map {
+                key: Box::new(entry_type(1)),
+                value: Box::new(entry_type(2)),
+            };
+            (map_type, FieldLabel::Optional)
+        }
+        None => {
+            let label = match field.label() {
+                Label::Repeated => FieldLabel::Repeated,
+                Label::Required => FieldLabel::Required,
+                Label::Optional => FieldLabel::Optional,
+            };
+            (field_type(field, scope), label)
+        }
+    };
+
+    Field {
+        name: field.name().to_string(),
+        field_type,
+        number: field.number() as u32,
+        label,
+        behaviors: FieldBehavior::from_options(&options),
+        options,
+        comments: Vec::new(),
+        packed,
+    }
+}
+
+fn field_type(field: &FieldDescriptorProto, scope: &TypeScope) -> FieldType {
+    match field.r#type() {
+        Type::Double => FieldType::Double,
+        Type::Float => FieldType::Float,
+        Type::Int64 => FieldType::Int64,
+        Type::Uint64 => FieldType::Uint64,
+        Type::Int32 => FieldType::Int32,
+        Type::Fixed64 => FieldType::Fixed64,
+        Type::Fixed32 => FieldType::Fixed32,
+        Type::Bool => FieldType::Bool,
+        Type::String => FieldType::String,
+        Type::Bytes => FieldType::Bytes,
+        Type::Uint32 => FieldType::Uint32,
+        Type::Sfixed32 => FieldType::Sfixed32,
+        Type::Sfixed64 => FieldType::Sfixed64,
+        Type::Sint32 => FieldType::Sint32,
+        Type::Sint64 => FieldType::Sint64,
+        Type::Group | Type::Message | Type::Enum => FieldType::MessageOrEnum(scope.type_reference(field.type_name())),
+    }
+}
+
+fn enum_def(enum_def: &EnumDescriptorProto) -> Enum {
+    Enum {
+        name: enum_def.name().to_string(),
+        values: enum_def.value.iter()
+            .map(|value| EnumValue {
+                name: value.name().to_string(),
+                number: value.number(),
+                options: Vec::new(),
+                comments: Vec::new(),
+            })
+            .collect(),
+        options: Vec::new(),
+        comments: Vec::new(),
+        source: None,
+    }
+}
+
+/// `google.api.FieldBehavior` value names, indexed by number
+const FIELD_BEHAVIOR_NAMES: &[&str] = &[
+    "FIELD_BEHAVIOR_UNSPECIFIED", "OPTIONAL", "REQUIRED", "OUTPUT_ONLY", "INPUT_ONLY",
+    "IMMUTABLE", "UNORDERED_LIST", "NON_EMPTY_DEFAULT", "IDENTIFIER",
+];
+
+// Mirrors of the descriptor messages declaring only the path to the extension
+// options; every other field is skipped while decoding.
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ExtensionSet {
+    #[prost(message, repeated, tag = "1")]
+    file: Vec<FileExtensions>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct FileExtensions {
+    #[prost(message, repeated, tag = "4")]
+    message_type: Vec<MessageExtensions>,
+    #[prost(message, repeated, tag = "6")]
+    service: Vec<ServiceExtensions>,
+    /// `google.protobuf.Edition` of an editions file, missing from `prost-types` 0.13
+    #[prost(int32, optional, tag = "14")]
+    edition: Option<i32>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct MessageExtensions {
+    #[prost(message, repeated, tag = "2")]
+    field: Vec<FieldExtensions>,
+    #[prost(message, repeated, tag = "3")]
+    nested_type: Vec<MessageExtensions>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct FieldExtensions {
+    #[prost(message, optional, tag = "8")]
+    options: Option<FieldOptionExtensions>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct FieldOptionExtensions {
+    /// `google.api.field_behavior`
+    #[prost(int32, repeated, packed = "false", tag = "1052")]
+    field_behavior: Vec<i32>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ServiceExtensions {
+    #[prost(message, repeated, tag = "2")]
+    method: Vec<MethodExtensions>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct MethodExtensions {
+    #[prost(message, optional, tag = "4")]
+    options: Option<MethodOptionExtensions>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct MethodOptionExtensions {
+    /// `google.api.method_signature`
+    #[prost(string, repeated, tag = "1051")]
+    method_signature: Vec<String>,
+    /// `google.api.http`
+    #[prost(message, optional, tag = "72295728")]
+    http: Option<HttpRule>,
+}
+
+/// `google.api.HttpRule`
+#[derive(Clone, PartialEq, prost::Message)]
+struct HttpRule {
+    #[prost(oneof = "HttpPattern", tags = "2, 3, 4, 5, 6, 8")]
+    pattern: Option<HttpPattern>,
+    #[prost(string, tag = "7")]
+    body: String,
+    #[prost(message, repeated, tag = "11")]
+    additional_bindings: Vec<HttpRule>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum HttpPattern {
+    #[prost(string, tag = "2")]
+    Get(String),
+    #[prost(string, tag = "3")]
+    Put(String),
+    #[prost(string, tag = "4")]
+    Post(String),
+    #[prost(string, tag = "5")]
+    Delete(String),
+    #[prost(string, tag = "6")]
+    Patch(String),
+    #[prost(message, tag = "8")]
+    Custom(CustomHttpPattern),
+}
+
+/// `google.api.CustomHttpPattern`
+#[derive(Clone, PartialEq, prost::Message)]
+struct CustomHttpPattern {
+    #[prost(string, tag = "1")]
+    kind: String,
+    #[prost(string, tag = "2")]
+    path: String,
+}
+
+impl HttpRule {
+    /// Method and path of the rule, if it declares a pattern
+    fn binding(&self) -> Option<(HttpMethod, String)> {
+        Some(match self.pattern.clone()? {
+            HttpPattern::Get(path) => (HttpMethod::Get, path),
+            HttpPattern::Put(path) => (HttpMethod::Put, path),
+            HttpPattern::Post(path) => (HttpMethod::Post, path),
+            HttpPattern::Delete(path) => (HttpMethod::Delete, path),
+            HttpPattern::Patch(path) => (HttpMethod::Patch, path),
+            HttpPattern::Custom(custom) => (HttpMethod::Custom(custom.kind), custom.path),
+        })
+    }
+
+    fn body(&self) -> Option<String> {
+        Some(self.body.clone()).filter(|body| !body.is_empty())
+    }
+
+    fn to_annotation(&self) -> Option<HttpAnnotation> {
+        let (method, path) = self.binding()?;
+        Some(HttpAnnotation {
+            method,
+            path,
+            body: self.body(),
+            additional_bindings: self.additional_bindings.iter()
+                .filter_map(|binding| {
+                    let (method, path) = binding.binding()?;
+                    Some(HttpBinding { method, path, body: binding.body() })
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::MethodOptions;
+
+    /// Encode `bytes` as length-delimited field `tag`
+    fn length_delimited(tag: u32, bytes: &[u8]) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        prost::encoding::encode_key(tag, prost::encoding::WireType::LengthDelimited, &mut encoded);
+        prost::encoding::encode_varint(bytes.len() as u64, &mut encoded);
+        encoded.extend_from_slice(bytes);
+        encoded
+    }
+
+    #[test]
+    fn test_parse_descriptor_set() {
+        // `prost-types` has no room for extensions, so the method options carrying
+        // `google.api.http` are appended to the encoded method by hand
+        let http_rule = HttpRule {
+            pattern: Some(HttpPattern::Get("/v1/users/{id}".to_string())),
+            body: String::new(),
+            additional_bindings: Vec::new(),
+        };
+        let mut method_options = MethodOptions {
+            idempotency_level: Some(DescriptorIdempotencyLevel::NoSideEffects as i32),
+            ..MethodOptions::default()
+        }.encode_to_vec();
+        method_options.extend(length_delimited(72295728, &http_rule.encode_to_vec()));
+
+        let mut method = MethodDescriptorProto {
+            name: Some("GetUser".to_string()),
+            input_type: Some(".user.v1.GetUserRequest".to_string()),
+            output_type: Some(".user.v1.User".to_string()),
+            ..MethodDescriptorProto::default()
+        }.encode_to_vec();
+        method.extend(length_delimited(4, &method_options));
+
+        let mut service = ServiceDescriptorProto {
+            name: Some("UserService".to_string()),
+            ..ServiceDescriptorProto::default()
+        }.encode_to_vec();
+        service.extend(length_delimited(2, &method));
+
+        let string_field = |name: &str, number: i32| FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(Type::String as i32),
+            ..FieldDescriptorProto::default()
+        };
+        let mut file = FileDescriptorProto {
+            name: Some("user/v1/user.proto".to_string()),
+            package: Some("user.v1".to_string()),
+            dependency: vec!["google/api/annotations.proto".to_string()],
+            message_type: vec![
+                DescriptorProto {
+                    name: Some("GetUserRequest".to_string()),
+                    field: vec![string_field("id", 1)],
+                    ..DescriptorProto::default()
+                },
+                DescriptorProto {
+                    name: Some("User".to_string()),
+                    field: vec![string_field("id", 1), string_field("name", 2)],
+                    ..DescriptorProto::default()
+                },
+            ],
+            syntax: Some("proto3".to_string()),
+            ..FileDescriptorProto::default()
+        }.encode_to_vec();
+        file.extend(length_delimited(6, &service));
+
+        let files = DescriptorSetParser::new().parse_bytes(&length_delimited(1, &file)).unwrap();
+        assert_eq!(files.len(), 1);
+        let (path, proto_file) = &files[0];
+        assert_eq!(path, &PathBuf::from("user/v1/user.proto"));
+        assert_eq!(proto_file.syntax, ProtocolVersion::Proto3);
+        assert_eq!(proto_file.package.as_deref(), Some("user.v1"));
+        assert_eq!(proto_file.imports[0].path, "google/api/annotations.proto");
+        assert_eq!(proto_file.messages.len(), 2);
+        assert_eq!(proto_file.messages[1].fields[1].name, "name");
+
+        let method = &proto_file.services[0].methods[0];
+        assert_eq!(proto_file.services[0].name, "UserService");
+        assert_eq!(method.input_type, TypeReference::new("GetUserRequest".to_string()));
+        assert_eq!(method.idempotency_level, Some(IdempotencyLevel::NoSideEffects));
+        let annotation = method.http_annotation.as_ref().unwrap();
+        assert_eq!(annotation.method, HttpMethod::Get);
+        assert_eq!(annotation.path, "/v1/users/{id}");
+
+        let routes = crate::GoogleApiHttpExtractor::new().extract_routes(proto_file).unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].path_template, "/v1/users/{id}");
+        assert_eq!(routes[0].path_parameters[0].name, "id");
+    }
+
+    #[test]
+    fn test_parse_editions_descriptor_set() {
+        let mut file = FileDescriptorProto {
+            name: Some("user/v1/user.proto".to_string()),
+            syntax: Some("editions".to_string()),
+            ..FileDescriptorProto::default()
+        }.encode_to_vec();
+        prost::encoding::int32::encode(14, &1000, &mut file);
+
+        let files = DescriptorSetParser::new().parse_bytes(&length_delimited(1, &file)).unwrap();
+        assert_eq!(files[0].1.syntax, ProtocolVersion::Edition("2023".to_string()));
+    }
+
+    #[test]
+    fn test_parse_invalid_descriptor_set() {
+        let result = DescriptorSetParser::new().parse_bytes(&[0x0a, 0xff]);
+        assert!(matches!(result, Err(ParseError::InvalidDescriptorSet { .. })));
+    }
+}
@@ -7,6 +7,7 @@ use crate::core::{
     errors::*,
     SourceLocation, ValidationSuggestion,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -112,6 +113,117 @@ pub enum ErrorSeverity {
     Hint,
 }
 
+impl ErrorSeverity {
+    /// Get the LSP `DiagnosticSeverity` number: 1 error, 2 warning, 3 information, 4 hint
+    pub fn lsp_severity(&self) -> u8 {
+        match self {
+            ErrorSeverity::Error => 1,
+            ErrorSeverity::Warning => 2,
+            ErrorSeverity::Info => 3,
+            ErrorSeverity::Hint => 4,
+        }
+    }
+}
+
+/// Machine-readable error in the shape of an LSP `Diagnostic`, for editor integration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Source range of the error; the start of the file when the error has no location
+    pub range: DiagnosticRange,
+    /// LSP severity, see [`ErrorSeverity::lsp_severity`]
+    pub severity: u8,
+    /// Error code for documentation lookup, e.g. `P001`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Name of the tool reporting the diagnostic
+    pub source: String,
+    /// Error message
+    pub message: String,
+    /// File the error occurred in, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+}
+
+/// Range between two positions of a [`Diagnostic`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticRange {
+    /// Start position, inclusive
+    pub start: DiagnosticPosition,
+    /// End position, exclusive
+    pub end: DiagnosticPosition,
+}
+
+/// Zero-based line and character offset, as in LSP
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticPosition {
+    /// Zero-based line
+    pub line: usize,
+    /// Zero-based character offset within the line
+    pub character: usize,
+}
+
+impl DetailedErrorReport {
+    /// Convert this report to an LSP-shaped diagnostic
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let (range, file) = diagnostic_range(self.location.as_ref());
+        
+        Diagnostic {
+            range,
+            severity: self.severity.lsp_severity(),
+            code: self.error_code.clone(),
+            source: env!("CARGO_PKG_NAME").to_string(),
+            message: self.error.to_string(),
+            file,
+        }
+    }
+}
+
+impl ProtoHttpParserError {
+    /// Convert this error to an LSP-shaped diagnostic
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let reporter = ErrorReporter::new();
+        let location = reporter.extract_location(self);
+        let (range, file) = diagnostic_range(location.as_ref());
+        
+        Diagnostic {
+            range,
+            severity: reporter.determine_severity(self).lsp_severity(),
+            code: reporter.get_error_code(self),
+            source: env!("CARGO_PKG_NAME").to_string(),
+            message: self.to_string(),
+            file,
+        }
+    }
+}
+
+impl ValidationError {
+    /// Convert this error to an LSP-shaped diagnostic
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        ProtoHttpParserError::Validation(self.clone()).to_diagnostic()
+    }
+}
+
+/// Get the diagnostic range and file of a source location
+///
+/// Locations are one-based; diagnostics are zero-based. Errors without a location
+/// point at the start of the file.
+fn diagnostic_range(location: Option<&SourceLocation>) -> (DiagnosticRange, Option<PathBuf>) {
+    let Some(location) = location else {
+        let start = DiagnosticPosition { line: 0, character: 0 };
+        return (DiagnosticRange { start, end: start }, None);
+    };
+    
+    let start = DiagnosticPosition {
+        line: location.line.saturating_sub(1),
+        character: location.column.saturating_sub(1),
+    };
+    let end = DiagnosticPosition {
+        character: start.character + location.length.unwrap_or(0),
+        ..start
+    };
+    (DiagnosticRange { start, end }, location.file.clone())
+}
+
 impl ErrorReporter {
     /// Create a new error reporter with default configuration
     pub fn new() -> Self {
@@ -275,10 +387,10 @@ impl ErrorReporter {
         let mut before_lines = Vec::new();
         let mut after_lines = Vec::new();
 
-        for i in start_idx..error_line_idx {
+        for (i, line) in lines.iter().enumerate().take(error_line_idx).skip(start_idx) {
             before_lines.push(ContextLine {
                 line_number: i + 1,
-                content: lines[i].to_string(),
+                content: line.to_string(),
                 is_error_line: false,
             });
         }
@@ -289,10 +401,10 @@ impl ErrorReporter {
             is_error_line: true,
         };
 
-        for i in (error_line_idx + 1)..end_idx {
+        for (i, line) in lines.iter().enumerate().take(end_idx).skip(error_line_idx + 1) {
             after_lines.push(ContextLine {
                 line_number: i + 1,
-                content: lines[i].to_string(),
+                content: line.to_string(),
                 is_error_line: false,
             });
         }
@@ -336,8 +448,8 @@ impl ErrorReporter {
                     location: None,
                 });
             }
-            ProtoHttpParserError::Validation(ValidationError::InvalidHttpAnnotation { message, .. }) => {
-                if message.contains("must start with '/'") {
+            ProtoHttpParserError::Validation(ValidationError::InvalidHttpAnnotation { message, .. })
+                if message.contains("must start with '/'") => {
                     suggestions.push(ValidationSuggestion {
                         issue_type: "InvalidHttpAnnotation".to_string(),
                         message: "Add a leading '/' to the path template".to_string(),
@@ -346,7 +458,6 @@ impl ErrorReporter {
                         location: None,
                     });
                 }
-            }
             _ => {}
         }
 
@@ -363,6 +474,13 @@ impl ErrorReporter {
             ProtoHttpParserError::Io(_) => ErrorSeverity::Error,
             ProtoHttpParserError::Config(_) => ErrorSeverity::Warning,
             ProtoHttpParserError::Plugin(_) => ErrorSeverity::Warning,
+            ProtoHttpParserError::Multiple(errors) => {
+                if errors.iter().any(|error| self.determine_severity(error) == ErrorSeverity::Error) {
+                    ErrorSeverity::Error
+                } else {
+                    ErrorSeverity::Warning
+                }
+            }
         }
     }
 
@@ -376,6 +494,7 @@ impl ErrorReporter {
             ProtoHttpParserError::Io(_) => "IO".to_string(),
             ProtoHttpParserError::Config(_) => "Config".to_string(),
             ProtoHttpParserError::Plugin(_) => "Plugin".to_string(),
+            ProtoHttpParserError::Multiple(_) => "Multiple".to_string(),
         }
     }
 
@@ -518,4 +637,29 @@ mod tests {
         
         assert_eq!(reporter.get_error_code(&syntax_error), Some("P001".to_string()));
     }
+
+    #[test]
+    fn test_parse_error_diagnostic() {
+        let syntax_error = ProtoHttpParserError::Parse(ParseError::Syntax {
+            line: 3,
+            column: 5,
+            message: "expected '{'".to_string(),
+        });
+        
+        let json = serde_json::to_value(syntax_error.to_diagnostic()).unwrap();
+        assert_eq!(json, serde_json::json!({
+            "range": {
+                "start": { "line": 2, "character": 4 },
+                "end": { "line": 2, "character": 4 },
+            },
+            "severity": 1,
+            "code": "P001",
+            "source": "proto-http-parser",
+            "message": "Parse error: Syntax error at line 3, column 5: expected '{'",
+        }));
+        
+        let diagnostic = ValidationError::UndefinedType { type_name: "Missing".to_string(), line: 7 }.to_diagnostic();
+        assert_eq!(diagnostic.code.as_deref(), Some("V001"));
+        assert_eq!(diagnostic.range.start.line, 6);
+    }
 }
\ No newline at end of file
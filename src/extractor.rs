@@ -2,7 +2,7 @@
 
 use crate::core::*;
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 /// Google API HTTP annotation extractor
 pub struct GoogleApiHttpExtractor {
@@ -28,7 +28,7 @@ impl GoogleApiHttpExtractor {
     }
     
     /// Extract path parameters from a path template
-    fn extract_path_parameters(&self, path_template: &str, _input_message: &TypeReference) -> Result<Vec<PathParameter>, ValidationError> {
+    fn extract_path_parameters(&self, path_template: &str, proto_file: &ProtoFile, input_message: &TypeReference) -> Result<Vec<PathParameter>, ValidationError> {
         let mut parameters = Vec::new();
         
         for cap in self.path_param_regex.captures_iter(path_template) {
@@ -46,8 +46,8 @@ impl GoogleApiHttpExtractor {
             // e.g., "book.id" -> "book_id", "user.profile.name" -> "user_profile_name"
             let param_name = self.normalize_parameter_name(raw_param_name);
             
-            // Infer parameter type based on common patterns
-            let param_type = self.infer_parameter_type(&param_name);
+            // Resolve the type from the bound field, falling back to common patterns
+            let param_type = self.resolve_parameter_type(proto_file, input_message, raw_param_name);
             
             parameters.push(PathParameter::new(param_name, param_type));
         }
@@ -73,23 +73,134 @@ impl GoogleApiHttpExtractor {
         }
     }
     
-    /// Extract query parameters based on configuration
-    fn extract_query_parameters(&self, _method: &RpcMethod) -> Vec<QueryParameter> {
-        if !self.config.infer_query_params {
-            return Vec::new();
+    /// Resolve a parameter's type from the request message field it is bound to
+    ///
+    /// Enum-typed fields produce [`ParameterType::Enum`] and `google.protobuf.Timestamp`
+    /// fields produce [`ParameterType::Timestamp`]; anything else falls back to
+    /// [`Self::infer_parameter_type`].
+    fn resolve_parameter_type(&self, proto_file: &ProtoFile, input_message: &TypeReference, field_path: &str) -> ParameterType {
+        let fallback = self.infer_parameter_type(&self.normalize_parameter_name(field_path));
+        
+        let (type_ref, scope) = match self.resolve_field_type(proto_file, input_message, field_path) {
+            Some(resolved) => resolved,
+            None => return fallback,
+        };
+        
+        if type_ref.fully_qualified_name() == "google.protobuf.Timestamp" {
+            ParameterType::Timestamp
+        } else if let Some(enum_def) = proto_file.find_enum_within(type_ref, &scope_path(&scope)) {
+            ParameterType::Enum(enum_def.name.clone())
+        } else {
+            fallback
         }
+    }
+    
+    /// Resolve the message or enum type of the field a dotted path points to
+    ///
+    /// The type is returned with the nesting path of the message declaring the field,
+    /// the scope the type reference is resolved in. Returns `None` when the path does
+    /// not resolve or ends at a scalar field.
+    fn resolve_field_type<'a>(&self, proto_file: &'a ProtoFile, input_message: &TypeReference, field_path: &str) -> Option<(&'a TypeReference, Vec<String>)> {
+        let (mut message, mut scope) = proto_file.find_message_within(input_message, &[])?;
         
-        let mut parameters = Vec::new();
+        let segments: Vec<&str> = field_path.split('.').collect();
+        for (index, segment) in segments.iter().enumerate() {
+            let field = message.fields.iter().find(|field| field.name == *segment)?;
+            
+            let type_ref = match &field.field_type {
+                FieldType::MessageOrEnum(type_ref) => type_ref,
+                _ => return None,
+            };
+            
+            if index + 1 == segments.len() {
+                return Some((type_ref, scope));
+            }
+            
+            // Descend into the nested message for the next path segment
+            (message, scope) = proto_file.find_message_within(type_ref, &scope_path(&scope))?;
+        }
+        
+        None
+    }
+    
+    /// Extract query parameters based on configuration
+    ///
+    /// Parameters bound to message-typed fields use the `deepObject` style so that
+    /// sub-fields can be passed as `?filter[status]=active`.
+    fn extract_query_parameters(&self, proto_file: &ProtoFile, method: &RpcMethod) -> Vec<QueryParameter> {
+        // An explicit declaration replaces inference for the method
+        let param_names = match method.declared_query_params() {
+            Some(declared) => declared,
+            None if !self.config.infer_query_params => return Vec::new(),
+            None if self.config.query_param_inference == QueryParamInference::ListMethods
+                && !self.is_list_method(proto_file, method) => return Vec::new(),
+            None => self.config.common_query_params.clone(),
+        };
+        
+        let mut parameters: Vec<QueryParameter> = Vec::new();
         
-        // Add common query parameters based on configuration
-        for param_name in &self.config.common_query_params {
-            let param_type = self.infer_parameter_type(param_name);
-            parameters.push(QueryParameter::optional(param_name.clone(), param_type));
+        for param_name in &param_names {
+            // A name listed more than once yields a single parameter
+            if parameters.iter().any(|parameter| parameter.name == *param_name) {
+                continue;
+            }
+            
+            let nested_message = self.resolve_field_type(proto_file, &method.input_type, param_name)
+                .filter(|(type_ref, _)| !type_ref.is_well_known_type())
+                .and_then(|(type_ref, scope)| proto_file.find_message_within(type_ref, &scope_path(&scope)))
+                .map(|(message, _)| message);
+            
+            // A proto2 `required` label or `REQUIRED` field behavior makes the parameter required
+            let required = self.bound_field(proto_file, &method.input_type, param_name)
+                .is_some_and(Field::is_required);
+            
+            let parameter = match nested_message {
+                Some(message) => QueryParameter::new(param_name.clone(), ParameterType::Custom(message.name.clone()), required)
+                    .with_style(QueryParameterStyle::DeepObject),
+                None => {
+                    // An explicit scalar field takes precedence over the type inferred from the name
+                    let param_type = self.scalar_parameter_type(proto_file, &method.input_type, param_name)
+                        .unwrap_or_else(|| self.resolve_parameter_type(proto_file, &method.input_type, param_name));
+                    QueryParameter::new(param_name.clone(), param_type, required)
+                }
+            };
+            parameters.push(parameter);
         }
         
         parameters
     }
     
+    /// Find the field a dotted path of the input message points to
+    fn bound_field<'a>(&self, proto_file: &'a ProtoFile, input_message: &TypeReference, field_path: &str) -> Option<&'a Field> {
+        let (parent_path, field_name) = match field_path.rsplit_once('.') {
+            Some((parent_path, field_name)) => (Some(parent_path), field_name),
+            None => (None, field_path),
+        };
+        let (type_ref, scope) = match parent_path {
+            Some(parent_path) => self.resolve_field_type(proto_file, input_message, parent_path)?,
+            None => (input_message, Vec::new()),
+        };
+        let (message, _) = proto_file.find_message_within(type_ref, &scope_path(&scope))?;
+        message.fields.iter().find(|field| field.name == field_name)
+    }
+    
+    /// Map the scalar field a dotted path points to onto a parameter type
+    ///
+    /// Returns `None` when the path does not resolve or ends at a message or enum field.
+    fn scalar_parameter_type(&self, proto_file: &ProtoFile, input_message: &TypeReference, field_path: &str) -> Option<ParameterType> {
+        let field = self.bound_field(proto_file, input_message, field_path)?;
+        
+        match field.field_type {
+            FieldType::Double | FieldType::Float => Some(ParameterType::Float),
+            FieldType::Int32 | FieldType::Int64 | FieldType::Uint32 | FieldType::Uint64
+            | FieldType::Sint32 | FieldType::Sint64 | FieldType::Fixed32 | FieldType::Fixed64
+            | FieldType::Sfixed32 | FieldType::Sfixed64 => Some(ParameterType::Integer),
+            FieldType::Bool => Some(ParameterType::Boolean),
+            FieldType::String | FieldType::Bytes => Some(ParameterType::String),
+            FieldType::MessageOrEnum(_) | FieldType::Map { .. } => None,
+        }
+    }
+    
     /// Parse HTTP method from option value
     fn parse_http_method(&self, method_str: &str) -> Result<HttpMethod, ValidationError> {
         match method_str.to_uppercase().as_str() {
@@ -147,8 +258,8 @@ impl GoogleApiHttpExtractor {
                             }
                         }
                         "additional_bindings" => {
-                            // Parse additional bindings if present
-                            additional_bindings = self.parse_additional_bindings(value)?;
+                            // Each repeated `additional_bindings` entry adds bindings
+                            additional_bindings.extend(self.parse_additional_bindings(value)?);
                         }
                         _ => {
                             // Ignore unknown fields
@@ -204,6 +315,74 @@ impl GoogleApiHttpExtractor {
         }
     }
     
+    /// Check whether a whole-message body carries no data, i.e. the request is
+    /// `google.protobuf.Empty` or a message without fields
+    fn is_empty_body(&self, proto_file: &ProtoFile, method: &RpcMethod, request_body: &RequestBody) -> bool {
+        request_body.is_entire_message && (
+            method.input_type.is_empty_message()
+                || proto_file.find_message(&method.input_type.name)
+                    .is_some_and(|message| message.fields.is_empty())
+        )
+    }
+    
+    /// Resolve the type and label of a `body: "field"` request body from the input message
+    fn resolve_body_field_type(&self, proto_file: &ProtoFile, method: &RpcMethod, request_body: RequestBody) -> RequestBody {
+        let field = request_body.field.as_ref().and_then(|field_name| {
+            proto_file.find_message(&method.input_type.name)?
+                .fields
+                .iter()
+                .find(|field| &field.name == field_name)
+        });
+        
+        match field {
+            Some(field) if field.label == FieldLabel::Repeated => {
+                request_body.with_field_type(field.field_type.to_type_reference()).repeated()
+            }
+            Some(field) => request_body.with_field_type(field.field_type.to_type_reference()),
+            None => request_body,
+        }
+    }
+    
+    /// Take the media type of a request body from a `(request_content_type)` method
+    /// option (e.g. `"application/x-protobuf"`), keeping JSON otherwise
+    fn apply_request_content_type(&self, method: &RpcMethod, request_body: RequestBody) -> RequestBody {
+        let content_type = method.options.iter()
+            .find(|option| option.name.trim_start_matches('(').trim_end_matches(')') == "request_content_type")
+            .and_then(|option| option.value.as_str());
+        
+        match content_type {
+            Some(content_type) => request_body.with_content_type(content_type.to_string()),
+            None => request_body,
+        }
+    }
+    
+    /// Check whether a method lists a collection
+    ///
+    /// Either the method name starts with a configured list prefix, or the first
+    /// field of its response is repeated (the AIP-132 list response layout).
+    fn is_list_method(&self, proto_file: &ProtoFile, method: &RpcMethod) -> bool {
+        let has_list_prefix = self.config.list_method_prefixes.iter()
+            .any(|prefix| method.name.starts_with(prefix.as_str()));
+        
+        has_list_prefix || proto_file.find_message(&method.output_type.name)
+            .and_then(|message| message.fields.first())
+            .is_some_and(|field| field.label == FieldLabel::Repeated)
+    }
+    
+    /// Check whether a method follows the AIP list pagination convention
+    fn is_paginated(&self, proto_file: &ProtoFile, method: &RpcMethod) -> bool {
+        let has_field = |type_ref: &TypeReference, field_name: &str| {
+            proto_file.find_message(&type_ref.name)
+                .map(|message| message.fields.iter().any(|field| field.name == field_name))
+                .unwrap_or(false)
+        };
+        
+        !method.output_type.is_stream
+            && has_field(&method.input_type, "page_size")
+            && has_field(&method.input_type, "page_token")
+            && has_field(&method.output_type, "next_page_token")
+    }
+    
     /// Validate path template syntax
     pub fn validate_path_template(&self, path_template: &str) -> Result<(), ValidationError> {
         // Check for basic path template validity
@@ -214,6 +393,18 @@ impl GoogleApiHttpExtractor {
             });
         }
         
+        // Reject oversized templates before scanning them
+        if path_template.len() > self.config.max_path_template_length {
+            return Err(ValidationError::InvalidHttpAnnotation {
+                message: format!(
+                    "Path template is {} bytes long, exceeding the maximum of {}",
+                    path_template.len(),
+                    self.config.max_path_template_length
+                ),
+                line: 0,
+            });
+        }
+        
         if !path_template.starts_with('/') {
             return Err(ValidationError::InvalidHttpAnnotation {
                 message: "Path template must start with '/'".to_string(),
@@ -262,53 +453,41 @@ impl GoogleApiHttpExtractor {
         Ok(())
     }
     
-    /// Check for conflicting routes
-    fn check_route_conflicts(&self, routes: &[HttpRoute]) -> Result<(), ValidationError> {
-        let mut route_signatures = HashSet::new();
-        
-        for route in routes {
-            let signature = format!("{} {}", route.http_method.as_str(), route.path_template);
-            
-            if route_signatures.contains(&signature) {
-                return Err(ValidationError::ConflictingRoutes {
-                    route1: signature.clone(),
-                    route2: signature,
-                });
-            }
-            
-            route_signatures.insert(signature);
-        }
-        
-        Ok(())
-    }
-}
-
-impl Default for GoogleApiHttpExtractor {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl HttpAnnotationExtractor for GoogleApiHttpExtractor {
-    type Error = ValidationError;
-    
-    fn extract_routes(&self, proto_file: &ProtoFile) -> Result<Vec<HttpRoute>, Self::Error> {
+    /// Extract routes, taking HTTP rules from `overrides` over inline annotations
+    ///
+    /// Overrides let HTTP rules live outside the proto file, e.g. in a service
+    /// config; methods without an inline annotation can be exposed this way.
+    pub fn extract_routes_with_overrides(&self, proto_file: &ProtoFile, overrides: &HttpRuleOverrides) -> Result<Vec<HttpRoute>, ValidationError> {
         let mut routes = Vec::new();
         
         for service in &proto_file.services {
             for method in &service.methods {
-                if let Some(http_annotation) = self.extract_http_annotation(method)? {
+                let http_annotation = match self.find_override(proto_file, service, method, overrides) {
+                    Some(annotation) => Some(annotation.clone()),
+                    None => self.extract_http_annotation(method)?,
+                };
+                
+                if let Some(mut http_annotation) = http_annotation {
+                    // Prefix paths with the service's base path
+                    http_annotation.path = service.http_path(&http_annotation.path);
+                    for binding in &mut http_annotation.additional_bindings {
+                        binding.path = service.http_path(&binding.path);
+                    }
+                    
                     // Validate path template
                     self.validate_path_template(&http_annotation.path)?;
                     
                     // Extract path parameters
-                    let path_parameters = self.extract_path_parameters(&http_annotation.path, &method.input_type)?;
+                    let path_parameters = self.extract_path_parameters(&http_annotation.path, proto_file, &method.input_type)?;
                     
                     // Extract query parameters
-                    let query_parameters = self.extract_query_parameters(method);
+                    let query_parameters = self.extract_query_parameters(proto_file, method);
                     
                     // Determine request body
-                    let request_body = self.determine_request_body(method, &http_annotation);
+                    let request_body = self.determine_request_body(method, &http_annotation)
+                        .filter(|body| !self.is_empty_body(proto_file, method, body))
+                        .map(|body| self.resolve_body_field_type(proto_file, method, body))
+                        .map(|body| self.apply_request_content_type(method, body));
                     
                     // Create HTTP route
                     let route = HttpRoute {
@@ -321,6 +500,12 @@ impl HttpAnnotationExtractor for GoogleApiHttpExtractor {
                         request_body,
                         input_type: method.input_type.clone(),
                         response_type: method.output_type.clone(),
+                        idempotency_level: method.idempotency_level,
+                        is_paginated: self.is_paginated(proto_file, method),
+                        deprecation: method.deprecation(),
+                        segments: PathSegment::parse(&http_annotation.path),
+                        rate_limit: method.rate_limit().and_then(Result::ok),
+                        security: proto_file.security_scheme(service, method),
                     };
                     
                     routes.push(route);
@@ -329,8 +514,8 @@ impl HttpAnnotationExtractor for GoogleApiHttpExtractor {
                     for binding in &http_annotation.additional_bindings {
                         self.validate_path_template(&binding.path)?;
                         
-                        let path_parameters = self.extract_path_parameters(&binding.path, &method.input_type)?;
-                        let query_parameters = self.extract_query_parameters(method);
+                        let path_parameters = self.extract_path_parameters(&binding.path, proto_file, &method.input_type)?;
+                        let query_parameters = self.extract_query_parameters(proto_file, method);
                         let request_body = if binding.body.is_some() {
                             match &binding.body {
                                 Some(body_field) if body_field == "*" => Some(RequestBody::entire_message()),
@@ -339,7 +524,10 @@ impl HttpAnnotationExtractor for GoogleApiHttpExtractor {
                             }
                         } else {
                             None
-                        };
+                        }
+                        .filter(|body| !self.is_empty_body(proto_file, method, body))
+                        .map(|body| self.resolve_body_field_type(proto_file, method, body))
+                        .map(|body| self.apply_request_content_type(method, body));
                         
                         let additional_route = HttpRoute {
                             service_name: service.name.clone(),
@@ -351,6 +539,12 @@ impl HttpAnnotationExtractor for GoogleApiHttpExtractor {
                             request_body,
                             input_type: method.input_type.clone(),
                             response_type: method.output_type.clone(),
+                            idempotency_level: method.idempotency_level,
+                            is_paginated: self.is_paginated(proto_file, method),
+                            deprecation: method.deprecation(),
+                            segments: PathSegment::parse(&binding.path),
+                            rate_limit: method.rate_limit().and_then(Result::ok),
+                            security: proto_file.security_scheme(service, method),
                         };
                         
                         routes.push(additional_route);
@@ -362,6 +556,49 @@ impl HttpAnnotationExtractor for GoogleApiHttpExtractor {
         Ok(routes)
     }
     
+    /// Look up an override for a method by its fully-qualified name
+    fn find_override<'a>(&self, proto_file: &ProtoFile, service: &Service, method: &RpcMethod, overrides: &'a HttpRuleOverrides) -> Option<&'a HttpAnnotation> {
+        let qualified_name = match &proto_file.package {
+            Some(package) => format!("{}.{}.{}", package, service.name, method.name),
+            None => format!("{}.{}", service.name, method.name),
+        };
+        
+        overrides.get(&qualified_name)
+            .or_else(|| overrides.get(&format!(".{}", qualified_name)))
+    }
+    
+    /// Check for conflicting routes
+    fn check_route_conflicts(&self, routes: &[HttpRoute]) -> Result<(), ValidationError> {
+        let mut route_signatures: HashMap<String, &HttpRoute> = HashMap::new();
+        
+        for route in routes {
+            if let Some(existing) = route_signatures.get(&route.conflict_signature()) {
+                return Err(ValidationError::ConflictingRoutes {
+                    route1: existing.describe(),
+                    route2: route.describe(),
+                });
+            }
+            
+            route_signatures.insert(route.conflict_signature(), route);
+        }
+        
+        Ok(())
+    }
+}
+
+impl Default for GoogleApiHttpExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpAnnotationExtractor for GoogleApiHttpExtractor {
+    type Error = ValidationError;
+    
+    fn extract_routes(&self, proto_file: &ProtoFile) -> Result<Vec<HttpRoute>, Self::Error> {
+        self.extract_routes_with_overrides(proto_file, &HttpRuleOverrides::new())
+    }
+    
     fn validate_annotations(&self, routes: &[HttpRoute]) -> Result<(), Self::Error> {
         // Check for conflicting routes
         self.check_route_conflicts(routes)?;
@@ -371,14 +608,13 @@ impl HttpAnnotationExtractor for GoogleApiHttpExtractor {
             // Validate HTTP method compatibility
             if self.config.validate_http_methods {
                 match route.http_method {
-                    HttpMethod::Get | HttpMethod::Delete => {
-                        if route.has_request_body() {
+                    HttpMethod::Get | HttpMethod::Delete
+                        if route.has_request_body() => {
                             return Err(ValidationError::InvalidHttpAnnotation {
                                 message: format!("{} methods should not have request bodies", route.http_method.as_str()),
                                 line: 0,
                             });
                         }
-                    }
                     HttpMethod::Custom(_) if !self.config.allow_custom_methods => {
                         return Err(ValidationError::InvalidHttpAnnotation {
                             message: "Custom HTTP methods are not allowed".to_string(),
@@ -395,4 +631,8 @@ impl HttpAnnotationExtractor for GoogleApiHttpExtractor {
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+/// Borrow a nesting path as the scope argument of the `*_within` lookups
+fn scope_path(scope: &[String]) -> Vec<&str> {
+    scope.iter().map(String::as_str).collect()
+}
@@ -1,7 +1,95 @@
 //! Code generation implementation for poem-openapi
 
 use crate::core::*;
-use crate::templates::HandlebarsTemplateEngine;
+use crate::templates::{field_wire_name, HandlebarsTemplateEngine};
+use std::collections::{HashMap, HashSet};
+
+/// Static OpenAPI document generation
+mod openapi;
+
+/// Object-safe code generator type stored in a [`GeneratorRegistry`]
+pub type DynCodeGenerator = dyn CodeGenerator<Error = CodeGenerationError> + Send + Sync;
+
+/// Registry mapping target frameworks to their code generators
+///
+/// The coordinator dispatches code generation through this registry, so
+/// third-party frameworks can be supported by registering a generator for
+/// a [`TargetFramework::Custom`] variant at runtime.
+pub struct GeneratorRegistry {
+    generators: HashMap<TargetFramework, Box<DynCodeGenerator>>,
+    /// Frameworks whose registered generator is still the built-in one
+    builtin: HashSet<TargetFramework>,
+}
+
+impl GeneratorRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self {
+            generators: HashMap::new(),
+            builtin: HashSet::new(),
+        }
+    }
+    
+    /// Create a registry with the built-in generators configured from `config`
+    pub fn with_builtin(config: &GeneratorConfig) -> Self {
+        let mut registry = Self::new();
+        registry.reconfigure_builtin(config);
+        registry
+    }
+    
+    /// Rebuild the built-in generators from `config`
+    ///
+    /// Frameworks that had a user generator registered are left untouched, so
+    /// reconfiguring never discards a caller's generator.
+    pub fn reconfigure_builtin(&mut self, config: &GeneratorConfig) {
+        let framework = TargetFramework::PoemOpenApi;
+        if self.generators.contains_key(&framework) && !self.builtin.contains(&framework) {
+            return;
+        }
+        self.generators.insert(
+            framework.clone(),
+            Box::new(PoemOpenApiGenerator::with_config(config.clone())),
+        );
+        self.builtin.insert(framework);
+    }
+    
+    /// Register a generator for a framework, returning any generator it replaces
+    pub fn register(
+        &mut self,
+        framework: TargetFramework,
+        generator: Box<DynCodeGenerator>,
+    ) -> Option<Box<DynCodeGenerator>> {
+        self.builtin.remove(&framework);
+        self.generators.insert(framework, generator)
+    }
+    
+    /// Remove the generator registered for a framework
+    pub fn unregister(&mut self, framework: &TargetFramework) -> Option<Box<DynCodeGenerator>> {
+        self.builtin.remove(framework);
+        self.generators.remove(framework)
+    }
+    
+    /// Get the generator registered for a framework
+    pub fn get(&self, framework: &TargetFramework) -> Option<&DynCodeGenerator> {
+        self.generators.get(framework).map(|generator| generator.as_ref())
+    }
+    
+    /// Check whether a generator is registered for a framework
+    pub fn contains(&self, framework: &TargetFramework) -> bool {
+        self.generators.contains_key(framework)
+    }
+    
+    /// Get all frameworks with a registered generator
+    pub fn frameworks(&self) -> Vec<&TargetFramework> {
+        self.generators.keys().collect()
+    }
+}
+
+impl Default for GeneratorRegistry {
+    fn default() -> Self {
+        Self::with_builtin(&GeneratorConfig::default())
+    }
+}
 
 /// Poem OpenAPI code generator
 #[allow(dead_code)]
@@ -26,6 +114,11 @@ impl PoemOpenApiGenerator {
             template_engine: HandlebarsTemplateEngine::new(),
         }
     }
+    
+    /// Get mutable access to the template engine (e.g. to override built-in templates)
+    pub fn template_engine_mut(&mut self) -> &mut HandlebarsTemplateEngine {
+        &mut self.template_engine
+    }
 }
 
 impl Default for PoemOpenApiGenerator {
@@ -37,115 +130,125 @@ impl Default for PoemOpenApiGenerator {
 impl CodeGenerator for PoemOpenApiGenerator {
     type Error = CodeGenerationError;
     
+    /// Reject negotiated responses whose model cannot derive `prost::Message`
+    fn check_models(&self, proto_file: &ProtoFile, routes: &[HttpRoute]) -> Result<(), Self::Error> {
+        for service in &proto_file.services {
+            let service_routes: Vec<&HttpRoute> = routes.iter()
+                .filter(|route| route.service_name == service.name)
+                .collect();
+            let response_payloads = self.response_payloads(service, &service_routes);
+            
+            for route in &service_routes {
+                if !matches!(response_payloads.get(&route.method_name), Some(TemplateValue::String(payload)) if payload == "Negotiated") {
+                    continue;
+                }
+                
+                let response_type = &route.response_type;
+                let reason = if self.external_type_path(response_type).is_some() {
+                    Some("it is mapped to an external type".to_string())
+                } else {
+                    match proto_file.find_message(&response_type.name) {
+                        Some(message) => protobuf_blocker(message)
+                            .map(|field| format!("its field `{}` has no protobuf encoding", field.name)),
+                        None => Some("it is not a message of the proto file".to_string()),
+                    }
+                };
+                if let Some(reason) = reason {
+                    return Err(CodeGenerationError::ContextError {
+                        message: format!(
+                            "{}.{} negotiates protobuf responses, but the {} model cannot derive prost::Message: {}",
+                            service.name, route.method_name, response_type.fully_qualified_name(), reason
+                        ),
+                    });
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
     fn generate_controller(&self, service: &Service, routes: &[HttpRoute]) -> Result<GeneratedCode, Self::Error> {
         // Filter routes for this service
         let service_routes: Vec<&HttpRoute> = routes.iter()
             .filter(|route| route.service_name == service.name)
             .collect();
         
-        // Collect all message types used in this service
-        let service_routes_slice: Vec<HttpRoute> = service_routes.iter().map(|&r| r.clone()).collect();
-        let message_types = self.collect_message_types(service, &service_routes_slice);
-        
-        // Debug output
-        println!("DEBUG: Controller - Collected message types for {}: {:?}", service.name, message_types);
-        
-        // Check if we need Path or Query imports
-        let has_path_params = service_routes.iter().any(|route| !route.path_parameters.is_empty());
-        let has_query_params = service_routes.iter().any(|route| !route.query_parameters.is_empty());
+        self.check_streaming(&service_routes)?;
+        self.check_request_bodies(&service_routes)?;
         
-        // Create template context
-        let mut custom_data = std::collections::HashMap::new();
-        if !message_types.is_empty() {
-            // Create a single string with the import list including braces
-            let import_list = format!("{{{}}}", message_types.join(", "));
-            custom_data.insert("message_types".to_string(), TemplateValue::String(import_list));
+        match self.controller_chunks(&service_routes) {
+            Some(chunks) => self.render_split_controller(service, chunks.len()),
+            None => self.render_controller(service, &service_routes, None),
         }
-        custom_data.insert("has_path_params".to_string(), TemplateValue::Boolean(has_path_params));
-        custom_data.insert("has_query_params".to_string(), TemplateValue::Boolean(has_query_params));
+    }
+    
+    fn generate_controller_parts(&self, service: &Service, routes: &[HttpRoute]) -> Result<Vec<GeneratedCode>, Self::Error> {
+        let service_routes: Vec<&HttpRoute> = routes.iter()
+            .filter(|route| route.service_name == service.name)
+            .collect();
         
-        // Add input_type for each route by matching with service methods
-        let mut enriched_routes = Vec::new();
-        for route in &service_routes {
-            let mut route_clone = (*route).clone();
-            
-            // Find the corresponding service method to get input type
-            if let Some(method) = service.methods.iter().find(|m| m.name == route.method_name) {
-                // Add input_type to the route context (we'll need to modify the route structure or use custom_data)
-                // For now, we'll add it to custom_data with a route-specific key
-                let input_type_key = format!("{}_input_type", route.method_name);
-                custom_data.insert(input_type_key, TemplateValue::String(method.input_type.name.clone()));
-            }
-            
-            enriched_routes.push(route_clone);
-        }
+        let Some(chunks) = self.controller_chunks(&service_routes) else {
+            return Ok(Vec::new());
+        };
+        chunks.into_iter()
+            .enumerate()
+            .map(|(index, chunk)| self.render_controller(service, chunk, Some(index + 1)))
+            .collect()
+    }
+    
+    fn generate_service_trait(&self, service: &Service, routes: &[HttpRoute]) -> Result<GeneratedCode, Self::Error> {
+        // Filter routes for this service
+        let service_routes: Vec<&HttpRoute> = routes.iter()
+            .filter(|route| route.service_name == service.name)
+            .collect();
         
         let context = TemplateContext {
             service: service.clone(),
-            routes: enriched_routes,
-            custom_data,
+            custom_data: self.service_trait_data(service, &service_routes),
+            routes: service_routes.into_iter().cloned().collect(),
         };
         
-        // Render the controller template
-        let content = self.template_engine
-            .render("controller", &context)
-            .map_err(|e| CodeGenerationError::ContextError {
-                message: format!("Failed to render controller template: {}", e),
-            })?;
+        // Render the service trait template
+        let content = self.render_template("service_trait", &context)?;
+        let content = self.with_lint_allows(content);
         
         // Generate required imports
-        let mut imports = vec![
-            "std::sync::Arc".to_string(),
-        ];
-        
-        // Add conditional imports
-        let mut openapi_imports = vec!["OpenApi".to_string(), "payload::Json".to_string()];
-        if has_path_params {
-            openapi_imports.push("param::Path".to_string());
-        }
-        if has_query_params {
-            openapi_imports.push("param::Query".to_string());
+        let mut imports = Vec::new();
+        if !self.config.use_native_async_trait {
+            imports.push("async_trait::async_trait".to_string());
         }
-        imports.push(format!("poem_openapi::{{{}}}", openapi_imports.join(", ")));
         
         // Add imports for custom types used in the service
         for route in &context.routes {
-            // Add import for response type if it's not a scalar
-            if !route.response_type.is_scalar() && !route.response_type.is_well_known_type() {
-                let type_name = &route.response_type.name;
-                if !type_name.starts_with("google.protobuf.") {
-                    imports.push(format!("crate::{}", type_name));
-                }
+            // Add import for input type if it's not a scalar
+            if let Some(path) = self.external_type_path(&route.response_type) {
+                imports.push(path.clone());
+            } else if !route.response_type.is_scalar()
+                && !route.response_type.is_well_known_type()
+                && !route.response_type.fully_qualified_name().starts_with("google.protobuf.")
+            {
+                imports.push(format!("crate::{}", route.response_type.name));
             }
             
             // Add imports for path and query parameter types
             for param in &route.path_parameters {
-                if let ParameterType::Custom(type_name) = &param.param_type {
+                if let ParameterType::Custom(type_name) | ParameterType::Enum(type_name) = &param.param_type {
                     imports.push(format!("crate::{}", type_name));
                 }
             }
             
             for param in &route.query_parameters {
-                if let ParameterType::Custom(type_name) = &param.param_type {
+                if let ParameterType::Custom(type_name) | ParameterType::Enum(type_name) = &param.param_type {
                     imports.push(format!("crate::{}", type_name));
                 }
             }
             
-            // Add import for request body type if present
-            if let Some(request_body) = &route.request_body {
-                if request_body.is_entire_message {
-                    // Find the input type from the service method
-                    if let Some(method) = service.methods.iter().find(|m| m.name == route.method_name) {
-                        if !method.input_type.is_scalar() && !method.input_type.is_well_known_type() {
-                            let type_name = &method.input_type.name;
-                            if !type_name.starts_with("google.protobuf.") {
-                                imports.push(format!("crate::{}", type_name));
-                            }
-                        }
-                    }
-                } else if let Some(field_type) = &request_body.field {
-                    // Custom field type import
-                    imports.push(format!("crate::{}", field_type));
+            // Add import for the resolved body field type
+            if let Some(field_type) = route.request_body.as_ref().and_then(|body| body.field_type.as_ref()) {
+                if let Some(path) = self.external_type_path(field_type) {
+                    imports.push(path.clone());
+                } else if !field_type.is_scalar() && !field_type.is_well_known_type() {
+                    imports.push(format!("crate::{}", field_type.name));
                 }
             }
         }
@@ -155,136 +258,1786 @@ impl CodeGenerator for PoemOpenApiGenerator {
         imports.dedup();
         
         // Generate required dependencies
-        let _dependencies = vec![
-            "poem-openapi".to_string(),
-            "poem".to_string(),
-        ];
+        let mut dependencies = Vec::new();
+        if !self.config.use_native_async_trait {
+            dependencies.push("async-trait".to_string());
+        }
+        
+        if context.routes.iter().any(|route| route.response_type.is_stream) {
+            dependencies.push("futures".to_string());
+        }
+        
+        if self.has_timestamp_parameters(&context.routes) {
+            dependencies.push("chrono".to_string());
+        }
         
-        Ok(GeneratedCode::new(content)
-            .with_import("std::sync::Arc".to_string())
-            .with_dependency("poem-openapi".to_string())
-            .with_dependency("poem".to_string()))
+        Ok(GeneratedCode {
+            content,
+            imports,
+            dependencies,
+        })
     }
     
-    fn generate_service_trait(&self, service: &Service, routes: &[HttpRoute]) -> Result<GeneratedCode, Self::Error> {
-        // Filter routes for this service
-        let service_routes: Vec<&HttpRoute> = routes.iter()
-            .filter(|route| route.service_name == service.name)
+    fn generate_tags_enum(&self, services: &[Service]) -> Result<Option<GeneratedCode>, Self::Error> {
+        if !self.config.generate_tags_enum || services.is_empty() {
+            return Ok(None);
+        }
+        
+        // One tag per service, described by the service's documentation comments
+        let tags = services.iter()
+            .map(|service| {
+                let description = service.comments.iter()
+                    .map(|comment| comment.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                
+                let mut tag = HashMap::new();
+                tag.insert("name".to_string(), TemplateValue::String(service.name.clone()));
+                tag.insert("description".to_string(), TemplateValue::String(description));
+                TemplateValue::Object(tag)
+            })
             .collect();
         
-        // Collect all message types used in this service
-        let service_routes_slice: Vec<HttpRoute> = service_routes.iter().map(|&r| r.clone()).collect();
-        let message_types = self.collect_message_types(service, &service_routes_slice);
+        let mut custom_data = HashMap::new();
+        custom_data.insert("tags".to_string(), TemplateValue::Array(tags));
+        custom_data.insert("non_exhaustive".to_string(), TemplateValue::Boolean(self.config.non_exhaustive));
         
-        // Create template context
-        let mut custom_data = std::collections::HashMap::new();
-        if !message_types.is_empty() {
-            // Create a single string with the import list including braces
-            let import_list = format!("{{{}}}", message_types.join(", "));
-            custom_data.insert("message_types".to_string(), TemplateValue::String(import_list));
+        let context = TemplateContext {
+            service: services[0].clone(),
+            routes: Vec::new(),
+            custom_data,
+        };
+        
+        // Render the tags enum template
+        let content = self.template_engine
+            .render("tags", &context)
+            .map_err(|e| CodeGenerationError::ContextError {
+                message: format!("Failed to render tags template: {}", e),
+            })?;
+        let content = self.with_lint_allows(content);
+        
+        Ok(Some(GeneratedCode {
+            content,
+            imports: Vec::new(),
+            dependencies: vec!["poem-openapi".to_string()],
+        }))
+    }
+    
+    fn generate_registration_helper(&self, services: &[Service]) -> Result<Option<GeneratedCode>, Self::Error> {
+        if !self.config.generate_registration_helper || services.is_empty() {
+            return Ok(None);
         }
         
+        let service_names = services.iter()
+            .map(|service| TemplateValue::String(service.name.clone()))
+            .collect();
+        
+        let mut custom_data = HashMap::new();
+        custom_data.insert("services".to_string(), TemplateValue::Array(service_names));
+        custom_data.insert("single_service".to_string(), TemplateValue::Boolean(services.len() == 1));
+        custom_data.insert("controller_groups".to_string(), TemplateValue::Array(
+            controller_groups(services, self.config.max_controller_tuple_arity)
+        ));
+        self.insert_casing_overrides(&mut custom_data);
+        self.insert_naming(&mut custom_data);
+        
         let context = TemplateContext {
-            service: service.clone(),
-            routes: service_routes.into_iter().cloned().collect(),
+            service: services[0].clone(),
+            routes: Vec::new(),
             custom_data,
         };
         
-        // Render the service trait template
+        // Render the registration helper template
         let content = self.template_engine
-            .render("service_trait", &context)
+            .render("registration", &context)
             .map_err(|e| CodeGenerationError::ContextError {
-                message: format!("Failed to render service trait template: {}", e),
+                message: format!("Failed to render registration template: {}", e),
             })?;
+        let content = self.with_lint_allows(content);
         
-        // Generate required imports
-        let mut imports = vec![
-            "async_trait::async_trait".to_string(),
-        ];
+        Ok(Some(GeneratedCode {
+            content,
+            imports: Vec::new(),
+            dependencies: vec!["poem-openapi".to_string()],
+        }))
+    }
+    
+    fn generate_error_module(&self, services: &[Service]) -> Result<Option<GeneratedCode>, Self::Error> {
+        if !self.config.generate_error_module || services.is_empty() {
+            return Ok(None);
+        }
         
-        // Add imports for custom types used in the service
-        for route in &context.routes {
-            // Add import for input type if it's not a scalar
-            if !route.response_type.is_scalar() && !route.response_type.is_well_known_type() {
-                let type_name = &route.response_type.name;
-                if !type_name.starts_with("google.protobuf.") {
-                    imports.push(format!("crate::{}", type_name));
+        let mut custom_data = HashMap::new();
+        if let Some(error_type) = &self.config.service_error_type {
+            custom_data.insert("service_error_type".to_string(), TemplateValue::String(error_type.clone()));
+        }
+        custom_data.insert("non_exhaustive".to_string(), TemplateValue::Boolean(self.config.non_exhaustive));
+        
+        let context = TemplateContext {
+            service: services[0].clone(),
+            routes: Vec::new(),
+            custom_data,
+        };
+        
+        // Render the errors module template
+        let content = self.template_engine
+            .render("errors", &context)
+            .map_err(|e| CodeGenerationError::ContextError {
+                message: format!("Failed to render errors template: {}", e),
+            })?;
+        let content = self.with_lint_allows(content);
+        
+        Ok(Some(GeneratedCode {
+            content,
+            imports: vec!["poem_openapi::payload::PlainText".to_string()],
+            dependencies: vec!["poem-openapi".to_string()],
+        }))
+    }
+    
+    fn generate_api_metadata(&self, metadata: &ApiMetadata) -> Result<Option<GeneratedCode>, Self::Error> {
+        if !self.config.generate_registration_helper {
+            return Ok(None);
+        }
+        
+        // Values are embedded as escaped Rust string literals
+        let mut custom_data = HashMap::new();
+        custom_data.insert("title".to_string(), TemplateValue::String(format!("{:?}", metadata.title)));
+        custom_data.insert("version".to_string(), TemplateValue::String(format!("{:?}", metadata.version)));
+        if let Some(description) = &metadata.description {
+            custom_data.insert("description".to_string(), TemplateValue::String(format!("{:?}", description)));
+        }
+        let servers = metadata.servers.iter()
+            .map(|server| {
+                let mut object = HashMap::new();
+                object.insert("url".to_string(), TemplateValue::String(format!("{:?}", server.default_url())));
+                if let Some(description) = &server.description {
+                    object.insert("description".to_string(), TemplateValue::String(format!("{:?}", description)));
                 }
-            }
+                TemplateValue::Object(object)
+            })
+            .collect();
+        custom_data.insert("servers".to_string(), TemplateValue::Array(servers));
+        
+        let context = TemplateContext {
+            service: Service::new(metadata.title.clone()),
+            routes: Vec::new(),
+            custom_data,
+        };
+        
+        let content = self.render_template("api_metadata", &context)?;
+        let content = self.with_lint_allows(content);
+        
+        Ok(Some(GeneratedCode {
+            content,
+            imports: Vec::new(),
+            dependencies: vec!["poem-openapi".to_string()],
+        }))
+    }
+    
+    fn generate_path_builders(&self, routes: &[HttpRoute]) -> Result<Option<GeneratedCode>, Self::Error> {
+        if !self.config.generate_path_builders || routes.is_empty() {
+            return Ok(None);
+        }
+        
+        let mut builders = Vec::new();
+        let mut name_counts: HashMap<String, usize> = HashMap::new();
+        for route in routes {
+            // Additional bindings and same-named methods of other services get a numeric suffix
+            let base_name = crate::utils::to_snake_case_with_overrides(&route.method_name, &self.config.casing_overrides, crate::utils::to_snake_case);
+            let count = name_counts.entry(base_name.clone()).or_insert(0);
+            *count += 1;
+            let name = if *count == 1 { base_name } else { format!("{}_{}", base_name, count) };
             
-            // Add imports for path and query parameter types
-            for param in &route.path_parameters {
-                if let ParameterType::Custom(type_name) = &param.param_type {
-                    imports.push(format!("crate::{}", type_name));
+            let path = self.rewrite_path(&route.path_template)?;
+            let mut format_string = String::new();
+            let mut params = Vec::new();
+            for segment in PathSegment::parse(&path) {
+                match segment {
+                    PathSegment::Literal(text) => format_string.push_str(&text.replace('{', "{{").replace('}', "}}")),
+                    PathSegment::Param { name, .. } => {
+                        format_string.push_str("{}");
+                        params.push(TemplateValue::String(crate::utils::sanitize_identifier(&name.replace('.', "_"))));
+                    }
                 }
             }
             
-            for param in &route.query_parameters {
-                if let ParameterType::Custom(type_name) = &param.param_type {
-                    imports.push(format!("crate::{}", type_name));
-                }
-            }
+            let mut builder = HashMap::new();
+            builder.insert("name".to_string(), TemplateValue::String(crate::utils::sanitize_identifier(&name)));
+            builder.insert("path".to_string(), TemplateValue::String(path));
+            builder.insert("method_name".to_string(), TemplateValue::String(route.method_name.clone()));
+            builder.insert("format_string".to_string(), TemplateValue::String(format!("{:?}", format_string)));
+            builder.insert("params".to_string(), TemplateValue::Array(params));
+            builders.push(TemplateValue::Object(builder));
         }
         
-        // Remove duplicates and sort
-        imports.sort();
-        imports.dedup();
+        let mut custom_data = HashMap::new();
+        custom_data.insert("builders".to_string(), TemplateValue::Array(builders));
         
-        // Generate required dependencies
-        let _dependencies = vec![
-            "async-trait".to_string(),
-        ];
+        let context = TemplateContext {
+            service: Service::new("paths".to_string()),
+            routes: routes.to_vec(),
+            custom_data,
+        };
+        
+        let content = self.render_template("paths", &context)?;
+        let content = self.with_lint_allows(content);
         
-        Ok(GeneratedCode::new(content)
-            .with_import("async_trait::async_trait".to_string())
-            .with_dependency("async-trait".to_string()))
+        Ok(Some(GeneratedCode {
+            content,
+            imports: Vec::new(),
+            dependencies: Vec::new(),
+        }))
     }
-}
-
-impl PoemOpenApiGenerator {
-    /// Collect all message types used in a service
-    fn collect_message_types(&self, service: &Service, routes: &[HttpRoute]) -> Vec<String> {
-        let mut message_types = std::collections::HashSet::new();
+    
+    fn generate_cors_config(&self, routes: &[HttpRoute]) -> Result<Option<GeneratedCode>, Self::Error> {
+        if !self.config.generate_cors_config || routes.is_empty() {
+            return Ok(None);
+        }
         
-        // Collect types from routes (more accurate than service methods)
+        // Group the routes by path pattern, in order of first appearance
+        let mut paths: Vec<(String, Vec<String>, Vec<&str>)> = Vec::new();
         for route in routes {
-            // Add input type (for request body)
-            if let Some(request_body) = &route.request_body {
-                if request_body.is_entire_message {
-                    if !route.input_type.is_scalar() && !route.input_type.is_well_known_type() {
-                        let type_name = &route.input_type.name;
-                        if !type_name.starts_with("google.protobuf.") {
-                            message_types.insert(type_name.clone());
-                        }
-                    }
+            let path = self.rewrite_path(&route.path_template)?;
+            let index = match paths.iter().position(|(existing, _, _)| *existing == path) {
+                Some(index) => index,
+                None => {
+                    paths.push((path, Vec::new(), Vec::new()));
+                    paths.len() - 1
                 }
-            }
+            };
+            let (_, methods, headers) = &mut paths[index];
             
-            // Add response type
-            if !route.response_type.is_scalar() && !route.response_type.is_well_known_type() {
-                let type_name = &route.response_type.name;
-                if !type_name.starts_with("google.protobuf.") {
-                    message_types.insert(type_name.clone());
-                }
+            let method = route.http_method.as_str().to_uppercase();
+            if !methods.contains(&method) {
+                methods.push(method);
             }
             
-            // Add parameter types (for custom types used in path/query parameters)
-            for param in &route.path_parameters {
-                if let ParameterType::Custom(type_name) = &param.param_type {
-                    message_types.insert(type_name.clone());
-                }
+            let mut route_headers = Vec::new();
+            if route.request_body.is_some() {
+                route_headers.push("content-type");
             }
-            
-            for param in &route.query_parameters {
-                if let ParameterType::Custom(type_name) = &param.param_type {
-                    message_types.insert(type_name.clone());
+            match route.security {
+                Some(SecurityScheme::Bearer) | Some(SecurityScheme::Basic) => route_headers.push("authorization"),
+                Some(SecurityScheme::ApiKey) => route_headers.push(API_KEY_HEADER),
+                None => {}
+            }
+            for header in route_headers {
+                if !headers.contains(&header) {
+                    headers.push(header);
                 }
             }
         }
         
-        // Convert to sorted vector
-        let mut result: Vec<String> = message_types.into_iter().collect();
-        result.sort();
-        result
+        let cors_routes = paths.into_iter()
+            .map(|(path, methods, headers)| {
+                let mut entry = HashMap::new();
+                entry.insert("path".to_string(), TemplateValue::String(format!("{:?}", path)));
+                entry.insert("methods".to_string(), TemplateValue::Array(
+                    methods.iter().map(|method| TemplateValue::String(format!("{:?}", method))).collect()
+                ));
+                entry.insert("headers".to_string(), TemplateValue::Array(
+                    headers.iter().map(|header| TemplateValue::String(format!("{:?}", header.to_lowercase()))).collect()
+                ));
+                TemplateValue::Object(entry)
+            })
+            .collect();
+        
+        let mut custom_data = HashMap::new();
+        custom_data.insert("cors_routes".to_string(), TemplateValue::Array(cors_routes));
+        
+        let context = TemplateContext {
+            service: Service::new("cors".to_string()),
+            routes: routes.to_vec(),
+            custom_data,
+        };
+        
+        let content = self.render_template("cors", &context)?;
+        let content = self.with_lint_allows(content);
+        
+        Ok(Some(GeneratedCode {
+            content,
+            imports: Vec::new(),
+            dependencies: Vec::new(),
+        }))
+    }
+    
+    fn generate_route_info(&self, routes: &[HttpRoute]) -> Result<Option<GeneratedCode>, Self::Error> {
+        if !self.config.generate_route_inventory || routes.is_empty() {
+            return Ok(None);
+        }
+        
+        let context = TemplateContext {
+            service: Service::new("route_info".to_string()),
+            routes: Vec::new(),
+            custom_data: HashMap::new(),
+        };
+        
+        let content = self.render_template("route_info", &context)?;
+        let content = self.with_lint_allows(content);
+        
+        Ok(Some(GeneratedCode {
+            content,
+            imports: Vec::new(),
+            dependencies: Vec::new(),
+        }))
+    }
+    
+    fn generate_prelude(
+        &self,
+        proto_file: &ProtoFile,
+        generated_files: &HashMap<String, GeneratedCode>,
+    ) -> Result<Option<GeneratedCode>, Self::Error> {
+        if !self.config.generate_prelude {
+            return Ok(None);
+        }
+        
+        let mut file_names: Vec<&String> = generated_files.keys().collect();
+        file_names.sort();
+        let modules = file_names.into_iter()
+            .filter_map(|file_name| {
+                let items = public_items(&generated_files[file_name].content);
+                if items.is_empty() {
+                    return None;
+                }
+                
+                let mut module = HashMap::new();
+                module.insert("name".to_string(), TemplateValue::String(file_name.trim_end_matches(".rs").to_string()));
+                module.insert("items".to_string(), TemplateValue::Array(items.into_iter().map(TemplateValue::String).collect()));
+                Some(TemplateValue::Object(module))
+            })
+            .collect();
+        let models = proto_file.messages.iter().map(|message| &message.name)
+            .chain(proto_file.enums.iter().map(|enum_def| &enum_def.name))
+            .map(|name| TemplateValue::String(name.clone()))
+            .collect::<Vec<_>>();
+        
+        let mut custom_data = HashMap::new();
+        custom_data.insert("modules".to_string(), TemplateValue::Array(modules));
+        if !models.is_empty() {
+            custom_data.insert("models".to_string(), TemplateValue::Array(models));
+        }
+        custom_data.insert("models_module_path".to_string(), TemplateValue::String(self.config.models_module_path.clone()));
+        
+        let context = TemplateContext {
+            service: Service::new("prelude".to_string()),
+            routes: Vec::new(),
+            custom_data,
+        };
+        
+        let content = self.render_template("prelude", &context)?;
+        let content = self.with_lint_allows(content);
+        
+        Ok(Some(GeneratedCode {
+            content,
+            imports: Vec::new(),
+            dependencies: Vec::new(),
+        }))
     }
-}
\ No newline at end of file
+    
+    fn generate_mock(&self, service: &Service, routes: &[HttpRoute]) -> Result<Option<GeneratedCode>, Self::Error> {
+        if !self.config.generate_mocks {
+            return Ok(None);
+        }
+        
+        let service_routes: Vec<&HttpRoute> = routes.iter()
+            .filter(|route| route.service_name == service.name)
+            .collect();
+        
+        let context = TemplateContext {
+            service: service.clone(),
+            custom_data: self.service_trait_data(service, &service_routes),
+            routes: service_routes.into_iter().cloned().collect(),
+        };
+        
+        let content = self.render_template("mock", &context)?;
+        let content = self.with_lint_allows(content);
+        
+        let mut imports = Vec::new();
+        let mut dependencies = Vec::new();
+        if !self.config.use_native_async_trait {
+            imports.push("async_trait::async_trait".to_string());
+            dependencies.push("async-trait".to_string());
+        }
+        if context.routes.iter().any(|route| route.response_type.is_stream) {
+            dependencies.push("futures".to_string());
+        }
+        
+        Ok(Some(GeneratedCode {
+            content,
+            imports,
+            dependencies,
+        }))
+    }
+    
+    fn generate_tests(&self, service: &Service, routes: &[HttpRoute], proto_file: &ProtoFile) -> Result<Option<GeneratedCode>, Self::Error> {
+        if !self.config.generate_tests {
+            return Ok(None);
+        }
+        
+        let service_routes: Vec<&HttpRoute> = routes.iter()
+            .filter(|route| route.service_name == service.name)
+            .collect();
+        
+        // Routes of the same method (additional bindings) get numbered test names
+        let mut seen_methods: HashMap<&str, usize> = HashMap::new();
+        let mut test_suffixes = Vec::new();
+        let mut canned_responses = Vec::new();
+        let mut sample_bodies = Vec::new();
+        let mut ignore_reasons = Vec::new();
+        let mut sample_routes = Vec::new();
+        for route in &service_routes {
+            let count = seen_methods.entry(route.method_name.as_str()).or_insert(0);
+            *count += 1;
+            test_suffixes.push(TemplateValue::String(if *count > 1 { format!("_{}", count) } else { String::new() }));
+            
+            // Tests whose canned response or request body cannot be built are left to fill in
+            let canned_response = self.canned_response(route, proto_file);
+            let sample_body = self.sample_body(route, proto_file);
+            let ignore_reason = if canned_response.is_none() {
+                "fill in a canned response"
+            } else if route.request_body.is_some() && sample_body.is_none() {
+                "fill in a request body"
+            } else {
+                ""
+            };
+            canned_responses.push(TemplateValue::String(canned_response.unwrap_or_default()));
+            sample_bodies.push(TemplateValue::String(sample_body.map(|body| body.to_string()).unwrap_or_default()));
+            ignore_reasons.push(TemplateValue::String(ignore_reason.to_string()));
+            
+            let mut sample_route = (*route).clone();
+            sample_route.path_template = self.sample_request_uri(route)?;
+            sample_routes.push(sample_route);
+        }
+        
+        let mut custom_data = self.service_trait_data(service, &service_routes);
+        custom_data.insert("test_suffixes".to_string(), TemplateValue::Array(test_suffixes));
+        custom_data.insert("canned_responses".to_string(), TemplateValue::Array(canned_responses));
+        custom_data.insert("sample_bodies".to_string(), TemplateValue::Array(sample_bodies));
+        custom_data.insert("ignore_reasons".to_string(), TemplateValue::Array(ignore_reasons));
+        custom_data.insert("response_statuses".to_string(), TemplateValue::Object(self.response_statuses(service, &service_routes)));
+        
+        let context = TemplateContext {
+            service: service.clone(),
+            routes: sample_routes,
+            custom_data,
+        };
+        
+        let content = self.render_template("tests", &context)?;
+        let content = self.with_lint_allows(content);
+        
+        let mut dependencies = vec![
+            "poem".to_string(),
+            "poem-openapi".to_string(),
+            "tokio".to_string(),
+        ];
+        if context.routes.iter().any(|route| route.request_body.is_some()) {
+            dependencies.push("serde_json".to_string());
+        }
+        if context.routes.iter().any(|route| route.response_type.is_stream) {
+            dependencies.push("futures".to_string());
+        }
+        
+        Ok(Some(GeneratedCode {
+            content,
+            imports: vec!["poem::test::TestClient".to_string()],
+            dependencies,
+        }))
+    }
+}
+
+impl PoemOpenApiGenerator {
+    /// Prefix generated file content with the configured `#![allow(...)]` attribute
+    fn with_lint_allows(&self, content: String) -> String {
+        let mut lint_allows = self.config.lint_allows.clone();
+        if self.config.method_naming != MethodNaming::SnakeCase {
+            lint_allows.push("non_snake_case".to_string());
+        }
+        
+        if lint_allows.is_empty() {
+            return content;
+        }
+        
+        format!("#![allow({})]\n{}", lint_allows.join(", "), content)
+    }
+    
+    /// Render a controller serving `service_routes`, or one part of a split controller
+    fn render_controller(&self, service: &Service, service_routes: &[&HttpRoute], part: Option<usize>) -> Result<GeneratedCode, CodeGenerationError> {
+        // Collect all message types used in this service
+        let service_routes_slice: Vec<HttpRoute> = service_routes.iter().map(|&r| r.clone()).collect();
+        let message_types = self.message_types(service, &service_routes_slice);
+        
+        // Check if we need Path or Query imports
+        let has_path_params = service_routes.iter().any(|route| !route.path_parameters.is_empty());
+        let has_query_params = service_routes.iter().any(|route| !route.query_parameters.is_empty());
+        let response_payloads = self.response_payloads(service, service_routes);
+        let poem_imports = controller_poem_imports(service_routes, &response_payloads);
+        let negotiated = response_payloads.values()
+            .any(|payload| matches!(payload, TemplateValue::String(payload) if payload == "Negotiated"));
+        
+        // Create template context
+        let mut custom_data = std::collections::HashMap::new();
+        if !message_types.is_empty() {
+            // Create a single string with the import list including braces
+            let import_list = format!("{{{}}}", message_types.join(", "));
+            custom_data.insert("message_types".to_string(), TemplateValue::String(import_list));
+        }
+        custom_data.insert("models_module_path".to_string(), TemplateValue::String(self.config.models_module_path.clone()));
+        custom_data.insert("tags_enum".to_string(), TemplateValue::Boolean(self.config.generate_tags_enum));
+        custom_data.insert("error_module".to_string(), TemplateValue::Boolean(self.config.generate_error_module));
+        custom_data.insert("negotiated".to_string(), TemplateValue::Boolean(negotiated));
+        custom_data.insert("pagination_helpers".to_string(), TemplateValue::Boolean(self.config.generate_pagination_helpers));
+        custom_data.insert("route_cfgs".to_string(), TemplateValue::Object(self.route_cfg_predicates(service)));
+        custom_data.insert("parsed_parameters".to_string(), TemplateValue::Object(self.parsed_parameters(service_routes)));
+        custom_data.insert("handler_attributes".to_string(), TemplateValue::Object(self.handler_attributes(service)?));
+        if !poem_imports.is_empty() {
+            custom_data.insert("poem_imports".to_string(), TemplateValue::String(poem_import_list(&poem_imports)));
+        }
+        custom_data.insert("response_payloads".to_string(), TemplateValue::Object(response_payloads));
+        custom_data.insert("response_statuses".to_string(), TemplateValue::Object(self.response_statuses(service, service_routes)));
+        custom_data.insert("request_content_types".to_string(), TemplateValue::Object(request_content_types(service_routes)));
+        let (security_types, security_schemes) = self.security_schemes(service, service_routes);
+        custom_data.insert("security_types".to_string(), TemplateValue::Object(security_types));
+        custom_data.insert("security_schemes".to_string(), TemplateValue::Array(security_schemes));
+        if let Some(wrapper) = &self.config.response_wrapper {
+            custom_data.insert("response_wrapper".to_string(), TemplateValue::String(wrapper.clone()));
+        }
+        if let Some(error_type) = &self.config.service_error_type {
+            custom_data.insert("service_error_type".to_string(), TemplateValue::String(error_type.clone()));
+        }
+        if self.config.generate_shared_state {
+            custom_data.insert("shared_state_type".to_string(), TemplateValue::String(self.config.shared_state_type.clone()));
+        }
+        custom_data.insert("method_naming".to_string(), TemplateValue::String(self.config.method_naming.as_str().to_string()));
+        self.insert_casing_overrides(&mut custom_data);
+        self.insert_external_types(&mut custom_data);
+        self.insert_naming(&mut custom_data);
+        if let Some(number) = part {
+            // Each part is a controller of its own, named after the controller it belongs to
+            if let Some(TemplateValue::Object(naming)) = custom_data.get_mut("naming") {
+                naming.insert("controller_suffix".to_string(), TemplateValue::String(format!("{}Part{}", self.config.naming.controller_suffix, number)));
+            }
+            custom_data.insert("controller_part".to_string(), TemplateValue::Boolean(true));
+        }
+        custom_data.insert("route_inventory".to_string(), TemplateValue::Boolean(self.config.generate_route_inventory));
+        custom_data.insert("route_infos".to_string(), TemplateValue::Array(
+            service_routes.iter()
+                .map(|route| {
+                    let mut info = HashMap::new();
+                    info.insert("method".to_string(), TemplateValue::String(route.http_method.as_str().to_uppercase()));
+                    info.insert("operation_id".to_string(), TemplateValue::String(route.operation_id()));
+                    TemplateValue::Object(info)
+                })
+                .collect()
+        ));
+        custom_data.insert("has_path_params".to_string(), TemplateValue::Boolean(has_path_params));
+        custom_data.insert("has_query_params".to_string(), TemplateValue::Boolean(has_query_params));
+        
+        // Add input_type for each route by matching with service methods
+        let mut enriched_routes = Vec::new();
+        for route in service_routes {
+            let mut route_clone = (*route).clone();
+            route_clone.path_template = self.rewrite_path(&route.path_template)?;
+            
+            // Find the corresponding service method to get input type
+            if let Some(method) = service.methods.iter().find(|m| m.name == route.method_name) {
+                // Add input_type to the route context (we'll need to modify the route structure or use custom_data)
+                // For now, we'll add it to custom_data with a route-specific key
+                let input_type_key = format!("{}_input_type", route.method_name);
+                custom_data.insert(input_type_key, TemplateValue::String(method.input_type.name.clone()));
+            }
+            
+            enriched_routes.push(route_clone);
+        }
+        
+        let context = TemplateContext {
+            service: service.clone(),
+            routes: enriched_routes,
+            custom_data,
+        };
+        
+        // Render the controller template
+        let content = self.render_template("controller", &context)?;
+        let content = self.with_lint_allows(content);
+        
+        // Generate required imports
+        let mut imports = vec![
+            "std::sync::Arc".to_string(),
+        ];
+        
+        if !poem_imports.is_empty() {
+            imports.push(format!("poem_openapi::{}", poem_import_list(&poem_imports)));
+        }
+        
+        // Add imports for custom types used in the service
+        for route in &context.routes {
+            // Add import for response type if it's not a scalar
+            if let Some(path) = self.external_type_path(&route.response_type) {
+                imports.push(path.clone());
+            } else if !route.response_type.is_scalar()
+                && !route.response_type.is_well_known_type()
+                && !route.response_type.fully_qualified_name().starts_with("google.protobuf.")
+            {
+                imports.push(format!("crate::{}", route.response_type.name));
+            }
+            
+            // Add imports for path and query parameter types
+            for param in &route.path_parameters {
+                if let ParameterType::Custom(type_name) | ParameterType::Enum(type_name) = &param.param_type {
+                    imports.push(format!("crate::{}", type_name));
+                }
+            }
+            
+            for param in &route.query_parameters {
+                if let ParameterType::Custom(type_name) | ParameterType::Enum(type_name) = &param.param_type {
+                    imports.push(format!("crate::{}", type_name));
+                }
+            }
+            
+            // Add import for request body type if present
+            if let Some(request_body) = &route.request_body {
+                if request_body.is_entire_message {
+                    // Find the input type from the service method
+                    if let Some(method) = service.methods.iter().find(|m| m.name == route.method_name) {
+                        if let Some(path) = self.external_type_path(&method.input_type) {
+                            imports.push(path.clone());
+                        } else if !method.input_type.is_scalar()
+                            && !method.input_type.is_well_known_type()
+                            && !method.input_type.fully_qualified_name().starts_with("google.protobuf.")
+                        {
+                            imports.push(format!("crate::{}", method.input_type.name));
+                        }
+                    }
+                } else if let Some(field_type) = &request_body.field_type {
+                    // Import the resolved body field type if it's a message or enum
+                    if let Some(path) = self.external_type_path(field_type) {
+                        imports.push(path.clone());
+                    } else if !field_type.is_scalar() && !field_type.is_well_known_type() {
+                        imports.push(format!("crate::{}", field_type.name));
+                    }
+                }
+            }
+        }
+        
+        // Remove duplicates and sort
+        imports.sort();
+        imports.dedup();
+        
+        // Generate required dependencies
+        let mut dependencies = vec![
+            "poem-openapi".to_string(),
+            "poem".to_string(),
+        ];
+        
+        if context.routes.iter().any(|route| route.response_type.is_stream) {
+            dependencies.push("futures".to_string());
+            // Event streams log the error that ends them
+            dependencies.push("tracing".to_string());
+        }
+        
+        if self.has_timestamp_parameters(&context.routes) {
+            dependencies.push("chrono".to_string());
+        }
+        
+        Ok(GeneratedCode {
+            content,
+            imports,
+            dependencies,
+        })
+    }
+    
+    /// Split the routes of a service into per-file chunks when they exceed the
+    /// configured maximum number of operations per file
+    fn controller_chunks<'a>(&self, service_routes: &'a [&'a HttpRoute]) -> Option<Vec<&'a [&'a HttpRoute]>> {
+        let max = self.config.max_operations_per_file?;
+        (service_routes.len() > max).then(|| service_routes.chunks(max).collect())
+    }
+    
+    /// Render the controller of a service whose operations are split into `part_count` parts
+    ///
+    /// The controller implements `OpenApi` by delegating to its parts, nested in pairs
+    /// so any number of parts fits poem-openapi's tuple implementations.
+    fn render_split_controller(&self, service: &Service, part_count: usize) -> Result<GeneratedCode, CodeGenerationError> {
+        let part_type = |number: usize| format!(
+            "{}{}Part{}",
+            crate::utils::to_pascal_case(&service.name), self.config.naming.controller_suffix, number
+        );
+        let mut parts_type = format!("{}<T>", part_type(part_count));
+        let mut parts_value = format!("{} {{ service: self.service.clone() }}", part_type(part_count));
+        for number in (1..part_count).rev() {
+            parts_type = format!("({}<T>, {})", part_type(number), parts_type);
+            parts_value = format!("({} {{ service: self.service.clone() }}, {})", part_type(number), parts_value);
+        }
+        
+        let mut custom_data = HashMap::new();
+        custom_data.insert("parts".to_string(), TemplateValue::Array(
+            (1..=part_count).map(|number| TemplateValue::String(number.to_string())).collect()
+        ));
+        custom_data.insert("parts_type".to_string(), TemplateValue::String(parts_type));
+        custom_data.insert("parts_value".to_string(), TemplateValue::String(parts_value));
+        custom_data.insert("route_inventory".to_string(), TemplateValue::Boolean(self.config.generate_route_inventory));
+        self.insert_casing_overrides(&mut custom_data);
+        self.insert_naming(&mut custom_data);
+        
+        let context = TemplateContext {
+            service: service.clone(),
+            routes: Vec::new(),
+            custom_data,
+        };
+        
+        let content = self.render_template("split_controller", &context)?;
+        let content = self.with_lint_allows(content);
+        
+        Ok(GeneratedCode {
+            content,
+            imports: vec!["std::sync::Arc".to_string()],
+            dependencies: vec!["poem-openapi".to_string(), "poem".to_string()],
+        })
+    }
+    
+    /// Collect the names of the message and enum types the routes of a service use
+    ///
+    /// Covers request bodies, responses, and path and query parameters of message or
+    /// enum type, i.e. the types generated service code imports from the models
+    /// module. Scalars, well-known types and externally mapped types are left out.
+    /// The names are sorted and distinct.
+    pub fn message_types(&self, service: &Service, routes: &[HttpRoute]) -> Vec<String> {
+        let is_model = |type_ref: &TypeReference| self.external_type_path(type_ref).is_none()
+            && !type_ref.is_scalar()
+            && !type_ref.is_well_known_type()
+            && !type_ref.fully_qualified_name().starts_with("google.protobuf.");
+        
+        let mut message_types = std::collections::BTreeSet::new();
+        for route in routes.iter().filter(|route| route.service_name == service.name) {
+            if let Some(request_body) = &route.request_body {
+                if request_body.is_entire_message && is_model(&route.input_type) {
+                    message_types.insert(route.input_type.name.clone());
+                }
+                if let Some(field_type) = request_body.field_type.as_ref().filter(|field_type| is_model(field_type)) {
+                    message_types.insert(field_type.name.clone());
+                }
+            }
+            
+            if is_model(&route.response_type) {
+                message_types.insert(route.response_type.name.clone());
+            }
+            
+            for param_type in route.path_parameters.iter().map(|param| &param.param_type)
+                .chain(route.query_parameters.iter().map(|param| &param.param_type))
+            {
+                if let ParameterType::Custom(type_name) | ParameterType::Enum(type_name) = param_type {
+                    message_types.insert(type_name.clone());
+                }
+            }
+        }
+        
+        message_types.into_iter().collect()
+    }
+    
+    /// Generate a model struct for a message
+    pub fn generate_model(&self, message: &Message) -> Result<GeneratedCode, CodeGenerationError> {
+        let fields = self.model_fields(message, &message.fields)?;
+        
+        let mut custom_data = HashMap::new();
+        custom_data.insert("name".to_string(), TemplateValue::String(message.name.clone()));
+        self.insert_casing_overrides(&mut custom_data);
+        self.insert_external_types(&mut custom_data);
+        custom_data.insert("fields".to_string(), fields);
+        custom_data.insert("non_exhaustive".to_string(), TemplateValue::Boolean(self.config.non_exhaustive));
+        let field_visibility = match self.config.field_visibility {
+            FieldVisibility::Public => "pub ",
+            FieldVisibility::Crate => "pub(crate) ",
+            FieldVisibility::PrivateWithAccessors => "",
+        };
+        custom_data.insert("field_visibility".to_string(), TemplateValue::String(field_visibility.to_string()));
+        if self.config.field_visibility == FieldVisibility::PrivateWithAccessors {
+            custom_data.insert("accessors".to_string(), TemplateValue::Boolean(true));
+        }
+        if self.config.generate_builders {
+            custom_data.insert("builders".to_string(), TemplateValue::Boolean(true));
+            custom_data.insert("builder_fields".to_string(), self.builder_fields(message)?);
+        }
+        // Models deriving `prost::Message` get their `Default` implementation from it
+        if self.config.content_negotiation {
+            match protobuf_blocker(message) {
+                Some(field) => {
+                    custom_data.insert("protobuf_blocker".to_string(), TemplateValue::String(field.name.clone()));
+                }
+                None => {
+                    custom_data.insert("prost_message".to_string(), TemplateValue::Boolean(true));
+                }
+            }
+        }
+        if self.config.generate_defaults && !self.derives_protobuf(message) {
+            self.insert_model_default(message, &mut custom_data);
+        }
+        
+        // Malformed examples are reported by the validator and skipped here
+        if let Some(example) = message.example_directive()
+            .and_then(|example| serde_json::from_str::<serde_json::Value>(example).ok())
+        {
+            // Embedded as an escaped Rust string literal
+            custom_data.insert("example".to_string(), TemplateValue::String(format!("{:?}", example.to_string())));
+        }
+        if self.config.constraint_validation == ConstraintValidation::Method {
+            let checked = message.fields.iter().any(|field| !constraint_checks(field, &field.constraints()).is_empty());
+            custom_data.insert("validate_method".to_string(), TemplateValue::Boolean(checked));
+        }
+        if let Some(variants) = &self.config.model_variants {
+            if message.is_resource() {
+                custom_data.insert("variants".to_string(), self.model_variants(message, variants)?);
+            }
+        }
+        
+        let context = TemplateContext {
+            service: Service::new(message.name.clone()),
+            routes: Vec::new(),
+            custom_data,
+        };
+        
+        let content = self.render_template("type_def", &context)?;
+        
+        Ok(GeneratedCode {
+            content,
+            imports: Vec::new(),
+            dependencies: vec!["serde".to_string()],
+        })
+    }
+    
+    /// Build the template data shared by the service trait and its mock
+    fn service_trait_data(&self, service: &Service, service_routes: &[&HttpRoute]) -> HashMap<String, TemplateValue> {
+        // Collect all message types used in this service
+        let service_routes_slice: Vec<HttpRoute> = service_routes.iter().map(|&r| r.clone()).collect();
+        let message_types = self.message_types(service, &service_routes_slice);
+        
+        let mut custom_data = HashMap::new();
+        if !message_types.is_empty() {
+            // Create a single string with the import list including braces
+            let import_list = format!("{{{}}}", message_types.join(", "));
+            custom_data.insert("message_types".to_string(), TemplateValue::String(import_list));
+        }
+        custom_data.insert("models_module_path".to_string(), TemplateValue::String(self.config.models_module_path.clone()));
+        custom_data.insert("response_payloads".to_string(), TemplateValue::Object(self.response_payloads(service, service_routes)));
+        if let Some(error_type) = &self.config.service_error_type {
+            custom_data.insert("service_error_type".to_string(), TemplateValue::String(error_type.clone()));
+        }
+        if self.config.generate_shared_state {
+            custom_data.insert("shared_state_type".to_string(), TemplateValue::String(self.config.shared_state_type.clone()));
+        }
+        custom_data.insert("method_naming".to_string(), TemplateValue::String(self.config.method_naming.as_str().to_string()));
+        custom_data.insert("native_async_trait".to_string(), TemplateValue::Boolean(self.config.use_native_async_trait));
+        self.insert_casing_overrides(&mut custom_data);
+        self.insert_external_types(&mut custom_data);
+        self.insert_naming(&mut custom_data);
+        
+        custom_data
+    }
+    
+    /// Expose the configured controller and service trait naming to the templates
+    fn insert_naming(&self, custom_data: &mut HashMap<String, TemplateValue>) {
+        let naming = &self.config.naming;
+        let suffixes = [
+            ("controller_suffix", &naming.controller_suffix),
+            ("trait_suffix", &naming.trait_suffix),
+            ("controller_module_suffix", &naming.controller_module_suffix),
+            ("trait_module_suffix", &naming.trait_module_suffix),
+        ];
+        let naming = suffixes.into_iter()
+            .map(|(key, suffix)| (key.to_string(), TemplateValue::String(suffix.clone())))
+            .collect();
+        custom_data.insert("naming".to_string(), TemplateValue::Object(naming));
+    }
+    
+    /// Expose the configured casing overrides to the snake_case template helpers
+    fn insert_casing_overrides(&self, custom_data: &mut HashMap<String, TemplateValue>) {
+        if self.config.casing_overrides.is_empty() {
+            return;
+        }
+        
+        let overrides = self.config.casing_overrides.iter()
+            .map(|(word, pinned)| (word.clone(), TemplateValue::String(pinned.clone())))
+            .collect();
+        custom_data.insert("casing_overrides".to_string(), TemplateValue::Object(overrides));
+    }
+    
+    /// Expose the configured external type mappings to the type mapping template helpers
+    fn insert_external_types(&self, custom_data: &mut HashMap<String, TemplateValue>) {
+        if self.config.external_type_mappings.is_empty() {
+            return;
+        }
+        
+        let mappings = self.config.external_type_mappings.iter()
+            .map(|(proto_type, rust_path)| (proto_type.trim_start_matches('.').to_string(), TemplateValue::String(rust_path.clone())))
+            .collect();
+        custom_data.insert("external_types".to_string(), TemplateValue::Object(mappings));
+    }
+    
+    /// Get the Rust path an external type mapping assigns to a type, if any
+    fn external_type_path(&self, type_ref: &TypeReference) -> Option<&String> {
+        let qualified_name = type_ref.fully_qualified_name();
+        self.config.external_type_mappings.iter()
+            .find(|(proto_type, _)| proto_type.trim_start_matches('.') == qualified_name)
+            .map(|(_, rust_path)| rust_path)
+    }
+    
+    /// Describe model fields, including their read-only and write-only access modes
+    /// and whether they are wrapped in `Option`
+    fn model_fields(&self, message: &Message, fields: &[Field]) -> Result<TemplateValue, CodeGenerationError> {
+        fields.iter()
+            .map(|field| {
+                let mut value = serde_json::to_value(field)?;
+                value["read_only"] = serde_json::Value::Bool(field.is_read_only());
+                value["write_only"] = serde_json::Value::Bool(field.is_write_only());
+                value["required"] = serde_json::Value::Bool(field.is_required());
+                value["optional"] = serde_json::Value::Bool(is_optional_field(message, field));
+                if let Some(attribute) = prost_attribute(message, field) {
+                    value["prost"] = serde_json::Value::String(attribute);
+                }
+                value["immutable"] = serde_json::Value::Bool(field.is_immutable());
+                let constraints = field.constraints();
+                match self.config.constraint_validation {
+                    ConstraintValidation::Attributes if !constraints.is_empty() => {
+                        let validators: Vec<String> = constraints.iter().map(validator_attribute).collect();
+                        value["validators"] = serde_json::Value::String(validators.join(", "));
+                    }
+                    ConstraintValidation::Method => {
+                        value["checks"] = serde_json::to_value(constraint_checks(field, &constraints))?;
+                    }
+                    _ => {}
+                }
+                Ok(value)
+            })
+            .collect::<Result<Vec<_>, serde_json::Error>>()
+            .and_then(|fields| serde_json::from_value::<TemplateValue>(serde_json::Value::Array(fields)))
+            .map_err(|e| CodeGenerationError::ContextError {
+                message: format!("Failed to serialize fields of {}: {}", message.name, e),
+            })
+    }
+    
+    /// Describe the create and update variants of a resource model
+    ///
+    /// Both variants leave out read-only and excluded fields; the update variant
+    /// also drops immutable fields and any field clashing with its field mask.
+    fn model_variants(&self, message: &Message, variants: &ModelVariantConfig) -> Result<TemplateValue, CodeGenerationError> {
+        let create_fields: Vec<Field> = message.fields.iter()
+            .filter(|field| !field.is_read_only() && !variants.excluded_fields.contains(&field.name))
+            .cloned()
+            .collect();
+        let update_fields: Vec<Field> = create_fields.iter()
+            .filter(|field| field.name != variants.update_mask_field && !field.is_immutable())
+            .cloned()
+            .collect();
+        
+        let mut variant = HashMap::new();
+        variant.insert("create_name".to_string(), TemplateValue::String(format!("{}{}", variants.create_prefix, message.name)));
+        variant.insert("update_name".to_string(), TemplateValue::String(format!("{}{}", variants.update_prefix, message.name)));
+        variant.insert("update_mask".to_string(), TemplateValue::String(variants.update_mask_field.clone()));
+        variant.insert("create_fields".to_string(), self.model_fields(message, &create_fields)?);
+        variant.insert("update_fields".to_string(), self.model_fields(message, &update_fields)?);
+        
+        Ok(TemplateValue::Object(variant))
+    }
+    
+    /// Check whether the model of a message derives `prost::Message`, which content
+    /// negotiation encodes protobuf responses with
+    fn derives_protobuf(&self, message: &Message) -> bool {
+        self.config.content_negotiation && protobuf_blocker(message).is_none()
+    }
+    
+    /// Describe how the model implements `Default`
+    ///
+    /// Without a default to fall back on, the first field blocking `Default` is
+    /// named in a note instead. Without proto2 `[default = ...]` values `Default`
+    /// is derived; otherwise it is implemented by hand.
+    fn insert_model_default(&self, message: &Message, custom_data: &mut HashMap<String, TemplateValue>) {
+        if let Some(field) = default_blocker(message) {
+            custom_data.insert("default_blocker".to_string(), TemplateValue::String(field.name.clone()));
+            return;
+        }
+        
+        let defaults: Vec<Option<String>> = message.fields.iter().map(proto_default_value).collect();
+        if defaults.iter().all(Option::is_none) {
+            custom_data.insert("derive_default".to_string(), TemplateValue::Boolean(true));
+            return;
+        }
+        
+        let default_fields = message.fields.iter().zip(defaults)
+            .map(|(field, value)| {
+                let mut entry = HashMap::new();
+                entry.insert("name".to_string(), TemplateValue::String(field.name.clone()));
+                let value = match value {
+                    Some(value) if is_optional_field(message, field) => format!("Some({})", value),
+                    Some(value) => value,
+                    None => "Default::default()".to_string(),
+                };
+                entry.insert("value".to_string(), TemplateValue::String(value));
+                TemplateValue::Object(entry)
+            })
+            .collect();
+        custom_data.insert("default_fields".to_string(), TemplateValue::Array(default_fields));
+    }
+    
+    /// Describe each field for the model builder
+    ///
+    /// Besides fields marked required, singular message and enum fields must be set
+    /// because generated models have no `Default` to fall back on, unless they are
+    /// wrapped in `Option`.
+    fn builder_fields(&self, message: &Message) -> Result<TemplateValue, CodeGenerationError> {
+        let fields = message.fields.iter()
+            .map(|field| {
+                let mut value = serde_json::to_value(field)?;
+                let optional = is_optional_field(message, field);
+                let required = field.is_required()
+                    || (field.label != FieldLabel::Repeated && !optional && matches!(field.field_type, FieldType::MessageOrEnum(_)));
+                value["required"] = serde_json::Value::Bool(required);
+                value["optional"] = serde_json::Value::Bool(optional);
+                Ok(value)
+            })
+            .collect::<Result<Vec<_>, serde_json::Error>>()
+            .and_then(|fields| serde_json::from_value::<TemplateValue>(serde_json::Value::Array(fields)))
+            .map_err(|e| CodeGenerationError::ContextError {
+                message: format!("Failed to serialize builder fields of {}: {}", message.name, e),
+            })?;
+        
+        Ok(fields)
+    }
+    
+    /// Build a request URI for a route, filling path and required query parameters with sample values
+    fn sample_request_uri(&self, route: &HttpRoute) -> Result<String, CodeGenerationError> {
+        let param_type = |name: &str| route.path_parameters.iter()
+            .find(|param| param.name == name.replace('.', "_"))
+            .map(|param| &param.param_type);
+        
+        let mut uri: String = PathSegment::parse(&self.rewrite_path(&route.path_template)?).iter()
+            .map(|segment| match segment {
+                PathSegment::Literal(text) => text.clone(),
+                PathSegment::Param { name, .. } => sample_parameter_value(param_type(name)).to_string(),
+            })
+            .collect();
+        
+        let query: Vec<String> = route.query_parameters.iter()
+            .filter(|param| param.required)
+            .map(|param| format!("{}={}", param.name, sample_parameter_value(Some(&param.param_type))))
+            .collect();
+        if !query.is_empty() {
+            uri.push('?');
+            uri.push_str(&query.join("&"));
+        }
+        
+        Ok(uri)
+    }
+    
+    /// Rust expression for a canned successful result of a route's mock, if one can be built
+    ///
+    /// Streams start out empty and `google.protobuf.Empty` is `()`. Response models
+    /// use their `Default` implementation, falling back to their `@example`.
+    fn canned_response(&self, route: &HttpRoute, proto_file: &ProtoFile) -> Option<String> {
+        let response_type = &route.response_type;
+        if response_type.is_stream {
+            return Some("Ok(futures::StreamExt::boxed(futures::stream::empty()))".to_string());
+        }
+        if response_type.fully_qualified_name() == "google.protobuf.Empty" {
+            return Some("Ok(())".to_string());
+        }
+        if self.external_type_path(response_type).is_some() {
+            return None;
+        }
+        
+        let message = proto_file.find_message(&response_type.name)?;
+        if self.derives_protobuf(message) || (self.config.generate_defaults && default_blocker(message).is_none()) {
+            return Some("Ok(Default::default())".to_string());
+        }
+        message.example_directive()
+            .filter(|example| serde_json::from_str::<serde_json::Value>(example).is_ok())
+            .map(|_| "Ok(poem_openapi::types::Example::example())".to_string())
+    }
+    
+    /// JSON request body of a route setting every field its model cannot do without,
+    /// if one can be built
+    ///
+    /// Optional and read-only fields are left out; the others get zero values.
+    fn sample_body(&self, route: &HttpRoute, proto_file: &ProtoFile) -> Option<serde_json::Value> {
+        let request_body = route.request_body.as_ref()?;
+        if request_body.is_repeated {
+            return Some(serde_json::Value::Array(Vec::new()));
+        }
+        
+        let input = proto_file.find_message(&route.input_type.name)?;
+        if request_body.is_entire_message {
+            return self.sample_message_json(input, proto_file, &mut Vec::new());
+        }
+        let field = input.fields.iter().find(|field| Some(&field.name) == request_body.field.as_ref())?;
+        self.sample_field_json(&field.field_type, proto_file, &mut Vec::new())
+    }
+    
+    /// Sample JSON object of a message; `visiting` guards against recursive messages
+    fn sample_message_json(&self, message: &Message, proto_file: &ProtoFile, visiting: &mut Vec<String>) -> Option<serde_json::Value> {
+        if visiting.contains(&message.name) {
+            return None;
+        }
+        visiting.push(message.name.clone());
+        
+        let mut object = serde_json::Map::new();
+        for field in &message.fields {
+            if is_optional_field(message, field) || field.is_read_only() {
+                continue;
+            }
+            let value = if field.label == FieldLabel::Repeated {
+                serde_json::Value::Array(Vec::new())
+            } else {
+                self.sample_field_json(&field.field_type, proto_file, visiting)?
+            };
+            object.insert(field_wire_name(&field.name), value);
+        }
+        
+        visiting.pop();
+        Some(serde_json::Value::Object(object))
+    }
+    
+    /// Sample JSON value of a singular field: a zero value, the first value of an
+    /// enum or a sample object of a message
+    fn sample_field_json(&self, field_type: &FieldType, proto_file: &ProtoFile, visiting: &mut Vec<String>) -> Option<serde_json::Value> {
+        let value = match field_type {
+            FieldType::String => serde_json::json!(""),
+            FieldType::Bool => serde_json::json!(false),
+            FieldType::Double | FieldType::Float => serde_json::json!(0.0),
+            FieldType::Bytes => serde_json::json!([]),
+            FieldType::Map { .. } => serde_json::json!({}),
+            FieldType::MessageOrEnum(type_ref) => {
+                if self.external_type_path(type_ref).is_some() {
+                    return None;
+                }
+                if type_ref.fully_qualified_name() == "google.protobuf.Timestamp" {
+                    return Some(serde_json::json!("1970-01-01T00:00:00Z"));
+                }
+                if let Some(message) = proto_file.find_message(&type_ref.name) {
+                    return self.sample_message_json(message, proto_file, visiting);
+                }
+                let first_value = proto_file.find_enum(&type_ref.name)?.values.first()?;
+                serde_json::json!(first_value.name)
+            }
+            _ => serde_json::json!(0),
+        };
+        Some(value)
+    }
+    
+    /// Apply the configured path rewrite rules and prefix to a route path
+    fn rewrite_path(&self, path: &str) -> Result<String, CodeGenerationError> {
+        let mut path = path.to_string();
+        
+        for (pattern, replacement) in &self.config.path_rewrites {
+            let regex = regex::Regex::new(pattern).map_err(|e| CodeGenerationError::ContextError {
+                message: format!("Invalid path rewrite pattern '{}': {}", pattern, e),
+            })?;
+            path = regex.replace_all(&path, replacement.as_str()).into_owned();
+        }
+        
+        Ok(self.prefix_path(&path))
+    }
+    
+    /// Prepend the configured path prefix to a route path, joining them with a single slash
+    fn prefix_path(&self, path: &str) -> String {
+        let prefix = match &self.config.path_prefix {
+            Some(prefix) => prefix.trim_matches('/'),
+            None => return path.to_string(),
+        };
+        let path = path.trim_start_matches('/');
+        
+        match (prefix.is_empty(), path.is_empty()) {
+            (true, _) => format!("/{}", path),
+            (false, true) => format!("/{}", prefix),
+            (false, false) => format!("/{}/{}", prefix, path),
+        }
+    }
+    
+    /// Collect `#[cfg(...)]` predicates for methods carrying the configured cfg option, keyed by method name
+    fn route_cfg_predicates(&self, service: &Service) -> HashMap<String, TemplateValue> {
+        let mut predicates = HashMap::new();
+        let option_name = match &self.config.route_cfg_option {
+            Some(option_name) => option_name.trim_start_matches('(').trim_end_matches(')'),
+            None => return predicates,
+        };
+        
+        for method in &service.methods {
+            let option = method.options.iter()
+                .find(|option| option.name.trim_start_matches('(').trim_end_matches(')') == option_name);
+            
+            let predicate = match option.map(|option| &option.value) {
+                Some(OptionValue::Boolean(true)) => "debug_assertions".to_string(),
+                Some(OptionValue::String(predicate)) => predicate.clone(),
+                _ => continue,
+            };
+            predicates.insert(method.name.clone(), TemplateValue::String(predicate));
+        }
+        
+        predicates
+    }
+    
+    /// Collect the parameters each handler takes as a string and parses itself, keyed by
+    /// method name and then parameter name
+    ///
+    /// With `validate_parameters`, every path and form-style query parameter of a type
+    /// other than `String` or a custom message is parsed in the handler.
+    fn parsed_parameters(&self, routes: &[&HttpRoute]) -> HashMap<String, TemplateValue> {
+        let mut parsed = HashMap::new();
+        if !self.config.validate_parameters {
+            return parsed;
+        }
+        
+        let is_parsed = |param_type: &ParameterType| !matches!(param_type, ParameterType::String | ParameterType::Custom(_));
+        for route in routes {
+            let names: HashMap<String, TemplateValue> = route.path_parameters.iter()
+                .filter(|param| is_parsed(&param.param_type))
+                .map(|param| &param.name)
+                .chain(route.query_parameters.iter()
+                    .filter(|param| param.style == QueryParameterStyle::Form && is_parsed(&param.param_type))
+                    .map(|param| &param.name))
+                .map(|name| (name.clone(), TemplateValue::Boolean(true)))
+                .collect();
+            if !names.is_empty() {
+                parsed.insert(route.method_name.clone(), TemplateValue::Object(names));
+            }
+        }
+        
+        parsed
+    }
+    
+    /// Collect the configured handler attributes applying to each method, keyed by method name
+    fn handler_attributes(&self, service: &Service) -> Result<HashMap<String, TemplateValue>, CodeGenerationError> {
+        let mut attributes: HashMap<String, Vec<TemplateValue>> = HashMap::new();
+        
+        for handler_attribute in &self.config.handler_attributes {
+            let pattern = handler_attribute.method_pattern.as_deref()
+                .map(regex::Regex::new)
+                .transpose()
+                .map_err(|e| CodeGenerationError::ContextError {
+                    message: format!("Invalid handler attribute method pattern: {}", e),
+                })?;
+            let attribute = handler_attribute.attribute.trim();
+            let attribute = if attribute.starts_with("#[") {
+                attribute.to_string()
+            } else {
+                format!("#[{}]", attribute)
+            };
+            
+            for method in &service.methods {
+                if pattern.as_ref().is_none_or(|pattern| pattern.is_match(&method.name)) {
+                    attributes.entry(method.name.clone())
+                        .or_default()
+                        .push(TemplateValue::String(attribute.clone()));
+                }
+            }
+        }
+        
+        Ok(attributes.into_iter()
+            .map(|(method_name, attributes)| (method_name, TemplateValue::Array(attributes)))
+            .collect())
+    }
+    
+    /// Resolve the response payload type of each route, keyed by method name
+    ///
+    /// A `(response_content_type)` method option (e.g. `"text/plain"`) overrides
+    /// the configured default content type. Unary `google.protobuf.Empty` responses
+    /// map to `Empty` and are sent without a body. With content negotiation enabled,
+    /// unary methods annotated with `(content_negotiation) = true` map to `Negotiated`.
+    fn response_payloads(&self, service: &Service, routes: &[&HttpRoute]) -> HashMap<String, TemplateValue> {
+        routes.iter()
+            .map(|route| {
+                if route.response_type.is_empty_message() && !route.response_type.is_stream {
+                    return (route.method_name.clone(), TemplateValue::String("Empty".to_string()));
+                }
+                
+                let method = service.methods.iter().find(|method| method.name == route.method_name);
+                let negotiated = self.config.content_negotiation
+                    && !route.response_type.is_stream
+                    && method.is_some_and(|method| method.options.iter().any(|option| {
+                        option.name.trim_start_matches('(').trim_end_matches(')') == "content_negotiation"
+                            && option.value.as_bool() == Some(true)
+                    }));
+                if negotiated {
+                    return (route.method_name.clone(), TemplateValue::String("Negotiated".to_string()));
+                }
+                
+                let content_type = method
+                    .and_then(|method| method.options.iter()
+                        .find(|option| option.name.trim_start_matches('(').trim_end_matches(')') == "response_content_type"))
+                    .and_then(|option| option.value.as_str())
+                    .and_then(ResponseContentType::from_hint)
+                    .unwrap_or(self.config.default_response_content_type);
+                (route.method_name.clone(), TemplateValue::String(content_type.payload_type().to_string()))
+            })
+            .collect()
+    }
+    
+    /// Collect the declared non-200 success status of each unary route, keyed by method name
+    ///
+    /// Each entry holds the status `code` and the `variant` naming it in the generated
+    /// response enum (e.g. `Created` for 201). Invalid statuses are reported by the
+    /// validator and ignored here.
+    fn response_statuses(&self, service: &Service, routes: &[&HttpRoute]) -> HashMap<String, TemplateValue> {
+        routes.iter()
+            .filter(|route| !route.response_type.is_stream)
+            .filter_map(|route| {
+                let method = service.methods.iter().find(|method| method.name == route.method_name)?;
+                let code = method.status_code()?.ok().filter(|&code| code != 200)?;
+                let variant = match code {
+                    201 => "Created".to_string(),
+                    202 => "Accepted".to_string(),
+                    203 => "NonAuthoritativeInformation".to_string(),
+                    204 => "NoContent".to_string(),
+                    205 => "ResetContent".to_string(),
+                    206 => "PartialContent".to_string(),
+                    code => format!("Status{}", code),
+                };
+                
+                let mut status = HashMap::new();
+                status.insert("code".to_string(), TemplateValue::String(code.to_string()));
+                status.insert("variant".to_string(), TemplateValue::String(variant));
+                Some((route.method_name.clone(), TemplateValue::Object(status)))
+            })
+            .collect()
+    }
+    
+    /// Describe the authentication schemes protecting the routes of a service
+    ///
+    /// Returns the scheme type guarding each method, keyed by method name, and the
+    /// distinct schemes in order of first use. Each scheme holds the generated type
+    /// `name`, its poem-openapi `ty` and `auth` extractor, and the `key_name` of API keys.
+    fn security_schemes(&self, service: &Service, routes: &[&HttpRoute]) -> (HashMap<String, TemplateValue>, Vec<TemplateValue>) {
+        let mut security_types = HashMap::new();
+        let mut schemes: Vec<SecurityScheme> = Vec::new();
+        for route in routes {
+            let Some(scheme) = route.security else {
+                continue;
+            };
+            security_types.insert(route.method_name.clone(), TemplateValue::String(security_scheme_type(&service.name, scheme)));
+            if !schemes.contains(&scheme) {
+                schemes.push(scheme);
+            }
+        }
+        
+        let schemes = schemes.into_iter()
+            .map(|scheme| {
+                let (auth, key_name) = match scheme {
+                    SecurityScheme::Bearer => ("Bearer", None),
+                    SecurityScheme::Basic => ("Basic", None),
+                    SecurityScheme::ApiKey => ("ApiKey", Some(API_KEY_HEADER)),
+                };
+                
+                let mut value = HashMap::new();
+                value.insert("name".to_string(), TemplateValue::String(security_scheme_type(&service.name, scheme)));
+                value.insert("ty".to_string(), TemplateValue::String(scheme.as_str().to_string()));
+                value.insert("auth".to_string(), TemplateValue::String(auth.to_string()));
+                if let Some(key_name) = key_name {
+                    value.insert("key_name".to_string(), TemplateValue::String(key_name.to_string()));
+                }
+                TemplateValue::Object(value)
+            })
+            .collect();
+        
+        (security_types, schemes)
+    }
+    
+    /// Render a service-level template, attributing failures to the service and route
+    ///
+    /// On failure each route is re-rendered on its own to find the one whose data
+    /// the template cannot handle.
+    fn render_template(&self, template_name: &str, context: &TemplateContext) -> Result<String, CodeGenerationError> {
+        self.template_engine.render(template_name, context).map_err(|e| {
+            let failing_route = context.routes.iter().find(|route| {
+                let route_context = TemplateContext {
+                    routes: vec![(*route).clone()],
+                    ..context.clone()
+                };
+                self.template_engine.render(template_name, &route_context).is_err()
+            });
+            
+            let context_json = match failing_route {
+                Some(route) => serde_json::to_string(route),
+                None => serde_json::to_string(&context.service),
+            }.unwrap_or_default();
+            
+            CodeGenerationError::RenderFailed {
+                template_name: template_name.to_string(),
+                service_name: context.service.name.clone(),
+                method_name: failing_route.map(|route| route.method_name.clone()),
+                context_snippet: truncate_snippet(&context_json, RENDER_ERROR_SNIPPET_LEN),
+                message: e.to_string(),
+            }
+        })
+    }
+    
+    /// Check whether any route takes a `Timestamp` parameter, parsed into `chrono::DateTime<Utc>`
+    fn has_timestamp_parameters(&self, routes: &[HttpRoute]) -> bool {
+        routes.iter().any(|route| {
+            route.path_parameters.iter().map(|param| &param.param_type)
+                .chain(route.query_parameters.iter().map(|param| &param.param_type))
+                .any(|param_type| *param_type == ParameterType::Timestamp)
+        })
+    }
+    
+    /// Reject streaming methods that cannot be served over HTTP
+    ///
+    /// Server-streaming methods are served as server-sent events, but HTTP requests
+    /// carry a single body, so client-streaming methods are unsupported. Event items
+    /// must implement poem-openapi's `Type` and `ToJSON` traits, which `()` does not.
+    fn check_streaming(&self, routes: &[&HttpRoute]) -> Result<(), CodeGenerationError> {
+        for route in routes {
+            if route.input_type.is_stream {
+                return Err(CodeGenerationError::UnsupportedClientStreaming {
+                    service_name: route.service_name.clone(),
+                    method_name: route.method_name.clone(),
+                });
+            }
+            
+            let response_type = &route.response_type;
+            if response_type.is_stream && response_type.fully_qualified_name() == "google.protobuf.Empty" {
+                return Err(CodeGenerationError::UnsupportedTypeMapping {
+                    from_type: format!("stream {}", response_type.fully_qualified_name()),
+                    to_type: "poem_openapi::payload::EventStream".to_string(),
+                });
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Reject protobuf request bodies the generated handlers cannot answer for
+    ///
+    /// A malformed protobuf body is client input, so decode failures are answered
+    /// with `ApiError::BadRequest`, which needs `generate_error_module`.
+    fn check_request_bodies(&self, routes: &[&HttpRoute]) -> Result<(), CodeGenerationError> {
+        if self.config.generate_error_module {
+            return Ok(());
+        }
+        
+        match routes.iter().find(|route| route.request_body.as_ref().is_some_and(|body| body.content_type == "application/x-protobuf")) {
+            Some(route) => Err(CodeGenerationError::ContextError {
+                message: format!(
+                    "{}.{} takes an application/x-protobuf request body, whose decode failures are answered with ApiError::BadRequest, so generate_error_module must be enabled",
+                    route.service_name, route.method_name
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Maximum number of characters of context JSON included in render errors
+const RENDER_ERROR_SNIPPET_LEN: usize = 200;
+
+/// Header carrying the key of [`SecurityScheme::ApiKey`]
+const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Collect the media type of each request body sent as something other than JSON, keyed by method name
+///
+/// Each entry holds the `content_type` and the `variant` of the generated request enum
+/// declaring it; protobuf bodies arrive as bytes and are decoded in the handler.
+fn request_content_types(routes: &[&HttpRoute]) -> HashMap<String, TemplateValue> {
+    routes.iter()
+        .filter_map(|route| {
+            let content_type = &route.request_body.as_ref()?.content_type;
+            if content_type == "application/json" {
+                return None;
+            }
+            
+            let protobuf = content_type == "application/x-protobuf";
+            let mut entry = HashMap::new();
+            entry.insert("content_type".to_string(), TemplateValue::String(content_type.clone()));
+            entry.insert("variant".to_string(), TemplateValue::String(if protobuf { "Protobuf" } else { "Body" }.to_string()));
+            entry.insert("protobuf".to_string(), TemplateValue::Boolean(protobuf));
+            Some((route.method_name.clone(), TemplateValue::Object(entry)))
+        })
+        .collect()
+}
+
+/// Find the first field keeping a model from implementing `Default`
+///
+/// Singular message and enum fields not wrapped in `Option` have no default to fall back on.
+fn default_blocker(message: &Message) -> Option<&Field> {
+    message.fields.iter()
+        .find(|field| field.label != FieldLabel::Repeated && !is_optional_field(message, field)
+            && matches!(field.field_type, FieldType::MessageOrEnum(_)))
+}
+
+/// Check whether a model wraps the fields that are not `REQUIRED` in `Option`
+///
+/// Once a message marks some fields `(google.api.field_behavior) = REQUIRED`, clients
+/// may omit the others, so their absence is kept instead of read as a zero value.
+fn has_optional_fields(message: &Message) -> bool {
+    message.fields.iter().any(|field| field.behaviors.contains(&FieldBehavior::Required))
+}
+
+/// Check whether a model field is generated as `Option<T>`
+///
+/// Repeated and map fields keep their collection type, which is empty when omitted.
+fn is_optional_field(message: &Message, field: &Field) -> bool {
+    has_optional_fields(message)
+        && !field.is_required()
+        && field.label != FieldLabel::Repeated
+        && !matches!(field.field_type, FieldType::Map { .. })
+}
+
+/// Find the first field keeping a model from deriving `prost::Message`
+///
+/// Enums, well-known and externally mapped types have no protobuf encoding in the
+/// generated model, so only scalars, maps of scalars and nested messages of the
+/// model that are encodable themselves are accepted.
+fn protobuf_blocker(message: &Message) -> Option<&Field> {
+    message.fields.iter().find(|field| prost_attribute(message, field).is_none())
+}
+
+/// Arguments of the `#[prost(...)]` attribute of a model field, if it has a protobuf encoding
+fn prost_attribute(message: &Message, field: &Field) -> Option<String> {
+    let nested_message = |type_ref: &TypeReference| {
+        let name = type_ref.name.rsplit('.').next().unwrap_or(&type_ref.name);
+        message.nested_messages.iter()
+            .find(|nested| nested.name == name)
+            .filter(|nested| protobuf_blocker(nested).is_none())
+    };
+    
+    let (kind, is_message) = match &field.field_type {
+        FieldType::Map { key, value } => {
+            let value = match value.as_ref() {
+                FieldType::MessageOrEnum(type_ref) => nested_message(type_ref).map(|_| "message")?,
+                value => prost_scalar(value)?,
+            };
+            return Some(format!("map = \"{}, {}\", tag = \"{}\"", prost_scalar(key)?, value, field.number));
+        }
+        FieldType::MessageOrEnum(type_ref) => nested_message(type_ref).map(|_| ("message".to_string(), true))?,
+        FieldType::Bytes => ("bytes = \"vec\"".to_string(), false),
+        scalar => (prost_scalar(scalar)?.to_string(), false),
+    };
+    
+    let label = if field.label == FieldLabel::Repeated {
+        ", repeated"
+    } else if is_optional_field(message, field) {
+        ", optional"
+    } else if is_message {
+        ", required"
+    } else {
+        ""
+    };
+    let default = field.options.iter()
+        .find(|option| option.name == "default")
+        .and_then(|option| match &option.value {
+            OptionValue::String(text) => Some(format!(", default = {:?}", text)),
+            OptionValue::Number(number) => Some(format!(", default = \"{}\"", number)),
+            OptionValue::Boolean(flag) => Some(format!(", default = \"{}\"", flag)),
+            _ => None,
+        })
+        .unwrap_or_default();
+    Some(format!("{}{}, tag = \"{}\"{}", kind, label, field.number, default))
+}
+
+/// Name of a scalar field type in `#[prost(...)]` attributes
+fn prost_scalar(field_type: &FieldType) -> Option<&'static str> {
+    let name = match field_type {
+        FieldType::Double => "double",
+        FieldType::Float => "float",
+        FieldType::Int32 => "int32",
+        FieldType::Int64 => "int64",
+        FieldType::Uint32 => "uint32",
+        FieldType::Uint64 => "uint64",
+        FieldType::Sint32 => "sint32",
+        FieldType::Sint64 => "sint64",
+        FieldType::Fixed32 => "fixed32",
+        FieldType::Fixed64 => "fixed64",
+        FieldType::Sfixed32 => "sfixed32",
+        FieldType::Sfixed64 => "sfixed64",
+        FieldType::Bool => "bool",
+        FieldType::String => "string",
+        FieldType::Bytes => "bytes",
+        FieldType::MessageOrEnum(_) | FieldType::Map { .. } => return None,
+    };
+    Some(name)
+}
+
+/// Name of the generated security scheme type of a service, e.g. `UserServiceBearerAuth`
+///
+/// The type name doubles as the scheme name in the OpenAPI document.
+fn security_scheme_type(service_name: &str, scheme: SecurityScheme) -> String {
+    format!("{}{}Auth", crate::utils::to_pascal_case(service_name), crate::utils::to_pascal_case(scheme.as_str()))
+}
+
+/// Rust expression for a proto2 `[default = ...]` value of a scalar field
+///
+/// Returns `None` when the field declares no default or its value does not fit
+/// the field type, leaving the type's own default in place.
+fn proto_default_value(field: &Field) -> Option<String> {
+    let value = &field.options.iter().find(|option| option.name == "default")?.value;
+    let float_type = match field.field_type {
+        FieldType::Float => "f32",
+        _ => "f64",
+    };
+    
+    match (&field.field_type, value) {
+        (FieldType::String, OptionValue::String(text)) => Some(format!("{:?}.to_string()", text)),
+        (FieldType::Bytes, OptionValue::String(text)) => Some(format!("{:?}.as_bytes().to_vec()", text)),
+        (FieldType::Bool, OptionValue::Boolean(flag)) => Some(flag.to_string()),
+        (FieldType::Double | FieldType::Float, OptionValue::Number(number)) => Some(format!("{:?}", number)),
+        (FieldType::Double | FieldType::Float, OptionValue::Identifier(name)) => match name.as_str() {
+            "inf" => Some(format!("{}::INFINITY", float_type)),
+            "nan" => Some(format!("{}::NAN", float_type)),
+            _ => None,
+        },
+        (FieldType::MessageOrEnum(_) | FieldType::Map { .. }, _) => None,
+        (_, OptionValue::Number(number)) if number.fract() == 0.0 => Some(format!("{}", number)),
+        _ => None,
+    }
+}
+
+/// Sample value of a path or query parameter used in generated tests
+fn sample_parameter_value(param_type: Option<&ParameterType>) -> &'static str {
+    match param_type {
+        Some(ParameterType::Integer) | Some(ParameterType::Float) => "1",
+        Some(ParameterType::Boolean) => "true",
+        Some(ParameterType::Timestamp) => "1970-01-01T00:00:00Z",
+        _ => "sample",
+    }
+}
+
+/// The `poem_openapi` items a controller refers to by their short names
+///
+/// Only items some route actually uses are listed, so the generated `use` does not
+/// trigger `unused_imports`. `Json` wraps request bodies and the results of unary
+/// JSON or negotiated responses; `Path` and `Query` wrap parameters.
+fn controller_poem_imports(routes: &[&HttpRoute], response_payloads: &HashMap<String, TemplateValue>) -> Vec<&'static str> {
+    let uses_json = routes.iter().any(|route| {
+        route.request_body.is_some()
+            || (!route.response_type.is_stream
+                && matches!(response_payloads.get(&route.method_name), Some(TemplateValue::String(payload)) if payload == "Json" || payload == "Negotiated"))
+    });
+    
+    let mut items = Vec::new();
+    if uses_json {
+        items.push("payload::Json");
+    }
+    if routes.iter().any(|route| !route.path_parameters.is_empty()) {
+        items.push("param::Path");
+    }
+    if routes.iter().any(|route| !route.query_parameters.is_empty()) {
+        items.push("param::Query");
+    }
+    items
+}
+
+/// Arrange the controllers of `services` into tuples of at most `max_arity` elements
+///
+/// Each element is either a `service` name or a `group` of elements forming a nested
+/// tuple. Controllers are grouped in order, chunk by chunk, until the outermost tuple
+/// fits; a lone controller left over by chunking is not wrapped.
+fn controller_groups(services: &[Service], max_arity: usize) -> Vec<TemplateValue> {
+    let mut items: Vec<TemplateValue> = services.iter()
+        .map(|service| {
+            let mut item = HashMap::new();
+            item.insert("service".to_string(), TemplateValue::String(service.name.clone()));
+            TemplateValue::Object(item)
+        })
+        .collect();
+    
+    let max_arity = max_arity.max(2);
+    while items.len() > max_arity {
+        items = items.chunks(max_arity)
+            .map(|chunk| match chunk {
+                [item] => item.clone(),
+                chunk => {
+                    let mut group = HashMap::new();
+                    group.insert("group".to_string(), TemplateValue::Array(chunk.to_vec()));
+                    TemplateValue::Object(group)
+                }
+            })
+            .collect();
+    }
+    items
+}
+
+/// Render `poem_openapi` items as the path following `poem_openapi::` in a `use`
+fn poem_import_list(items: &[&str]) -> String {
+    match items {
+        [item] => item.to_string(),
+        items => format!("{{{}}}", items.join(", ")),
+    }
+}
+
+/// Render a constraint as an argument of poem-openapi's `#[oai(validator(...))]`
+///
+/// Numeric bounds take poem-openapi's `maximum(value = "..")` form, whose `exclusive`
+/// flag marks bounds the value may not equal.
+fn validator_attribute(constraint: &FieldConstraint) -> String {
+    match constraint {
+        FieldConstraint::Maximum(value) => format!("maximum(value = \"{}\")", value),
+        FieldConstraint::ExclusiveMaximum(value) => format!("maximum(value = \"{}\", exclusive)", value),
+        FieldConstraint::Minimum(value) => format!("minimum(value = \"{}\")", value),
+        FieldConstraint::ExclusiveMinimum(value) => format!("minimum(value = \"{}\", exclusive)", value),
+        FieldConstraint::MaxLength(length) => format!("max_length = {}", length),
+        FieldConstraint::MinLength(length) => format!("min_length = {}", length),
+        FieldConstraint::Pattern(pattern) => format!("pattern = {:?}", pattern),
+        FieldConstraint::MaxItems(count) => format!("max_items = {}", count),
+        FieldConstraint::MinItems(count) => format!("min_items = {}", count),
+    }
+}
+
+/// Describe the checks a generated `validate` method runs for a field
+///
+/// Each check holds the accessor appended to the field (`.len()` for lengths), the
+/// comparison that fails validation, its bound as an `f64` literal and the error message
+/// as an escaped string literal. Constraints not applicable to the field type, and
+/// patterns, which would need a regex dependency, produce no check.
+fn constraint_checks(field: &Field, constraints: &[FieldConstraint]) -> Vec<HashMap<&'static str, String>> {
+    let repeated = field.label == FieldLabel::Repeated || matches!(field.field_type, FieldType::Map { .. });
+    let numeric = !repeated && !matches!(
+        field.field_type,
+        FieldType::Bool | FieldType::String | FieldType::Bytes | FieldType::MessageOrEnum(_) | FieldType::Map { .. }
+    );
+    let text = !repeated && matches!(field.field_type, FieldType::String | FieldType::Bytes);
+    let length_accessor = if field.field_type == FieldType::String { ".chars().count()" } else { ".len()" };
+    
+    constraints.iter()
+        .filter_map(|constraint| {
+            let (accessor, failing_op, bound, expectation) = match constraint {
+                FieldConstraint::Maximum(value) if numeric => ("", ">", *value, format!("at most {}", value)),
+                FieldConstraint::ExclusiveMaximum(value) if numeric => ("", ">=", *value, format!("less than {}", value)),
+                FieldConstraint::Minimum(value) if numeric => ("", "<", *value, format!("at least {}", value)),
+                FieldConstraint::ExclusiveMinimum(value) if numeric => ("", "<=", *value, format!("greater than {}", value)),
+                FieldConstraint::MaxLength(length) if text => (length_accessor, ">", *length as f64, format!("at most {} long", length)),
+                FieldConstraint::MinLength(length) if text => (length_accessor, "<", *length as f64, format!("at least {} long", length)),
+                FieldConstraint::MaxItems(count) if repeated => (".len()", ">", *count as f64, format!("at most {} items", count)),
+                FieldConstraint::MinItems(count) if repeated => (".len()", "<", *count as f64, format!("at least {} items", count)),
+                _ => return None,
+            };
+            
+            let mut check = HashMap::new();
+            check.insert("accessor", accessor.to_string());
+            check.insert("op", failing_op.to_string());
+            check.insert("bound", format!("{:?}", bound));
+            check.insert("message", format!("{:?}", format!("`{}` must be {}", field.name, expectation)));
+            Some(check)
+        })
+        .collect()
+}
+
+/// Names of the public items declared at the top level of generated code
+///
+/// Generated files declare each top-level item on an unindented line, so lines such as
+/// `pub struct Name<T: Bound> {` or `pub async fn name(` are enough to find them.
+fn public_items(content: &str) -> Vec<String> {
+    const ITEM_KEYWORDS: [&str; 6] = ["struct ", "enum ", "trait ", "type ", "fn ", "const "];
+    
+    content.lines()
+        .filter_map(|line| line.strip_prefix("pub "))
+        .map(|rest| rest.strip_prefix("async ").unwrap_or(rest))
+        .filter_map(|rest| ITEM_KEYWORDS.iter().find_map(|keyword| rest.strip_prefix(keyword)))
+        .map(|rest| rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect::<String>())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Truncate a string to at most `max_chars` characters, marking the cut with `...`
+fn truncate_snippet(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    
+    let truncated: String = text.chars().take(max_chars).collect();
+    format!("{}...", truncated)
+}
@@ -0,0 +1,357 @@
+//! Static OpenAPI document generation
+//!
+//! Builds an OpenAPI 3.0 document straight from the parsed model, so the spec
+//! can be produced without compiling the generated poem-openapi service.
+
+use super::{security_scheme_type, PoemOpenApiGenerator, API_KEY_HEADER};
+use crate::core::*;
+use crate::utils::to_snake_case;
+use serde_json::{json, Map, Value};
+
+/// OpenAPI version of the generated documents
+const OPENAPI_VERSION: &str = "3.0.3";
+
+impl PoemOpenApiGenerator {
+    /// Generate an OpenAPI 3.0 document describing the HTTP surface of `routes`
+    ///
+    /// Every route becomes an operation under its path, and every message and enum
+    /// of `proto_file` becomes a schema under `components.schemas`, keyed by its
+    /// nesting path (e.g. `User.Address`) so nested types never shadow top-level
+    /// ones. Routes whose method has no OpenAPI operation (such as a custom `LIST`
+    /// verb) are left out. Operations and
+    /// properties reference those schemas through `$ref` rather than inlining them,
+    /// so a message shared by many operations is described once. Paths carry the
+    /// configured path prefix and property names follow the wire names of the
+    /// generated models.
+    pub fn generate_openapi_spec(&self, proto_file: &ProtoFile, routes: &[HttpRoute]) -> Value {
+        let mut paths = Map::new();
+        let mut security_schemes = Map::new();
+        for route in routes {
+            let Some(method_key) = http_method_key(&route.http_method) else {
+                continue;
+            };
+            let path = self.prefix_path(&openapi_path(&route.segments));
+            let operations = paths.entry(path)
+                .or_insert_with(|| Value::Object(Map::new()));
+            operations[method_key] = operation(proto_file, route);
+            
+            if let Some(scheme) = route.security {
+                security_schemes.insert(security_scheme_type(&route.service_name, scheme), security_scheme(scheme));
+            }
+        }
+        
+        let mut schemas = Map::new();
+        for message in &proto_file.messages {
+            collect_schemas(proto_file, message, &[], &mut schemas);
+        }
+        for enum_def in &proto_file.enums {
+            schemas.insert(enum_def.name.clone(), enum_schema(enum_def));
+        }
+        
+        let metadata = proto_file.api_metadata();
+        let mut info = json!({
+            "title": metadata.title,
+            "version": metadata.version,
+        });
+        if let Some(description) = &metadata.description {
+            info["description"] = json!(description);
+        }
+        
+        let mut spec = json!({
+            "openapi": OPENAPI_VERSION,
+            "info": info,
+            "paths": paths,
+            "components": {
+                "schemas": schemas,
+            },
+        });
+        if !security_schemes.is_empty() {
+            spec["components"]["securitySchemes"] = Value::Object(security_schemes);
+        }
+        if !metadata.servers.is_empty() {
+            spec["servers"] = metadata.servers.iter().map(server).collect();
+        }
+        spec
+    }
+}
+
+/// Describe a server, with its URL template variables and their defaults
+fn server(server: &ApiServer) -> Value {
+    let mut object = json!({ "url": server.url });
+    if let Some(description) = &server.description {
+        object["description"] = json!(description);
+    }
+    if !server.variables.is_empty() {
+        let variables: Map<String, Value> = server.variables.iter()
+            .map(|variable| {
+                let mut object = json!({ "default": variable.default });
+                if let Some(description) = &variable.description {
+                    object["description"] = json!(description);
+                }
+                (variable.name.clone(), object)
+            })
+            .collect();
+        object["variables"] = Value::Object(variables);
+    }
+    object
+}
+
+/// Convert the segments of a google.api.http path template to an OpenAPI path
+///
+/// `{book.id}` becomes `{book_id}` to match the extracted parameter name, and
+/// segment patterns such as `{name=shelves/*}` are dropped.
+fn openapi_path(segments: &[PathSegment]) -> String {
+    segments.iter()
+        .map(|segment| match segment {
+            PathSegment::Literal(text) => text.clone(),
+            PathSegment::Param { name, .. } => format!("{{{}}}", name.replace('.', "_")),
+        })
+        .collect()
+}
+
+/// Get the key of an operation within an OpenAPI path item
+///
+/// Custom methods are matched case-insensitively against the remaining OpenAPI
+/// operations; `None` for verbs OpenAPI cannot describe.
+fn http_method_key(method: &HttpMethod) -> Option<&'static str> {
+    let key = match method {
+        HttpMethod::Get => "get",
+        HttpMethod::Post => "post",
+        HttpMethod::Put => "put",
+        HttpMethod::Patch => "patch",
+        HttpMethod::Delete => "delete",
+        HttpMethod::Custom(method) => {
+            return ["get", "put", "post", "delete", "options", "head", "patch", "trace"].into_iter()
+                .find(|key| method.eq_ignore_ascii_case(key));
+        }
+    };
+    Some(key)
+}
+
+/// Describe a single route as an OpenAPI operation
+fn operation(proto_file: &ProtoFile, route: &HttpRoute) -> Value {
+    let mut operation = Map::new();
+    operation.insert("operationId".to_string(), json!(route.operation_id()));
+    operation.insert("tags".to_string(), json!([route.service_name]));
+    
+    let method = proto_file.services.iter()
+        .find(|service| service.name == route.service_name)
+        .and_then(|service| service.methods.iter().find(|method| method.name == route.method_name));
+    let description = method
+        .map(|method| method.comments.iter()
+            .map(|comment| comment.text.trim())
+            .filter(|text| !text.starts_with('@'))
+            .collect::<Vec<_>>()
+            .join("\n"))
+        .filter(|description| !description.is_empty());
+    if let Some(description) = description {
+        operation.insert("description".to_string(), json!(description));
+    }
+    
+    let mut parameters: Vec<Value> = route.path_parameters.iter()
+        .map(|param| json!({
+            "name": param.name,
+            "in": "path",
+            "required": true,
+            "schema": parameter_schema(proto_file, &param.param_type),
+        }))
+        .collect();
+    parameters.extend(route.query_parameters.iter().map(|param| {
+        let mut parameter = json!({
+            "name": param.name,
+            "in": "query",
+            "required": param.required,
+            "schema": parameter_schema(proto_file, &param.param_type),
+        });
+        if param.style == QueryParameterStyle::DeepObject {
+            parameter["style"] = json!("deepObject");
+            parameter["explode"] = json!(true);
+        }
+        parameter
+    }));
+    if !parameters.is_empty() {
+        operation.insert("parameters".to_string(), Value::Array(parameters));
+    }
+    
+    if let Some(request_body) = &route.request_body {
+        let body_schema = if request_body.is_entire_message {
+            Some(type_schema(proto_file, &route.input_type, &[]))
+        } else {
+            // A body field's type is resolved from the request message declaring it
+            let input_path = proto_file.type_path_within(&route.input_type, &[]).unwrap_or_default();
+            let scope: Vec<&str> = input_path.iter().map(String::as_str).collect();
+            request_body.field_type.as_ref().map(|field_type| type_schema(proto_file, field_type, &scope))
+        };
+        let mut schema = body_schema.unwrap_or_else(|| json!({ "type": "object" }));
+        if request_body.is_repeated {
+            schema = json!({ "type": "array", "items": schema });
+        }
+        operation.insert("requestBody".to_string(), json!({
+            "required": true,
+            "content": { request_body.content_type.clone(): { "schema": schema } },
+        }));
+    }
+    
+    let response = if route.response_type.is_empty_message() && !route.response_type.is_stream {
+        json!({ "description": "Successful response" })
+    } else {
+        let content_type = if route.response_type.is_stream { "text/event-stream" } else { "application/json" };
+        json!({
+            "description": "Successful response",
+            "content": { content_type: { "schema": type_schema(proto_file, &route.response_type, &[]) } },
+        })
+    };
+    let status = method
+        .filter(|_| !route.response_type.is_stream)
+        .and_then(|method| method.status_code())
+        .and_then(Result::ok)
+        .unwrap_or(200);
+    operation.insert("responses".to_string(), json!({ status.to_string(): response }));
+    
+    if let Some(rate_limit) = &route.rate_limit {
+        operation.insert("x-rate-limit".to_string(), json!(rate_limit));
+    }
+    
+    if let Some(scheme) = route.security {
+        operation.insert("security".to_string(), json!([{ security_scheme_type(&route.service_name, scheme): [] }]));
+    }
+    
+    if let Some(deprecation) = &route.deprecation {
+        operation.insert("deprecated".to_string(), json!(true));
+        if let Some(sunset) = &deprecation.sunset {
+            operation.insert("x-sunset".to_string(), json!(sunset));
+        }
+    }
+    
+    Value::Object(operation)
+}
+
+/// Describe a security scheme under `components.securitySchemes`
+fn security_scheme(scheme: SecurityScheme) -> Value {
+    match scheme {
+        SecurityScheme::Bearer => json!({ "type": "http", "scheme": "bearer" }),
+        SecurityScheme::Basic => json!({ "type": "http", "scheme": "basic" }),
+        SecurityScheme::ApiKey => json!({ "type": "apiKey", "name": API_KEY_HEADER, "in": "header" }),
+    }
+}
+
+/// Get the schema of a path or query parameter
+fn parameter_schema(proto_file: &ProtoFile, param_type: &ParameterType) -> Value {
+    match param_type {
+        ParameterType::String => json!({ "type": "string" }),
+        ParameterType::Integer => json!({ "type": "integer", "format": "int64" }),
+        ParameterType::Float => json!({ "type": "number", "format": "double" }),
+        ParameterType::Boolean => json!({ "type": "boolean" }),
+        ParameterType::Timestamp => json!({ "type": "string", "format": "date-time" }),
+        ParameterType::Enum(name) | ParameterType::Custom(name) => {
+            schema_ref(proto_file, &TypeReference::new(name.clone()), &[])
+        }
+    }
+}
+
+/// Get the schema of a scalar, message, enum or well-known type referenced from the
+/// message nesting scope `scope`
+fn type_schema(proto_file: &ProtoFile, type_ref: &TypeReference, scope: &[&str]) -> Value {
+    match type_ref.fully_qualified_name().as_str() {
+        "double" => json!({ "type": "number", "format": "double" }),
+        "float" => json!({ "type": "number", "format": "float" }),
+        "int32" | "sint32" | "sfixed32" => json!({ "type": "integer", "format": "int32" }),
+        "uint32" | "fixed32" => json!({ "type": "integer", "format": "int32", "minimum": 0 }),
+        "int64" | "sint64" | "sfixed64" => json!({ "type": "integer", "format": "int64" }),
+        "uint64" | "fixed64" => json!({ "type": "integer", "format": "int64", "minimum": 0 }),
+        "bool" => json!({ "type": "boolean" }),
+        "string" => json!({ "type": "string" }),
+        "bytes" => json!({ "type": "string", "format": "byte" }),
+        "google.protobuf.Timestamp" => json!({ "type": "string", "format": "date-time" }),
+        "google.protobuf.Duration" | "google.protobuf.FieldMask" => json!({ "type": "string" }),
+        "google.protobuf.Empty" | "google.protobuf.Struct" | "google.protobuf.Any" => json!({ "type": "object" }),
+        "google.protobuf.Value" => json!({}),
+        "google.protobuf.ListValue" => json!({ "type": "array", "items": {} }),
+        _ => match type_ref.wrapped_scalar() {
+            Some(scalar) => {
+                let mut schema = type_schema(proto_file, &TypeReference::new(scalar.to_string()), scope);
+                schema["nullable"] = json!(true);
+                schema
+            }
+            None => schema_ref(proto_file, type_ref, scope),
+        },
+    }
+}
+
+/// Get the schema of a field type declared in the message at `scope`, ignoring its label
+fn field_type_schema(proto_file: &ProtoFile, field_type: &FieldType, scope: &[&str]) -> Value {
+    match field_type {
+        FieldType::Map { value, .. } => json!({
+            "type": "object",
+            "additionalProperties": field_type_schema(proto_file, value, scope),
+        }),
+        field_type => type_schema(proto_file, &field_type.to_type_reference(), scope),
+    }
+}
+
+/// Add the schema of a message nested in `parents` and of its nested messages and enums
+fn collect_schemas(proto_file: &ProtoFile, message: &Message, parents: &[&str], schemas: &mut Map<String, Value>) {
+    let mut path = parents.to_vec();
+    path.push(&message.name);
+    
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    
+    for field in &message.fields {
+        let name = to_snake_case(&field.name);
+        let mut schema = match field.label {
+            FieldLabel::Repeated => json!({ "type": "array", "items": field_type_schema(proto_file, &field.field_type, &path) }),
+            _ => field_type_schema(proto_file, &field.field_type, &path),
+        };
+        
+        // Siblings of `$ref` are ignored in OpenAPI 3.0, so wrap the reference
+        let has_access_mode = field.is_read_only() || field.is_write_only();
+        if has_access_mode && schema.get("$ref").is_some() {
+            schema = json!({ "allOf": [schema] });
+        }
+        if field.is_read_only() {
+            schema["readOnly"] = json!(true);
+        }
+        if field.is_write_only() {
+            schema["writeOnly"] = json!(true);
+        }
+        if field.is_required() {
+            required.push(name.clone());
+        }
+        properties.insert(name, schema);
+    }
+    
+    let mut schema = json!({
+        "type": "object",
+        "properties": properties,
+    });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+    schemas.insert(path.join("."), schema);
+    
+    for nested in &message.nested_messages {
+        collect_schemas(proto_file, nested, &path, schemas);
+    }
+    for enum_def in &message.nested_enums {
+        schemas.insert(format!("{}.{}", path.join("."), enum_def.name), enum_schema(enum_def));
+    }
+}
+
+/// Get the schema of an enum, listing its value names
+fn enum_schema(enum_def: &Enum) -> Value {
+    json!({
+        "type": "string",
+        "enum": enum_def.values.iter().map(|value| value.name.as_str()).collect::<Vec<_>>(),
+    })
+}
+
+/// Reference the schema of a message or enum under `components.schemas`
+///
+/// Types not defined in `proto_file` are referenced by their simple name.
+fn schema_ref(proto_file: &ProtoFile, type_ref: &TypeReference, scope: &[&str]) -> Value {
+    let name = proto_file.type_path_within(type_ref, scope)
+        .map(|path| path.join("."))
+        .unwrap_or_else(|| type_ref.name.rsplit('.').next().unwrap_or(&type_ref.name).to_string());
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
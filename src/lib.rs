@@ -108,6 +108,9 @@ pub mod core;
 // Parser implementation using nom
 pub mod parser;
 
+// FileDescriptorSet decoding
+pub mod descriptor;
+
 // HTTP annotation extraction
 pub mod extractor;
 
@@ -140,13 +143,14 @@ pub mod plugins;
 
 // Re-export core types for convenience
 pub use core::*;
-pub use parser::NomProtoParser;
+pub use parser::{NomProtoParser, UnknownOption};
+pub use descriptor::DescriptorSetParser;
 pub use extractor::GoogleApiHttpExtractor;
-pub use generator::PoemOpenApiGenerator;
+pub use generator::{PoemOpenApiGenerator, GeneratorRegistry, DynCodeGenerator};
 pub use templates::HandlebarsTemplateEngine;
 pub use validation::ValidationEngine;
-pub use error_reporter::ErrorReporter;
-pub use coordinator::{ProtoHttpCoordinator, ProcessResult, BatchProcessResult};
+pub use error_reporter::{Diagnostic, DiagnosticPosition, DiagnosticRange, ErrorReporter};
+pub use coordinator::{ProtoHttpCoordinator, ProcessResult, BatchProcessResult, ProcessMetrics, ParameterInfo, ParameterKind, GenerationDiff, FileDiff, DiffLine, TAGS_FILENAME, REGISTRATION_FILENAME, ERRORS_FILENAME, API_METADATA_FILENAME, PATHS_FILENAME, CORS_FILENAME, ROUTE_INFO_FILENAME, PRELUDE_FILENAME};
 
 // Re-export utility functions
 pub use utils::*;
@@ -3,25 +3,32 @@
 use crate::core::*;
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_until},
+    bytes::complete::{tag, take_until, take_while},
     character::complete::{
-        alpha1, alphanumeric1, char, digit1, line_ending, multispace0, multispace1, space0, space1,
+        char, digit0, digit1, line_ending, multispace0, multispace1, not_line_ending,
+        satisfy, space0, space1,
     },
-    combinator::{map, opt, recognize, value, verify},
-    multi::{many0, separated_list0},
+    combinator::{eof, map, map_res, opt, recognize, value},
+    multi::{many0, many0_count, separated_list0, separated_list1},
     sequence::{delimited, pair, preceded, terminated, tuple},
     IResult,
 };
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 /// nom-based Protocol Buffer parser
 pub struct NomProtoParser {
     config: ParserConfig,
-    /// Cache for parsed imports to prevent circular dependencies
-    import_cache: std::cell::RefCell<HashMap<PathBuf, ProtoFile>>,
+    /// Cache of parsed files with the hash of the content they were parsed from
+    import_cache: std::cell::RefCell<HashMap<PathBuf, (u64, ProtoFile)>>,
     /// Track import chain to detect cycles
     import_chain: std::cell::RefCell<Vec<PathBuf>>,
+    /// Depth of the import currently being resolved (0 for the root file)
+    import_depth: std::cell::Cell<usize>,
+    /// Errors of the declarations skipped by recovering parses, see [`ParserConfig::error_recovery`]
+    recovered_errors: std::cell::RefCell<Vec<ParseError>>,
 }
 
 impl NomProtoParser {
@@ -31,6 +38,8 @@ impl NomProtoParser {
             config: ParserConfig::default(),
             import_cache: std::cell::RefCell::new(HashMap::new()),
             import_chain: std::cell::RefCell::new(Vec::new()),
+            import_depth: std::cell::Cell::new(0),
+            recovered_errors: std::cell::RefCell::new(Vec::new()),
         }
     }
     
@@ -40,6 +49,8 @@ impl NomProtoParser {
             config,
             import_cache: std::cell::RefCell::new(HashMap::new()),
             import_chain: std::cell::RefCell::new(Vec::new()),
+            import_depth: std::cell::Cell::new(0),
+            recovered_errors: std::cell::RefCell::new(Vec::new()),
         }
     }
     
@@ -47,6 +58,96 @@ impl NomProtoParser {
     pub fn clear_cache(&self) {
         self.import_cache.borrow_mut().clear();
         self.import_chain.borrow_mut().clear();
+        self.import_depth.set(0);
+    }
+    
+    /// Find the options of `proto_file` that [`UnknownOptionPolicy`] treats as unknown
+    ///
+    /// Options are listed in file order: file options first, then services and
+    /// their methods, messages and their fields, and enums and their values.
+    pub fn unknown_options(&self, proto_file: &ProtoFile) -> Vec<UnknownOption> {
+        let mut collector = UnknownOptionCollector::default();
+        collector.check(&proto_file.options, || "file".to_string());
+        proto_file.accept(&mut collector);
+        collector.unknown
+    }
+    
+    /// Take the errors of the declarations skipped since the last root file was parsed
+    ///
+    /// Only parses with [`ParserConfig::error_recovery`] enabled skip declarations;
+    /// errors are listed in source order, followed by those of any resolved imports.
+    pub fn take_recovered_errors(&self) -> Vec<ParseError> {
+        std::mem::take(&mut *self.recovered_errors.borrow_mut())
+    }
+}
+
+/// Options of the `google.` namespace defined by the protobuf and Google API descriptors
+const KNOWN_GOOGLE_OPTIONS: &[&str] = &[
+    "google.api.http",
+    "google.api.field_behavior",
+    "google.api.field_info",
+    "google.api.resource",
+    "google.api.resource_reference",
+    "google.api.resource_definition",
+    "google.api.method_signature",
+    "google.api.default_host",
+    "google.api.oauth_scopes",
+    "google.api.api_version",
+    "google.api.routing",
+    "google.longrunning.operation_info",
+];
+
+/// An option whose name is not recognized
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownOption {
+    /// Option name, without parentheses
+    pub name: String,
+    /// Definition carrying the option, e.g. `method UserService.GetUser`
+    pub location: String,
+}
+
+/// Visitor collecting the unknown options of every definition
+#[derive(Default)]
+struct UnknownOptionCollector {
+    unknown: Vec<UnknownOption>,
+}
+
+impl UnknownOptionCollector {
+    fn check(&mut self, options: &[ProtoOption], location: impl Fn() -> String) {
+        for option in options {
+            let name = option.name.trim_start_matches('(').trim_end_matches(')');
+            if name.starts_with("google.") && !KNOWN_GOOGLE_OPTIONS.contains(&name) {
+                self.unknown.push(UnknownOption {
+                    name: name.to_string(),
+                    location: location(),
+                });
+            }
+        }
+    }
+}
+
+impl Visitor for UnknownOptionCollector {
+    fn visit_service(&mut self, service: &Service) {
+        self.check(&service.options, || format!("service {}", service.name));
+    }
+    
+    fn visit_method(&mut self, service: &Service, method: &RpcMethod) {
+        self.check(&method.options, || format!("method {}.{}", service.name, method.name));
+    }
+    
+    fn visit_message(&mut self, message: &Message) {
+        self.check(&message.options, || format!("message {}", message.name));
+    }
+    
+    fn visit_field(&mut self, message: &Message, field: &Field) {
+        self.check(&field.options, || format!("field {}.{}", message.name, field.name));
+    }
+    
+    fn visit_enum(&mut self, enum_def: &Enum) {
+        self.check(&enum_def.options, || format!("enum {}", enum_def.name));
+        for value in &enum_def.values {
+            self.check(&value.options, || format!("enum value {}.{}", enum_def.name, value.name));
+        }
     }
 }
 
@@ -75,9 +176,12 @@ impl ProtoParser for NomProtoParser {
             });
         }
         
-        // Check cache first
-        if let Some(cached) = self.import_cache.borrow().get(&canonical_path) {
-            return Ok(cached.clone());
+        // Check cache first; a file edited since it was cached is parsed again
+        let content_hash = content_hash(&content);
+        if let Some((cached_hash, cached)) = self.import_cache.borrow().get(&canonical_path) {
+            if *cached_hash == content_hash {
+                return Ok(cached.clone());
+            }
         }
         
         // Add to import chain
@@ -90,23 +194,64 @@ impl ProtoParser for NomProtoParser {
         
         // Cache successful parse
         if let Ok(ref proto_file) = result {
-            self.import_cache.borrow_mut().insert(canonical_path, proto_file.clone());
+            self.import_cache.borrow_mut().insert(canonical_path, (content_hash, proto_file.clone()));
         }
         
         result
     }
     
-    fn parse_content(&self, content: &str) -> Result<ProtoFile, Self::Error> {
-        match proto_file(content) {
+    fn parse_content(&self, source: &str) -> Result<ProtoFile, Self::Error> {
+        // Some editors prefix files with a UTF-8 byte order mark
+        let content = source.strip_prefix('\u{feff}').unwrap_or(source);
+        
+        NESTING.with(|state| state.set(NestingState::new(self.config.max_nesting_depth)));
+        KEEP_SOURCE.with(|keep| keep.set(self.config.keep_source_spans));
+        if self.import_depth.get() == 0 {
+            self.recovered_errors.borrow_mut().clear();
+        }
+        let parsed = if self.config.error_recovery || self.config.allow_unknown_statements {
+            lenient_proto_file(content, self.config.allow_unknown_statements, self.config.error_recovery).map(
+                |(rest, (proto_file, skipped))| {
+                    self.recovered_errors.borrow_mut().extend(skipped.into_iter().map(|start| {
+                        let span = error_span(source, start);
+                        ParseError::InvalidSyntax {
+                            message: format!("Skipped invalid declaration: {}", &source[span.clone()]),
+                            span: Some(span),
+                        }
+                    }));
+                    (rest, proto_file)
+                },
+            )
+        } else {
+            proto_file(content)
+        };
+        if let Some(name) = NESTING.with(|state| state.take().exceeded_by) {
+            return Err(ParseError::NestingDepthExceeded {
+                name,
+                max_depth: self.config.max_nesting_depth,
+            });
+        }
+        
+        match parsed {
             Ok((remaining, mut proto_file)) => {
                 // Check if there's unparsed content (should be only whitespace/comments)
-                let remaining = remaining.trim();
-                if !remaining.is_empty() && !remaining.starts_with("//") && !remaining.starts_with("/*") {
+                let trimmed = remaining.trim();
+                if !trimmed.is_empty() && !trimmed.starts_with("//") && !trimmed.starts_with("/*") {
                     return Err(ParseError::InvalidSyntax {
-                        message: format!("Unexpected content at end of file: {}", remaining),
+                        message: format!("Unexpected content at end of file: {}", trimmed),
+                        span: Some(error_span(source, remaining)),
                     });
                 }
                 
+                if self.config.unknown_option_policy == UnknownOptionPolicy::Error {
+                    if let Some(option) = self.unknown_options(&proto_file).into_iter().next() {
+                        return Err(ParseError::UnknownOption {
+                            name: option.name,
+                            location: option.location,
+                        });
+                    }
+                }
+                
                 // Resolve imports if configured and not in test mode
                 if !proto_file.imports.is_empty() && !cfg!(test) {
                     self.resolve_imports(&mut proto_file)?;
@@ -117,11 +262,13 @@ impl ProtoParser for NomProtoParser {
             Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
                 Err(ParseError::InvalidSyntax {
                     message: format!("Parse error: {:?}", e),
+                    span: Some(error_span(source, e.input)),
                 })
             }
             Err(nom::Err::Incomplete(_)) => {
                 Err(ParseError::InvalidSyntax {
                     message: "Incomplete input".to_string(),
+                    span: None,
                 })
             }
         }
@@ -137,6 +284,30 @@ impl ProtoParser for NomProtoParser {
     }
 }
 
+/// Whether an import names a file whose definitions are built into the crate
+fn is_builtin_import(import_path: &str) -> bool {
+    import_path.starts_with("google/api/") || import_path.starts_with("google/protobuf/")
+}
+
+/// Hash of a file's content, used to detect edits to cached files
+fn content_hash(content: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Byte range in `source` of the offending line where parsing stopped with `rest` unconsumed
+///
+/// The range starts at the first non-whitespace character of `rest`, which must be a
+/// suffix of `source`, and runs to the end of that line.
+fn error_span(source: &str, rest: &str) -> Range<usize> {
+    let rest = rest.trim_start();
+    let start = source.len() - rest.len();
+    let line = rest.lines().next().unwrap_or_default().trim_end();
+    start..start + line.len()
+}
+
 impl NomProtoParser {
     /// Resolve imports in a proto file
     fn resolve_imports(&self, proto_file: &mut ProtoFile) -> Result<(), ParseError> {
@@ -153,6 +324,17 @@ impl NomProtoParser {
                 Ok(_) => {
                     // 成功解析导入
                 }
+                Err(error @ ParseError::ImportDepthExceeded { .. }) => {
+                    // Exceeding the configured depth is always an error
+                    return Err(error);
+                }
+                Err(error @ ParseError::ImportNotFound { .. })
+                    if self.config.missing_import_policy == MissingImportPolicy::Error
+                        && !is_builtin_import(&import.path)
+                        && import.import_type != ImportType::Weak =>
+                {
+                    return Err(error);
+                }
                 Err(_) => {
                     // 导入解析失败，但继续处理
                     // 在实际应用中，可能需要记录警告
@@ -162,13 +344,47 @@ impl NomProtoParser {
         Ok(())
     }
     
-    /// Resolve a single import
+    /// Resolve a single import of the file currently being parsed
     fn resolve_single_import(&self, import_path: &str) -> Result<ProtoFile, ParseError> {
+        let importing_dir = self.import_chain.borrow().last()
+            .and_then(|path| path.parent())
+            .map(Path::to_path_buf);
+        let full_path = self.find_import(import_path, importing_dir.as_deref())?;
+        
+        let parent_depth = self.import_depth.get();
+        let depth = parent_depth + 1;
+        if depth > self.config.max_import_depth {
+            let mut chain: Vec<String> = self.import_chain.borrow().iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect();
+            chain.push(full_path.to_string_lossy().to_string());
+            return Err(ParseError::ImportDepthExceeded {
+                max_depth: self.config.max_import_depth,
+                chain,
+            });
+        }
+        
+        self.import_depth.set(depth);
+        let result = self.parse_file(&full_path);
+        self.import_depth.set(parent_depth);
+        
+        result
+    }
+    
+    /// Locate an import relative to the importing file's directory, then on the configured include paths
+    fn find_import(&self, import_path: &str, importing_dir: Option<&Path>) -> Result<PathBuf, ParseError> {
+        // Like protoc, prefer a file next to the importer
+        if let Some(full_path) = importing_dir.map(|dir| dir.join(import_path)) {
+            if full_path.exists() {
+                return Ok(full_path);
+            }
+        }
+        
         // Try each include path
         for include_path in &self.config.include_paths {
             let full_path = include_path.join(import_path);
             if full_path.exists() {
-                return self.parse_file(&full_path);
+                return Ok(full_path);
             }
         }
         
@@ -176,18 +392,147 @@ impl NomProtoParser {
             import_path: import_path.to_string(),
         })
     }
+    
+    /// Find the imports of a proto file read from `source_path` whose file cannot be found
+    ///
+    /// Weak imports are left out, as protobuf allows their files to be absent, and
+    /// so are the built-in `google/api/` and `google/protobuf/` imports.
+    pub fn missing_imports(&self, proto_file: &ProtoFile, source_path: Option<&Path>) -> Vec<String> {
+        let source_dir = source_path.and_then(Path::parent);
+        proto_file.imports.iter()
+            .filter(|import| import.import_type != ImportType::Weak && !is_builtin_import(&import.path))
+            .filter(|import| self.find_import(&import.path, source_dir).is_err())
+            .map(|import| import.path.clone())
+            .collect()
+    }
+    
+    /// Parse all files transitively imported by a proto file
+    ///
+    /// Imports that cannot be found are skipped, matching the lenient behavior
+    /// of import resolution during parsing, but an imported file that fails to
+    /// parse fails the whole collection. `google/api/` annotation imports are
+    /// never parsed. Import chains longer than `max_import_depth` fail with
+    /// [`ParseError::ImportDepthExceeded`].
+    pub fn parse_imported_files(&self, proto_file: &ProtoFile) -> Result<Vec<(PathBuf, ProtoFile)>, ParseError> {
+        self.parse_imported_files_from(proto_file, None)
+    }
+    
+    /// Parse all files transitively imported by a proto file read from `source_path`
+    ///
+    /// Like [`Self::parse_imported_files`], but each import is first looked up in
+    /// the directory of the file importing it, starting with `source_path`.
+    pub fn parse_imported_files_from(&self, proto_file: &ProtoFile, source_path: Option<&Path>) -> Result<Vec<(PathBuf, ProtoFile)>, ParseError> {
+        let mut imported = Vec::new();
+        // Files are identified by canonical path, as different import strings can name the same file
+        let mut visited: std::collections::HashSet<PathBuf> = source_path
+            .map(|path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf()))
+            .into_iter()
+            .collect();
+        let source_dir = source_path.and_then(Path::parent).map(Path::to_path_buf);
+        let root_chain: Vec<String> = source_path.map(|path| path.to_string_lossy().to_string()).into_iter().collect();
+        // Each pending import carries the chain of files that led to it. Imports are
+        // visited breadth-first, so a file shared by several chains is reached at its
+        // smallest depth.
+        let mut pending: std::collections::VecDeque<(String, Option<PathBuf>, Vec<String>)> = proto_file.imports.iter()
+            .map(|import| (import.path.clone(), source_dir.clone(), root_chain.clone()))
+            .collect();
+        
+        while let Some((import_path, importing_dir, chain)) = pending.pop_front() {
+            if import_path.starts_with("google/api/") {
+                continue;
+            }
+            
+            let full_path = match self.find_import(&import_path, importing_dir.as_deref()) {
+                Ok(full_path) => full_path,
+                Err(_) => continue,
+            };
+            if !visited.insert(full_path.canonicalize().unwrap_or_else(|_| full_path.clone())) {
+                continue;
+            }
+            
+            let mut chain = chain;
+            chain.push(full_path.to_string_lossy().to_string());
+            if chain.len() - root_chain.len() > self.config.max_import_depth {
+                return Err(ParseError::ImportDepthExceeded {
+                    max_depth: self.config.max_import_depth,
+                    chain,
+                });
+            }
+            
+            let imported_file = self.parse_file(&full_path)?;
+            let dir = full_path.parent().map(Path::to_path_buf);
+            pending.extend(imported_file.imports.iter().map(|import| (import.path.clone(), dir.clone(), chain.clone())));
+            imported.push((full_path, imported_file));
+        }
+        
+        Ok(imported)
+    }
 }
 
 // Parser combinators for Protocol Buffer syntax
 
+/// Nesting state shared by the recursive message/enum combinators of one parse
+#[derive(Debug)]
+struct NestingState {
+    depth: usize,
+    max_depth: usize,
+    exceeded_by: Option<String>,
+}
+
+impl NestingState {
+    fn new(max_depth: usize) -> Self {
+        Self { depth: 0, max_depth, exceeded_by: None }
+    }
+}
+
+impl Default for NestingState {
+    /// Unlimited nesting for combinators used outside of `parse_content`
+    fn default() -> Self {
+        Self::new(usize::MAX)
+    }
+}
+
+thread_local! {
+    static NESTING: Cell<NestingState> = Cell::new(NestingState::default());
+    /// Whether top-level definitions record their source text during the current parse
+    static KEEP_SOURCE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// One level of message/enum nesting, released when the definition finishes parsing
+struct NestingGuard;
+
+impl NestingGuard {
+    /// Enter a definition body, failing the parse once the configured depth is exceeded
+    fn enter<'a>(input: &'a str, name: &str) -> Result<Self, nom::Err<nom::error::Error<&'a str>>> {
+        NESTING.with(|cell| {
+            let mut state = cell.take();
+            let result = if state.depth >= state.max_depth {
+                state.exceeded_by.get_or_insert_with(|| name.to_string());
+                Err(nom::Err::Failure(nom::error::Error::new(input, nom::error::ErrorKind::TooLarge)))
+            } else {
+                state.depth += 1;
+                Ok(NestingGuard)
+            };
+            cell.set(state);
+            result
+        })
+    }
+}
+
+impl Drop for NestingGuard {
+    fn drop(&mut self) {
+        NESTING.with(|cell| {
+            let mut state = cell.take();
+            state.depth = state.depth.saturating_sub(1);
+            cell.set(state);
+        });
+    }
+}
+
 /// Parse a complete proto file
 fn proto_file(input: &str) -> IResult<&str, ProtoFile> {
     // Skip initial comments and whitespace
-    let (input, _) = many0(alt((
-        map(comment, |_| ()),
-        map(multispace1, |_| ()),
-    )))(input)?;
-    let (input, _) = multispace0(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
     
     let (input, syntax) = opt(syntax_statement)(input)?;
     let (input, _) = multispace0(input)?;
@@ -202,7 +547,9 @@ fn proto_file(input: &str) -> IResult<&str, ProtoFile> {
     let (input, _) = multispace0(input)?;
     
     let (input, definitions) = many0(terminated(top_level_definition, multispace0))(input)?;
-    let (input, _) = multispace0(input)?;
+    
+    // Skip trailing comments and whitespace
+    let (input, _) = comments_and_whitespace(input)?;
     
     let mut services = Vec::new();
     let mut messages = Vec::new();
@@ -224,11 +571,167 @@ fn proto_file(input: &str) -> IResult<&str, ProtoFile> {
         services,
         messages,
         enums,
+        unknown_statements: Vec::new(),
     }))
 }
 
-/// Parse syntax statement
+/// Parse a complete proto file, accepting top-level statements in any order
+///
+/// Unrecognized statements are kept in [`ProtoFile::unknown_statements`] when
+/// `allow_unknown_statements` is set. With `recover`, a declaration that fails to parse
+/// is skipped up to the `;` ending it or the `}` closing its first block, or to the end
+/// of its line when neither balances, instead of stopping. Returns the parsed file and
+/// the input at the start of each skipped declaration.
+fn lenient_proto_file(
+    input: &str,
+    allow_unknown_statements: bool,
+    recover: bool,
+) -> IResult<&str, (ProtoFile, Vec<&str>)> {
+    let mut proto_file = ProtoFile::new();
+    let mut syntax = None;
+    let mut skipped = Vec::new();
+    let mut input = input;
+    
+    loop {
+        input = match comments_and_whitespace(input) {
+            Ok((rest, _)) => rest,
+            Err(_) if recover => input,
+            Err(error) => return Err(error),
+        };
+        if input.is_empty() {
+            break;
+        }
+        
+        if let Ok((rest, version)) = syntax_statement(input) {
+            syntax = Some(version);
+            input = rest;
+        } else if let Ok((rest, package)) = package_statement(input) {
+            proto_file.package = Some(package);
+            input = rest;
+        } else if let Ok((rest, import)) = import_statement(input) {
+            proto_file.imports.push(import);
+            input = rest;
+        } else if let Ok((rest, option)) = option_statement(input) {
+            proto_file.options.push(option);
+            input = rest;
+        } else if let Ok((rest, definition)) = top_level_definition(input) {
+            match definition {
+                TopLevelDefinition::Service(service) => proto_file.services.push(service),
+                TopLevelDefinition::Message(message) => proto_file.messages.push(message),
+                TopLevelDefinition::Enum(enum_def) => proto_file.enums.push(enum_def),
+            }
+            input = rest;
+        } else if let Some((rest, statement)) = allow_unknown_statements.then(|| unknown_statement(input).ok()).flatten() {
+            proto_file.unknown_statements.push(statement.to_string());
+            input = rest;
+        } else if recover {
+            skipped.push(input);
+            input = match statement_len(input) {
+                Some(len) => &input[len..],
+                None => input.find('\n').map_or("", |end| &input[end + 1..]),
+            };
+        } else {
+            break;
+        }
+    }
+    
+    proto_file.syntax = syntax.unwrap_or(ProtocolVersion::Proto3);
+    Ok((input, (proto_file, skipped)))
+}
+
+/// Recognize an unrecognized statement starting with an identifier, up to the `;`
+/// ending it or the `}` closing its first block, skipping over string literals
+fn unknown_statement(input: &str) -> IResult<&str, &str> {
+    let (_, _) = identifier(input)?;
+    
+    match statement_len(input) {
+        Some(len) => Ok((&input[len..], &input[..len])),
+        None => Err(nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::TakeUntil))),
+    }
+}
+
+/// Length of the statement at the start of `input`, up to the `;` ending it or the `}`
+/// closing its first block, skipping over string literals
+///
+/// `None` when the statement is not terminated or an unmatched `}` comes first.
+fn statement_len(input: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, ch) in input.char_indices() {
+        if let Some(open) = quote {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == open {
+                quote = None;
+            }
+            continue;
+        }
+        
+        let end = match ch {
+            '"' | '\'' => {
+                quote = Some(ch);
+                false
+            }
+            '{' => {
+                depth += 1;
+                false
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                depth == 0
+            }
+            '}' => break,
+            ';' => depth == 0,
+            _ => false,
+        };
+        if end {
+            return Some(index + ch.len_utf8());
+        }
+    }
+    
+    None
+}
+
+/// Skip any mix of comments and whitespace
+///
+/// Unlike [`leading_comments`] the comments are not collected, so nothing is allocated.
+fn comments_and_whitespace(input: &str) -> IResult<&str, ()> {
+    let mut input = input;
+    while input.starts_with([' ', '\t', '\r', '\n', '/']) {
+        input = input.trim_start_matches([' ', '\t', '\r', '\n']);
+        if let Some(line) = input.strip_prefix("//") {
+            input = line.find('\n').map_or("", |end| &line[end + 1..]);
+        } else if let Some(block) = input.strip_prefix("/*") {
+            match block.find("*/") {
+                Some(end) => input = &block[end + 2..],
+                None => break,
+            }
+        } else {
+            break;
+        }
+    }
+    
+    Ok((input, ()))
+}
+
+/// Peek at the keyword or type name starting the next statement, after any comments
+///
+/// Used to try the matching statement parser first instead of every alternative in turn.
+fn statement_keyword(input: &str) -> &str {
+    let (input, _) = comments_and_whitespace(input).unwrap_or((input, ()));
+    identifier(input).map_or("", |(_, keyword)| keyword)
+}
+
+/// Parse syntax statement, or the `edition` statement taking its place in editions files
 fn syntax_statement(input: &str) -> IResult<&str, ProtocolVersion> {
+    alt((syntax_version, edition_statement))(input)
+}
+
+/// Parse `syntax = "proto2";` or `syntax = "proto3";`
+fn syntax_version(input: &str) -> IResult<&str, ProtocolVersion> {
     let (input, _) = tag("syntax")(input)?;
     let (input, _) = space0(input)?;
     let (input, _) = char('=')(input)?;
@@ -243,6 +746,19 @@ fn syntax_statement(input: &str) -> IResult<&str, ProtocolVersion> {
     Ok((input, version))
 }
 
+/// Parse an editions statement such as `edition = "2023";`
+fn edition_statement(input: &str) -> IResult<&str, ProtocolVersion> {
+    let (input, _) = tag("edition")(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = char('=')(input)?;
+    let (input, _) = space0(input)?;
+    let (input, edition) = string_literal(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = char(';')(input)?;
+    
+    Ok((input, ProtocolVersion::Edition(edition)))
+}
+
 /// Parse package statement
 fn package_statement(input: &str) -> IResult<&str, String> {
     let (input, _) = tag("package")(input)?;
@@ -251,7 +767,7 @@ fn package_statement(input: &str) -> IResult<&str, String> {
     let (input, _) = space0(input)?;
     let (input, _) = char(';')(input)?;
     
-    Ok((input, package_name))
+    Ok((input, package_name.to_string()))
 }
 
 /// Parse import statement
@@ -287,25 +803,25 @@ fn option_statement(input: &str) -> IResult<&str, ProtoOption> {
 /// Parse option name (can be complex like (google.api.http) or validate.rules)
 fn option_name(input: &str) -> IResult<&str, String> {
     alt((
-        // Parenthesized option name
-        delimited(
-            char('('),
-            full_identifier,
-            char(')')
+        // Parenthesized option name, optionally followed by subfields as in `(buf.validate.field).string`
+        map(
+            pair(
+                delimited(char('('), full_identifier, char(')')),
+                recognize(many0_count(preceded(char('.'), identifier))),
+            ),
+            |(extension, subfields)| [extension, subfields].concat()
         ),
         // Complex option name with dots and brackets
         map(
             recognize(pair(
                 full_identifier,
-                many0(alt((
+                many0_count(alt((
                     preceded(char('.'), full_identifier),
                     delimited(char('['), full_identifier, char(']')),
                 )))
             )),
             |s: &str| s.to_string()
         ),
-        // Simple option name
-        full_identifier,
     ))(input)
 }
 
@@ -316,42 +832,64 @@ fn option_value(input: &str) -> IResult<&str, OptionValue> {
         map(number_literal, OptionValue::Number),
         map(boolean_literal, OptionValue::Boolean),
         map(message_literal, OptionValue::MessageLiteral),
-        map(identifier, OptionValue::Identifier),
+        map(identifier, |name| OptionValue::Identifier(name.to_string())),
     ))(input)
 }
 
 /// Parse message literal (for complex option values)
-fn message_literal(input: &str) -> IResult<&str, HashMap<String, OptionValue>> {
+fn message_literal(input: &str) -> IResult<&str, Vec<(String, OptionValue)>> {
     let (input, _) = char('{')(input)?;
     let (input, _) = multispace0(input)?;
-    let (input, fields) = separated_list0(
-        tuple((multispace0, opt(char(',')), multispace0)),
-        message_field
-    )(input)?;
-    let (input, _) = multispace0(input)?;
+    // Each field may be followed by a `,` or `;`, including the last one
+    let (input, fields) = many0(terminated(
+        message_field,
+        tuple((multispace0, opt(alt((char(','), char(';')))), multispace0)),
+    ))(input)?;
     let (input, _) = char('}')(input)?;
     
-    Ok((input, fields.into_iter().collect()))
+    Ok((input, fields))
 }
 
 /// Parse message field in a message literal
+///
+/// The colon may be omitted before a nested message value, as in `nested { x: 1 }`.
 fn message_field(input: &str) -> IResult<&str, (String, OptionValue)> {
-    let (input, name) = identifier(input)?;
+    let (input, name) = map(identifier, str::to_string)(input)?;
     let (input, _) = space0(input)?;
-    let (input, _) = char(':')(input)?;
-    let (input, _) = space0(input)?;
-    let (input, value) = option_value(input)?;
+    let (input, value) = alt((
+        preceded(pair(char(':'), multispace0), option_value),
+        map(message_literal, OptionValue::MessageLiteral),
+    ))(input)?;
     
     Ok((input, (name, value)))
 }
 
 /// Parse top-level definitions
 fn top_level_definition(input: &str) -> IResult<&str, TopLevelDefinition> {
-    alt((
-        map(service_definition, TopLevelDefinition::Service),
-        map(message_definition, TopLevelDefinition::Message),
-        map(enum_definition, TopLevelDefinition::Enum),
-    ))(input)
+    let (rest, mut definition) = match statement_keyword(input) {
+        "service" => map(service_definition, TopLevelDefinition::Service)(input)?,
+        "message" => map(message_definition, TopLevelDefinition::Message)(input)?,
+        "enum" => map(enum_definition, TopLevelDefinition::Enum)(input)?,
+        _ => return Err(nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Alt))),
+    };
+    
+    if KEEP_SOURCE.with(Cell::get) {
+        let source = Some(definition_source(input, rest));
+        match &mut definition {
+            TopLevelDefinition::Service(service) => service.source = source,
+            TopLevelDefinition::Message(message) => message.source = source,
+            TopLevelDefinition::Enum(enum_def) => enum_def.source = source,
+        }
+    }
+    
+    Ok((rest, definition))
+}
+
+/// Text of the definition parsed from `input` with `rest` left over, without its leading comments
+fn definition_source(input: &str, rest: &str) -> String {
+    let consumed = &input[..input.len() - rest.len()];
+    let declaration = leading_comments(consumed).map_or(consumed, |(declaration, _)| declaration);
+    declaration.trim().to_string()
 }
 
 #[derive(Debug, Clone)]
@@ -363,11 +901,11 @@ enum TopLevelDefinition {
 
 /// Parse service definition
 fn service_definition(input: &str) -> IResult<&str, Service> {
-    let (input, comments) = many0(comment)(input)?;
+    let (input, comments) = leading_comments(input)?;
     let (input, _) = multispace0(input)?;
     let (input, _) = tag("service")(input)?;
     let (input, _) = space1(input)?;
-    let (input, name) = identifier(input)?;
+    let (input, name) = map(identifier, str::to_string)(input)?;
     let (input, _) = space0(input)?;
     let (input, _) = char('{')(input)?;
     let (input, _) = multispace0(input)?;
@@ -381,7 +919,7 @@ fn service_definition(input: &str) -> IResult<&str, Service> {
     
     for item in body_items {
         match item {
-            ServiceBodyItem::Method(method) => methods.push(method),
+            ServiceBodyItem::Method(method) => methods.push(*method),
             ServiceBodyItem::Option(option) => options.push(option),
         }
     }
@@ -391,49 +929,51 @@ fn service_definition(input: &str) -> IResult<&str, Service> {
         methods,
         options,
         comments,
+        source: None,
     }))
 }
 
 #[derive(Debug, Clone)]
 enum ServiceBodyItem {
-    Method(RpcMethod),
+    Method(Box<RpcMethod>),
     Option(ProtoOption),
 }
 
 /// Parse service body item
 fn service_body_item(input: &str) -> IResult<&str, ServiceBodyItem> {
     alt((
-        map(rpc_method, ServiceBodyItem::Method),
+        map(rpc_method, |method| ServiceBodyItem::Method(Box::new(method))),
         map(option_statement, ServiceBodyItem::Option),
     ))(input)
 }
 
 /// Parse RPC method
 fn rpc_method(input: &str) -> IResult<&str, RpcMethod> {
-    let (input, comments) = many0(comment)(input)?;
+    let (input, comments) = leading_comments(input)?;
     let (input, _) = multispace0(input)?;
     let (input, _) = tag("rpc")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, name) = identifier(input)?;
-    let (input, _) = space0(input)?;
+    let (input, _) = alt((value((), multispace1), value((), comment)))(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
+    let (input, name) = map(identifier, str::to_string)(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
     let (input, _) = char('(')(input)?;
-    let (input, _) = space0(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
     let (input, input_stream) = opt(tag("stream"))(input)?;
-    let (input, _) = space0(input)?;
-    let (input, input_type_name) = type_name(input)?;
-    let (input, _) = space0(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
+    let (input, input_type_name) = full_identifier(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
     let (input, _) = char(')')(input)?;
-    let (input, _) = space0(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
     let (input, _) = tag("returns")(input)?;
-    let (input, _) = space0(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
     let (input, _) = char('(')(input)?;
-    let (input, _) = space0(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
     let (input, output_stream) = opt(tag("stream"))(input)?;
-    let (input, _) = space0(input)?;
-    let (input, output_type_name) = type_name(input)?;
-    let (input, _) = space0(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
+    let (input, output_type_name) = full_identifier(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
     let (input, _) = char(')')(input)?;
-    let (input, _) = space0(input)?;
+    let (input, _) = comments_and_whitespace(input)?;
     
     let (input, method_options) = alt((
         // Method with options block
@@ -447,25 +987,36 @@ fn rpc_method(input: &str) -> IResult<&str, RpcMethod> {
     ))(input)?;
     
     let input_type = TypeReference {
-        name: input_type_name,
-        package: None,
         is_stream: input_stream.is_some(),
+        ..TypeReference::from_qualified_name(input_type_name)
     };
     
     let output_type = TypeReference {
-        name: output_type_name,
-        package: None,
         is_stream: output_stream.is_some(),
+        ..TypeReference::from_qualified_name(output_type_name)
     };
     
     // Extract HTTP annotation from options
     let mut http_annotation = None;
+    let mut idempotency_level = None;
+    let mut method_signature = None;
     let mut options = Vec::new();
     
     for option in method_options {
         if option.name == "google.api.http" || option.name == "(google.api.http)" {
             http_annotation = parse_http_annotation(&option.value);
         } else {
+            if option.name == "idempotency_level" {
+                if let OptionValue::Identifier(level) = &option.value {
+                    idempotency_level = IdempotencyLevel::from_proto_name(level);
+                }
+            }
+            // The first of possibly several signatures is the preferred one
+            if option.name.trim_start_matches('(').trim_end_matches(')') == "google.api.method_signature" {
+                if let (None, OptionValue::String(signature)) = (&method_signature, &option.value) {
+                    method_signature = Some(parse_method_signature(signature));
+                }
+            }
             options.push(option);
         }
     }
@@ -477,9 +1028,20 @@ fn rpc_method(input: &str) -> IResult<&str, RpcMethod> {
         options,
         comments,
         http_annotation,
+        idempotency_level,
+        method_signature: method_signature.unwrap_or_default(),
     }))
 }
 
+/// Split a `google.api.method_signature` value such as `"parent,user"` into field names
+pub(crate) fn parse_method_signature(signature: &str) -> Vec<String> {
+    signature.split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 /// Parse method option
 fn method_option(input: &str) -> IResult<&str, ProtoOption> {
     option_statement(input)
@@ -548,70 +1110,199 @@ fn parse_http_annotation(value: &OptionValue) -> Option<HttpAnnotation> {
 
 /// Parse message definition
 fn message_definition(input: &str) -> IResult<&str, Message> {
-    let (input, comments) = many0(comment)(input)?;
+    let (input, comments) = leading_comments(input)?;
     let (input, _) = multispace0(input)?;
     let (input, _) = tag("message")(input)?;
     let (input, _) = space1(input)?;
-    let (input, name) = identifier(input)?;
+    let (input, name) = map(identifier, str::to_string)(input)?;
     let (input, _) = space0(input)?;
+    let (input, mut message) = message_body(input, name)?;
+    message.comments = comments;
+    
+    Ok((input, message))
+}
+
+/// Parse the `{ ... }` body of a message or group into a message named `name`
+fn message_body(input: &str, name: String) -> IResult<&str, Message> {
     let (input, _) = char('{')(input)?;
     let (input, _) = multispace0(input)?;
     
-    let (input, body_items) = many0(terminated(message_body_item, multispace0))(input)?;
-    
-    let (input, _) = char('}')(input)?;
+    let _guard = NestingGuard::enter(input, &name)?;
     
     let mut fields = Vec::new();
     let mut nested_messages = Vec::new();
     let mut nested_enums = Vec::new();
     let mut options = Vec::new();
+    let mut reserved_ranges = Vec::new();
+    let mut reserved_names = Vec::new();
     
-    for item in body_items {
+    // Sort each item into place as it is parsed instead of collecting them first
+    let mut input = input;
+    loop {
+        let item = match message_body_item(input) {
+            Ok((rest, item)) => {
+                input = multispace0(rest)?.0;
+                item
+            }
+            Err(nom::Err::Error(_)) => break,
+            Err(e) => return Err(e),
+        };
         match item {
             MessageBodyItem::Field(field) => fields.push(field),
+            MessageBodyItem::Group(field, group) => {
+                fields.push(field);
+                nested_messages.push(group);
+            }
             MessageBodyItem::Message(message) => nested_messages.push(message),
             MessageBodyItem::Enum(enum_def) => nested_enums.push(enum_def),
             MessageBodyItem::Option(option) => options.push(option),
+            MessageBodyItem::Reserved(Reserved::Ranges(ranges)) => reserved_ranges.extend(ranges),
+            MessageBodyItem::Reserved(Reserved::Names(names)) => reserved_names.extend(names),
         }
     }
     
+    let (input, _) = char('}')(input)?;
+    
     Ok((input, Message {
         name,
         fields,
         nested_messages,
         nested_enums,
         options,
-        comments,
+        comments: Vec::new(),
+        is_group: false,
+        reserved_ranges,
+        reserved_names,
+        source: None,
     }))
 }
 
+/// Parse a proto2 group, which declares a nested message and a field of that type at once
+///
+/// `optional group Result = 1 { ... }` yields a `Result` message flagged as a group
+/// and a `result` field referencing it.
+fn group_definition(input: &str) -> IResult<&str, (Field, Message)> {
+    let (input, comments) = leading_comments(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, label) = opt(field_label)(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = tag("group")(input)?;
+    let (input, _) = space1(input)?;
+    let (input, name) = map(identifier, str::to_string)(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = char('=')(input)?;
+    let (input, _) = space0(input)?;
+    let (input, number) = field_number(input)?;
+    let (input, _) = space0(input)?;
+    let (input, options) = opt(field_options)(input)?;
+    let (input, _) = space0(input)?;
+    let (input, mut group) = message_body(input, name.clone())?;
+    group.is_group = true;
+    group.comments = comments.clone();
+    
+    let options = options.unwrap_or_default();
+    let field = Field {
+        name: name.to_lowercase(),
+        field_type: FieldType::MessageOrEnum(TypeReference::new(name)),
+        number,
+        label: label.unwrap_or(FieldLabel::Optional),
+        behaviors: FieldBehavior::from_options(&options),
+        options,
+        comments,
+        packed: false,
+    };
+    
+    Ok((input, (field, group)))
+}
+
 #[derive(Debug, Clone)]
 enum MessageBodyItem {
     Field(Field),
+    Group(Field, Message),
     Message(Message),
     Enum(Enum),
     Option(ProtoOption),
+    Reserved(Reserved),
+}
+
+/// Contents of a `reserved` statement, which lists either numbers or names
+#[derive(Debug, Clone)]
+enum Reserved {
+    Ranges(Vec<ReservedRange>),
+    Names(Vec<String>),
 }
 
 /// Parse message body item
+///
+/// The parser matching the leading keyword is tried first; anything else, such as
+/// a field whose type is named like a keyword, falls back to trying each in turn.
 fn message_body_item(input: &str) -> IResult<&str, MessageBodyItem> {
+    let predicted = match statement_keyword(input) {
+        // Every item starts with a keyword or type name
+        "" => return Err(nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Alt))),
+        "reserved" => map(reserved_statement, MessageBodyItem::Reserved)(input),
+        "message" => map(message_definition, MessageBodyItem::Message)(input),
+        "enum" => map(enum_definition, MessageBodyItem::Enum)(input),
+        "option" => map(option_statement, MessageBodyItem::Option)(input),
+        _ => map(field_definition, MessageBodyItem::Field)(input),
+    };
+    if !matches!(predicted, Err(nom::Err::Error(_))) {
+        return predicted;
+    }
+    
     alt((
+        map(reserved_statement, MessageBodyItem::Reserved),
         map(field_definition, MessageBodyItem::Field),
+        map(group_definition, |(field, group)| MessageBodyItem::Group(field, group)),
         map(message_definition, MessageBodyItem::Message),
         map(enum_definition, MessageBodyItem::Enum),
         map(option_statement, MessageBodyItem::Option),
     ))(input)
 }
 
+/// Parse a `reserved` statement: `reserved 2, 9 to 11, 100 to max;` or `reserved "foo", "bar";`
+fn reserved_statement(input: &str) -> IResult<&str, Reserved> {
+    let (input, _) = leading_comments(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, _) = tag("reserved")(input)?;
+    let (input, _) = space1(input)?;
+    let separator = || tuple((space0, char(','), space0));
+    let (input, reserved) = alt((
+        map(separated_list1(separator(), reserved_range), Reserved::Ranges),
+        map(separated_list1(separator(), string_literal), Reserved::Names),
+    ))(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = char(';')(input)?;
+    
+    Ok((input, reserved))
+}
+
+/// Parse a single reserved number or `start to end` range, where `end` may be `max`
+fn reserved_range(input: &str) -> IResult<&str, ReservedRange> {
+    let (input, start) = field_number(input)?;
+    let (input, end) = opt(preceded(
+        tuple((space1, tag("to"), space1)),
+        alt((
+            map(tag("max"), |_| None),
+            map(field_number, Some),
+        )),
+    ))(input)?;
+    
+    Ok((input, ReservedRange {
+        start,
+        end: end.unwrap_or(Some(start)),
+    }))
+}
+
 /// Parse field definition
 fn field_definition(input: &str) -> IResult<&str, Field> {
-    let (input, comments) = many0(comment)(input)?;
+    let (input, comments) = leading_comments(input)?;
     let (input, _) = multispace0(input)?;
     let (input, label) = opt(field_label)(input)?;
     let (input, _) = space0(input)?;
     let (input, field_type) = field_type(input)?;
     let (input, _) = space1(input)?;
-    let (input, name) = identifier(input)?;
+    let (input, name) = map(identifier, str::to_string)(input)?;
     let (input, _) = space0(input)?;
     let (input, _) = char('=')(input)?;
     let (input, _) = space0(input)?;
@@ -621,13 +1312,19 @@ fn field_definition(input: &str) -> IResult<&str, Field> {
     let (input, _) = space0(input)?;
     let (input, _) = char(';')(input)?;
     
+    let options = options.unwrap_or_default();
+    let packed = options.iter()
+        .any(|option| option.name == "packed" && option.value == OptionValue::Boolean(true));
+    
     Ok((input, Field {
         name,
         field_type,
         number,
         label: label.unwrap_or(FieldLabel::Optional),
-        options: options.unwrap_or_default(),
+        behaviors: FieldBehavior::from_options(&options),
+        options,
         comments,
+        packed,
     }))
 }
 
@@ -641,30 +1338,62 @@ fn field_label(input: &str) -> IResult<&str, FieldLabel> {
 }
 
 /// Parse field type
+///
+/// The type name is read once and matched against the scalar types, rather than
+/// trying a tag per scalar type.
 fn field_type(input: &str) -> IResult<&str, FieldType> {
-    alt((
-        value(FieldType::Double, tag("double")),
-        value(FieldType::Float, tag("float")),
-        value(FieldType::Int32, tag("int32")),
-        value(FieldType::Int64, tag("int64")),
-        value(FieldType::Uint32, tag("uint32")),
-        value(FieldType::Uint64, tag("uint64")),
-        value(FieldType::Sint32, tag("sint32")),
-        value(FieldType::Sint64, tag("sint64")),
-        value(FieldType::Fixed32, tag("fixed32")),
-        value(FieldType::Fixed64, tag("fixed64")),
-        value(FieldType::Sfixed32, tag("sfixed32")),
-        value(FieldType::Sfixed64, tag("sfixed64")),
-        value(FieldType::Bool, tag("bool")),
-        value(FieldType::String, tag("string")),
-        value(FieldType::Bytes, tag("bytes")),
-        map(type_name, |name| FieldType::MessageOrEnum(TypeReference::new(name))),
-    ))(input)
+    if input.starts_with("map") {
+        if let Ok(result) = map_field_type(input) {
+            return Ok(result);
+        }
+    }
+    
+    let (rest, name) = full_identifier(input)?;
+    let field_type = match name {
+        "double" => FieldType::Double,
+        "float" => FieldType::Float,
+        "int32" => FieldType::Int32,
+        "int64" => FieldType::Int64,
+        "uint32" => FieldType::Uint32,
+        "uint64" => FieldType::Uint64,
+        "sint32" => FieldType::Sint32,
+        "sint64" => FieldType::Sint64,
+        "fixed32" => FieldType::Fixed32,
+        "fixed64" => FieldType::Fixed64,
+        "sfixed32" => FieldType::Sfixed32,
+        "sfixed64" => FieldType::Sfixed64,
+        "bool" => FieldType::Bool,
+        "string" => FieldType::String,
+        "bytes" => FieldType::Bytes,
+        _ => FieldType::MessageOrEnum(TypeReference::from_qualified_name(name)),
+    };
+    
+    Ok((rest, field_type))
+}
+
+/// Parse map field type (`map<K, V>`)
+fn map_field_type(input: &str) -> IResult<&str, FieldType> {
+    let (input, _) = tag("map")(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = char('<')(input)?;
+    let (input, _) = space0(input)?;
+    let (input, key) = field_type(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = char(',')(input)?;
+    let (input, _) = space0(input)?;
+    let (input, value) = field_type(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = char('>')(input)?;
+    
+    Ok((input, FieldType::Map {
+        key: Box::new(key),
+        value: Box::new(value),
+    }))
 }
 
-/// Parse field number
+/// Parse field number (always unsigned; the valid range is checked during validation)
 fn field_number(input: &str) -> IResult<&str, u32> {
-    map(digit1, |s: &str| s.parse().unwrap_or(0))(input)
+    map_res(digit1, str::parse)(input)
 }
 
 /// Parse field options
@@ -692,15 +1421,16 @@ fn field_option(input: &str) -> IResult<&str, ProtoOption> {
 
 /// Parse enum definition
 fn enum_definition(input: &str) -> IResult<&str, Enum> {
-    let (input, comments) = many0(comment)(input)?;
+    let (input, comments) = leading_comments(input)?;
     let (input, _) = multispace0(input)?;
     let (input, _) = tag("enum")(input)?;
     let (input, _) = space1(input)?;
-    let (input, name) = identifier(input)?;
+    let (input, name) = map(identifier, str::to_string)(input)?;
     let (input, _) = space0(input)?;
     let (input, _) = char('{')(input)?;
     let (input, _) = multispace0(input)?;
     
+    let _guard = NestingGuard::enter(input, &name)?;
     let (input, body_items) = many0(terminated(enum_body_item, multispace0))(input)?;
     
     let (input, _) = char('}')(input)?;
@@ -720,6 +1450,7 @@ fn enum_definition(input: &str) -> IResult<&str, Enum> {
         values,
         options,
         comments,
+        source: None,
     }))
 }
 
@@ -739,9 +1470,9 @@ fn enum_body_item(input: &str) -> IResult<&str, EnumBodyItem> {
 
 /// Parse enum value
 fn enum_value(input: &str) -> IResult<&str, EnumValue> {
-    let (input, comments) = many0(comment)(input)?;
+    let (input, comments) = leading_comments(input)?;
     let (input, _) = multispace0(input)?;
-    let (input, name) = identifier(input)?;
+    let (input, name) = map(identifier, str::to_string)(input)?;
     let (input, _) = space0(input)?;
     let (input, _) = char('=')(input)?;
     let (input, _) = space0(input)?;
@@ -759,12 +1490,9 @@ fn enum_value(input: &str) -> IResult<&str, EnumValue> {
     }))
 }
 
-/// Parse enum number (can be negative)
+/// Parse enum number (can be negative, but not fractional)
 fn enum_number(input: &str) -> IResult<&str, i32> {
-    let (input, sign) = opt(char('-'))(input)?;
-    let (input, digits) = digit1(input)?;
-    let number: i32 = digits.parse().unwrap_or(0);
-    Ok((input, if sign.is_some() { -number } else { number }))
+    map_res(numeric_literal, str::parse)(input)
 }
 
 /// Parse comments
@@ -775,11 +1503,26 @@ fn comment(input: &str) -> IResult<&str, Comment> {
     ))(input)
 }
 
+/// Parse the comments preceding a definition, which may be indented on each line
+///
+/// Most definitions have no comments, so the list is only allocated once one is found.
+fn leading_comments(input: &str) -> IResult<&str, Vec<Comment>> {
+    let mut input = input;
+    let mut comments = Vec::new();
+    while let Ok((rest, comment)) = preceded(multispace0, comment)(input) {
+        comments.push(comment);
+        input = rest;
+    }
+    
+    Ok((input, comments))
+}
+
 /// Parse line comment
 fn line_comment(input: &str) -> IResult<&str, Comment> {
     let (input, _) = tag("//")(input)?;
-    let (input, text) = take_until("\n")(input)?;
-    let (input, _) = line_ending(input)?;
+    let (input, text) = not_line_ending(input)?;
+    // Accept `\n`, `\r\n`, or a final comment with no trailing newline
+    let (input, _) = alt((line_ending, eof))(input)?;
     
     Ok((input, Comment {
         text: text.trim().to_string(),
@@ -794,68 +1537,70 @@ fn block_comment(input: &str) -> IResult<&str, Comment> {
     let (input, _) = tag("*/")(input)?;
     
     Ok((input, Comment {
-        text: text.trim().to_string(),
+        text: text.replace("\r\n", "\n").trim().to_string(),
         comment_type: CommentType::Leading,
     }))
 }
 
-/// Parse identifier
-fn identifier(input: &str) -> IResult<&str, String> {
-    map(
-        recognize(pair(
-            alt((alpha1, tag("_"))),
-            many0(alt((alphanumeric1, tag("_"))))
-        )),
-        |s: &str| s.to_string()
-    )(input)
-}
-
-/// Parse full identifier (with dots)
-fn full_identifier(input: &str) -> IResult<&str, String> {
-    map(
-        recognize(pair(
-            identifier,
-            many0(preceded(char('.'), identifier))
-        )),
-        |s: &str| s.to_string()
-    )(input)
+/// Recognize an identifier, borrowing it from the input
+fn identifier(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        satisfy(|ch| ch.is_ascii_alphabetic() || ch == '_'),
+        take_while(|ch: char| ch.is_ascii_alphanumeric() || ch == '_'),
+    ))(input)
 }
 
-/// Parse type name (can be fully qualified)
-fn type_name(input: &str) -> IResult<&str, String> {
-    full_identifier(input)
+/// Recognize a full identifier (with dots), borrowing it from the input
+fn full_identifier(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        identifier,
+        many0_count(preceded(char('.'), identifier))
+    ))(input)
 }
 
 /// Parse string literal
+///
+/// Runs of plain characters are copied at once; only escapes are decoded one by one.
 fn string_literal(input: &str) -> IResult<&str, String> {
-    delimited(
-        char('"'),
-        map(
-            many0(alt((
-                map(tag("\\\""), |_| '"'),
-                map(tag("\\\\"), |_| '\\'),
-                map(tag("\\n"), |_| '\n'),
-                map(tag("\\r"), |_| '\r'),
-                map(tag("\\t"), |_| '\t'),
-                verify(nom::character::complete::anychar, |c| *c != '"' && *c != '\\'),
-            ))),
-            |chars| chars.into_iter().collect()
-        ),
-        char('"')
-    )(input)
+    let (mut input, _) = char('"')(input)?;
+    let mut literal = String::new();
+    
+    loop {
+        let plain = input.find(['"', '\\']).unwrap_or(input.len());
+        literal.push_str(&input[..plain]);
+        input = &input[plain..];
+        
+        if let Some(rest) = input.strip_prefix('"') {
+            return Ok((rest, literal));
+        }
+        
+        let (rest, escaped) = alt((
+            value('"', tag("\\\"")),
+            value('\\', tag("\\\\")),
+            value('\n', tag("\\n")),
+            value('\r', tag("\\r")),
+            value('\t', tag("\\t")),
+        ))(input)?;
+        literal.push(escaped);
+        input = rest;
+    }
 }
 
 /// Parse number literal
 fn number_literal(input: &str) -> IResult<&str, f64> {
-    map(
-        recognize(tuple((
-            opt(char('-')),
-            digit1,
-            opt(tuple((char('.'), digit1))),
-            opt(tuple((alt((char('e'), char('E'))), opt(alt((char('+'), char('-')))), digit1))),
-        ))),
-        |s: &str| s.parse().unwrap_or(0.0)
-    )(input)
+    map_res(numeric_literal, str::parse)(input)
+}
+
+/// Recognize a signed numeric literal with optional fraction and exponent
+fn numeric_literal(input: &str) -> IResult<&str, &str> {
+    recognize(tuple((
+        opt(alt((char('-'), char('+')))),
+        alt((
+            recognize(pair(digit1, opt(pair(char('.'), digit0)))),
+            recognize(pair(char('.'), digit1)),
+        )),
+        opt(tuple((alt((char('e'), char('E'))), opt(alt((char('+'), char('-')))), digit1))),
+    )))(input)
 }
 
 /// Parse boolean literal
@@ -935,6 +1680,11 @@ message GetUserResponse {
         let result = parser.parse_content(proto3);
         assert!(result.is_ok());
         assert_eq!(result.unwrap().syntax, ProtocolVersion::Proto3);
+        
+        let edition = r#"edition = "2023";"#;
+        let result = parser.parse_content(edition);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().syntax, ProtocolVersion::Edition("2023".to_string()));
     }
 
     #[test]
@@ -1355,4 +2105,707 @@ message ValidationExample {
         assert!(!message.fields[0].options.is_empty());
         assert!(!message.fields[1].options.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_packed_field() {
+        let content = r#"
+syntax = "proto2";
+
+message Samples {
+    repeated int32 x = 1 [packed = true];
+    repeated int32 y = 2 [packed = false];
+    repeated int32 z = 3;
+}
+"#;
+        
+        let proto_file = NomProtoParser::new().parse_content(content).unwrap();
+        let fields = &proto_file.messages[0].fields;
+        
+        assert!(fields[0].packed);
+        assert!(!fields[1].packed);
+        assert!(!fields[2].packed);
+    }
+
+    #[test]
+    fn test_parse_crlf_line_endings() {
+        let content = "syntax = \"proto3\";\r\n\r\n// User service\r\nservice UserService {\r\n    // Get a user\r\n    rpc GetUser(GetUserRequest) returns (User) {\r\n        option (google.api.http) = {\r\n            get: \"/v1/users/{id}\"\r\n        };\r\n    }\r\n}\r\n\r\n/* Request\r\n * message */\r\nmessage GetUserRequest {\r\n    // Identifier\r\n    string id = 1;\r\n}\r\n\r\nmessage User {\r\n    string id = 1;\r\n}\r\n";
+        
+        let parser = NomProtoParser::new();
+        let result = parser.parse_content(content);
+        
+        assert!(result.is_ok(), "Failed to parse CRLF content: {:?}", result.err());
+        let proto_file = result.unwrap();
+        assert_eq!(proto_file.services.len(), 1);
+        assert_eq!(proto_file.messages.len(), 2);
+        
+        let service = &proto_file.services[0];
+        assert_eq!(service.comments[0].text, "User service");
+        assert_eq!(service.methods[0].comments[0].text, "Get a user");
+        assert_eq!(service.methods[0].http_annotation.as_ref().unwrap().path, "/v1/users/{id}");
+        
+        // No carriage returns should leak into comment text
+        let request = &proto_file.messages[0];
+        assert!(request.comments.iter().all(|c| !c.text.contains('\r')));
+        assert_eq!(request.fields[0].comments[0].text, "Identifier");
+    }
+
+    #[test]
+    fn test_keep_source_spans() {
+        let service_source = r#"service UserService {
+  // Fetch a user
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = { get: "/v1/users/{id}" };
+  }
+}"#;
+        let message_source = "message User {\n  string id = 1;\n}";
+        let content = format!(
+            "syntax = \"proto3\";\n\n// The user API\n{}\n\n{}\n",
+            service_source, message_source
+        );
+        
+        let proto_file = NomProtoParser::new().parse_content(&content).unwrap();
+        assert_eq!(proto_file.services[0].source, None);
+        
+        let parser = NomProtoParser::with_config(ParserConfig {
+            keep_source_spans: true,
+            ..ParserConfig::default()
+        });
+        let proto_file = parser.parse_content(&content).unwrap();
+        assert_eq!(proto_file.services[0].source.as_deref(), Some(service_source));
+        assert_eq!(proto_file.services[0].comments.len(), 1);
+        assert_eq!(proto_file.messages[0].source.as_deref(), Some(message_source));
+    }
+    
+    #[test]
+    fn test_parse_large_file() {
+        let mut content = String::from("syntax = \"proto3\";\n\npackage large.v1;\n");
+        for i in 0..1000 {
+            content.push_str(&format!(r#"
+// Service {i} manages items.
+service Service{i} {{
+  // Fetch an item
+  rpc GetItem{i}(GetItem{i}Request) returns (Item{i}) {{
+    option (google.api.http) = {{ get: "/v1/items{i}/{{id}}" }};
+  }}
+}}
+
+/* Request for item {i}. */
+message GetItem{i}Request {{
+  // The item id
+  string id = 1;
+  repeated string tags = 2 [deprecated = true];
+  map<string, int32> counts = 3;
+}}
+
+message Item{i} {{
+  string id = 1;
+  Status status = 2;
+  enum Status {{
+    STATUS_UNSPECIFIED = 0;
+    STATUS_ACTIVE = 1;
+  }}
+}}
+"#));
+        }
+        assert!(content.lines().count() > 25_000);
+        
+        let proto_file = NomProtoParser::new().parse_content(&content).unwrap();
+        assert_eq!(proto_file.services.len(), 1000);
+        assert_eq!(proto_file.messages.len(), 2000);
+        
+        let last = &proto_file.services[999];
+        assert_eq!(last.name, "Service999");
+        assert_eq!(last.comments[0].text, "Service 999 manages items.");
+        let method = &last.methods[0];
+        assert_eq!(method.input_type.name, "GetItem999Request");
+        assert_eq!(method.http_annotation.as_ref().unwrap().path, "/v1/items999/{id}");
+        
+        let request = &proto_file.messages[1998];
+        assert_eq!(request.comments[0].text, "Request for item 999.");
+        assert_eq!(request.fields.len(), 3);
+        assert_eq!(request.fields[0].comments[0].text, "The item id");
+        assert_eq!(request.fields[1].label, FieldLabel::Repeated);
+        assert_eq!(request.fields[1].options[0].name, "deprecated");
+        assert!(matches!(request.fields[2].field_type, FieldType::Map { .. }));
+        
+        let item = &proto_file.messages[1999];
+        assert_eq!(item.fields[1].field_type, FieldType::MessageOrEnum(TypeReference::new("Status".to_string())));
+        assert_eq!(item.nested_enums[0].values.len(), 2);
+    }
+    
+    #[test]
+    fn test_error_recovery() {
+        let content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User);
+}
+
+service BrokenService {
+  rpc GetThing(GetThingRequest) returns {
+    option (google.api.http) = { get: "/v1/things/{id}" };
+  }
+}
+
+service OrderService {
+  rpc GetOrder(GetOrderRequest) returns (Order);
+}
+
+service 42Service;
+
+service ItemService {
+  rpc GetItem(GetItemRequest) returns (Item);
+}
+"#;
+        
+        assert!(NomProtoParser::new().parse_content(content).is_err());
+        
+        let parser = NomProtoParser::with_config(ParserConfig {
+            error_recovery: true,
+            ..ParserConfig::default()
+        });
+        let proto_file = parser.parse_content(content).unwrap();
+        let names: Vec<&str> = proto_file.services.iter().map(|service| service.name.as_str()).collect();
+        assert_eq!(names, vec!["UserService", "OrderService", "ItemService"]);
+        
+        let errors = parser.take_recovered_errors();
+        assert_eq!(errors.len(), 2);
+        let lines: Vec<&str> = errors.iter().map(|error| match error {
+            ParseError::InvalidSyntax { span: Some(span), .. } => &content[span.clone()],
+            other => panic!("unexpected error: {:?}", other),
+        }).collect();
+        assert_eq!(lines, vec!["service BrokenService {", "service 42Service;"]);
+        assert!(parser.take_recovered_errors().is_empty());
+    }
+    
+    #[test]
+    fn test_allow_unknown_statements() {
+        let content = r#"
+edition = "2023";
+
+package example.v1;
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = { get: "/v1/users/{id}" };
+  }
+}
+
+extend_features Future { label: "a;b}" }
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+        
+        assert!(NomProtoParser::new().parse_content(content).is_err());
+        
+        let parser = NomProtoParser::with_config(ParserConfig {
+            allow_unknown_statements: true,
+            ..ParserConfig::default()
+        });
+        let proto_file = parser.parse_content(content).unwrap();
+        assert_eq!(proto_file.package.as_deref(), Some("example.v1"));
+        assert_eq!(proto_file.services.len(), 1);
+        assert_eq!(proto_file.services[0].methods[0].name, "GetUser");
+        assert_eq!(proto_file.messages.len(), 2);
+        assert_eq!(proto_file.syntax, ProtocolVersion::Edition("2023".to_string()));
+        assert_eq!(proto_file.unknown_statements, vec![
+            "extend_features Future { label: \"a;b}\" }".to_string(),
+        ]);
+    }
+    
+    #[test]
+    fn test_unknown_option_policy() {
+        let content = r#"
+syntax = "proto3";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.htttp) = { get: "/v1/users/{id}" };
+  }
+}
+
+message GetUserRequest {
+  string id = 1 [(google.api.field_behavior) = REQUIRED, (my.custom) = true];
+}
+
+message User {
+  string id = 1;
+}
+"#;
+        
+        // Ignored by default
+        let parser = NomProtoParser::new();
+        let proto_file = parser.parse_content(content).unwrap();
+        assert_eq!(parser.unknown_options(&proto_file), vec![UnknownOption {
+            name: "google.api.htttp".to_string(),
+            location: "method UserService.GetUser".to_string(),
+        }]);
+        
+        let parser = NomProtoParser::with_config(ParserConfig {
+            unknown_option_policy: UnknownOptionPolicy::Error,
+            ..ParserConfig::default()
+        });
+        match parser.parse_content(content) {
+            Err(ParseError::UnknownOption { name, location }) => {
+                assert_eq!(name, "google.api.htttp");
+                assert_eq!(location, "method UserService.GetUser");
+            }
+            other => panic!("Expected UnknownOption, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_parse_file_reparses_changed_content() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("service.proto");
+        let parser = NomProtoParser::new();
+
+        std::fs::write(&path, "syntax = \"proto3\";\nmessage User { string id = 1; }\n").unwrap();
+        let first = parser.parse_file(&path).unwrap();
+        assert_eq!(first.messages[0].name, "User");
+
+        // Identical content is served from the cache
+        assert_eq!(parser.parse_file(&path).unwrap(), first);
+
+        std::fs::write(&path, "syntax = \"proto3\";\nmessage Account { string id = 1; }\n").unwrap();
+        let second = parser.parse_file(&path).unwrap();
+        assert_eq!(second.messages.len(), 1);
+        assert_eq!(second.messages[0].name, "Account");
+    }
+
+    #[test]
+    fn test_parse_file_level_aggregate_option() {
+        let parser = NomProtoParser::new();
+        
+        let content = r#"syntax = "proto3";
+
+option (my.file_opt) = {
+    key: "value"
+    nested { x: 1 }
+    tag: "a",
+    tag: "b";
+};
+option java_package = "com.example";
+
+message User {
+    string id = 1;
+}
+"#;
+        let proto_file = parser.parse_content(content).unwrap();
+        
+        let file_opt = proto_file.option("(my.file_opt)").unwrap();
+        assert_eq!(file_opt.get("key"), Some(&OptionValue::String("value".to_string())));
+        assert_eq!(file_opt.get("nested").and_then(|nested| nested.get("x")), Some(&OptionValue::Number(1.0)));
+        let tags: Vec<_> = file_opt.get_all("tag").collect();
+        assert_eq!(tags, vec![&OptionValue::String("a".to_string()), &OptionValue::String("b".to_string())]);
+        
+        // Entries keep their source order
+        match file_opt {
+            OptionValue::MessageLiteral(entries) => {
+                let keys: Vec<_> = entries.iter().map(|(key, _)| key.as_str()).collect();
+                assert_eq!(keys, vec!["key", "nested", "tag", "tag"]);
+            }
+            other => panic!("Expected an aggregate value, got {:?}", other),
+        }
+        
+        assert_eq!(proto_file.option("java_package"), Some(&OptionValue::String("com.example".to_string())));
+        assert!(proto_file.option("missing").is_none());
+    }
+
+    #[test]
+    fn test_parse_aggregate_option_separators() {
+        let parser = NomProtoParser::new();
+        
+        let content = r#"syntax = "proto3";
+
+option (my.trailing_comma) = { a: 1, b: "x", };
+option (my.semicolons) = { a: 1; b: "x"; };
+option (my.compact) = {a: 1,b: "x",};
+option (my.unspaced) = {a:1;b:"x"};
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{id}",
+            additional_bindings { get: "/v1/people/{id}"; },
+        };
+    }
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+        let proto_file = parser.parse_content(content).unwrap();
+        
+        let expected = OptionValue::MessageLiteral(vec![
+            ("a".to_string(), OptionValue::Number(1.0)),
+            ("b".to_string(), OptionValue::String("x".to_string())),
+        ]);
+        for name in ["my.trailing_comma", "my.semicolons", "my.compact", "my.unspaced"] {
+            assert_eq!(proto_file.option(name), Some(&expected), "Unexpected value of {}", name);
+        }
+        
+        let http = proto_file.services[0].methods[0].http_annotation.as_ref().unwrap();
+        assert_eq!(http.method, HttpMethod::Get);
+        assert_eq!(http.path, "/v1/users/{id}");
+    }
+
+    #[test]
+    fn test_parse_error_span() {
+        let parser = NomProtoParser::new();
+        
+        let content = "syntax = \"proto3\";\n\nmessage User {\n    string id = 1;\n}\n\nmesage Broken {}\n";
+        let start = content.find("mesage").unwrap();
+        match parser.parse_content(content) {
+            Err(ParseError::InvalidSyntax { span, .. }) => {
+                assert_eq!(span, Some(start..start + "mesage Broken {}".len()));
+            }
+            other => panic!("Expected a syntax error, got {:?}", other),
+        }
+        
+        // Offsets refer to the original source, including a byte order mark
+        let with_bom = format!("\u{feff}{}", content);
+        match parser.parse_content(&with_bom) {
+            Err(ParseError::InvalidSyntax { span, .. }) => {
+                assert_eq!(span.unwrap().start, start + '\u{feff}'.len_utf8());
+            }
+            other => panic!("Expected a syntax error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_indented_multiline_field_comments() {
+        let parser = NomProtoParser::new();
+        
+        let content = "syntax = \"proto3\";\n\nmessage User {\n    // Only accepted on signup\n    // @writeonly\n    string password = 1;\n}\n";
+        let proto_file = parser.parse_content(content).unwrap();
+        
+        let comments = &proto_file.messages[0].fields[0].comments;
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].text, "Only accepted on signup");
+        assert_eq!(comments[1].text, "@writeonly");
+    }
+
+    #[test]
+    fn test_parse_reserved_statements() {
+        let parser = NomProtoParser::new();
+        
+        let content = r#"
+syntax = "proto3";
+
+message User {
+    reserved 2, 15, 9 to 11;
+    reserved 100 to max;
+    reserved "foo", "bar";
+    string id = 1;
+}
+"#;
+        let proto_file = parser.parse_content(content).unwrap();
+        let message = &proto_file.messages[0];
+        
+        assert_eq!(message.fields.len(), 1);
+        assert_eq!(message.reserved_ranges, vec![
+            ReservedRange { start: 2, end: Some(2) },
+            ReservedRange { start: 15, end: Some(15) },
+            ReservedRange { start: 9, end: Some(11) },
+            ReservedRange { start: 100, end: None },
+        ]);
+        assert_eq!(message.reserved_names, vec!["foo".to_string(), "bar".to_string()]);
+        
+        assert_eq!(message.reserved_range_for(150).map(ToString::to_string), Some("100 to max".to_string()));
+        assert_eq!(message.reserved_range_for(10).map(ToString::to_string), Some("9 to 11".to_string()));
+        assert!(message.reserved_range_for(12).is_none());
+    }
+
+    #[test]
+    fn test_parse_comment_at_eof_without_newline() {
+        let parser = NomProtoParser::new();
+        
+        let content = "syntax = \"proto3\";\n\nmessage User {\n    string id = 1;\n}\n// end of file";
+        let result = parser.parse_content(content);
+        assert!(result.is_ok(), "Failed to parse trailing comment: {:?}", result.err());
+        assert_eq!(result.unwrap().messages.len(), 1);
+        
+        let content = "syntax = \"proto3\";\r\n\r\nmessage User {\r\n    string id = 1;\r\n}\r\n// end of file";
+        let result = parser.parse_content(content);
+        assert!(result.is_ok(), "Failed to parse trailing CRLF comment: {:?}", result.err());
+        assert_eq!(result.unwrap().messages.len(), 1);
+        
+        // A file consisting only of a comment
+        let result = parser.parse_content("// only a comment");
+        assert!(result.is_ok(), "Failed to parse comment-only file: {:?}", result.err());
+    }
+
+    #[test]
+    fn test_parse_package_qualified_type_references() {
+        let parser = NomProtoParser::new();
+        
+        let content = r#"
+            syntax = "proto3";
+            
+            service BazService {
+                rpc GetBaz(foo.bar.GetBazRequest) returns (foo.bar.Baz);
+                rpc Ping(PingRequest) returns (google.protobuf.Empty);
+            }
+            
+            message Holder {
+                foo.bar.Baz baz = 1;
+                Local local = 2;
+            }
+        "#;
+        
+        let result = parser.parse_content(content);
+        assert!(result.is_ok(), "Failed to parse qualified types: {:?}", result.err());
+        let proto_file = result.unwrap();
+        
+        let get_baz = &proto_file.services[0].methods[0];
+        assert_eq!(get_baz.output_type.name, "Baz");
+        assert_eq!(get_baz.output_type.package, Some("foo.bar".to_string()));
+        assert_eq!(get_baz.output_type.fully_qualified_name(), "foo.bar.Baz");
+        assert_eq!(get_baz.input_type.name, "GetBazRequest");
+        assert_eq!(get_baz.input_type.package, Some("foo.bar".to_string()));
+        
+        let ping = &proto_file.services[0].methods[1];
+        assert_eq!(ping.output_type.name, "Empty");
+        assert_eq!(ping.output_type.package, Some("google.protobuf".to_string()));
+        assert!(ping.output_type.is_well_known_type());
+        assert_eq!(ping.input_type.package, None);
+        
+        let fields = &proto_file.messages[0].fields;
+        assert_eq!(fields[0].field_type, FieldType::MessageOrEnum(TypeReference::with_package("Baz".to_string(), "foo.bar".to_string())));
+        assert_eq!(fields[1].field_type, FieldType::MessageOrEnum(TypeReference::new("Local".to_string())));
+    }
+    
+    #[test]
+    fn test_parse_multiline_rpc_with_interleaved_comments() {
+        let parser = NomProtoParser::new();
+        
+        let content = r#"
+            syntax = "proto3";
+            
+            service UserService {
+                rpc GetUser(
+                    // The lookup request
+                    GetUserRequest
+                )
+                    returns /* the resolved user */
+                    // on its own line
+                    (User) {
+                    option (google.api.http) = {
+                        get: "/users/{id}"
+                    };
+                }
+                rpc /* streaming */ WatchUsers(WatchUsersRequest)
+                    returns (stream User);
+            }
+        "#;
+        
+        let result = parser.parse_content(content);
+        assert!(result.is_ok(), "Failed to parse multi-line RPC: {:?}", result.err());
+        
+        let methods = &result.unwrap().services[0].methods;
+        assert_eq!(methods.len(), 2);
+        assert_eq!(methods[0].name, "GetUser");
+        assert_eq!(methods[0].input_type.name, "GetUserRequest");
+        assert_eq!(methods[0].output_type.name, "User");
+        assert!(methods[0].http_annotation.is_some());
+        assert_eq!(methods[1].name, "WatchUsers");
+        assert!(methods[1].output_type.is_stream);
+    }
+    
+    #[test]
+    fn test_parse_idempotency_level() {
+        let parser = NomProtoParser::new();
+        
+        let content = r#"
+            syntax = "proto3";
+            
+            service UserService {
+                rpc GetUser(GetUserRequest) returns (User) {
+                    option idempotency_level = IDEMPOTENT;
+                    option (google.api.http) = {
+                        get: "/users/{id}"
+                    };
+                }
+                rpc ListUsers(ListUsersRequest) returns (ListUsersResponse) {
+                    option idempotency_level = NO_SIDE_EFFECTS;
+                }
+                rpc DeleteUser(DeleteUserRequest) returns (Empty);
+            }
+        "#;
+        
+        let result = parser.parse_content(content);
+        assert!(result.is_ok(), "Failed to parse idempotency level: {:?}", result.err());
+        
+        let methods = &result.unwrap().services[0].methods;
+        assert_eq!(methods[0].idempotency_level, Some(IdempotencyLevel::Idempotent));
+        assert!(methods[0].http_annotation.is_some());
+        assert_eq!(methods[1].idempotency_level, Some(IdempotencyLevel::NoSideEffects));
+        assert_eq!(methods[2].idempotency_level, None);
+    }
+
+    #[test]
+    fn test_parse_proto2_group() {
+        let parser = NomProtoParser::new();
+        
+        let content = r#"
+syntax = "proto2";
+
+message SearchResponse {
+    repeated group Result = 1 {
+        required string url = 2;
+        optional string title = 3;
+    }
+    optional int32 total = 4;
+}
+"#;
+        
+        let result = parser.parse_content(content);
+        assert!(result.is_ok(), "Failed to parse group: {:?}", result.err());
+        
+        let proto_file = result.unwrap();
+        let message = &proto_file.messages[0];
+        assert!(!message.is_group);
+        assert_eq!(message.fields.len(), 2);
+        
+        let field = &message.fields[0];
+        assert_eq!(field.name, "result");
+        assert_eq!(field.number, 1);
+        assert_eq!(field.label, FieldLabel::Repeated);
+        assert_eq!(field.field_type, FieldType::MessageOrEnum(TypeReference::new("Result".to_string())));
+        
+        let group = &message.nested_messages[0];
+        assert_eq!(group.name, "Result");
+        assert!(group.is_group);
+        assert_eq!(group.fields.len(), 2);
+        assert_eq!(group.fields[0].label, FieldLabel::Required);
+    }
+    
+    #[test]
+    fn test_parse_method_signature() {
+        let parser = NomProtoParser::new();
+        
+        let content = r#"
+            syntax = "proto3";
+            
+            service UserService {
+                rpc CreateUser(CreateUserRequest) returns (User) {
+                    option (google.api.method_signature) = "parent,user";
+                    option (google.api.method_signature) = "user";
+                }
+                rpc GetUser(GetUserRequest) returns (User);
+            }
+        "#;
+        
+        let result = parser.parse_content(content);
+        assert!(result.is_ok(), "Failed to parse method signature: {:?}", result.err());
+        
+        let methods = &result.unwrap().services[0].methods;
+        assert_eq!(methods[0].method_signature, vec!["parent".to_string(), "user".to_string()]);
+        assert!(methods[1].method_signature.is_empty());
+    }
+
+    #[test]
+    fn test_parse_with_byte_order_mark() {
+        let parser = NomProtoParser::new();
+        
+        let content = "syntax = \"proto3\";\n\npackage example.v1;\n\nmessage User {\n    string id = 1;\n}\n";
+        let expected = parser.parse_content(content).unwrap();
+        
+        let result = parser.parse_content(&format!("\u{feff}{}", content));
+        assert!(result.is_ok(), "Failed to parse content with BOM: {:?}", result.err());
+        assert_eq!(result.unwrap(), expected);
+        
+        let result = parser.parse_content(&format!("\u{feff}  \n\t{}", content));
+        assert!(result.is_ok(), "Failed to parse content with BOM and leading whitespace: {:?}", result.err());
+        assert_eq!(result.unwrap(), expected);
+    }
+    
+    #[test]
+    fn test_parse_signed_and_fractional_numbers() {
+        assert_eq!(number_literal("-1.5;"), Ok((";", -1.5)));
+        assert_eq!(number_literal("+2e3;"), Ok((";", 2000.0)));
+        assert_eq!(number_literal(".25;"), Ok((";", 0.25)));
+        assert_eq!(number_literal("3.;"), Ok((";", 3.0)));
+        
+        assert_eq!(enum_number("-2;"), Ok((";", -2)));
+        assert_eq!(enum_number("+7;"), Ok((";", 7)));
+        assert!(enum_number("1.5;").is_err());
+        assert!(enum_number("99999999999;").is_err());
+        
+        assert_eq!(field_number("42;"), Ok((";", 42)));
+        assert!(field_number("-1;").is_err());
+        assert!(field_number("99999999999;").is_err());
+        
+        let content = r#"
+syntax = "proto3";
+
+message Limits {
+    double min = 1 [(bounds.min) = -0.5, (bounds.max) = 1e-3];
+}
+
+enum Level {
+    LEVEL_UNSPECIFIED = 0;
+    LEVEL_NEGATIVE = -1;
+}
+"#;
+        let proto_file = NomProtoParser::new().parse_content(content).unwrap();
+        let options = &proto_file.messages[0].fields[0].options;
+        assert_eq!(options[0].value, OptionValue::Number(-0.5));
+        assert_eq!(options[1].value, OptionValue::Number(0.001));
+        assert_eq!(proto_file.enums[0].values[1].number, -1);
+    }
+    
+    #[test]
+    fn test_parse_nesting_depth_limit() {
+        let content = r#"
+syntax = "proto3";
+
+message Outer {
+    message Middle {
+        message Inner {
+            enum Kind {
+                KIND_UNSPECIFIED = 0;
+            }
+            string id = 1;
+        }
+    }
+}
+"#;
+        
+        let parser = NomProtoParser::with_config(ParserConfig {
+            max_nesting_depth: 4,
+            ..ParserConfig::default()
+        });
+        assert!(parser.parse_content(content).is_ok());
+        
+        let parser = NomProtoParser::with_config(ParserConfig {
+            max_nesting_depth: 3,
+            ..ParserConfig::default()
+        });
+        match parser.parse_content(content) {
+            Err(ParseError::NestingDepthExceeded { name, max_depth }) => {
+                assert_eq!(name, "Kind");
+                assert_eq!(max_depth, 3);
+            }
+            other => panic!("Expected NestingDepthExceeded, got {:?}", other),
+        }
+        
+        // The limit is reset between parses
+        assert!(parser.parse_content("syntax = \"proto3\";\n\nmessage User {\n    string id = 1;\n}\n").is_ok());
+    }
+}
@@ -5,7 +5,6 @@
 //! code generators, template engines, and other extensions.
 
 use crate::core::*;
-use crate::errors::*;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
@@ -40,6 +39,34 @@ pub trait Plugin: Send + Sync {
         let _ = library_version;
         true
     }
+    
+    /// Expose the proto validator extension point, if implemented
+    ///
+    /// Used by [`PluginManager::register`] to wire a type-erased plugin into
+    /// its extension points; implementors return `Some(self)`.
+    fn into_proto_validator(self: Arc<Self>) -> Option<Arc<dyn ProtoValidatorPlugin>> {
+        None
+    }
+    
+    /// Expose the HTTP validator extension point, if implemented
+    fn into_http_validator(self: Arc<Self>) -> Option<Arc<dyn HttpValidatorPlugin>> {
+        None
+    }
+    
+    /// Expose the code generator extension point, if implemented
+    fn into_code_generator(self: Arc<Self>) -> Option<Arc<dyn CodeGeneratorPlugin>> {
+        None
+    }
+    
+    /// Expose the code formatter extension point, if implemented
+    fn into_code_formatter(self: Arc<Self>) -> Option<Arc<dyn CodeFormatterPlugin>> {
+        None
+    }
+    
+    /// Expose the error reporter extension point, if implemented
+    fn into_error_reporter(self: Arc<Self>) -> Option<Arc<dyn ErrorReporterPlugin>> {
+        None
+    }
 }
 
 /// Plugin capabilities that define what extension points a plugin supports
@@ -84,6 +111,20 @@ impl Default for PluginConfig {
     }
 }
 
+impl PluginConfig {
+    /// Deserialize the setting `key` into `T`
+    /// 
+    /// Returns `Ok(None)` when the setting is absent and a configuration error
+    /// naming the key when its value does not match `T`.
+    pub fn get_typed<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<Option<T>, PluginError> {
+        self.settings.get(key)
+            .map(|value| T::deserialize(value).map_err(|e| PluginError::ConfigurationError {
+                message: format!("Invalid value {} for setting '{}': {}", value, key, e),
+            }))
+            .transpose()
+    }
+}
+
 
 
 /// Extension point for proto file validation
@@ -152,6 +193,7 @@ pub struct PluginManager {
     validators: Vec<Arc<dyn ProtoValidatorPlugin>>,
     http_validators: Vec<Arc<dyn HttpValidatorPlugin>>,
     code_generators: Vec<Arc<dyn CodeGeneratorPlugin>>,
+    #[allow(dead_code)]
     template_engines: Vec<Arc<dyn TemplateEnginePlugin>>,
     code_formatters: Vec<Arc<dyn CodeFormatterPlugin>>,
     error_reporters: Vec<Arc<dyn ErrorReporterPlugin>>,
@@ -303,6 +345,47 @@ impl PluginManager {
         Ok(())
     }
     
+    /// Register a type-erased plugin instance with a default configuration
+    ///
+    /// The plugin is wired into every extension point it exposes through the
+    /// `into_*` methods of [`Plugin`].
+    pub fn register(&mut self, mut plugin: Box<dyn Plugin>) -> Result<(), PluginError> {
+        let name = plugin.name().to_string();
+        
+        if !plugin.is_compatible(crate::VERSION) {
+            return Err(PluginError::Incompatible {
+                name: name.clone(),
+                required: "compatible version".to_string(),
+                found: crate::VERSION.to_string(),
+            });
+        }
+        
+        let config = PluginConfig::default();
+        plugin.initialize(&config)?;
+        let plugin_arc: Arc<dyn Plugin> = Arc::from(plugin);
+        
+        if let Some(validator) = plugin_arc.clone().into_proto_validator() {
+            self.validators.push(validator);
+        }
+        if let Some(validator) = plugin_arc.clone().into_http_validator() {
+            self.http_validators.push(validator);
+        }
+        if let Some(generator) = plugin_arc.clone().into_code_generator() {
+            self.code_generators.push(generator);
+        }
+        if let Some(formatter) = plugin_arc.clone().into_code_formatter() {
+            self.code_formatters.push(formatter);
+        }
+        if let Some(reporter) = plugin_arc.clone().into_error_reporter() {
+            self.error_reporters.push(reporter);
+        }
+        
+        self.plugins.insert(name.clone(), plugin_arc);
+        self.configs.insert(name, config);
+        
+        Ok(())
+    }
+    
     /// Get a plugin by name
     pub fn get_plugin(&self, name: &str) -> Option<Arc<dyn Plugin>> {
         self.plugins.get(name).cloned()
@@ -539,6 +622,17 @@ mod tests {
         assert_eq!(manager.plugins().len(), 0);
     }
     
+    #[test]
+    fn test_register_proto_validator() {
+        let mut manager = PluginManager::new();
+        let plugin = MockValidatorPlugin { name: "mock".to_string() };
+        
+        manager.register_proto_validator(plugin, PluginConfig::default()).unwrap();
+        
+        assert!(manager.get_plugin("mock").is_some());
+        assert!(manager.validate_proto_file(&ProtoFile::new()).unwrap().is_empty());
+    }
+    
     #[test]
     fn test_plugin_config_default() {
         let config = PluginConfig::default();
@@ -69,7 +69,7 @@ impl PluginConfigLoader {
                     
                     // Try to parse as JSON value, fallback to string
                     let json_value = serde_json::from_str(&value)
-                        .unwrap_or_else(|_| serde_json::Value::String(value));
+                        .unwrap_or(serde_json::Value::String(value));
                     
                     config.settings.insert(setting_name, json_value);
                 }
@@ -240,6 +240,7 @@ impl Default for PluginConfigBuilder {
 
 /// Example plugin configuration file structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Default)]
 pub struct PluginConfigFile {
     /// Plugin configurations
     pub plugins: HashMap<String, PluginConfig>,
@@ -271,14 +272,6 @@ impl Default for GlobalPluginSettings {
     }
 }
 
-impl Default for PluginConfigFile {
-    fn default() -> Self {
-        Self {
-            plugins: HashMap::new(),
-            global: GlobalPluginSettings::default(),
-        }
-    }
-}
 
 #[cfg(test)]
 mod tests {
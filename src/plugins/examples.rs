@@ -4,8 +4,6 @@
 //! to demonstrate how to extend the proto-http-parser-v2 library.
 
 use super::*;
-use crate::core::*;
-use crate::errors::*;
 use std::collections::HashSet;
 
 /// Example custom validator plugin that enforces naming conventions
@@ -54,6 +52,12 @@ impl NamingConventionValidator {
     }
 }
 
+impl Default for NamingConventionValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Plugin for NamingConventionValidator {
     fn name(&self) -> &str {
         &self.name
@@ -102,6 +106,10 @@ impl Plugin for NamingConventionValidator {
     fn capabilities(&self) -> Vec<PluginCapability> {
         vec![PluginCapability::ProtoValidator]
     }
+    
+    fn into_proto_validator(self: Arc<Self>) -> Option<Arc<dyn ProtoValidatorPlugin>> {
+        Some(self)
+    }
 }
 
 impl ProtoValidatorPlugin for NamingConventionValidator {
@@ -174,6 +182,12 @@ impl RestApiValidator {
     }
 }
 
+impl Default for RestApiValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Plugin for RestApiValidator {
     fn name(&self) -> &str {
         &self.name
@@ -188,16 +202,16 @@ impl Plugin for RestApiValidator {
     }
     
     fn initialize(&mut self, config: &PluginConfig) -> Result<(), PluginError> {
-        if let Some(require_resource) = config.settings.get("require_resource_paths") {
-            self.require_resource_paths = require_resource.as_bool().unwrap_or(true);
+        if let Some(require_resource) = config.get_typed("require_resource_paths")? {
+            self.require_resource_paths = require_resource;
         }
         
-        if let Some(allow_nested) = config.settings.get("allow_nested_resources") {
-            self.allow_nested_resources = allow_nested.as_bool().unwrap_or(true);
+        if let Some(allow_nested) = config.get_typed("allow_nested_resources")? {
+            self.allow_nested_resources = allow_nested;
         }
         
-        if let Some(max_depth) = config.settings.get("max_path_depth") {
-            self.max_path_depth = max_depth.as_u64().unwrap_or(4) as usize;
+        if let Some(max_depth) = config.get_typed("max_path_depth")? {
+            self.max_path_depth = max_depth;
         }
         
         Ok(())
@@ -206,6 +220,10 @@ impl Plugin for RestApiValidator {
     fn capabilities(&self) -> Vec<PluginCapability> {
         vec![PluginCapability::HttpValidator]
     }
+    
+    fn into_http_validator(self: Arc<Self>) -> Option<Arc<dyn HttpValidatorPlugin>> {
+        Some(self)
+    }
 }
 
 impl HttpValidatorPlugin for RestApiValidator {
@@ -300,6 +318,12 @@ impl CustomCodeFormatter {
     }
 }
 
+impl Default for CustomCodeFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Plugin for CustomCodeFormatter {
     fn name(&self) -> &str {
         &self.name
@@ -314,16 +338,16 @@ impl Plugin for CustomCodeFormatter {
     }
     
     fn initialize(&mut self, config: &PluginConfig) -> Result<(), PluginError> {
-        if let Some(indent_size) = config.settings.get("indent_size") {
-            self.indent_size = indent_size.as_u64().unwrap_or(4) as usize;
+        if let Some(indent_size) = config.get_typed("indent_size")? {
+            self.indent_size = indent_size;
         }
         
-        if let Some(use_tabs) = config.settings.get("use_tabs") {
-            self.use_tabs = use_tabs.as_bool().unwrap_or(false);
+        if let Some(use_tabs) = config.get_typed("use_tabs")? {
+            self.use_tabs = use_tabs;
         }
         
-        if let Some(max_line_length) = config.settings.get("max_line_length") {
-            self.max_line_length = max_line_length.as_u64().unwrap_or(100) as usize;
+        if let Some(max_line_length) = config.get_typed("max_line_length")? {
+            self.max_line_length = max_line_length;
         }
         
         Ok(())
@@ -332,6 +356,10 @@ impl Plugin for CustomCodeFormatter {
     fn capabilities(&self) -> Vec<PluginCapability> {
         vec![PluginCapability::CodeFormatter]
     }
+    
+    fn into_code_formatter(self: Arc<Self>) -> Option<Arc<dyn CodeFormatterPlugin>> {
+        Some(self)
+    }
 }
 
 impl CodeFormatterPlugin for CustomCodeFormatter {
@@ -413,6 +441,12 @@ impl DocumentationGenerator {
     }
 }
 
+impl Default for DocumentationGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Plugin for DocumentationGenerator {
     fn name(&self) -> &str {
         &self.name
@@ -443,6 +477,10 @@ impl Plugin for DocumentationGenerator {
     fn capabilities(&self) -> Vec<PluginCapability> {
         vec![PluginCapability::CodeGenerator]
     }
+    
+    fn into_code_generator(self: Arc<Self>) -> Option<Arc<dyn CodeGeneratorPlugin>> {
+        Some(self)
+    }
 }
 
 impl CodeGeneratorPlugin for DocumentationGenerator {
@@ -1,5 +1,7 @@
 //! Utility functions
 
+use std::collections::HashMap;
+
 /// Convert string to snake_case
 pub fn to_snake_case(input: &str) -> String {
     let mut result = String::new();
@@ -32,6 +34,35 @@ pub fn to_snake_case(input: &str) -> String {
     result
 }
 
+/// Convert string to snake_case with `convert`, pinning the words listed in `overrides`
+///
+/// An exact match of `input` yields its override as-is. Otherwise the longest override
+/// key found in `input` is replaced by its value and the text on either side is
+/// converted separately, so `{"gRPC": "grpc"}` turns `gRPCService` into `grpc_service`.
+pub fn to_snake_case_with_overrides(input: &str, overrides: &HashMap<String, String>, convert: fn(&str) -> String) -> String {
+    if let Some(pinned) = overrides.get(input) {
+        return pinned.clone();
+    }
+    
+    let longest_match = overrides.iter()
+        .filter(|(word, _)| !word.is_empty())
+        .filter_map(|(word, pinned)| input.find(word.as_str()).map(|start| (start, word, pinned)))
+        .max_by_key(|(_, word, _)| word.len());
+    
+    match longest_match {
+        Some((start, word, pinned)) => {
+            let before = to_snake_case_with_overrides(&input[..start], overrides, convert);
+            let after = to_snake_case_with_overrides(&input[start + word.len()..], overrides, convert);
+            [before.as_str(), pinned.as_str(), after.as_str()].iter()
+                .map(|part| part.trim_matches('_'))
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("_")
+        }
+        None => convert(input),
+    }
+}
+
 /// Convert string to camelCase
 pub fn to_camel_case(input: &str) -> String {
     let mut result = String::new();
@@ -89,6 +120,11 @@ pub fn sanitize_identifier(name: &str) -> String {
         "macro", "override", "priv", "typeof", "unsized", "virtual", "yield", "try"
     ];
     
+    // Keywords that cannot be raw identifiers get a trailing underscore instead
+    if matches!(name, "self" | "Self" | "super" | "crate") {
+        return format!("{}_", name);
+    }
+    
     if rust_keywords.contains(&name) || !is_valid_rust_identifier(name) {
         format!("r#{}", name)
     } else {
@@ -107,6 +143,20 @@ mod tests {
         assert_eq!(to_snake_case("getUserById"), "get_user_by_id");
     }
     
+    #[test]
+    fn test_to_snake_case_with_overrides() {
+        let overrides = HashMap::from([
+            ("gRPC".to_string(), "grpc".to_string()),
+            ("IOPS".to_string(), "iops".to_string()),
+            ("gRPCService".to_string(), "grpc_svc".to_string()),
+        ]);
+        
+        assert_eq!(to_snake_case_with_overrides("gRPCService", &overrides, to_snake_case), "grpc_svc");
+        assert_eq!(to_snake_case_with_overrides("gRPCGateway", &overrides, to_snake_case), "grpc_gateway");
+        assert_eq!(to_snake_case_with_overrides("MaxIOPSLimit", &overrides, to_snake_case), "max_iops_limit");
+        assert_eq!(to_snake_case_with_overrides("HelloWorld", &overrides, to_snake_case), "hello_world");
+    }
+    
     #[test]
     fn test_to_camel_case() {
         assert_eq!(to_camel_case("hello_world"), "helloWorld");
@@ -132,6 +182,7 @@ mod tests {
     fn test_sanitize_identifier() {
         assert_eq!(sanitize_identifier("hello"), "hello");
         assert_eq!(sanitize_identifier("type"), "r#type");
+        assert_eq!(sanitize_identifier("self"), "self_");
         assert_eq!(sanitize_identifier("123invalid"), "r#123invalid");
         assert_eq!(sanitize_identifier("valid_name"), "valid_name");
     }
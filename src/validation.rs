@@ -9,19 +9,49 @@
 use crate::core::{
     data::*,
     errors::*,
-    Validator, ValidationReport, ValidationSuggestion,
+    Validator, ValidationReport, ValidationSuggestion, ValidationWarning, WarningCategory,
 };
 use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
 use std::path::PathBuf;
 
+/// Largest field number allowed by protobuf (2^29 - 1)
+const MAX_FIELD_NUMBER: u32 = 536_870_911;
+
+/// Field numbers reserved for the protobuf implementation
+const RESERVED_FIELD_NUMBERS: RangeInclusive<u32> = 19_000..=19_999;
+
+/// Marker of the warning about a POST/PUT/PATCH method missing its `body`, matched
+/// when generating suggestions
+const MISSING_BODY_WARNING: &str = "has no request body";
+
+/// Legal values of the enum-typed standard method options, by option name
+const METHOD_ENUM_OPTIONS: &[(&str, &[&str])] = &[
+    ("idempotency_level", &["IDEMPOTENCY_UNKNOWN", "NO_SIDE_EFFECTS", "IDEMPOTENT"]),
+];
+
+/// Legal values of the enum-typed standard field options, by option name
+const FIELD_ENUM_OPTIONS: &[(&str, &[&str])] = &[
+    ("ctype", &["STRING", "CORD", "STRING_PIECE"]),
+    ("jstype", &["JS_NORMAL", "JS_STRING", "JS_NUMBER"]),
+    ("google.api.field_behavior", &[
+        "FIELD_BEHAVIOR_UNSPECIFIED", "OPTIONAL", "REQUIRED", "OUTPUT_ONLY", "INPUT_ONLY",
+        "IMMUTABLE", "UNORDERED_LIST", "NON_EMPTY_DEFAULT", "IDENTIFIER",
+    ]),
+];
+
 /// Validation engine for Protocol Buffer files and HTTP annotations
 pub struct ValidationEngine {
     /// Configuration for validation behavior
     config: ValidationConfig,
     /// Collected errors during validation
     errors: Vec<ValidationError>,
+    /// Collected non-fatal warnings during validation
+    warnings: Vec<ValidationWarning>,
     /// Type registry for reference validation
     type_registry: TypeRegistry,
+    /// Imported files whose types are registered under their own package
+    imported_files: Vec<ProtoFile>,
 }
 
 /// Configuration for the validation engine
@@ -39,6 +69,14 @@ pub struct ValidationConfig {
     pub max_errors: usize,
     /// Whether to generate suggestions for common errors
     pub generate_suggestions: bool,
+    /// Whether to promote collected warnings into errors
+    pub warnings_as_errors: bool,
+    /// Whether to warn about methods without an HTTP annotation in services that expose
+    /// other methods over HTTP, unless they are marked as excluded
+    pub require_http_annotations: bool,
+    /// Fully-qualified types defined outside the validated files, e.g. those given an
+    /// external type mapping, which references may use without an import defining them
+    pub external_types: HashSet<String>,
 }
 
 impl Default for ValidationConfig {
@@ -50,6 +88,9 @@ impl Default for ValidationConfig {
             validate_field_references: true,
             max_errors: 50,
             generate_suggestions: true,
+            warnings_as_errors: false,
+            require_http_annotations: false,
+            external_types: HashSet::new(),
         }
     }
 }
@@ -111,6 +152,8 @@ pub struct ValidationResult {
     pub is_valid: bool,
     /// Collected validation errors
     pub errors: Vec<ValidationError>,
+    /// Collected non-fatal warnings
+    pub warnings: Vec<ValidationWarning>,
     /// Generated suggestions for fixing errors
     pub suggestions: Vec<ErrorSuggestion>,
     /// Type registry after validation
@@ -141,17 +184,43 @@ impl ValidationEngine {
         Self {
             config,
             errors: Vec::new(),
+            warnings: Vec::new(),
             type_registry: TypeRegistry::default(),
+            imported_files: Vec::new(),
         }
     }
 
+    /// Get the configuration of this engine
+    pub fn config(&self) -> &ValidationConfig {
+        &self.config
+    }
+
+    /// Validate a proto file merged with the definitions of its imports
+    ///
+    /// Types of the imported files are also registered under their own package,
+    /// so package-qualified references such as `common.v1.Region` resolve.
+    pub fn validate_proto_file_with_imports(&self, proto_file: &ProtoFile, imported_files: &[ProtoFile]) -> Result<ValidationReport, ValidationError> {
+        let mut engine = self.clone();
+        engine.imported_files = imported_files.to_vec();
+        engine.validate_proto_file(proto_file)
+    }
+
     /// Validate a Protocol Buffer file
     pub fn validate_proto_file_internal(&mut self, proto_file: &ProtoFile) -> ValidationResult {
         self.errors.clear();
+        self.warnings.clear();
         self.type_registry = TypeRegistry::default();
 
         // Build type registry first
         self.build_type_registry(proto_file);
+        for imported_file in self.imported_files.clone() {
+            for message in &imported_file.messages {
+                self.register_message(message, &imported_file.package);
+            }
+            for enum_def in &imported_file.enums {
+                self.register_enum(enum_def, &imported_file.package);
+            }
+        }
 
         // Validate syntax and structure
         self.validate_syntax(proto_file);
@@ -163,7 +232,12 @@ impl ValidationEngine {
 
         // Validate services and methods
         for service in &proto_file.services {
-            self.validate_service(service);
+            self.validate_service(service, proto_file);
+        }
+
+        // Check `@example` directives on messages
+        for message in &proto_file.messages {
+            self.check_message_examples(message);
         }
 
         // Check for unused imports
@@ -171,6 +245,10 @@ impl ValidationEngine {
             self.check_unused_imports(proto_file);
         }
 
+        if self.config.warnings_as_errors {
+            self.promote_warnings();
+        }
+
         // Generate suggestions
         let suggestions = if self.config.generate_suggestions {
             self.generate_suggestions()
@@ -181,6 +259,7 @@ impl ValidationEngine {
         ValidationResult {
             is_valid: self.errors.is_empty(),
             errors: self.errors.clone(),
+            warnings: self.warnings.clone(),
             suggestions,
             type_registry: std::mem::take(&mut self.type_registry),
         }
@@ -189,6 +268,7 @@ impl ValidationEngine {
     /// Validate HTTP routes for consistency
     pub fn validate_http_routes_internal(&mut self, routes: &[HttpRoute]) -> ValidationResult {
         self.errors.clear();
+        self.warnings.clear();
 
         // Check for route conflicts
         self.check_route_conflicts(routes);
@@ -198,6 +278,10 @@ impl ValidationEngine {
             self.validate_http_route(route);
         }
 
+        if self.config.warnings_as_errors {
+            self.promote_warnings();
+        }
+
         // Generate suggestions
         let suggestions = if self.config.generate_suggestions {
             self.generate_suggestions()
@@ -208,6 +292,7 @@ impl ValidationEngine {
         ValidationResult {
             is_valid: self.errors.is_empty(),
             errors: self.errors.clone(),
+            warnings: self.warnings.clone(),
             suggestions,
             type_registry: std::mem::take(&mut self.type_registry),
         }
@@ -347,6 +432,18 @@ impl ValidationEngine {
 
     /// Validate syntax and basic structure
     fn validate_syntax(&mut self, proto_file: &ProtoFile) {
+        self.check_security_option(&proto_file.options, "file");
+
+        // Statements skipped by a lenient parse may change the meaning of the file
+        for statement in &proto_file.unknown_statements {
+            let first_line = statement.lines().next().unwrap_or_default();
+            self.warnings.push(ValidationWarning {
+                message: format!("Skipped unrecognized statement `{}`", first_line.trim()),
+                location: None,
+                category: WarningCategory::Style,
+            });
+        }
+
         // Check for duplicate service names
         let mut service_names = HashSet::new();
         for service in &proto_file.services {
@@ -379,6 +476,118 @@ impl ValidationEngine {
                 });
             }
         }
+
+        // Field numbers must be in protobuf's valid range
+        for message in &proto_file.messages {
+            self.validate_field_numbers(message, &message.name);
+            self.validate_field_behaviors(message, &message.name);
+            self.validate_field_option_values(message, &message.name);
+        }
+
+        // proto3 and editions enums are open, so they must start with a zero value
+        if matches!(proto_file.syntax, ProtocolVersion::Proto3 | ProtocolVersion::Edition(_)) {
+            for enum_def in &proto_file.enums {
+                self.validate_proto3_enum(enum_def, &enum_def.name);
+            }
+            for message in &proto_file.messages {
+                self.validate_proto3_nested_enums(message, &message.name);
+            }
+        }
+    }
+
+    /// Check field numbers of a message (and its nested messages) against the valid range
+    fn validate_field_numbers(&mut self, message: &Message, full_name: &str) {
+        for field in &message.fields {
+            let reason = match field.number {
+                0 => Some("field numbers start at 1".to_string()),
+                number if number > MAX_FIELD_NUMBER => Some("field numbers must not exceed 536870911".to_string()),
+                number if RESERVED_FIELD_NUMBERS.contains(&number) => {
+                    Some("19000 through 19999 are reserved for the protobuf implementation".to_string())
+                }
+                number => message.reserved_range_for(number)
+                    .map(|range| format!("the number is reserved by `reserved {}`", range)),
+            };
+
+            if let Some(reason) = reason {
+                self.add_error(ValidationError::InvalidFieldNumber {
+                    message_name: full_name.to_string(),
+                    field_name: field.name.clone(),
+                    number: field.number,
+                    reason,
+                });
+            }
+        }
+        for nested in &message.nested_messages {
+            self.validate_field_numbers(nested, &format!("{}.{}", full_name, nested.name));
+        }
+    }
+
+    /// Check that fields a client must set can be set by a client
+    ///
+    /// A `REQUIRED` field marked `OUTPUT_ONLY` can never be supplied in a request.
+    fn validate_field_behaviors(&mut self, message: &Message, full_name: &str) {
+        for field in &message.fields {
+            if field.behaviors.contains(&FieldBehavior::Required) && field.behaviors.contains(&FieldBehavior::OutputOnly) {
+                self.warnings.push(ValidationWarning {
+                    message: format!(
+                        "Field {}.{} is both REQUIRED and OUTPUT_ONLY; clients cannot set it",
+                        full_name, field.name
+                    ),
+                    location: None,
+                    category: WarningCategory::Style,
+                });
+            }
+        }
+        for nested in &message.nested_messages {
+            self.validate_field_behaviors(nested, &format!("{}.{}", full_name, nested.name));
+        }
+    }
+
+    /// Check enum-typed standard options of the fields of a message (and its nested messages)
+    fn validate_field_option_values(&mut self, message: &Message, full_name: &str) {
+        for field in &message.fields {
+            self.check_enum_options(&field.options, FIELD_ENUM_OPTIONS, || format!("field {}.{}", full_name, field.name));
+        }
+        for nested in &message.nested_messages {
+            self.validate_field_option_values(nested, &format!("{}.{}", full_name, nested.name));
+        }
+    }
+
+    /// Validate enums nested in a message (recursively) against proto3 rules
+    fn validate_proto3_nested_enums(&mut self, message: &Message, scope: &str) {
+        for enum_def in &message.nested_enums {
+            self.validate_proto3_enum(enum_def, &format!("{}.{}", scope, enum_def.name));
+        }
+        for nested in &message.nested_messages {
+            self.validate_proto3_nested_enums(nested, &format!("{}.{}", scope, nested.name));
+        }
+    }
+
+    /// Check that a proto3 enum starts with a `0` value named `*_UNSPECIFIED` or `*_UNKNOWN`
+    fn validate_proto3_enum(&mut self, enum_def: &Enum, full_name: &str) {
+        let first = match enum_def.values.first() {
+            Some(first) => first,
+            None => return,
+        };
+
+        if first.number != 0 {
+            self.add_error(ValidationError::InvalidEnum {
+                enum_name: full_name.to_string(),
+                message: format!(
+                    "first value '{}' is {}, but proto3 enums must start with 0",
+                    first.name, first.number
+                ),
+            });
+        } else if !first.name.ends_with("_UNSPECIFIED") && !first.name.ends_with("_UNKNOWN") {
+            self.warnings.push(ValidationWarning {
+                message: format!(
+                    "Zero value '{}' of enum {} should be named with an _UNSPECIFIED or _UNKNOWN suffix",
+                    first.name, full_name
+                ),
+                location: None,
+                category: WarningCategory::Style,
+            });
+        }
     }
 
     /// Validate type references (internal implementation)
@@ -403,20 +612,23 @@ impl ValidationEngine {
 
     /// Validate a single type reference
     fn validate_type_reference(&mut self, type_ref: &TypeReference, _line: Option<usize>) {
+        let type_name = type_ref.fully_qualified_name();
+        
         // Mark as used
-        self.type_registry.used_types.insert(type_ref.name.clone());
+        self.type_registry.used_types.insert(type_name.clone());
         
         // Check if type exists
-        if !self.is_type_defined(&type_ref.name) && !self.is_builtin_type(&type_ref.name) {
+        if !self.is_type_defined(&type_name) && !self.is_builtin_type(&type_name) {
             self.add_error(ValidationError::UndefinedType {
-                type_name: type_ref.name.clone(),
+                type_name,
                 line: 0, // TODO: Add line tracking to data structures
             });
         }
     }
 
-    /// Check if a type is defined in the registry
+    /// Check if a type is defined in the registry or declared external
     fn is_type_defined(&self, type_name: &str) -> bool {
+        self.config.external_types.contains(type_name) ||
         self.type_registry.types.contains_key(type_name) ||
         self.type_registry.types.keys().any(|key| key.ends_with(&format!(".{}", type_name)))
     }
@@ -430,11 +642,13 @@ impl ValidationEngine {
             "google.protobuf.Duration" | "google.protobuf.Any" |
             "google.protobuf.Empty" | "google.protobuf.Struct" |
             "google.protobuf.Value" | "google.protobuf.ListValue"
-        )
+        ) || wrapped_scalar(type_name).is_some()
     }
 
     /// Validate a service definition
-    fn validate_service(&mut self, service: &Service) {
+    fn validate_service(&mut self, service: &Service, proto_file: &ProtoFile) {
+        self.check_security_option(&service.options, &format!("service {}", service.name));
+
         // Check for duplicate method names
         let mut method_names = HashSet::new();
         for method in &service.methods {
@@ -448,8 +662,70 @@ impl ValidationEngine {
             // Validate HTTP annotations if present
             if let Some(http_annotation) = &method.http_annotation {
                 self.validate_http_annotation(http_annotation, method);
+                if self.config.validate_http_methods {
+                    self.check_missing_request_body(http_annotation, service, method, proto_file);
+                }
+            }
+
+            // Unusable status codes fall back to 200 at generation time
+            if let Some(Err(status)) = method.status_code() {
+                self.warnings.push(ValidationWarning {
+                    message: format!(
+                        "Ignoring status '{}' on method {}.{}: expected a 2xx status code",
+                        status, service.name, method.name
+                    ),
+                    location: None,
+                    category: WarningCategory::Style,
+                });
+            }
+
+            self.check_security_option(&method.options, &format!("method {}.{}", service.name, method.name));
+            self.check_enum_options(&method.options, METHOD_ENUM_OPTIONS, || format!("method {}.{}", service.name, method.name));
+
+            // Unparseable rate limits are left off the route
+            if let Some(Err(rate_limit)) = method.rate_limit() {
+                self.warnings.push(ValidationWarning {
+                    message: format!(
+                        "Ignoring rate limit '{}' on method {}.{}: expected '<requests>/<period>', e.g. '100/min'",
+                        rate_limit, service.name, method.name
+                    ),
+                    location: None,
+                    category: WarningCategory::Style,
+                });
             }
         }
+
+        if self.config.require_http_annotations {
+            self.check_unannotated_methods(service);
+        }
+    }
+
+    /// Warn about methods left off the HTTP surface of a service without being excluded
+    fn check_unannotated_methods(&mut self, service: &Service) {
+        let is_annotated = |method: &RpcMethod| method.http_annotation.is_some()
+            || method.options.iter().any(|option| option.name.trim_start_matches('(').trim_end_matches(')') == "google.api.http");
+        if !service.methods.iter().any(is_annotated) {
+            return;
+        }
+
+        let unannotated: Vec<&str> = service.methods.iter()
+            .filter(|method| !is_annotated(method) && !method.is_http_excluded())
+            .map(|method| method.name.as_str())
+            .collect();
+        if !unannotated.is_empty() {
+            self.warnings.push(ValidationWarning {
+                message: format!(
+                    "Service {} exposes methods over HTTP, but {} {} no HTTP annotation; annotate {} or mark {} with `@no_http`",
+                    service.name,
+                    unannotated.join(", "),
+                    if unannotated.len() == 1 { "has" } else { "have" },
+                    if unannotated.len() == 1 { "it" } else { "them" },
+                    if unannotated.len() == 1 { "it" } else { "them" },
+                ),
+                location: None,
+                category: WarningCategory::Style,
+            });
+        }
     }
 
     /// Validate HTTP annotation
@@ -488,11 +764,11 @@ impl ValidationEngine {
             });
         }
 
-        // Validate parameter syntax
+        // Validate parameter syntax and the characters of literal segments
         let mut brace_count = 0;
-        let chars = path.chars().peekable();
+        let chars: Vec<char> = path.chars().collect();
         
-        for ch in chars {
+        for (index, &ch) in chars.iter().enumerate() {
             match ch {
                 '{' => {
                     brace_count += 1;
@@ -512,6 +788,29 @@ impl ValidationEngine {
                     }
                     brace_count -= 1;
                 }
+                '%' if brace_count == 0 => {
+                    let is_escape = chars.get(index + 1..index + 3)
+                        .is_some_and(|digits| digits.iter().all(char::is_ascii_hexdigit));
+                    if !is_escape {
+                        return Err(ValidationError::InvalidHttpAnnotation {
+                            message: format!(
+                                "Invalid percent-encoding at position {} in path template '{}'",
+                                index, path
+                            ),
+                            line: 0,
+                        });
+                    }
+                }
+                _ if brace_count == 0 && !is_path_literal_char(ch) => {
+                    let shown = if ch.is_whitespace() { format!("{:?}", ch) } else { format!("'{}'", ch) };
+                    return Err(ValidationError::InvalidHttpAnnotation {
+                        message: format!(
+                            "Path template '{}' contains {} at position {}, which is not allowed in a URL path; percent-encode it",
+                            path, shown, index
+                        ),
+                        line: 0,
+                    });
+                }
                 _ => {}
             }
         }
@@ -545,32 +844,106 @@ impl ValidationEngine {
     /// Validate HTTP method compatibility
     fn validate_http_method_compatibility(&mut self, method: &HttpMethod, body: &Option<String>, line: Option<usize>) {
         match method {
-            HttpMethod::Get | HttpMethod::Delete => {
-                if body.is_some() && body.as_ref().unwrap() != "*" {
+            HttpMethod::Get | HttpMethod::Delete
+                if body.is_some() && body.as_ref().unwrap() != "*" => {
                     self.add_error(ValidationError::InvalidHttpAnnotation {
                         message: format!("{:?} methods should not have request body", method),
                         line: line.unwrap_or(0),
                     });
                 }
-            }
             _ => {} // Other methods can have bodies
         }
     }
 
+    /// Warn about a `security` option naming an unknown scheme, which protects nothing
+    fn check_security_option(&mut self, options: &[ProtoOption], location: &str) {
+        if let Some(Err(scheme)) = SecurityScheme::from_options(options) {
+            self.warnings.push(ValidationWarning {
+                message: format!(
+                    "Ignoring security scheme '{}' on {}: expected 'bearer', 'basic' or 'api_key'",
+                    scheme, location
+                ),
+                location: None,
+                category: WarningCategory::Style,
+            });
+        }
+    }
+
+    /// Report identifiers that are not legal values of the enum-typed options in `schema`
+    ///
+    /// Options outside `schema` have no known type and are not checked.
+    fn check_enum_options(&mut self, options: &[ProtoOption], schema: &[(&str, &'static [&'static str])], location: impl Fn() -> String) {
+        for option in options {
+            let OptionValue::Identifier(value) = &option.value else {
+                continue;
+            };
+            let name = option.name.trim_start_matches('(').trim_end_matches(')');
+            if enum_option_values(schema, name).is_some_and(|values| !values.contains(&value.as_str())) {
+                self.add_error(ValidationError::InvalidOptionValue {
+                    option: name.to_string(),
+                    value: value.clone(),
+                    location: location(),
+                });
+            }
+        }
+    }
+
+    /// Warn about POST/PUT/PATCH methods without a body whose request message has
+    /// fields not bound by the path, which then silently become query parameters
+    fn check_missing_request_body(&mut self, annotation: &HttpAnnotation, service: &Service, method: &RpcMethod, proto_file: &ProtoFile) {
+        if annotation.body.is_some() || !matches!(annotation.method, HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch) {
+            return;
+        }
+        let request = proto_file.find_message(&method.input_type.name)
+            .or_else(|| self.imported_files.iter().find_map(|imported| imported.find_message(&method.input_type.name)));
+        let Some(request) = request else {
+            return;
+        };
+        
+        let segments = PathSegment::parse(&annotation.path);
+        let path_fields: HashSet<&str> = segments.iter()
+            .filter_map(|segment| match segment {
+                PathSegment::Param { name, .. } => name.split('.').next(),
+                PathSegment::Literal(_) => None,
+            })
+            .collect();
+        let unbound_fields: Vec<&str> = request.fields.iter()
+            .map(|field| field.name.as_str())
+            .filter(|name| !path_fields.contains(name))
+            .collect();
+        if unbound_fields.is_empty() {
+            return;
+        }
+        
+        self.warnings.push(ValidationWarning {
+            message: format!(
+                "{} method {}.{} {}: fields of {} outside the path ({}) are only accepted as query parameters",
+                annotation.method.as_str(),
+                service.name,
+                method.name,
+                MISSING_BODY_WARNING,
+                request.name,
+                unbound_fields.join(", ")
+            ),
+            location: None,
+            category: WarningCategory::Style,
+        });
+    }
+
     /// Check for conflicting HTTP routes
     fn check_route_conflicts(&mut self, routes: &[HttpRoute]) {
-        let mut route_signatures = HashMap::new();
+        let mut route_signatures: HashMap<String, String> = HashMap::new();
         
         for route in routes {
-            let signature = format!("{} {}", route.http_method, route.path_template);
+            let signature = route.conflict_signature();
             
-            if let Some(_existing) = route_signatures.get(&signature) {
+            if let Some(existing) = route_signatures.get(&signature) {
                 self.add_error(ValidationError::ConflictingRoutes {
-                    route1: signature.clone(),
-                    route2: format!("{}.{}", route.service_name, route.method_name),
+                    route1: existing.clone(),
+                    route2: route.describe(),
                 });
             } else {
-                route_signatures.insert(signature, format!("{}.{}", route.service_name, route.method_name));
+                route_signatures.insert(signature, route.describe());
             }
         }
     }
@@ -593,21 +966,72 @@ impl ValidationEngine {
         }
     }
 
+    /// Warn about `@example` directives (including on nested messages) that are not valid JSON
+    fn check_message_examples(&mut self, message: &Message) {
+        if let Some(example) = message.example_directive() {
+            if let Err(e) = serde_json::from_str::<serde_json::Value>(example) {
+                self.warnings.push(ValidationWarning {
+                    message: format!("Ignoring malformed @example on message {}: {}", message.name, e),
+                    location: None,
+                    category: WarningCategory::Style,
+                });
+            }
+        }
+
+        for nested in &message.nested_messages {
+            self.check_message_examples(nested);
+        }
+    }
+
     /// Check for unused imports
     fn check_unused_imports(&mut self, proto_file: &ProtoFile) {
+        let uses_http_annotations = proto_file.services.iter()
+            .flat_map(|service| &service.methods)
+            .any(|method| method.http_annotation.is_some()
+                || method.options.iter().any(|option| option.name.contains("google.api")));
+
+        let mut referenced_types: HashSet<String> = self.type_registry.used_types.clone();
+        for message in &proto_file.messages {
+            collect_field_types(message, &mut referenced_types);
+        }
+
         for import in &proto_file.imports {
-            // Simple heuristic: check if any used type starts with the import path
-            let import_prefix = import.path.replace(".proto", "").replace("/", ".");
-            let is_used = self.type_registry.used_types.iter()
-                .any(|used_type| used_type.starts_with(&import_prefix));
+            // Only google/ imports follow the directory-is-package convention, so
+            // other imports cannot be attributed to the types they define
+            if !import.path.starts_with("google/") {
+                continue;
+            }
+
+            let is_used = if import.path.starts_with("google/api/") {
+                uses_http_annotations
+            } else {
+                let package_prefix = match import.path.rsplit_once('/') {
+                    Some((dir, _)) => format!("{}.", dir.replace('/', ".")),
+                    None => continue,
+                };
+                referenced_types.iter()
+                    .any(|referenced| referenced.starts_with(&package_prefix))
+            };
             
             if !is_used {
-                // This is more of a warning than an error, but we'll track it
-                // TODO: Add warning system
+                self.warnings.push(ValidationWarning {
+                    message: format!("Import '{}' is never used", import.path),
+                    location: None,
+                    category: WarningCategory::Unused,
+                });
             }
         }
     }
 
+    /// Move collected warnings into the error list
+    fn promote_warnings(&mut self) {
+        for warning in std::mem::take(&mut self.warnings) {
+            self.add_error(ValidationError::PromotedWarning {
+                message: warning.message,
+            });
+        }
+    }
+
     /// Generate suggestions for fixing errors
     fn generate_suggestions(&self) -> Vec<ErrorSuggestion> {
         let mut suggestions = Vec::new();
@@ -625,8 +1049,8 @@ impl ValidationEngine {
                         });
                     }
                 }
-                ValidationError::InvalidHttpAnnotation { message, .. } => {
-                    if message.contains("must start with '/'") {
+                ValidationError::InvalidHttpAnnotation { message, .. }
+                    if message.contains("must start with '/'") => {
                         suggestions.push(ErrorSuggestion {
                             error_type: "InvalidHttpAnnotation".to_string(),
                             message: "Add a leading '/' to the path template".to_string(),
@@ -634,11 +1058,38 @@ impl ValidationEngine {
                             confidence: 0.9,
                         });
                     }
+                ValidationError::InvalidHttpAnnotation { message, .. }
+                    if message.contains("should not have request body") => {
+                        suggestions.push(ErrorSuggestion {
+                            error_type: "InvalidHttpAnnotation".to_string(),
+                            message: "Remove the `body` from the HTTP annotation and pass the fields in the path or query string".to_string(),
+                            suggested_fix: None,
+                            confidence: 0.8,
+                        });
+                    }
+                ValidationError::InvalidOptionValue { option, value, .. } => {
+                    if let Some(suggestion) = suggest_enum_option_value(option, value) {
+                        suggestions.push(ErrorSuggestion {
+                            error_type: "InvalidOptionValue".to_string(),
+                            message: format!("Did you mean '{}'?", suggestion),
+                            suggested_fix: Some(suggestion.to_string()),
+                            confidence: 0.8,
+                        });
+                    }
+                }
+                ValidationError::PromotedWarning { message } if message.contains(MISSING_BODY_WARNING) => {
+                    suggestions.push(missing_body_suggestion());
                 }
                 _ => {}
             }
         }
 
+        for warning in &self.warnings {
+            if warning.message.contains(MISSING_BODY_WARNING) {
+                suggestions.push(missing_body_suggestion());
+            }
+        }
+
         suggestions
     }
 
@@ -678,6 +1129,51 @@ impl ValidationEngine {
     }
 }
 
+/// Legal values of the option `name` in `schema`, if it is an enum-typed option listed there
+fn enum_option_values(schema: &[(&str, &'static [&'static str])], name: &str) -> Option<&'static [&'static str]> {
+    schema.iter()
+        .find(|(option, _)| *option == name)
+        .map(|(_, values)| *values)
+}
+
+/// Closest legal value of a known enum-typed option to a misspelled `value`
+fn suggest_enum_option_value(option: &str, value: &str) -> Option<&'static str> {
+    enum_option_values(METHOD_ENUM_OPTIONS, option)
+        .or_else(|| enum_option_values(FIELD_ENUM_OPTIONS, option))?
+        .iter()
+        .map(|candidate| (levenshtein_distance(value, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Suggestion for a POST/PUT/PATCH method missing its `body`
+fn missing_body_suggestion() -> ErrorSuggestion {
+    ErrorSuggestion {
+        error_type: "MissingRequestBody".to_string(),
+        message: "Add `body: \"*\"` to the HTTP annotation to accept the remaining fields in the request body".to_string(),
+        suggested_fix: Some("body: \"*\"".to_string()),
+        confidence: 0.7,
+    }
+}
+
+impl ProtoFile {
+    /// Run the full validation pipeline on this file with a fresh [`ValidationEngine`]
+    ///
+    /// Builds the type registry, then checks syntax, type references and services,
+    /// as [`Validator::validate_proto_file`] does.
+    pub fn validate(&self, config: &ValidationConfig) -> ValidationReport {
+        match ValidationEngine::with_config(config.clone()).validate_proto_file(self) {
+            Ok(report) => report,
+            Err(error) => ValidationReport {
+                is_valid: false,
+                errors: vec![error],
+                ..ValidationReport::new()
+            },
+        }
+    }
+}
+
 impl Default for ValidationEngine {
     fn default() -> Self {
         Self::new()
@@ -694,7 +1190,7 @@ impl Validator for ValidationEngine {
         Ok(ValidationReport {
             is_valid: result.is_valid,
             errors: result.errors,
-            warnings: Vec::new(), // TODO: Implement warnings
+            warnings: result.warnings,
             suggestions: result.suggestions.into_iter().map(|s| ValidationSuggestion {
                 issue_type: s.error_type,
                 message: s.message,
@@ -712,7 +1208,7 @@ impl Validator for ValidationEngine {
         Ok(ValidationReport {
             is_valid: result.is_valid,
             errors: result.errors,
-            warnings: Vec::new(), // TODO: Implement warnings
+            warnings: result.warnings,
             suggestions: result.suggestions.into_iter().map(|s| ValidationSuggestion {
                 issue_type: s.error_type,
                 message: s.message,
@@ -728,10 +1224,10 @@ impl Validator for ValidationEngine {
         engine.build_type_registry(proto_file);
         engine.validate_type_references_internal(proto_file);
         
-        if engine.errors.is_empty() {
-            Ok(())
-        } else {
-            Err(engine.errors.into_iter().next().unwrap())
+        match engine.errors.len() {
+            0 => Ok(()),
+            1 => Err(engine.errors.swap_remove(0)),
+            _ => Err(ValidationError::Multiple(engine.errors)),
         }
     }
 }
@@ -742,8 +1238,35 @@ impl Clone for ValidationEngine {
         Self {
             config: self.config.clone(),
             errors: Vec::new(), // Start with empty errors for new instance
+            warnings: Vec::new(),
             type_registry: TypeRegistry::default(), // Start with empty registry
+            imported_files: self.imported_files.clone(),
+        }
+    }
+}
+
+/// Check whether a character may appear unescaped in a literal path segment
+///
+/// Allows RFC 3986 `pchar` characters and `/`; `%` escapes are checked separately.
+fn is_path_literal_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || "/-._~!$&'()*+,;=:@".contains(ch)
+}
+
+/// Collect the fully-qualified names of message and enum types referenced by a
+/// message's fields, including map values and nested messages
+fn collect_field_types(message: &Message, types: &mut HashSet<String>) {
+    for field in &message.fields {
+        let mut field_type = &field.field_type;
+        while let FieldType::Map { value, .. } = field_type {
+            field_type = value;
         }
+        if let FieldType::MessageOrEnum(type_ref) = field_type {
+            types.insert(type_ref.fully_qualified_name());
+        }
+    }
+
+    for nested in &message.nested_messages {
+        collect_field_types(nested, types);
     }
 }
 
@@ -762,11 +1285,11 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
     let mut matrix = vec![vec![0; len2 + 1]; len1 + 1];
 
     // Initialize first row and column
-    for i in 0..=len1 {
-        matrix[i][0] = i;
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
     }
-    for j in 0..=len2 {
-        matrix[0][j] = j;
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j;
     }
 
     let s1_chars: Vec<char> = s1.chars().collect();
@@ -826,5 +1349,17 @@ mod tests {
         assert!(engine.validate_path_template("/users/{id").is_err());
         assert!(engine.validate_path_template("/users/id}").is_err());
         assert!(engine.validate_path_template("/users/{{id}}").is_err());
+        
+        // Literal segments must be URL-safe; parameters are exempt
+        assert!(engine.validate_path_template("/v1/{name=shelves/*}:archive").is_ok());
+        assert!(engine.validate_path_template("/v1/files/**").is_ok());
+        assert!(engine.validate_path_template("/v1/user%20s/{id}").is_ok());
+        assert!(engine.validate_path_template("/v1/users/{user id}").is_ok());
+        assert!(engine.validate_path_template("/v1/user%2/{id}").is_err());
+        assert!(engine.validate_path_template("/v1/users?page=1").is_err());
+        assert!(engine.validate_path_template("/v1/usérs").is_err());
+        
+        let error = engine.validate_path_template("/v1/user s/{id}").unwrap_err();
+        assert!(error.to_string().contains("' '"), "Unexpected message: {}", error);
     }
 }
\ No newline at end of file
@@ -6,6 +6,62 @@
 use proto_http_parser::*;
 use proptest::prelude::*;
 
+// Helper functions for tests
+
+/// Convert string to snake_case
+fn to_snake_case(s: &str) -> String {
+    let mut result = String::new();
+    let mut prev_was_upper = false;
+    
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 && !prev_was_upper {
+                result.push('_');
+            }
+            result.push(c.to_lowercase().next().unwrap());
+            prev_was_upper = true;
+        } else {
+            result.push(c);
+            prev_was_upper = false;
+        }
+    }
+    
+    result
+}
+
+/// Convert string to PascalCase
+fn to_pascal_case(s: &str) -> String {
+    // If the string contains underscores, split on them
+    if s.contains('_') {
+        let words: Vec<&str> = s.split('_').collect();
+        words.iter()
+            .map(|word| capitalize_first(word))
+            .collect::<String>()
+    } else {
+        // If no underscores, assume it's already camelCase or PascalCase
+        // Just ensure the first letter is capitalized while preserving the rest
+        capitalize_first_preserve_case(s)
+    }
+}
+
+/// Capitalize first letter of a string
+fn capitalize_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
+    }
+}
+
+/// Capitalize first letter while preserving the rest of the string case
+fn capitalize_first_preserve_case(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+    }
+}
+
 /// Test that generated code has correct syntax and compiles
 #[cfg(test)]
 mod code_generation_property_tests {
@@ -242,6 +298,7 @@ mod code_generation_property_tests {
                     methods,
                     options: vec![],
                     comments: vec![],
+                    source: None,
                 }
             })
     }
@@ -257,6 +314,8 @@ mod code_generation_property_tests {
                     options: vec![],
                     comments: vec![],
                     http_annotation: None,
+                    idempotency_level: None,
+                    method_signature: vec![],
                 }
             })
     }
@@ -264,7 +323,7 @@ mod code_generation_property_tests {
     /// Generate valid type references
     fn valid_type_reference_generator() -> impl Strategy<Value = TypeReference> {
         "[a-zA-Z][a-zA-Z0-9_]*"
-            .prop_map(|name| TypeReference::new(name))
+            .prop_map(TypeReference::new)
     }
 
     /// Generate valid HTTP routes
@@ -293,6 +352,12 @@ mod code_generation_property_tests {
                 request_body: None,
                 input_type: TypeReference::new("Empty".to_string()),
                 response_type,
+                idempotency_level: None,
+                is_paginated: false,
+                deprecation: None,
+                segments: Vec::new(),
+                rate_limit: None,
+                security: None,
             }
         })
     }
@@ -370,59 +435,3 @@ mod code_generation_property_tests {
         Ok(())
     }
 }
-
-// Helper functions for tests
-
-/// Convert string to snake_case
-fn to_snake_case(s: &str) -> String {
-    let mut result = String::new();
-    let mut prev_was_upper = false;
-    
-    for (i, c) in s.chars().enumerate() {
-        if c.is_uppercase() {
-            if i > 0 && !prev_was_upper {
-                result.push('_');
-            }
-            result.push(c.to_lowercase().next().unwrap());
-            prev_was_upper = true;
-        } else {
-            result.push(c);
-            prev_was_upper = false;
-        }
-    }
-    
-    result
-}
-
-/// Convert string to PascalCase
-fn to_pascal_case(s: &str) -> String {
-    // If the string contains underscores, split on them
-    if s.contains('_') {
-        let words: Vec<&str> = s.split('_').collect();
-        words.iter()
-            .map(|word| capitalize_first(word))
-            .collect::<String>()
-    } else {
-        // If no underscores, assume it's already camelCase or PascalCase
-        // Just ensure the first letter is capitalized while preserving the rest
-        capitalize_first_preserve_case(s)
-    }
-}
-
-/// Capitalize first letter of a string
-fn capitalize_first(s: &str) -> String {
-    let mut chars = s.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
-    }
-}
-
-/// Capitalize first letter while preserving the rest of the string case
-fn capitalize_first_preserve_case(s: &str) -> String {
-    let mut chars = s.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-    }
-}
\ No newline at end of file
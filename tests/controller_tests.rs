@@ -365,7 +365,7 @@ mod controller_unit_tests {
         let controller_code = result.unwrap();
         
         // Should include required imports
-        assert!(controller_code.imports.contains(&"poem_openapi::{OpenApi, payload::Json, param::Path, param::Query}".to_string()), 
+        assert!(controller_code.imports.contains(&"poem_openapi::payload::Json".to_string()), 
                 "Should include poem_openapi imports");
         
         assert!(controller_code.imports.contains(&"std::sync::Arc".to_string()), 
@@ -378,4 +378,348 @@ mod controller_unit_tests {
         assert!(controller_code.dependencies.contains(&"poem".to_string()), 
                 "Should include poem dependency");
     }
-}
\ No newline at end of file
+
+    /// Test that generated controllers suppress lints via an inner allow attribute
+    #[test]
+    fn test_controller_lint_allows() {
+        let service = Service::new("TestService".to_string());
+        let routes = Vec::new();
+        
+        // Default configuration allows common lints
+        let generator = PoemOpenApiGenerator::new();
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(controller_code.content.starts_with("#![allow(unused_imports, unused_variables)]"),
+                "Should start with the default allow attribute");
+        
+        // Additional lints are appended to the attribute
+        let config = ConfigBuilder::new()
+            .add_lint_allow("clippy::too_many_arguments")
+            .build_unchecked();
+        let generator = PoemOpenApiGenerator::with_config(config.generator);
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(controller_code.content.contains("clippy::too_many_arguments"),
+                "Should include the configured lint");
+        
+        // An empty list disables the attribute
+        let config = ConfigBuilder::new()
+            .lint_allows(Vec::<String>::new())
+            .build_unchecked();
+        let generator = PoemOpenApiGenerator::with_config(config.generator);
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(!controller_code.content.contains("#![allow("),
+                "Should not emit an allow attribute when no lints are configured");
+    }
+
+    /// Test that a configured response wrapper envelopes unary responses
+    #[test]
+    fn test_controller_response_wrapper() {
+        let service = Service::new("UserService".to_string())
+            .with_method(RpcMethod::new(
+                "GetUser".to_string(),
+                TypeReference::new("GetUserRequest".to_string()),
+                TypeReference::new("User".to_string())
+            ));
+        
+        let routes = vec![
+            HttpRoute::new(
+                "UserService".to_string(),
+                "GetUser".to_string(),
+                HttpMethod::Get,
+                "/users/{id}".to_string(),
+            )
+            .with_path_parameter(PathParameter::new("id".to_string(), ParameterType::String))
+            .with_response_type(TypeReference::new("User".to_string()))
+        ];
+        
+        let config = ConfigBuilder::new()
+            .response_wrapper("ApiResponse")
+            .build_unchecked();
+        let generator = PoemOpenApiGenerator::with_config(config.generator);
+        
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(controller_code.content.contains("-> poem_openapi::payload::Json<ApiResponse<User>>"),
+                "Controller should return the wrapped response type");
+        assert!(controller_code.content.contains("Json(<ApiResponse<User>>::from(result))"),
+                "Controller should wrap the service result");
+        
+        // Services return the bare type and stay unaware of the envelope
+        let trait_code = generator.generate_service_trait(&service, &routes).unwrap();
+        assert!(trait_code.content.contains("-> Result<User, Box<dyn std::error::Error>>"),
+                "Service trait should return the bare response type");
+        
+        // Without a wrapper the bare type is returned
+        let generator = PoemOpenApiGenerator::new();
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(controller_code.content.contains("-> poem_openapi::payload::Json<User>"));
+    }
+    
+    #[test]
+    fn test_service_error_type() {
+        let service = Service::new("UserService".to_string())
+            .with_method(RpcMethod::new(
+                "GetUser".to_string(),
+                TypeReference::new("GetUserRequest".to_string()),
+                TypeReference::new("User".to_string())
+            ));
+        
+        let routes = vec![
+            HttpRoute::new(
+                "UserService".to_string(),
+                "GetUser".to_string(),
+                HttpMethod::Get,
+                "/users/{id}".to_string(),
+            )
+            .with_path_parameter(PathParameter::new("id".to_string(), ParameterType::String))
+            .with_response_type(TypeReference::new("User".to_string()))
+        ];
+        
+        let config = ConfigBuilder::new()
+            .service_error_type("crate::Error")
+            .build_unchecked();
+        let generator = PoemOpenApiGenerator::with_config(config.generator);
+        
+        let trait_code = generator.generate_service_trait(&service, &routes).unwrap();
+        assert!(trait_code.content.contains("-> Result<User, crate::Error>;"),
+                "Service trait should return the configured error type");
+        assert!(!trait_code.content.contains("Box<dyn std::error::Error>"));
+        
+        // The boxed error remains the default
+        let generator = PoemOpenApiGenerator::new();
+        let trait_code = generator.generate_service_trait(&service, &routes).unwrap();
+        assert!(trait_code.content.contains("-> Result<User, Box<dyn std::error::Error>>;"));
+    }
+    
+    #[test]
+    fn test_controller_trait_doc_links() {
+        let service = Service::new("UserService".to_string())
+            .with_method(RpcMethod::new(
+                "GetUser".to_string(),
+                TypeReference::new("GetUserRequest".to_string()),
+                TypeReference::new("User".to_string())
+            ));
+        
+        let routes = vec![
+            HttpRoute::new(
+                "UserService".to_string(),
+                "GetUser".to_string(),
+                HttpMethod::Get,
+                "/users/{id}".to_string(),
+            )
+            .with_path_parameter(PathParameter::new("id".to_string(), ParameterType::String))
+            .with_response_type(TypeReference::new("User".to_string()))
+        ];
+        
+        let generator = PoemOpenApiGenerator::new();
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(controller_code.content.contains("/// Delegates to [`UserServiceService::get_user`]."),
+                "Controller should link to the trait method:\n{}", controller_code.content);
+        
+        let trait_code = generator.generate_service_trait(&service, &routes).unwrap();
+        assert!(trait_code.content.contains(
+            "/// Served over HTTP by [`UserServiceController`](super::user_service_controller::UserServiceController)."
+        ), "Service trait should link to the controller:\n{}", trait_code.content);
+    }
+    
+    #[test]
+    fn test_controller_method_naming() {
+        let service = Service::new("UserService".to_string())
+            .with_method(RpcMethod::new(
+                "GetUser".to_string(),
+                TypeReference::new("GetUserRequest".to_string()),
+                TypeReference::new("User".to_string())
+            ));
+        
+        let routes = vec![
+            HttpRoute::new(
+                "UserService".to_string(),
+                "GetUser".to_string(),
+                HttpMethod::Get,
+                "/users/{id}".to_string(),
+            )
+            .with_path_parameter(PathParameter::new("id".to_string(), ParameterType::String))
+            .with_response_type(TypeReference::new("User".to_string()))
+        ];
+        
+        // Snake case by default
+        let generator = PoemOpenApiGenerator::new();
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(controller_code.content.contains("async fn get_user("));
+        assert!(!controller_code.content.contains("non_snake_case"));
+        
+        let config = ConfigBuilder::new()
+            .method_naming(MethodNaming::Preserve)
+            .build_unchecked();
+        let generator = PoemOpenApiGenerator::with_config(config.generator);
+        
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(controller_code.content.contains("async fn GetUser("),
+                "Controller should keep the proto method name:\n{}", controller_code.content);
+        assert!(controller_code.content.contains("self.service.GetUser("));
+        assert!(controller_code.content.contains("non_snake_case"));
+        
+        let trait_code = generator.generate_service_trait(&service, &routes).unwrap();
+        assert!(trait_code.content.contains("async fn GetUser("));
+        
+        let config = ConfigBuilder::new()
+            .method_naming(MethodNaming::CamelCase)
+            .build_unchecked();
+        let generator = PoemOpenApiGenerator::with_config(config.generator);
+        
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(controller_code.content.contains("async fn getUser("),
+                "Controller should use camelCase method names:\n{}", controller_code.content);
+        assert!(controller_code.content.contains("self.service.getUser("));
+        
+        let trait_code = generator.generate_service_trait(&service, &routes).unwrap();
+        assert!(trait_code.content.contains("async fn getUser("));
+    }
+    
+    #[test]
+    fn test_controller_render_error_context() {
+        let service = Service::new("UserService".to_string())
+            .with_method(RpcMethod::new(
+                "GetUser".to_string(),
+                TypeReference::new("GetUserRequest".to_string()),
+                TypeReference::new("User".to_string())
+            ));
+        
+        let routes = vec![
+            HttpRoute::new(
+                "UserService".to_string(),
+                "GetUser".to_string(),
+                HttpMethod::Get,
+                "/users/{id}".to_string(),
+            )
+            .with_response_type(TypeReference::new("User".to_string()))
+        ];
+        
+        // `map_type` fails for a field that doesn't exist on the route
+        let mut generator = PoemOpenApiGenerator::new();
+        generator.template_engine_mut()
+            .register_template("controller", "{{#each routes}}{{map_type missing_field}}{{/each}}")
+            .unwrap();
+        
+        let error = generator.generate_controller(&service, &routes).unwrap_err();
+        match &error {
+            CodeGenerationError::RenderFailed { template_name, service_name, method_name, context_snippet, .. } => {
+                assert_eq!(template_name, "controller");
+                assert_eq!(service_name, "UserService");
+                assert_eq!(method_name.as_deref(), Some("GetUser"));
+                assert!(context_snippet.contains("/users/{id}"));
+            }
+            other => panic!("Expected RenderFailed, got: {:?}", other),
+        }
+        
+        let message = error.to_string();
+        assert!(message.contains("UserService"), "Error should name the service: {}", message);
+        assert!(message.contains("method GetUser"), "Error should name the method: {}", message);
+    }
+    
+    #[test]
+    fn test_controller_models_module_path() {
+        let service = Service::new("UserService".to_string())
+            .with_method(RpcMethod::new(
+                "GetUser".to_string(),
+                TypeReference::new("GetUserRequest".to_string()),
+                TypeReference::new("User".to_string())
+            ));
+        
+        let routes = vec![
+            HttpRoute::new(
+                "UserService".to_string(),
+                "GetUser".to_string(),
+                HttpMethod::Get,
+                "/users/{id}".to_string(),
+            )
+            .with_path_parameter(PathParameter::new("id".to_string(), ParameterType::String))
+            .with_response_type(TypeReference::new("User".to_string()))
+        ];
+        
+        // Models are imported from the parent module by default
+        let generator = PoemOpenApiGenerator::new();
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(controller_code.content.contains("use super::{User};"));
+        
+        let config = ConfigBuilder::new()
+            .models_module_path("crate::api::models")
+            .build_unchecked();
+        let generator = PoemOpenApiGenerator::with_config(config.generator);
+        
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(controller_code.content.contains("use crate::api::models::{User};"),
+                "Controller should import from the configured module:\n{}", controller_code.content);
+        assert!(!controller_code.content.contains("use super::{User};"));
+        
+        // Generated sibling modules are still imported relative to the controller
+        assert!(controller_code.content.contains("use super::user_service_service::UserServiceService;"));
+        
+        let trait_code = generator.generate_service_trait(&service, &routes).unwrap();
+        assert!(trait_code.content.contains("use crate::api::models::{User};"));
+    }
+    
+    #[test]
+    fn test_controller_path_rewrites() {
+        let service = Service::new("UserService".to_string())
+            .with_method(RpcMethod::new(
+                "GetUser".to_string(),
+                TypeReference::new("GetUserRequest".to_string()),
+                TypeReference::new("User".to_string())
+            ));
+        
+        let routes = vec![
+            HttpRoute::new(
+                "UserService".to_string(),
+                "GetUser".to_string(),
+                HttpMethod::Get,
+                "/v1/users/{id}".to_string(),
+            )
+            .with_path_parameter(PathParameter::new("id".to_string(), ParameterType::String))
+            .with_response_type(TypeReference::new("User".to_string()))
+        ];
+        
+        let config = ConfigBuilder::new()
+            .path_rewrite("^/v1/", "/v2/")
+            .build()
+            .unwrap();
+        let generator = PoemOpenApiGenerator::with_config(config.generator);
+        
+        let controller_code = generator.generate_controller(&service, &routes).unwrap();
+        assert!(controller_code.content.contains("#[oai(path = \"/v2/users/{id}\", method = \"get\")]"),
+                "Path should be rewritten:\n{}", controller_code.content);
+        assert!(!controller_code.content.contains("/v1/users"));
+    }
+    
+    #[test]
+    fn test_controller_path_prefix() {
+        let service = Service::new("UserService".to_string())
+            .with_method(RpcMethod::new(
+                "GetUser".to_string(),
+                TypeReference::new("GetUserRequest".to_string()),
+                TypeReference::new("User".to_string())
+            ));
+        
+        let routes = vec![
+            HttpRoute::new(
+                "UserService".to_string(),
+                "GetUser".to_string(),
+                HttpMethod::Get,
+                "/v1/users/{id}".to_string(),
+            )
+            .with_path_parameter(PathParameter::new("id".to_string(), ParameterType::String))
+            .with_response_type(TypeReference::new("User".to_string()))
+        ];
+        
+        // Slashes between the prefix and the path are collapsed
+        for prefix in ["/api", "/api/", "api"] {
+            let config = ConfigBuilder::new()
+                .path_prefix(prefix)
+                .build()
+                .unwrap();
+            let generator = PoemOpenApiGenerator::with_config(config.generator);
+            
+            let controller_code = generator.generate_controller(&service, &routes).unwrap();
+            assert!(controller_code.content.contains("#[oai(path = \"/api/v1/users/{id}\", method = \"get\")]"),
+                    "Path should carry the prefix {:?}:\n{}", prefix, controller_code.content);
+        }
+    }
+}
@@ -102,6 +102,8 @@ fn rpc_method_with_http_generator() -> impl Strategy<Value = RpcMethod> {
             options: Vec::new(),
             comments: Vec::new(),
             http_annotation: Some(http_annotation),
+            idempotency_level: None,
+            method_signature: vec![],
         }
     })
 }
@@ -117,6 +119,7 @@ fn service_with_http_generator() -> impl Strategy<Value = Service> {
             methods,
             options: Vec::new(),
             comments: Vec::new(),
+            source: None,
         }
     })
 }
@@ -135,6 +138,7 @@ fn proto_file_with_http_generator() -> impl Strategy<Value = ProtoFile> {
             services,
             messages: Vec::new(),
             enums: Vec::new(),
+            unknown_statements: Vec::new(),
         }
     })
 }
@@ -283,6 +287,8 @@ mod http_annotation_extraction_tests {
                     body: None,
                     additional_bindings: Vec::new(),
                 }),
+                idempotency_level: None,
+                method_signature: vec![],
             };
             
             let method2 = RpcMethod {
@@ -297,6 +303,8 @@ mod http_annotation_extraction_tests {
                     body: None,
                     additional_bindings: Vec::new(),
                 }),
+                idempotency_level: None,
+                method_signature: vec![],
             };
             
             let service = Service {
@@ -304,6 +312,7 @@ mod http_annotation_extraction_tests {
                 methods: vec![method1, method2],
                 options: Vec::new(),
                 comments: Vec::new(),
+                source: None,
             };
             
             let proto_file = ProtoFile {
@@ -314,6 +323,7 @@ mod http_annotation_extraction_tests {
                 services: vec![service],
                 messages: Vec::new(),
                 enums: Vec::new(),
+                unknown_statements: Vec::new(),
             };
             
             let extractor = GoogleApiHttpExtractor::new();
@@ -387,6 +397,8 @@ mod http_pattern_mapping_tests {
                 options: Vec::new(),
                 comments: Vec::new(),
                 http_annotation: Some(http_annotation),
+                idempotency_level: None,
+                method_signature: vec![],
             };
             
             let service = Service {
@@ -394,6 +406,7 @@ mod http_pattern_mapping_tests {
                 methods: vec![rpc_method],
                 options: Vec::new(),
                 comments: Vec::new(),
+                source: None,
             };
             
             let proto_file = ProtoFile {
@@ -404,9 +417,14 @@ mod http_pattern_mapping_tests {
                 services: vec![service],
                 messages: Vec::new(),
                 enums: Vec::new(),
+                unknown_statements: Vec::new(),
             };
             
-            let extractor = GoogleApiHttpExtractor::new();
+            // Infer common query parameters for every method, not only list-style ones
+            let extractor = GoogleApiHttpExtractor::with_config(ExtractorConfig {
+                query_param_inference: QueryParamInference::AllMethods,
+                ..ExtractorConfig::default()
+            });
             let result = extractor.extract_routes(&proto_file);
             
             prop_assert!(result.is_ok(), "Route extraction should succeed for valid patterns");
@@ -597,4 +615,158 @@ mod http_pattern_mapping_tests {
             }
         }
     }
-}
\ No newline at end of file
+}
+/// HTTP rules supplied outside the proto file via `extract_routes_with_overrides`
+#[cfg(test)]
+mod http_rule_override_tests {
+    use super::*;
+    
+    fn proto_file_with_methods(methods: Vec<RpcMethod>) -> ProtoFile {
+        ProtoFile {
+            syntax: ProtocolVersion::Proto3,
+            package: Some("example.v1".to_string()),
+            imports: Vec::new(),
+            options: Vec::new(),
+            services: vec![Service {
+                name: "UserService".to_string(),
+                methods,
+                options: Vec::new(),
+                comments: Vec::new(),
+                source: None,
+            }],
+            messages: Vec::new(),
+            enums: Vec::new(),
+            unknown_statements: Vec::new(),
+        }
+    }
+    
+    fn method(name: &str, http_annotation: Option<HttpAnnotation>) -> RpcMethod {
+        RpcMethod {
+            name: name.to_string(),
+            input_type: TypeReference::new(format!("{}Request", name)),
+            output_type: TypeReference::new("User".to_string()),
+            options: Vec::new(),
+            comments: Vec::new(),
+            http_annotation,
+            idempotency_level: None,
+            method_signature: vec![],
+        }
+    }
+    
+    fn annotation(method: HttpMethod, path: &str) -> HttpAnnotation {
+        HttpAnnotation {
+            method,
+            path: path.to_string(),
+            body: None,
+            additional_bindings: Vec::new(),
+        }
+    }
+    
+    #[test]
+    fn test_override_supplies_route_for_unannotated_method() {
+        let proto_file = proto_file_with_methods(vec![
+            method("GetUser", Some(annotation(HttpMethod::Get, "/users/{id}"))),
+            method("DeleteUser", None),
+        ]);
+        
+        let mut overrides = HttpRuleOverrides::new();
+        overrides.insert(
+            "example.v1.UserService.DeleteUser".to_string(),
+            annotation(HttpMethod::Delete, "/users/{id}"),
+        );
+        
+        let extractor = GoogleApiHttpExtractor::new();
+        assert_eq!(extractor.extract_routes(&proto_file).unwrap().len(), 1);
+        
+        let routes = extractor.extract_routes_with_overrides(&proto_file, &overrides).unwrap();
+        assert_eq!(routes.len(), 2);
+        
+        let delete_route = routes.iter().find(|r| r.method_name == "DeleteUser").unwrap();
+        assert_eq!(delete_route.http_method, HttpMethod::Delete);
+        assert_eq!(delete_route.path_template, "/users/{id}");
+        assert_eq!(delete_route.path_parameters.len(), 1);
+        assert_eq!(delete_route.path_parameters[0].name, "id");
+    }
+    
+    #[test]
+    fn test_override_takes_precedence_over_inline_annotation() {
+        let proto_file = proto_file_with_methods(vec![
+            method("GetUser", Some(annotation(HttpMethod::Get, "/users/{id}"))),
+        ]);
+        
+        let mut overrides = HttpRuleOverrides::new();
+        overrides.insert(
+            ".example.v1.UserService.GetUser".to_string(),
+            annotation(HttpMethod::Get, "/v2/users/{id}"),
+        );
+        
+        let extractor = GoogleApiHttpExtractor::new();
+        let routes = extractor.extract_routes_with_overrides(&proto_file, &overrides).unwrap();
+        
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].path_template, "/v2/users/{id}");
+    }
+}
+
+/// Oversized or malformed path templates are rejected before any parsing work
+#[cfg(test)]
+mod path_template_guard_tests {
+    use super::*;
+    
+    fn proto_file_with_path(path: String) -> ProtoFile {
+        let method = RpcMethod {
+            name: "GetUser".to_string(),
+            input_type: TypeReference::new("GetUserRequest".to_string()),
+            output_type: TypeReference::new("User".to_string()),
+            options: Vec::new(),
+            comments: Vec::new(),
+            http_annotation: Some(HttpAnnotation {
+                method: HttpMethod::Get,
+                path,
+                body: None,
+                additional_bindings: Vec::new(),
+            }),
+            idempotency_level: None,
+            method_signature: vec![],
+        };
+        
+        ProtoFile {
+            syntax: ProtocolVersion::Proto3,
+            package: None,
+            imports: Vec::new(),
+            options: Vec::new(),
+            services: vec![Service {
+                name: "UserService".to_string(),
+                methods: vec![method],
+                options: Vec::new(),
+                comments: Vec::new(),
+                source: None,
+            }],
+            messages: Vec::new(),
+            enums: Vec::new(),
+            unknown_statements: Vec::new(),
+        }
+    }
+    
+    #[test]
+    fn test_oversized_path_template_rejected() {
+        let extractor = GoogleApiHttpExtractor::new();
+        
+        // A very long template of unbalanced braces fails on its length alone
+        let path = format!("/{}", "{a".repeat(500_000));
+        let error = extractor.extract_routes(&proto_file_with_path(path)).unwrap_err();
+        assert!(error.to_string().contains("exceeding the maximum of 2048"), "Unexpected error: {}", error);
+        
+        // Deeply nested braces within the limit fail at the first nesting
+        let path = format!("/{}", "{".repeat(1000));
+        assert!(extractor.extract_routes(&proto_file_with_path(path)).is_err());
+        
+        // The limit is configurable
+        let extractor = GoogleApiHttpExtractor::with_config(ExtractorConfig {
+            max_path_template_length: 8,
+            ..ExtractorConfig::default()
+        });
+        assert!(extractor.validate_path_template("/users/{id}").is_err());
+        assert!(extractor.validate_path_template("/u/{id}").is_ok());
+    }
+}
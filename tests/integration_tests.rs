@@ -667,4 +667,5239 @@ message Response2 { int32 count = 1; }
     assert!(output_dir.join("service2").exists());
     assert!(output_dir.join("service1/service1_controller.rs").exists());
     assert!(output_dir.join("service2/service2_controller.rs").exists());
-}
\ No newline at end of file
+}
+/// Dummy generator used to verify registry dispatch
+struct MarkerGenerator;
+
+impl CodeGenerator for MarkerGenerator {
+    type Error = CodeGenerationError;
+
+    fn generate_controller(&self, service: &Service, _routes: &[HttpRoute]) -> std::result::Result<GeneratedCode, Self::Error> {
+        Ok(GeneratedCode::new(format!("// marker controller for {}", service.name)))
+    }
+
+    fn generate_service_trait(&self, service: &Service, _routes: &[HttpRoute]) -> std::result::Result<GeneratedCode, Self::Error> {
+        Ok(GeneratedCode::new(format!("// marker service for {}", service.name)))
+    }
+}
+
+/// Test that the coordinator dispatches through the generator registry
+#[test]
+fn test_custom_generator_registry_dispatch() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service MarkerService {
+    rpc Ping(PingRequest) returns (PingResponse) {
+        option (google.api.http) = {
+            get: "/v1/ping"
+        };
+    }
+}
+
+message PingRequest {}
+message PingResponse {}
+"#;
+
+    let framework = TargetFramework::Custom("marker".to_string());
+
+    let mut config = ProtoHttpParserConfig::new();
+    config.generator.target_framework = framework.clone();
+
+    // Without a registered generator the coordinator reports the missing framework
+    let coordinator = ProtoHttpCoordinator::with_config(config.clone());
+    match coordinator.process_content(proto_content) {
+        Err(ProtoHttpParserError::CodeGeneration(CodeGenerationError::GeneratorNotRegistered { .. })) => {}
+        other => panic!("Expected GeneratorNotRegistered, got: {:?}", other),
+    }
+
+    let mut coordinator = ProtoHttpCoordinator::with_config(config);
+    coordinator.register_generator(framework.clone(), Box::new(MarkerGenerator));
+    assert!(coordinator.generator_registry().contains(&framework));
+
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    assert_eq!(
+        result.generated_files["marker_service_controller.rs"].content,
+        "// marker controller for MarkerService"
+    );
+    assert_eq!(
+        result.generated_files["marker_service_service.rs"].content,
+        "// marker service for MarkerService"
+    );
+}
+
+/// Test that updating the configuration keeps a user-registered generator
+#[test]
+fn test_update_config_keeps_registered_generator() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service MarkerService {
+    rpc Ping(PingRequest) returns (PingResponse) {
+        option (google.api.http) = {
+            get: "/v1/ping"
+        };
+    }
+}
+
+message PingRequest {}
+message PingResponse {}
+"#;
+
+    let mut coordinator = ProtoHttpCoordinator::new();
+    coordinator.register_generator(TargetFramework::PoemOpenApi, Box::new(MarkerGenerator));
+    coordinator.update_config(ProtoHttpParserConfig::new());
+
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+    assert_eq!(
+        result.generated_files["marker_service_controller.rs"].content,
+        "// marker controller for MarkerService"
+    );
+}
+
+/// Test that `body: "field"` resolves the named field's type for the JSON payload
+#[test]
+fn test_body_field_type_resolution() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc UpdateUser(UpdateUserRequest) returns (User) {
+        option (google.api.http) = {
+            put: "/v1/users/{user_id}"
+            body: "user"
+        };
+    }
+}
+
+message UpdateUserRequest {
+    string user_id = 1;
+    User user = 2;
+}
+
+message User {
+    string id = 1;
+    string name = 2;
+}
+"#;
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let route = &result.routes[0];
+    let request_body = route.request_body.as_ref().expect("Route should have a request body");
+    assert_eq!(request_body.field.as_deref(), Some("user"));
+    assert_eq!(request_body.field_type.as_ref().map(|t| t.name.as_str()), Some("User"));
+
+    let controller = &result.generated_files["user_service_controller.rs"];
+    assert!(controller.content.contains("body: Json<User>"), "Controller should take Json<User>:\n{}", controller.content);
+    assert!(!controller.content.contains("Json<String>"));
+
+    let service = &result.generated_files["user_service_service.rs"];
+    assert!(service.content.contains("user: User"), "Service trait should take the resolved field type:\n{}", service.content);
+}
+
+/// Test that `body` naming a repeated field takes a JSON list of the field type
+#[test]
+fn test_repeated_body_field() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service CartService {
+    rpc AddItems(AddItemsRequest) returns (Cart) {
+        option (google.api.http) = {
+            post: "/v1/carts/{cart_id}/items"
+            body: "items"
+        };
+    }
+}
+
+message AddItemsRequest {
+    string cart_id = 1;
+    repeated Item items = 2;
+}
+
+message Item {
+    string sku = 1;
+}
+
+message Cart {
+    string id = 1;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .generate_mocks(true)
+        .generate_tests(true)
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let request_body = result.routes[0].request_body.as_ref().expect("Route should have a request body");
+    assert_eq!(request_body.field_type.as_ref().map(|t| t.name.as_str()), Some("Item"));
+    assert!(request_body.is_repeated);
+
+    let controller = &result.generated_files["cart_service_controller.rs"].content;
+    assert!(controller.contains("body: Json<Vec<Item>>"), "Controller should take Json<Vec<Item>>:\n{}", controller);
+    let service = &result.generated_files["cart_service_service.rs"].content;
+    assert!(service.contains("items: Vec<Item>"), "Service trait should take a list:\n{}", service);
+    let tests = &result.generated_files["cart_service_tests.rs"].content;
+    assert!(tests.contains(".body_json(&serde_json::json!([]))"), "Sample body should be a list:\n{}", tests);
+
+    let spec = PoemOpenApiGenerator::new().generate_openapi_spec(&result.proto_file, &result.routes);
+    assert_eq!(
+        spec["paths"]["/v1/carts/{cart_id}/items"]["post"]["requestBody"]["content"]["application/json"]["schema"],
+        serde_json::json!({ "type": "array", "items": { "$ref": "#/components/schemas/Item" } })
+    );
+}
+
+/// Test that the configured file header is prepended to every written file
+#[test]
+fn test_file_header_prepended_to_generated_files() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service HeaderService {
+    rpc GetHeader(HeaderRequest) returns (HeaderResponse) {
+        option (google.api.http) = {
+            get: "/v1/header"
+        };
+    }
+}
+
+message HeaderRequest {}
+message HeaderResponse {}
+"#;
+    let header = "// SPDX-License-Identifier: Apache-2.0\n// Copyright Example Authors";
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let proto_path = temp_dir.path().join("header.proto");
+    std::fs::write(&proto_path, proto_content).expect("Failed to write proto file");
+
+    let config = ConfigBuilder::new()
+        .use_rustfmt(false)
+        .file_header(header)
+        .build_unchecked();
+
+    // Coordinator output
+    let coordinator = ProtoHttpCoordinator::with_config(config.clone());
+    let result = coordinator.process_file(&proto_path).expect("Failed to process proto file");
+    let output_dir = temp_dir.path().join("output");
+    coordinator.write_generated_code(&result, &output_dir).expect("Failed to write generated code");
+
+    // Flat build integration output
+    let build_output_dir = temp_dir.path().join("build_output");
+    let build_result = BuildIntegration::new()
+        .with_config(config)
+        .add_proto_file(&proto_path)
+        .output_dir(&build_output_dir)
+        .generate()
+        .expect("Build integration failed");
+
+    let mut written_files: Vec<std::path::PathBuf> = std::fs::read_dir(&output_dir)
+        .expect("Failed to read output dir")
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    written_files.extend(build_result.generated_files);
+    assert_eq!(written_files.len(), 4);
+
+    for path in written_files {
+        let content = std::fs::read_to_string(&path).expect("Failed to read generated file");
+        assert!(
+            content.starts_with(&format!("{}\n", header)),
+            "{} should start with the file header:\n{}",
+            path.display(),
+            content
+        );
+    }
+}
+
+/// Test that path variables with a segment pattern only conflict with routes matching the same segments
+#[test]
+fn test_route_conflicts_with_segment_patterns() {
+    let proto_template = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service ShelfService {
+    rpc GetShelf(GetShelfRequest) returns (Shelf) {
+        option (google.api.http) = {
+            get: "/v1/{name=shelves/*}"
+        };
+    }
+
+    rpc GetItem(GetShelfRequest) returns (Shelf) {
+        option (google.api.http) = {
+            get: "/v1/OTHER"
+        };
+    }
+}
+
+message GetShelfRequest {
+    string name = 1;
+    string id = 2;
+}
+
+message Shelf {
+    string name = 1;
+}
+"#;
+
+    // One segment against two segments is a different route
+    let result = ProtoHttpCoordinator::new().process_content(&proto_template.replace("OTHER", "{id}"))
+        .expect("Routes matching different segment counts should not conflict");
+    assert_eq!(result.routes.len(), 2);
+    assert_eq!(result.routes[0].conflict_signature(), "GET /v1/shelves/{}");
+    assert_eq!(result.routes[1].conflict_signature(), "GET /v1/{}");
+
+    // The literal expansion of the pattern still conflicts
+    match ProtoHttpCoordinator::new().process_content(&proto_template.replace("OTHER", "shelves/{id}")) {
+        Err(ProtoHttpParserError::Validation(ValidationError::HttpAnnotationError { message })) => {
+            assert!(message.contains("Conflicting HTTP routes"), "Unexpected message: {}", message);
+        }
+        other => panic!("Expected a route conflict, got: {:?}", other),
+    }
+}
+
+/// Test that route conflicts are detected across services and across batch files
+#[test]
+fn test_route_conflicts_across_services() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{id}"
+        };
+    }
+}
+
+service AccountService {
+    rpc GetAccount(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{user_id}"
+        };
+    }
+}
+
+message GetUserRequest {
+    string id = 1;
+    string user_id = 2;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+
+    let coordinator = ProtoHttpCoordinator::new();
+    match coordinator.process_content(proto_content) {
+        Err(ProtoHttpParserError::Validation(ValidationError::HttpAnnotationError { message })) => {
+            assert!(message.contains("Conflicting HTTP routes"), "Unexpected message: {}", message);
+            assert!(message.contains("UserService.GetUser"), "Unexpected message: {}", message);
+            assert!(message.contains("AccountService.GetAccount"), "Unexpected message: {}", message);
+        }
+        other => panic!("Expected a route conflict, got: {:?}", other),
+    }
+
+    // The same conflict split across two files is reported by batch processing
+    let user_proto = r#"
+syntax = "proto3";
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{id}"
+        };
+    }
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+    let account_proto = user_proto
+        .replace("UserService", "AccountService")
+        .replace("GetUser(", "GetAccount(");
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let user_path = temp_dir.path().join("user.proto");
+    let account_path = temp_dir.path().join("account.proto");
+    std::fs::write(&user_path, user_proto).expect("Failed to write proto file");
+    std::fs::write(&account_path, account_proto).expect("Failed to write proto file");
+
+    let batch_result = coordinator
+        .process_files(&[&user_path, &account_path])
+        .expect("Batch processing failed");
+
+    assert_eq!(batch_result.success_count(), 1);
+    assert_eq!(batch_result.error_count(), 1);
+    let (error_path, error) = &batch_result.errors[0];
+    assert_eq!(error_path, &account_path);
+    assert!(
+        matches!(error, ProtoHttpParserError::Validation(ValidationError::ConflictingRoutes { .. })),
+        "Expected ConflictingRoutes, got: {:?}",
+        error
+    );
+}
+
+/// Test that enabling the tags enum emits one variant per service
+#[test]
+fn test_tags_enum_generation() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+// Manages user accounts
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{id}"
+        };
+    }
+}
+
+service OrderService {
+    rpc GetOrder(GetOrderRequest) returns (Order) {
+        option (google.api.http) = {
+            get: "/v1/orders/{id}"
+        };
+    }
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+}
+
+message GetOrderRequest {
+    string id = 1;
+}
+
+message Order {
+    string id = 1;
+}
+"#;
+
+    // Disabled by default
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+    assert!(!result.generated_files.contains_key(TAGS_FILENAME));
+
+    let config = ConfigBuilder::new()
+        .generate_tags_enum(true)
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let tags = &result.generated_files[TAGS_FILENAME].content;
+    assert!(tags.contains("#[derive(poem_openapi::Tags)]"), "Missing Tags derive:\n{}", tags);
+    assert!(tags.contains("pub enum Tags"));
+    assert!(tags.contains("UserService,"));
+    assert!(tags.contains("OrderService,"));
+    assert!(tags.contains("/// Manages user accounts"));
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(controller.contains("use super::tags::Tags;"));
+    assert!(
+        controller.contains(r#"tag = "Tags::UserService""#),
+        "Controller should reference its tag:\n{}",
+        controller
+    );
+}
+
+/// Test that the registration helper assembles every service's controller
+#[test]
+fn test_registration_helper_generation() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+}
+
+service OrderService {
+  rpc GetOrder(GetOrderRequest) returns (Order) {
+    option (google.api.http) = {
+      get: "/v1/orders/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+
+message GetOrderRequest {
+  string id = 1;
+}
+
+message Order {
+  string id = 1;
+}
+"#;
+
+    // Disabled by default
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+    assert!(!result.generated_files.contains_key(REGISTRATION_FILENAME));
+
+    let config = ConfigBuilder::new()
+        .generate_registration_helper(true)
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let apis = &result.generated_files[REGISTRATION_FILENAME].content;
+    assert!(apis.contains("pub fn register_apis<UserServiceImpl, OrderServiceImpl>("), "Missing helper:\n{}", apis);
+    assert!(apis.contains("use super::user_service_controller::UserServiceController;"));
+    assert!(apis.contains("use super::order_service_controller::OrderServiceController;"));
+    assert!(apis.contains("-> (UserServiceController<UserServiceImpl>, OrderServiceController<OrderServiceImpl>)"));
+    assert!(apis.contains("UserServiceController::new(user_service),"));
+    assert!(apis.contains("OrderServiceController::new(order_service),"));
+}
+
+/// Test that the registration helper names the controller tuple with a type alias
+#[test]
+fn test_all_controllers_alias() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+}
+
+service ProductService {
+  rpc GetProduct(GetProductRequest) returns (Product) {
+    option (google.api.http) = {
+      get: "/v1/products/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+
+message GetProductRequest {
+  string id = 1;
+}
+
+message Product {
+  string id = 1;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .generate_registration_helper(true)
+        .build_unchecked();
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let apis = &result.generated_files[REGISTRATION_FILENAME].content;
+    assert!(apis.contains(
+        "pub type AllControllers<UserServiceImpl, ProductServiceImpl> = (UserServiceController<UserServiceImpl>, ProductServiceController<ProductServiceImpl>);"
+    ), "Missing alias:\n{}", apis);
+    assert!(apis.contains("pub fn register_apis<UserServiceImpl, ProductServiceImpl>("));
+}
+
+/// Test that parameters bound to enum fields use the enum type
+#[test]
+fn test_enum_typed_parameters() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc ListUsers(ListUsersRequest) returns (ListUsersResponse) {
+        option (google.api.http) = {
+            get: "/v1/users"
+        };
+    }
+}
+
+enum UserStatus {
+    USER_STATUS_UNSPECIFIED = 0;
+    USER_STATUS_ACTIVE = 1;
+}
+
+message ListUsersRequest {
+    UserStatus status = 1;
+    int32 limit = 2;
+}
+
+message ListUsersResponse {
+    repeated string ids = 1;
+}
+"#;
+
+    let mut config = ProtoHttpParserConfig::new();
+    config.extractor.common_query_params = vec!["status".to_string(), "limit".to_string()];
+
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let query_parameters = &result.routes[0].query_parameters;
+    assert_eq!(query_parameters[0].param_type, ParameterType::Enum("UserStatus".to_string()));
+    assert_eq!(query_parameters[1].param_type, ParameterType::Integer);
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(
+        controller.contains("status: Query<Option<UserStatus>>"),
+        "Controller should use the enum type:\n{}",
+        controller
+    );
+    assert!(!controller.contains("status: Query<Option<String>>"));
+
+    let service = &result.generated_files["user_service_service.rs"].content;
+    assert!(service.contains("status: Option<UserStatus>"), "Service trait should use the enum type:\n{}", service);
+}
+
+/// Test that request and response messages defined in an imported file resolve
+#[test]
+fn test_cross_file_message_resolution() {
+    let messages_proto = r#"
+syntax = "proto3";
+
+package test.v1;
+
+enum UserStatus {
+    USER_STATUS_UNSPECIFIED = 0;
+    USER_STATUS_ACTIVE = 1;
+}
+
+message UpdateUserRequest {
+    string user_id = 1;
+    User user = 2;
+}
+
+message User {
+    string id = 1;
+    UserStatus status = 2;
+}
+"#;
+
+    let service_proto = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "messages.proto";
+
+service UserService {
+    rpc UpdateUser(UpdateUserRequest) returns (User) {
+        option (google.api.http) = {
+            put: "/v1/users/{user_id}"
+            body: "user"
+        };
+    }
+}
+"#;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let messages_path = temp_dir.path().join("messages.proto");
+    let service_path = temp_dir.path().join("service.proto");
+    std::fs::write(&messages_path, messages_proto).expect("Failed to write proto file");
+    std::fs::write(&service_path, service_proto).expect("Failed to write proto file");
+
+    let config = ConfigBuilder::new()
+        .add_include_path(temp_dir.path())
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+
+    // A single file resolves types from its imports
+    let result = coordinator.process_file(&service_path).expect("Failed to process service proto");
+    assert!(result.type_registry.resolve_type(&TypeReference::new("User".to_string()), Some("test.v1")).is_some());
+    let request_body = result.routes[0].request_body.as_ref().expect("Route should have a request body");
+    assert_eq!(request_body.field_type.as_ref().map(|t| t.name.as_str()), Some("User"));
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(controller.contains("body: Json<User>"), "Controller should take Json<User>:\n{}", controller);
+
+    // A batch shares one registry across all parsed files
+    let batch_result = coordinator
+        .process_files(&[&service_path, &messages_path])
+        .expect("Batch processing failed");
+    assert!(batch_result.is_success(), "Batch failed: {:?}", batch_result.errors);
+    assert!(batch_result.type_registry.types.contains_key("test.v1.UpdateUserRequest"));
+    assert!(batch_result.type_registry.types.contains_key("test.v1.UserStatus"));
+}
+
+/// Test that per-phase timings are collected when metrics are enabled
+#[test]
+fn test_process_metrics_collection() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service MetricsService {
+    rpc GetMetric(GetMetricRequest) returns (Metric) {
+        option (google.api.http) = {
+            get: "/v1/metrics/{id}"
+        };
+    }
+}
+
+message GetMetricRequest {
+    string id = 1;
+}
+
+message Metric {
+    string id = 1;
+}
+"#;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let proto_path = temp_dir.path().join("metrics.proto");
+    std::fs::write(&proto_path, proto_content).expect("Failed to write proto file");
+
+    // Metrics are off by default
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_file(&proto_path).expect("Failed to process proto file");
+    assert!(result.metrics.is_none());
+
+    let config = ConfigBuilder::new().collect_metrics(true).build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+
+    let result = coordinator.process_file(&proto_path).expect("Failed to process proto file");
+    let metrics = result.metrics.expect("Metrics should be collected");
+    assert!(!metrics.parse.is_zero());
+    assert!(!metrics.validate.is_zero());
+    assert!(!metrics.extract.is_zero());
+    assert!(!metrics.generate.is_zero());
+    assert_eq!(metrics.total(), metrics.parse + metrics.validate + metrics.extract + metrics.generate);
+
+    let batch_result = coordinator.process_files(&[&proto_path]).expect("Batch processing failed");
+    let batch_metrics = batch_result.metrics.expect("Batch metrics should be collected");
+    assert!(!batch_metrics.total().is_zero());
+}
+
+/// Test that server-streaming methods generate event stream endpoints
+#[test]
+fn test_server_streaming_generates_event_stream() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service WatchService {
+    rpc Watch(WatchRequest) returns (stream Event) {
+        option (google.api.http) = {
+            get: "/v1/watch/{topic}"
+        };
+    }
+}
+
+message WatchRequest {
+    string topic = 1;
+}
+
+message Event {
+    string payload = 1;
+}
+"#;
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let controller = &result.generated_files["watch_service_controller.rs"];
+    assert!(
+        controller.content.contains("poem_openapi::payload::EventStream<futures::stream::BoxStream<'static, Event>>"),
+        "Controller should return an event stream:\n{}",
+        controller.content
+    );
+    assert!(controller.content.contains("poem_openapi::payload::EventStream::new("));
+    assert!(!controller.content.contains("poem_openapi::payload::Json<Event>"));
+    assert!(controller.dependencies.contains(&"futures".to_string()));
+
+    // The first failing item ends the stream with its error logged instead of being skipped
+    assert!(controller.content.contains("futures::StreamExt::scan(result, (), |_, item| futures::future::ready(match item {"));
+    assert!(controller.content.contains("tracing::error!(\"WatchService.Watch event stream failed: {:?}\", error);"), "Missing error log:\n{}", controller.content);
+    assert!(!controller.content.contains("item.ok()"));
+    assert!(controller.dependencies.contains(&"tracing".to_string()));
+
+    let service = &result.generated_files["watch_service_service.rs"];
+    assert!(
+        service.content.contains("Result<futures::stream::BoxStream<'static, Result<Event, Box<dyn std::error::Error + Send + Sync>>>, Box<dyn std::error::Error>>"),
+        "Service trait should return a stream:\n{}",
+        service.content
+    );
+
+    // Streams of `google.protobuf.Empty` cannot be sent as events
+    let empty_stream_proto = proto_content.replace("returns (stream Event)", "returns (stream google.protobuf.Empty)");
+    match coordinator.process_content(&empty_stream_proto) {
+        Err(ProtoHttpParserError::CodeGeneration(CodeGenerationError::UnsupportedTypeMapping { from_type, .. })) => {
+            assert_eq!(from_type, "stream google.protobuf.Empty");
+        }
+        other => panic!("Expected UnsupportedTypeMapping, got: {:?}", other),
+    }
+}
+
+/// Test HTTP bindings on streaming methods: server streaming is served as
+/// server-sent events on the bound route, client streaming is rejected
+#[test]
+fn test_http_bindings_on_streaming_methods() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service LogService {
+    rpc TailLogs(TailLogsRequest) returns (stream LogLine) {
+        option (google.api.http) = {
+            get: "/v1/apps/{app}/logs:tail"
+        };
+    }
+}
+
+message TailLogsRequest {
+    string app = 1;
+}
+
+message LogLine {
+    string text = 1;
+}
+"#;
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+    assert_eq!(result.routes.len(), 1);
+    assert!(result.routes[0].response_type.is_stream);
+
+    let controller = &result.generated_files["log_service_controller.rs"].content;
+    assert!(controller.contains(r#"#[oai(path = "/v1/apps/{app}/logs:tail", method = "get")]"#), "Missing route:\n{}", controller);
+    assert!(controller.contains("poem_openapi::payload::EventStream<futures::stream::BoxStream<'static, LogLine>>"));
+
+    let spec = PoemOpenApiGenerator::new().generate_openapi_spec(&result.proto_file, &result.routes);
+    let operation = spec["paths"].as_object().unwrap().values().next().unwrap();
+    assert!(operation["get"]["responses"]["200"]["content"]["text/event-stream"].is_object(), "Missing SSE response:\n{}", spec);
+
+    let client_streaming = proto_content.replace("rpc TailLogs(TailLogsRequest)", "rpc TailLogs(stream TailLogsRequest)");
+    match coordinator.process_content(&client_streaming) {
+        Err(ProtoHttpParserError::CodeGeneration(error @ CodeGenerationError::UnsupportedClientStreaming { .. })) => {
+            assert!(error.to_string().contains("LogService.TailLogs"));
+        }
+        other => panic!("Expected UnsupportedClientStreaming, got: {:?}", other),
+    }
+}
+
+/// Test that import chains deeper than `max_import_depth` are rejected
+#[test]
+fn test_import_depth_limit() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    // root.proto -> a.proto -> b.proto -> c.proto
+    let files = [
+        ("root.proto", Some("a.proto")),
+        ("a.proto", Some("b.proto")),
+        ("b.proto", Some("c.proto")),
+        ("c.proto", None),
+    ];
+    for (name, import) in files {
+        let import_line = import
+            .map(|path| format!("import \"{}\";\n", path))
+            .unwrap_or_default();
+        let message_name = name.trim_end_matches(".proto").to_uppercase();
+        let content = format!(
+            "syntax = \"proto3\";\n\npackage test.v1;\n\n{}\nmessage Message{} {{\n    string id = 1;\n}}\n",
+            import_line, message_name
+        );
+        std::fs::write(temp_dir.path().join(name), content).expect("Failed to write proto file");
+    }
+
+    let config = ConfigBuilder::new()
+        .add_include_path(temp_dir.path())
+        .max_import_depth(2)
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+
+    match coordinator.process_file(temp_dir.path().join("root.proto")) {
+        Err(ProtoHttpParserError::Parse(ParseError::ImportDepthExceeded { max_depth, chain })) => {
+            assert_eq!(max_depth, 2);
+            assert_eq!(chain.len(), 4, "Unexpected chain: {:?}", chain);
+            assert!(chain[0].ends_with("root.proto"));
+            assert!(chain[3].ends_with("c.proto"));
+        }
+        other => panic!("Expected ImportDepthExceeded, got: {:?}", other),
+    }
+
+    // A chain within the limit parses successfully
+    let result = coordinator.process_file(temp_dir.path().join("a.proto"));
+    assert!(result.is_ok(), "Import chain within the limit should parse: {:?}", result.err());
+}
+
+/// Test that the depth limit holds when the imported files were already parsed and cached
+#[test]
+fn test_import_depth_limit_with_cached_imports() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    // root.proto -> a.proto -> b.proto -> c.proto -> d.proto
+    let files = [
+        ("root.proto", Some("a.proto")),
+        ("a.proto", Some("b.proto")),
+        ("b.proto", Some("c.proto")),
+        ("c.proto", Some("d.proto")),
+        ("d.proto", None),
+    ];
+    for (name, import) in files {
+        let import_line = import
+            .map(|path| format!("import \"{}\";\n", path))
+            .unwrap_or_default();
+        let message_name = name.trim_end_matches(".proto").to_uppercase();
+        let content = format!(
+            "syntax = \"proto3\";\n\npackage test.v1;\n\n{}\nmessage Message{} {{\n    string id = 1;\n}}\n",
+            import_line, message_name
+        );
+        std::fs::write(temp_dir.path().join(name), content).expect("Failed to write proto file");
+    }
+
+    let config = ConfigBuilder::new()
+        .add_include_path(temp_dir.path())
+        .max_import_depth(3)
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+
+    // Processing a.proto first caches every file of its chain, which is within the limit
+    let result = coordinator.process_file(temp_dir.path().join("a.proto"));
+    assert!(result.is_ok(), "Import chain within the limit should parse: {:?}", result.err());
+
+    // root.proto is one import further from d.proto, so it still exceeds the limit
+    match coordinator.process_file(temp_dir.path().join("root.proto")) {
+        Err(ProtoHttpParserError::Parse(ParseError::ImportDepthExceeded { max_depth, chain })) => {
+            assert_eq!(max_depth, 3);
+            assert_eq!(chain.len(), 5, "Unexpected chain: {:?}", chain);
+            assert!(chain[0].ends_with("root.proto"));
+            assert!(chain[4].ends_with("d.proto"));
+        }
+        other => panic!("Expected ImportDepthExceeded, got: {:?}", other),
+    }
+}
+
+/// Test combining a controller and service trait into a single fragment
+#[test]
+fn test_generated_code_combine() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{id}"
+        };
+    }
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let controller = result.generated_files["user_service_controller.rs"].clone();
+    let service = result.generated_files["user_service_service.rs"].clone();
+
+    // Both fragments import the message types and carry the lint attribute
+    let shared_use = "use super::{User};";
+    assert!(controller.content.contains(shared_use));
+    assert!(service.content.contains(shared_use));
+
+    let combined = controller.clone().combine(service.clone());
+
+    assert_eq!(combined.content.matches(shared_use).count(), 1, "Duplicate use:\n{}", combined.content);
+    assert_eq!(combined.content.matches("#![allow(").count(), 1);
+    assert!(combined.content.starts_with("#![allow("));
+    assert!(combined.content.contains("pub struct UserServiceController"));
+    assert!(combined.content.contains("pub trait UserServiceService"));
+
+    // Import and dependency metadata is unioned without duplicates
+    for import in controller.imports.iter().chain(service.imports.iter()) {
+        assert_eq!(combined.imports.iter().filter(|i| *i == import).count(), 1, "Duplicate import {}", import);
+    }
+    assert!(combined.dependencies.contains(&"poem-openapi".to_string()));
+    assert!(combined.dependencies.contains(&"async-trait".to_string()));
+}
+
+/// Test validating a directory without generating code
+#[test]
+fn test_validate_directory() {
+    let temp_dir = TempDir::new().unwrap();
+
+    std::fs::write(temp_dir.path().join("valid.proto"), r#"
+syntax = "proto3";
+
+package valid.v1;
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{id}"
+        };
+    }
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#).unwrap();
+
+    // References a response message that is never defined
+    std::fs::write(temp_dir.path().join("invalid.proto"), r#"
+syntax = "proto3";
+
+package invalid.v1;
+
+service OrderService {
+    rpc GetOrder(GetOrderRequest) returns (MissingOrder) {
+        option (google.api.http) = {
+            get: "/v1/orders/{id}"
+        };
+    }
+}
+
+message GetOrderRequest {
+    string id = 1;
+}
+"#).unwrap();
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let (all_valid, reports) = coordinator.validate_directory(temp_dir.path())
+        .expect("Failed to validate directory");
+
+    assert!(!all_valid);
+    assert_eq!(reports.len(), 2);
+
+    let report_for = |name: &str| {
+        reports.iter()
+            .find(|(path, _)| path.file_name().unwrap() == name)
+            .map(|(_, report)| report)
+            .unwrap_or_else(|| panic!("Missing report for {}", name))
+    };
+
+    let valid_report = report_for("valid.proto");
+    assert!(valid_report.is_valid, "Unexpected errors: {:?}", valid_report.errors);
+    assert!(valid_report.errors.is_empty());
+
+    let invalid_report = report_for("invalid.proto");
+    assert!(!invalid_report.is_valid);
+    assert!(invalid_report.errors.iter().any(|error| error.to_string().contains("MissingOrder")),
+        "Expected an undefined type error, got: {:?}", invalid_report.errors);
+
+    // Validation never writes generated code
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 2);
+}
+
+/// Test pagination helper generation for AIP-style list methods
+#[test]
+fn test_pagination_helper_generation() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc ListUsers(ListUsersRequest) returns (ListUsersResponse) {
+        option (google.api.http) = {
+            get: "/v1/users"
+        };
+    }
+
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{id}"
+        };
+    }
+}
+
+message ListUsersRequest {
+    int32 page_size = 1;
+    string page_token = 2;
+}
+
+message ListUsersResponse {
+    repeated User users = 1;
+    string next_page_token = 2;
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+
+    // The convention is detected regardless of the generator setting
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+    let list_route = result.routes.iter().find(|route| route.method_name == "ListUsers").unwrap();
+    let get_route = result.routes.iter().find(|route| route.method_name == "GetUser").unwrap();
+    assert!(list_route.is_paginated);
+    assert!(!get_route.is_paginated);
+
+    // Helpers are only emitted when enabled
+    let controller = &result.generated_files["user_service_controller.rs"];
+    assert!(!controller.content.contains("list_users_all_pages"));
+
+    let config = ConfigBuilder::new()
+        .generate_pagination_helpers(true)
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let controller = &result.generated_files["user_service_controller.rs"];
+    assert!(controller.content.contains("pub async fn list_users_all_pages"),
+        "Missing pagination helper:\n{}", controller.content);
+    assert!(controller.content.contains("page_token"));
+    assert!(controller.content.contains("Result<Vec<ListUsersResponse>, E>"));
+    assert!(!controller.content.contains("get_user_all_pages"));
+}
+
+/// Test container types for repeated and map fields in generated models
+#[test]
+fn test_model_container_field_types() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+enum Role {
+    ROLE_UNSPECIFIED = 0;
+    ROLE_ADMIN = 1;
+}
+
+message User {
+    string id = 1;
+}
+
+message Team {
+    string name = 1;
+    repeated string tags = 2;
+    repeated User users = 3;
+    repeated Role roles = 4;
+    map<string, int32> scores = 5;
+    map<string, User> members = 6;
+}
+"#;
+
+    let parser = NomProtoParser::new();
+    let proto_file = parser.parse_content(proto_content).expect("Failed to parse proto content");
+    let team = proto_file.find_message("Team").unwrap();
+
+    assert_eq!(team.fields[4].field_type, FieldType::Map {
+        key: Box::new(FieldType::String),
+        value: Box::new(FieldType::Int32),
+    });
+
+    let generator = PoemOpenApiGenerator::new();
+    let model = generator.generate_model(team).expect("Failed to generate model");
+
+    assert!(model.content.contains("pub struct Team"), "Missing struct:\n{}", model.content);
+    assert!(model.content.contains("pub name: String,"));
+    assert!(model.content.contains("pub tags: Vec<String>,"), "Repeated scalar:\n{}", model.content);
+    assert!(model.content.contains("pub users: Vec<User>,"));
+    assert!(model.content.contains("pub roles: Vec<Role>,"));
+    assert!(model.content.contains("pub scores: std::collections::HashMap<String, i32>,"));
+    assert!(model.content.contains("pub members: std::collections::HashMap<String, User>,"));
+}
+
+/// Test gating operations behind `#[cfg(...)]` via a method option
+#[test]
+fn test_route_cfg_option() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service DebugService {
+    rpc GetStatus(GetStatusRequest) returns (Status) {
+        option (google.api.http) = {
+            get: "/v1/status"
+        };
+    }
+
+    rpc DumpState(GetStatusRequest) returns (Status) {
+        option (debug_only) = true;
+        option (google.api.http) = {
+            get: "/v1/debug/state"
+        };
+    }
+
+    rpc ResetState(GetStatusRequest) returns (Status) {
+        option (debug_only) = "feature = \"admin\"";
+        option (google.api.http) = {
+            post: "/v1/debug/reset"
+            body: "*"
+        };
+    }
+}
+
+message GetStatusRequest {
+    string id = 1;
+}
+
+message Status {
+    string state = 1;
+}
+"#;
+
+    // Without a configured option no cfg attributes are emitted
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+    assert!(!result.generated_files["debug_service_controller.rs"].content.contains("#[cfg("));
+
+    let config = ConfigBuilder::new()
+        .route_cfg_option("(debug_only)")
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let controller = &result.generated_files["debug_service_controller.rs"].content;
+    assert!(controller.contains("#[cfg(debug_assertions)]"), "Missing debug cfg:\n{}", controller);
+    assert!(controller.contains("#[cfg(feature = \"admin\")]"), "Missing feature cfg:\n{}", controller);
+    assert_eq!(controller.matches("#[cfg(").count(), 2);
+
+    // The attribute is attached to the gated operation
+    let cfg_pos = controller.find("#[cfg(debug_assertions)]").unwrap();
+    let dump_pos = controller.find("async fn dump_state(").unwrap();
+    let status_pos = controller.find("async fn get_status(").unwrap();
+    assert!(cfg_pos < dump_pos);
+    assert!(!(status_pos > cfg_pos && status_pos < dump_pos));
+}
+
+/// Test choosing the response payload from a method option and the configured default
+#[test]
+fn test_response_content_type() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service ReportService {
+    rpc GetReport(GetReportRequest) returns (Report) {
+        option (google.api.http) = {
+            get: "/v1/reports/{id}"
+        };
+    }
+
+    rpc RenderReport(GetReportRequest) returns (ReportText) {
+        option (response_content_type) = "text/plain";
+        option (google.api.http) = {
+            get: "/v1/reports/{id}:render"
+        };
+    }
+}
+
+message GetReportRequest {
+    string id = 1;
+}
+
+message Report {
+    string title = 1;
+}
+
+message ReportText {
+    string text = 1;
+}
+"#;
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let controller = &result.generated_files["report_service_controller.rs"].content;
+    assert!(controller.contains("-> poem_openapi::payload::Json<Report>"), "Missing JSON response:\n{}", controller);
+    assert!(controller.contains("-> poem_openapi::payload::PlainText<ReportText>"), "Missing plain text response:\n{}", controller);
+    assert!(controller.contains("poem_openapi::payload::PlainText(result)"));
+    assert!(!controller.contains("payload::Json<ReportText>"));
+
+    // The configured default applies to methods without the option
+    let config = ConfigBuilder::new()
+        .default_response_content_type(ResponseContentType::Binary)
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let controller = &result.generated_files["report_service_controller.rs"].content;
+    assert!(controller.contains("-> poem_openapi::payload::Binary<Report>"), "Missing binary response:\n{}", controller);
+    assert!(controller.contains("-> poem_openapi::payload::PlainText<ReportText>"));
+}
+
+/// Test emitting OpenAPI examples from `@example` message comments
+#[test]
+fn test_message_example_directive() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+// A user account
+// @example: {"name": "Ada", "age": 36}
+message User {
+    string name = 1;
+    int32 age = 2;
+}
+
+// @example: {"name": }
+message Broken {
+    string name = 1;
+}
+"#;
+
+    let parser = NomProtoParser::new();
+    let proto_file = parser.parse_content(proto_content).expect("Failed to parse proto content");
+    let generator = PoemOpenApiGenerator::new();
+
+    let user = proto_file.find_message("User").unwrap();
+    let model = generator.generate_model(user).expect("Failed to generate model");
+    assert!(model.content.contains("#[oai(example)]"), "Missing example attribute:\n{}", model.content);
+    assert!(model.content.contains("impl poem_openapi::types::Example for User"));
+    assert!(model.content.contains(r#"serde_json::from_str("{\"age\":36,\"name\":\"Ada\"}")"#),
+        "Missing example value:\n{}", model.content);
+
+    // Malformed examples are skipped during generation and reported as warnings
+    let broken = proto_file.find_message("Broken").unwrap();
+    let model = generator.generate_model(broken).expect("Malformed example should not fail generation");
+    assert!(!model.content.contains("#[oai(example)]"));
+
+    let report = ValidationEngine::new().validate_proto_file(&proto_file).unwrap();
+    assert!(report.is_valid);
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].message.contains("Broken"), "Unexpected warning: {}", report.warnings[0].message);
+}
+
+/// Test flattening path and query parameters across all routes
+#[test]
+fn test_process_result_all_parameters() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{id}"
+        };
+    }
+
+    rpc GetPost(GetPostRequest) returns (Post) {
+        option (google.api.http) = {
+            get: "/v1/users/{user_id}/posts/{post_id}"
+        };
+    }
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message GetPostRequest {
+    string user_id = 1;
+    string post_id = 2;
+    int32 page_size = 3;
+}
+
+message User {
+    string name = 1;
+}
+
+message Post {
+    string title = 1;
+}
+"#;
+
+    let mut config = ConfigBuilder::new().build_unchecked();
+    config.extractor.common_query_params = vec!["page_size".to_string()];
+    config.extractor.query_param_inference = QueryParamInference::AllMethods;
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let parameters = result.all_parameters();
+    assert_eq!(parameters.len(), 5, "Unexpected parameters: {:#?}", parameters);
+
+    let path_parameters: Vec<_> = parameters.iter().filter(|p| p.kind == ParameterKind::Path).collect();
+    assert_eq!(path_parameters.len(), 3);
+    assert!(path_parameters.iter().all(|p| p.required && p.service_name == "UserService"));
+
+    let post_id = parameters.iter().find(|p| p.name == "post_id").unwrap();
+    assert_eq!(post_id.method_name, "GetPost");
+    assert_eq!(post_id.kind, ParameterKind::Path);
+    assert_eq!(post_id.param_type, ParameterType::String);
+
+    let page_sizes: Vec<_> = parameters.iter().filter(|p| p.name == "page_size").collect();
+    assert_eq!(page_sizes.len(), 2);
+    assert!(page_sizes.iter().all(|p| p.kind == ParameterKind::Query && !p.required));
+}
+
+/// Test binding `google.protobuf.Timestamp` fields to `chrono::DateTime` parameters
+#[test]
+fn test_timestamp_parameters() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "google/protobuf/timestamp.proto";
+
+service EventService {
+    rpc ListEvents(ListEventsRequest) returns (ListEventsResponse) {
+        option (google.api.http) = {
+            get: "/v1/events"
+        };
+    }
+}
+
+message ListEventsRequest {
+    google.protobuf.Timestamp since = 1;
+}
+
+message ListEventsResponse {
+    repeated string names = 1;
+}
+"#;
+
+    let mut config = ConfigBuilder::new().build_unchecked();
+    config.extractor.common_query_params = vec!["since".to_string()];
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    assert_eq!(result.routes[0].query_parameters[0].param_type, ParameterType::Timestamp);
+
+    let controller = &result.generated_files["event_service_controller.rs"];
+    assert!(controller.content.contains("since: Query<Option<chrono::DateTime<chrono::Utc>>>"),
+        "Missing DateTime query parameter:\n{}", controller.content);
+    assert!(controller.dependencies.contains(&"chrono".to_string()));
+
+    let service = &result.generated_files["event_service_service.rs"].content;
+    assert!(service.contains("since: Option<chrono::DateTime<chrono::Utc>>"), "Missing DateTime argument:\n{}", service);
+}
+
+/// Test generating fluent builders for model structs
+#[test]
+fn test_model_builders() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+message CreateUserRequest {
+    string name = 1 [(google.api.field_behavior) = REQUIRED];
+    string email = 2;
+    repeated string roles = 3;
+}
+"#;
+
+    let parser = NomProtoParser::new();
+    let proto_file = parser.parse_content(proto_content).expect("Failed to parse proto content");
+    let request = proto_file.find_message("CreateUserRequest").unwrap();
+
+    // Builders are opt-in
+    let model = PoemOpenApiGenerator::new().generate_model(request).expect("Failed to generate model");
+    assert!(!model.content.contains("fn builder()"));
+
+    let config = ConfigBuilder::new()
+        .generate_builders(true)
+        .build_unchecked();
+    let generator = PoemOpenApiGenerator::with_config(config.generator);
+    let model = generator.generate_model(request).expect("Failed to generate model");
+
+    assert!(model.content.contains("pub fn builder() -> CreateUserRequestBuilder"), "Missing builder():\n{}", model.content);
+    assert!(model.content.contains("pub struct CreateUserRequestBuilder"));
+    assert!(model.content.contains("pub fn name(mut self, value: String) -> Self"));
+    assert!(model.content.contains("pub fn email(mut self, value: String) -> Self"));
+    assert!(model.content.contains("pub fn roles(mut self, value: Vec<String>) -> Self"));
+    assert!(model.content.contains("pub fn build(self) -> Result<CreateUserRequest, String>"));
+
+    // Required fields are checked when building, optional ones stay unset and
+    // collections fall back to defaults
+    assert!(model.content.contains("name: self.name.ok_or_else(|| \"missing required field `name`\".to_string())?,"),
+        "Required field should be checked:\n{}", model.content);
+    assert!(model.content.contains("email: self.email,"));
+    assert!(model.content.contains("roles: self.roles.unwrap_or_default(),"));
+}
+
+/// Test omitting bodies for empty request and response messages
+#[test]
+fn test_empty_request_and_response_messages() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "google/protobuf/empty.proto";
+
+service CacheService {
+    rpc ClearCache(ClearCacheRequest) returns (google.protobuf.Empty) {
+        option (google.api.http) = {
+            post: "/v1/cache:clear"
+            body: "*"
+        };
+    }
+
+    rpc Ping(google.protobuf.Empty) returns (PingResponse) {
+        option (google.api.http) = {
+            post: "/v1/ping"
+            body: "*"
+        };
+    }
+}
+
+message ClearCacheRequest {
+    string region = 1;
+}
+
+message PingResponse {
+    string status = 1;
+}
+"#;
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let ping = result.routes.iter().find(|route| route.method_name == "Ping").unwrap();
+    assert!(ping.request_body.is_none());
+
+    let controller = &result.generated_files["cache_service_controller.rs"].content;
+    assert!(!controller.contains("Json<()>"), "Empty response should not be JSON:\n{}", controller);
+    assert!(!controller.contains("Json<Empty>"));
+
+    // The empty response handler returns nothing
+    let clear_start = controller.find("async fn clear_cache(").unwrap();
+    let ping_start = controller.find("async fn ping(").unwrap();
+    let clear_handler = &controller[clear_start..ping_start];
+    assert!(clear_handler.contains("body: Json<ClearCacheRequest>"));
+    assert!(!clear_handler.contains("->"), "Empty response handler should return unit:\n{}", clear_handler);
+
+    // The empty request handler takes no body
+    let ping_handler = &controller[ping_start..];
+    assert!(!ping_handler.contains("body:"), "Empty request should have no body:\n{}", ping_handler);
+    assert!(ping_handler.contains("-> poem_openapi::payload::Json<PingResponse>"));
+
+    let service = &result.generated_files["cache_service_service.rs"].content;
+    assert!(service.contains("-> Result<(), Box<dyn std::error::Error>>;"), "Service should return unit:\n{}", service);
+}
+
+/// Test that `collect_all_errors` reports every validation error of a file
+#[test]
+fn test_collect_all_validation_errors() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+message User {
+  string id = 0;
+  string name = 19000;
+  string email = 19999;
+}
+"#;
+
+    // By default only the first error is reported
+    let coordinator = ProtoHttpCoordinator::new();
+    match coordinator.process_content(proto_content) {
+        Err(ProtoHttpParserError::Validation(ValidationError::InvalidFieldNumber { field_name, .. })) => {
+            assert_eq!(field_name, "id");
+        }
+        other => panic!("Expected a single validation error, got {:?}", other),
+    }
+
+    let config = ConfigBuilder::new()
+        .collect_all_errors(true)
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let errors = match coordinator.process_content(proto_content) {
+        Err(ProtoHttpParserError::Multiple(errors)) => errors,
+        other => panic!("Expected multiple errors, got {:?}", other),
+    };
+
+    let field_names: Vec<_> = errors.iter()
+        .map(|error| match error {
+            ProtoHttpParserError::Validation(ValidationError::InvalidFieldNumber { field_name, .. }) => field_name.as_str(),
+            other => panic!("Unexpected error: {:?}", other),
+        })
+        .collect();
+    assert_eq!(field_names, ["id", "name", "email"]);
+
+    let message = ProtoHttpParserError::Multiple(errors).to_string();
+    assert!(message.starts_with("3 errors occurred: "), "Unexpected message: {}", message);
+
+    // Checking type references alone also reports every undefined type
+    let proto_file = NomProtoParser::new().parse_content(r#"
+syntax = "proto3";
+
+message User {
+  Missing first = 1;
+  Absent second = 2;
+  Unknown third = 3;
+}
+"#).expect("Failed to parse proto content");
+    let types = match ValidationEngine::new().validate_type_references(&proto_file) {
+        Err(ValidationError::Multiple(errors)) => errors.into_iter()
+            .map(|error| match error {
+                ValidationError::UndefinedType { type_name, .. } => type_name,
+                other => panic!("Unexpected error: {:?}", other),
+            })
+            .collect::<Vec<_>>(),
+        other => panic!("Expected multiple errors, got {:?}", other),
+    };
+    assert_eq!(types, ["Missing", "Absent", "Unknown"]);
+}
+
+/// Test that query parameters backed by nested messages use the deepObject style
+#[test]
+fn test_deep_object_query_parameter() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc ListUsers(ListUsersRequest) returns (ListUsersResponse) {
+    option (google.api.http) = {
+      get: "/v1/users"
+    };
+  }
+}
+
+message UserFilter {
+  string status = 1;
+}
+
+message ListUsersRequest {
+  UserFilter filter = 1;
+  int32 limit = 2;
+}
+
+message ListUsersResponse {
+  repeated string ids = 1;
+}
+"#;
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let route = &result.routes[0];
+    let filter = route.query_parameters.iter().find(|param| param.name == "filter").unwrap();
+    assert_eq!(filter.style, QueryParameterStyle::DeepObject);
+    assert_eq!(filter.param_type, ParameterType::Custom("UserFilter".to_string()));
+    let limit = route.query_parameters.iter().find(|param| param.name == "limit").unwrap();
+    assert_eq!(limit.style, QueryParameterStyle::Form);
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(
+        controller.contains(r#"#[oai(style = "deepObject")] #[oai(default)] filter: Query<Option<UserFilter>>"#),
+        "Filter should be a deepObject query parameter:\n{}",
+        controller
+    );
+    assert!(controller.contains("#[oai(default)] limit: Query<Option<i32>>"));
+    assert_eq!(controller.matches("deepObject").count(), 1);
+}
+
+/// Test that model fields named after Rust keywords keep their wire name
+#[test]
+fn test_model_keyword_field_names() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+message Resource {
+    string type = 1;
+    string self = 2;
+    string name = 3;
+}
+"#;
+
+    let parser = NomProtoParser::new();
+    let proto_file = parser.parse_content(proto_content).expect("Failed to parse proto content");
+    let resource = proto_file.find_message("Resource").unwrap();
+
+    let model = PoemOpenApiGenerator::new().generate_model(resource).expect("Failed to generate model");
+    // Raw identifiers serialize without the `r#` prefix
+    assert!(model.content.contains("pub r#type: String,"), "Missing raw identifier:\n{}", model.content);
+    // Keywords that cannot be raw identifiers are renamed back on the wire
+    assert!(model.content.contains("#[serde(rename = \"self\")]\n    #[oai(rename = \"self\")]\n    pub self_: String,"), "Missing renamed field:\n{}", model.content);
+    assert!(model.content.contains("pub name: String,"));
+    assert_eq!(model.content.matches("rename").count(), 2);
+}
+
+/// Test that methods named after Rust keywords become raw identifiers without changing their route
+#[test]
+fn test_keyword_method_names() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "google/api/annotations.proto";
+
+service ItemService {
+  rpc Move(MoveRequest) returns (Item) {
+    option (google.api.http) = {
+      post: "/v1/items/{id}/move"
+      body: "*"
+    };
+  }
+}
+
+message MoveRequest {
+  string id = 1;
+  string destination = 2;
+}
+
+message Item {
+  string id = 1;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .generate_mocks(true)
+        .build()
+        .unwrap();
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let service_trait = &result.generated_files["item_service_service.rs"].content;
+    assert!(service_trait.contains("async fn r#move("), "Trait method should be a raw identifier:\n{}", service_trait);
+
+    let controller = &result.generated_files["item_service_controller.rs"].content;
+    assert!(controller.contains(r#"#[oai(path = "/v1/items/{id}/move", method = "post")]"#), "Route should be unchanged:\n{}", controller);
+    assert!(controller.contains("async fn r#move("));
+    assert!(controller.contains("self.service.r#move("));
+
+    // Names derived from the method stay plain identifiers
+    let mock = &result.generated_files["item_service_mock.rs"].content;
+    assert!(mock.contains("pub fn on_move("), "Mock setter should not embed a raw identifier:\n{}", mock);
+    assert!(mock.contains("r#move: Option<Box<dyn Fn("));
+}
+
+/// Test that content processed under a virtual path matches processing the file itself
+#[test]
+fn test_process_content_named() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let proto_path = temp_dir.path().join("orders.proto");
+    std::fs::write(&proto_path, proto_content).expect("Failed to write proto file");
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let from_file = coordinator.process_file(&proto_path).expect("Failed to process proto file");
+    let named = coordinator.process_content_named(proto_content, &proto_path)
+        .expect("Failed to process named content");
+
+    assert_eq!(named.source_path.as_deref(), Some(proto_path.as_path()));
+    assert_eq!(named.source_path, from_file.source_path);
+    assert!(named.type_registry.dependencies.contains_key(proto_path.to_string_lossy().as_ref()));
+
+    // Generated files are named after the virtual path
+    let mut named_files: Vec<_> = named.generated_files.keys().cloned().collect();
+    let mut file_files: Vec<_> = from_file.generated_files.keys()
+        .map(|filename| format!("orders/{}", filename))
+        .collect();
+    named_files.sort();
+    file_files.sort();
+    assert_eq!(named_files, file_files);
+    assert!(named.generated_files.contains_key("orders/user_service_controller.rs"));
+
+    let output_dir = temp_dir.path().join("output");
+    coordinator.write_generated_code(&named, &output_dir).expect("Failed to write generated code");
+    assert!(output_dir.join("orders/user_service_controller.rs").exists());
+
+    // Anonymous content has no source path
+    let anonymous = coordinator.process_content(proto_content).expect("Failed to process proto content");
+    assert!(anonymous.source_path.is_none());
+}
+
+/// Test that in-memory build generation matches the files written to disk
+#[test]
+fn test_build_integration_generate_to_memory() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let proto_path = temp_dir.path().join("user_service.proto");
+    std::fs::write(&proto_path, proto_content).expect("Failed to write proto file");
+
+    let memory_output_dir = temp_dir.path().join("memory_output");
+    let in_memory = BuildIntegration::new()
+        .add_proto_file(&proto_path)
+        .output_dir(&memory_output_dir)
+        .generate_to_memory()
+        .expect("In-memory generation failed");
+    assert!(!memory_output_dir.exists(), "Nothing should be written to disk");
+
+    let output_dir = temp_dir.path().join("build_output");
+    let build_result = BuildIntegration::new()
+        .add_proto_file(&proto_path)
+        .output_dir(&output_dir)
+        .generate()
+        .expect("Build integration failed");
+
+    assert_eq!(in_memory.len(), build_result.file_count());
+    assert!(in_memory.contains_key("user_service_controller.rs"));
+    for path in &build_result.generated_files {
+        let filename = path.file_name().unwrap().to_string_lossy();
+        let written = std::fs::read_to_string(path).expect("Failed to read generated file");
+        assert_eq!(in_memory[filename.as_ref()], written, "{} differs from the written file", filename);
+    }
+}
+
+/// Test that common query parameters are only inferred for list-style methods by default
+#[test]
+fn test_query_param_inference_list_methods() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+
+  rpc ListUsers(ListUsersRequest) returns (ListUsersResponse) {
+    option (google.api.http) = {
+      get: "/v1/users"
+    };
+  }
+
+  rpc RecentUsers(RecentUsersRequest) returns (RecentUsersResponse) {
+    option (google.api.http) = {
+      get: "/v1/users:recent"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message ListUsersRequest {
+  int32 page = 1;
+}
+
+message ListUsersResponse {
+  repeated User users = 1;
+}
+
+message RecentUsersRequest {}
+
+message RecentUsersResponse {
+  repeated User users = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let route = |name: &str| result.routes.iter().find(|r| r.method_name == name).unwrap();
+    assert!(route("GetUser").query_parameters.is_empty());
+    assert!(route("ListUsers").query_parameters.iter().any(|param| param.name == "page"));
+    assert!(route("RecentUsers").query_parameters.iter().any(|param| param.name == "limit"));
+
+    let config = ConfigBuilder::new()
+        .query_param_inference(QueryParamInference::AllMethods)
+        .build()
+        .expect("Failed to build config");
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let get_user = result.routes.iter().find(|r| r.method_name == "GetUser").unwrap();
+    assert!(get_user.query_parameters.iter().any(|param| param.name == "page"));
+}
+
+/// Test that the errors module defines `ApiError` and controllers map service errors into it
+#[test]
+fn test_error_module_generation() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+import "google/protobuf/empty.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+
+  rpc DeleteUser(GetUserRequest) returns (google.protobuf.Empty) {
+    option (google.api.http) = {
+      delete: "/v1/users/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    // Disabled by default
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+    assert!(!result.generated_files.contains_key(ERRORS_FILENAME));
+    assert!(result.generated_files["user_service_controller.rs"].content.contains(".await.unwrap();"));
+
+    let config = ConfigBuilder::new()
+        .generate_error_module(true)
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let errors = &result.generated_files[ERRORS_FILENAME].content;
+    assert!(errors.contains("#[derive(Debug, poem_openapi::ApiResponse)]"), "Missing derive:\n{}", errors);
+    assert!(errors.contains("pub enum ApiError {"));
+    assert!(errors.contains("NotFound(PlainText<String>),"));
+    assert!(errors.contains("impl From<Box<dyn std::error::Error>> for ApiError"));
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(controller.contains("use super::errors::ApiError;"), "Missing import:\n{}", controller);
+    assert!(controller.contains("-> Result<poem_openapi::payload::Json<User>, ApiError> {"));
+    assert!(controller.contains("-> Result<(), ApiError> {"));
+    assert!(controller.contains(".await.map_err(ApiError::from)?;"));
+    assert!(controller.contains("Ok(Json(result))"));
+    assert!(controller.contains("Ok(())"));
+    assert!(!controller.contains("unwrap()"));
+
+    // A custom service error type must provide its own conversion
+    let config = ConfigBuilder::new()
+        .generate_error_module(true)
+        .service_error_type("crate::Error")
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+    assert!(!result.generated_files[ERRORS_FILENAME].content.contains("impl From<"));
+}
+
+/// Test that read-only and write-only fields emit the matching poem-openapi attributes
+#[test]
+fn test_model_field_access_modes() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "google/api/field_behavior.proto";
+
+message User {
+    // @readonly
+    string id = 1;
+    string name = 2;
+    // Only accepted on signup
+    // @writeonly
+    string password = 3;
+    string etag = 4 [(google.api.field_behavior) = OUTPUT_ONLY];
+}
+
+message Plain {
+    message Address {
+        string city = 1;
+    }
+
+    string name = 1;
+    Address address = 2;
+}
+"#;
+
+    let parser = NomProtoParser::new();
+    let proto_file = parser.parse_content(proto_content).expect("Failed to parse proto content");
+    let generator = PoemOpenApiGenerator::new();
+
+    let user = proto_file.find_message("User").unwrap();
+    assert!(user.fields[0].is_read_only());
+    assert!(!user.fields[1].is_read_only() && !user.fields[1].is_write_only());
+    assert!(user.fields[2].is_write_only());
+    assert!(user.fields[3].is_read_only());
+
+    let model = generator.generate_model(user).expect("Failed to generate model");
+    assert!(model.content.contains("poem_openapi::Object)]"), "Access modes need the Object derive:\n{}", model.content);
+    assert!(model.content.contains("#[oai(read_only)]\n    pub id: String,"), "Missing read-only attribute:\n{}", model.content);
+    assert!(model.content.contains("#[oai(write_only)]\n    pub password: String,"));
+    assert!(model.content.contains("#[oai(read_only)]\n    pub etag: String,"));
+    assert_eq!(model.content.matches("#[oai(read_only)]").count(), 2);
+    assert!(!model.content.contains("#[oai(example)]"));
+
+    // Models without access modes still derive Object, as do the messages they reference
+    let plain = proto_file.find_message("Plain").unwrap();
+    let model = generator.generate_model(plain).expect("Failed to generate model");
+    assert!(model.content.contains("poem_openapi::Object)]"), "Missing Object derive:\n{}", model.content);
+    assert!(!model.content.contains("#[oai(read_only)]"));
+
+    let address = &plain.nested_messages[0];
+    let model = generator.generate_model(address).expect("Failed to generate model");
+    assert!(model.content.contains("poem_openapi::Object)]"), "Nested model needs the Object derive:\n{}", model.content);
+}
+
+/// Test that resource models get create/update variants leaving out output-only fields
+#[test]
+fn test_model_variants_generation() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "google/api/field_behavior.proto";
+
+message User {
+    string id = 1 [(google.api.field_behavior) = OUTPUT_ONLY];
+    string name = 2;
+    string etag = 3;
+}
+
+message Plain {
+    string name = 1;
+}
+"#;
+
+    let parser = NomProtoParser::new();
+    let proto_file = parser.parse_content(proto_content).expect("Failed to parse proto content");
+
+    // Disabled by default
+    let user = proto_file.find_message("User").unwrap();
+    let model = PoemOpenApiGenerator::new().generate_model(user).expect("Failed to generate model");
+    assert!(!model.content.contains("CreateUser"));
+
+    let config = ConfigBuilder::new()
+        .model_variants(ModelVariantConfig {
+            excluded_fields: vec!["etag".to_string()],
+            ..ModelVariantConfig::default()
+        })
+        .build()
+        .expect("Failed to build config");
+    let generator = PoemOpenApiGenerator::with_config(config.generator);
+
+    let model = generator.generate_model(user).expect("Failed to generate model");
+    let create = model.content.split("pub struct CreateUser {").nth(1)
+        .and_then(|rest| rest.split('}').next())
+        .unwrap_or_else(|| panic!("Missing create variant:\n{}", model.content));
+    assert!(create.contains("pub name: String,"));
+    assert!(!create.contains("pub id:"), "Output-only field should be omitted:\n{}", create);
+    assert!(!create.contains("pub etag:"), "Excluded field should be omitted:\n{}", create);
+
+    let update = model.content.split("pub struct UpdateUser {").nth(1)
+        .and_then(|rest| rest.split('}').next())
+        .unwrap_or_else(|| panic!("Missing update variant:\n{}", model.content));
+    assert!(update.contains("pub name: Option<String>,"));
+    assert!(update.contains("pub update_mask: Vec<String>,"));
+    assert!(!update.contains("pub id:"));
+
+    // The full resource keeps every field
+    assert!(model.content.contains("pub struct User {"));
+    assert!(model.content.contains("pub id: String,"));
+
+    // Messages that are not resources get no variants
+    let plain = proto_file.find_message("Plain").unwrap();
+    let model = generator.generate_model(plain).expect("Failed to generate model");
+    assert!(!model.content.contains("CreatePlain"));
+}
+
+/// Test that a visitor walks every service, method and nested definition of a file
+#[test]
+fn test_proto_file_visitor() {
+    #[derive(Default)]
+    struct Counter {
+        methods: Vec<String>,
+        messages: usize,
+        fields: usize,
+        enums: usize,
+    }
+
+    impl Visitor for Counter {
+        fn visit_method(&mut self, service: &Service, method: &RpcMethod) {
+            self.methods.push(format!("{}.{}", service.name, method.name));
+        }
+
+        fn visit_message(&mut self, _message: &Message) {
+            self.messages += 1;
+        }
+
+        fn visit_field(&mut self, _message: &Message, _field: &Field) {
+            self.fields += 1;
+        }
+
+        fn visit_enum(&mut self, _enum_def: &Enum) {
+            self.enums += 1;
+        }
+    }
+
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User);
+    rpc DeleteUser(GetUserRequest) returns (User);
+}
+
+service OrderService {
+    rpc GetOrder(GetUserRequest) returns (User);
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+    Profile profile = 2;
+
+    message Profile {
+        string bio = 1;
+        Visibility visibility = 2;
+
+        enum Visibility {
+            VISIBILITY_UNSPECIFIED = 0;
+        }
+    }
+}
+
+enum Status {
+    STATUS_UNSPECIFIED = 0;
+}
+"#;
+
+    let parser = NomProtoParser::new();
+    let proto_file = parser.parse_content(proto_content).expect("Failed to parse proto content");
+
+    let mut counter = Counter::default();
+    proto_file.accept(&mut counter);
+
+    assert_eq!(counter.methods, vec!["UserService.GetUser", "UserService.DeleteUser", "OrderService.GetOrder"]);
+    assert_eq!(counter.messages, 3);
+    assert_eq!(counter.fields, 5);
+    assert_eq!(counter.enums, 2);
+}
+
+/// Test that deprecated methods generate deprecated operations carrying their sunset date
+#[test]
+fn test_deprecated_operation_generation() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  // Fetch a user by id
+  // @deprecated: 2025-06-01
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+
+  rpc DeleteUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      delete: "/v1/users/{id}"
+    };
+    option deprecated = true;
+  }
+
+  rpc UpdateUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      patch: "/v1/users/{id}"
+      body: "*"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let route = |name: &str| result.routes.iter().find(|r| r.method_name == name).unwrap();
+    assert_eq!(route("GetUser").deprecation, Some(Deprecation { sunset: Some("2025-06-01".to_string()) }));
+    assert_eq!(route("DeleteUser").deprecation, Some(Deprecation::default()));
+    assert_eq!(route("UpdateUser").deprecation, None);
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(controller.contains(r#"#[oai(path = "/v1/users/{id}", method = "get", deprecated)]"#),
+        "GetUser should be deprecated:\n{}", controller);
+    assert!(controller.contains("/// Deprecated; sunset on 2025-06-01."));
+    assert!(controller.contains(r#"#[oai(path = "/v1/users/{id}", method = "delete", deprecated)]"#));
+    assert!(controller.contains(r#"#[oai(path = "/v1/users/{id}", method = "patch")]"#));
+    assert_eq!(controller.matches("/// Deprecated").count(), 2);
+}
+
+/// Test that casing overrides pin the snake_case form used in file names and module paths
+#[test]
+fn test_casing_overrides() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service gRPCService {
+  rpc GetStatus(GetStatusRequest) returns (Status) {
+    option (google.api.http) = {
+      get: "/v1/status"
+    };
+  }
+}
+
+message GetStatusRequest {}
+
+message Status {
+  string state = 1;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .casing_override("gRPCService", "grpc_service")
+        .generate_registration_helper(true)
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let controller = &result.generated_files.get("grpc_service_controller.rs")
+        .unwrap_or_else(|| panic!("Unexpected files: {:?}", result.generated_files.keys()))
+        .content;
+    assert!(result.generated_files.contains_key("grpc_service_service.rs"));
+    assert!(controller.contains("use super::grpc_service_service::"), "Module path should use the override:\n{}", controller);
+
+    let apis = &result.generated_files[REGISTRATION_FILENAME].content;
+    assert!(apis.contains("use super::grpc_service_controller::"), "Missing override in registration:\n{}", apis);
+    assert!(apis.contains("use super::grpc_service_service::"));
+}
+
+/// Test diffing the generated files of two results
+#[test]
+fn test_process_result_diff() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let coordinator = ProtoHttpCoordinator::new();
+    let before = coordinator.process_content(proto_content).expect("Failed to process proto content");
+    assert!(before.diff(&before).is_empty());
+
+    let changed_content = proto_content.replace("/v1/users/{id}", "/v2/users/{id}");
+    let after = coordinator.process_content(&changed_content).expect("Failed to process proto content");
+
+    let diff = before.diff(&after);
+    assert!(diff.added.is_empty());
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.changed.len(), 1, "Unexpected diff: {:#?}", diff);
+    assert_eq!(diff.changed[0].filename, "user_service_controller.rs");
+
+    let changes: Vec<_> = diff.changed[0].changes().collect();
+    assert_eq!(changes.len(), 2, "Unexpected changes: {:#?}", changes);
+    assert!(matches!(changes[0], DiffLine::Removed(line) if line.contains(r#"path = "/v1/users/{id}""#)));
+    assert!(matches!(changes[1], DiffLine::Added(line) if line.contains(r#"path = "/v2/users/{id}""#)));
+
+    // Enabling an extra output shows up as an added file
+    let config = ConfigBuilder::new()
+        .generate_tags_enum(true)
+        .build_unchecked();
+    let with_tags = ProtoHttpCoordinator::with_config(config)
+        .process_content(proto_content)
+        .expect("Failed to process proto content");
+    let diff = before.diff(&with_tags);
+    assert_eq!(diff.added, vec![TAGS_FILENAME.to_string()]);
+    assert_eq!(with_tags.diff(&before).removed, vec![TAGS_FILENAME.to_string()]);
+}
+
+/// Test that the OpenAPI spec is generated straight from the parsed model
+#[test]
+fn test_openapi_spec_generation() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  // Fetch a user by id
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{user.id}"
+    };
+  }
+
+  rpc CreateUser(User) returns (User) {
+    option (google.api.http) = {
+      post: "/v1/users"
+      body: "*"
+    };
+  }
+}
+
+message GetUserRequest {
+  UserRef user = 1;
+}
+
+message UserRef {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+  repeated string tags = 2;
+  Status status = 3;
+}
+
+enum Status {
+  STATUS_UNSPECIFIED = 0;
+  STATUS_ACTIVE = 1;
+}
+"#;
+
+    let proto_file = NomProtoParser::new().parse_content(proto_content).unwrap();
+    let routes = GoogleApiHttpExtractor::new().extract_routes(&proto_file).unwrap();
+    let spec = PoemOpenApiGenerator::new().generate_openapi_spec(&proto_file, &routes);
+
+    assert_eq!(spec["openapi"], "3.0.3");
+    assert_eq!(spec["info"]["title"], "example.v1");
+
+    let get_user = &spec["paths"]["/v1/users/{user_id}"]["get"];
+    assert_eq!(get_user["operationId"], "UserService_GetUser");
+    assert_eq!(get_user["description"], "Fetch a user by id");
+    assert_eq!(get_user["parameters"][0]["name"], "user_id");
+    assert_eq!(get_user["parameters"][0]["in"], "path");
+    assert_eq!(
+        get_user["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/User"
+    );
+
+    let create_user = &spec["paths"]["/v1/users"]["post"];
+    assert_eq!(
+        create_user["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/User"
+    );
+
+    let user_schema = &spec["components"]["schemas"]["User"];
+    assert_eq!(user_schema["type"], "object");
+    assert_eq!(user_schema["properties"]["id"]["type"], "string");
+    assert_eq!(user_schema["properties"]["tags"]["type"], "array");
+    assert_eq!(user_schema["properties"]["status"]["$ref"], "#/components/schemas/Status");
+    assert_eq!(spec["components"]["schemas"]["Status"]["enum"][1], "STATUS_ACTIVE");
+}
+
+/// Test that enabling mocks emits a mock implementation of each service trait
+#[test]
+fn test_mock_service_generation() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+
+  rpc CreateUser(User) returns (User) {
+    option (google.api.http) = {
+      post: "/v1/users"
+      body: "*"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    // Mocks are opt-in
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    assert!(!result.generated_files.contains_key("user_service_mock.rs"));
+
+    let config = ConfigBuilder::new()
+        .generate_mocks(true)
+        .build()
+        .unwrap();
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let mock = &result.generated_files["user_service_mock.rs"].content;
+    assert!(mock.contains("use super::user_service_service::UserServiceService;"));
+    assert!(mock.contains("pub struct MockUserService {"), "Mock struct missing:\n{}", mock);
+    assert!(mock.contains("impl UserServiceService for MockUserService {"));
+    assert!(mock.contains(
+        "pub fn on_get_user(&mut self, handler: impl Fn(String, ) -> Result<User, Box<dyn std::error::Error>> + Send + Sync + 'static) -> &mut Self {"
+    ), "Setter for GetUser missing:\n{}", mock);
+    assert!(mock.contains("pub fn on_create_user(&mut self, handler: impl Fn(User) -> "));
+    assert!(mock.contains("async fn get_user("));
+    assert!(mock.contains("async fn create_user("));
+}
+
+/// Test that extracted routes carry their path template split into segments
+#[test]
+fn test_route_path_segments() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service PostService {
+  rpc ListPosts(ListPostsRequest) returns (ListPostsResponse) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}/posts"
+    };
+  }
+
+  rpc SearchPosts(SearchPostsRequest) returns (ListPostsResponse) {
+    option (google.api.http) = {
+      get: "/v1/{parent=users/*}/posts:search"
+    };
+  }
+}
+
+message ListPostsRequest {
+  string id = 1;
+}
+
+message SearchPostsRequest {
+  string parent = 1;
+}
+
+message ListPostsResponse {
+  repeated string posts = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    assert_eq!(result.routes[0].segments, vec![
+        PathSegment::Literal("/v1/users/".to_string()),
+        PathSegment::Param { name: "id".to_string(), pattern: None },
+        PathSegment::Literal("/posts".to_string()),
+    ]);
+    assert_eq!(result.routes[1].segments, vec![
+        PathSegment::Literal("/v1/".to_string()),
+        PathSegment::Param { name: "parent".to_string(), pattern: Some("users/*".to_string()) },
+        PathSegment::Literal("/posts:search".to_string()),
+    ]);
+}
+
+/// Test that enabling tests emits one TestClient-based test per route
+#[test]
+fn test_integration_test_scaffolding() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+
+  rpc CreateUser(User) returns (User) {
+    option (google.api.http) = {
+      post: "/v1/users"
+      body: "*"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .generate_mocks(true)
+        .generate_tests(true)
+        .build()
+        .unwrap();
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let tests = &result.generated_files["user_service_tests.rs"].content;
+    assert!(tests.contains("use poem::test::TestClient;"), "TestClient import missing:\n{}", tests);
+    assert!(tests.contains("use super::user_service_mock::MockUserService;"));
+    assert_eq!(tests.matches("#[tokio::test]").count(), 2);
+    assert!(tests.contains("async fn test_get_user() {"));
+    assert!(tests.contains("async fn test_create_user() {"));
+    assert!(tests.contains(r#".get("/v1/users/sample")"#));
+    assert!(tests.contains(r#".post("/v1/users")"#));
+    assert_eq!(tests.matches("response.assert_status_is_ok();").count(), 2);
+    // Without generated `Default` implementations there is no canned response to return
+    assert_eq!(tests.matches("#[ignore = \"fill in a canned response\"]").count(), 2, "Tests should be ignored:\n{}", tests);
+}
+
+/// Test that generated tests return canned responses and post the fields a body requires
+#[test]
+fn test_integration_test_canned_values() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+
+  rpc CreateUser(User) returns (User) {
+    option (google.api.http) = {
+      post: "/v1/users"
+      body: "*"
+    };
+  }
+
+  rpc UpdateProfile(UpdateProfileRequest) returns (User) {
+    option (google.api.http) = {
+      patch: "/v1/users/{user_id}/profile"
+      body: "profile"
+    };
+  }
+
+  rpc GetProfileCard(GetUserRequest) returns (ProfileCard) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}/card"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1 [(google.api.field_behavior) = OUTPUT_ONLY];
+  string name = 2 [(google.api.field_behavior) = REQUIRED];
+  string nickname = 3;
+  Profile profile = 4;
+}
+
+message UpdateProfileRequest {
+  string user_id = 1;
+  Profile profile = 2;
+}
+
+message Profile {
+  string bio = 1;
+  int32 age = 2;
+  repeated string tags = 3;
+  Status status = 4;
+}
+
+enum Status {
+  STATUS_UNSPECIFIED = 0;
+  ACTIVE = 1;
+}
+
+message ProfileCard {
+  Profile profile = 1;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .generate_mocks(true)
+        .generate_tests(true)
+        .generate_defaults(true)
+        .build()
+        .unwrap();
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let tests = &result.generated_files["user_service_tests.rs"].content;
+    assert!(tests.contains("service.on_get_user(|_, | Ok(Default::default()));"), "Missing canned response:\n{}", tests);
+    // Only the required field is posted; read-only and optional fields are left out
+    assert!(tests.contains(r#".body_json(&serde_json::json!({"name":""}))"#), "Unexpected body:\n{}", tests);
+    assert!(tests.contains(r#".body_json(&serde_json::json!({"age":0,"bio":"","status":"STATUS_UNSPECIFIED","tags":[]}))"#),
+        "Unexpected body:\n{}", tests);
+    assert!(!tests.contains("json!({})"));
+
+    // ProfileCard has no `Default`, so its test is left to fill in
+    assert_eq!(tests.matches("#[ignore").count(), 1, "Only one test should be ignored:\n{}", tests);
+    assert!(tests.contains("#[ignore = \"fill in a canned response\"]\nasync fn test_get_profile_card() {"));
+    assert!(tests.contains("Err(\"fill in a canned GetProfileCard response\".into())"));
+    assert!(!tests.contains("todo!"));
+}
+
+/// Test that a declared success status is emitted into the generated response
+#[test]
+fn test_custom_response_status() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  // Creates a user
+  // @status: 201
+  rpc CreateUser(User) returns (User) {
+    option (google.api.http) = {
+      post: "/v1/users"
+      body: "*"
+    };
+  }
+
+  // @status: abc
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(controller.contains(") -> CreateUserHttpResponse {"), "Missing response type:\n{}", controller);
+    assert!(controller.contains("CreateUserHttpResponse::Created(Json(result))"));
+    assert!(controller.contains("#[oai(status = 201)]"));
+    assert!(controller.contains("Created(poem_openapi::payload::Json<User>),"));
+    assert!(!controller.contains("GetUserHttpResponse"));
+
+    let generator = PoemOpenApiGenerator::new();
+    let spec = generator.generate_openapi_spec(&result.proto_file, &result.routes);
+    assert!(spec["paths"]["/v1/users"]["post"]["responses"]["201"].is_object());
+    assert!(spec["paths"]["/v1/users/{id}"]["get"]["responses"]["200"].is_object());
+
+    // Invalid codes fall back to 200 and are reported as warnings
+    let report = ValidationEngine::new().validate_proto_file(&result.proto_file).unwrap();
+    assert!(report.warnings.iter().any(|warning| warning.message.contains("Ignoring status 'abc' on method UserService.GetUser")),
+        "Missing status warning: {:?}", report.warnings);
+}
+
+/// Test that operations sharing a message reference one schema in the OpenAPI spec
+#[test]
+fn test_openapi_shared_schema_refs() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+
+  rpc UpdateUser(User) returns (User) {
+    option (google.api.http) = {
+      put: "/v1/users/{id}"
+      body: "*"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+  string name = 2;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    let spec = PoemOpenApiGenerator::new().generate_openapi_spec(&result.proto_file, &result.routes);
+
+    let operations = &spec["paths"]["/v1/users/{id}"];
+    for method in ["get", "put"] {
+        let schema = &operations[method]["responses"]["200"]["content"]["application/json"]["schema"];
+        assert_eq!(schema, &serde_json::json!({ "$ref": "#/components/schemas/User" }), "Inlined {} response", method);
+    }
+    assert_eq!(operations["put"]["requestBody"]["content"]["application/json"]["schema"]["$ref"], "#/components/schemas/User");
+
+    let schemas = spec["components"]["schemas"].as_object().unwrap();
+    assert_eq!(schemas.keys().filter(|name| *name == "User").count(), 1);
+    assert_eq!(schemas.len(), 2);
+}
+
+/// Test that nested schemas are keyed by nesting path and custom methods map to operations
+#[test]
+fn test_openapi_nested_schemas_and_custom_methods() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+
+  rpc CheckUser(GetUserRequest) returns (Profile) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}/check"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message Profile {
+  string bio = 1;
+}
+
+message User {
+  message Profile {
+    string avatar_url = 1;
+  }
+
+  string id = 1;
+  Profile profile = 2;
+}
+"#;
+
+    let proto_file = NomProtoParser::new().parse_content(proto_content).expect("Failed to parse proto content");
+    let mut routes = GoogleApiHttpExtractor::new().extract_routes(&proto_file).expect("Failed to extract routes");
+    // Custom verbs come from descriptor sets, so they are set on the routes directly
+    routes[1].http_method = HttpMethod::Custom("HEAD".to_string());
+    let mut list_route = routes[0].clone();
+    list_route.http_method = HttpMethod::Custom("LIST".to_string());
+    routes.push(list_route);
+    let spec = PoemOpenApiGenerator::new().generate_openapi_spec(&proto_file, &routes);
+
+    // The nested Profile does not overwrite the top-level one
+    let schemas = &spec["components"]["schemas"];
+    assert!(schemas["Profile"]["properties"]["bio"].is_object());
+    assert!(schemas["User.Profile"]["properties"]["avatar_url"].is_object());
+    assert_eq!(schemas["User"]["properties"]["profile"]["$ref"], "#/components/schemas/User.Profile");
+
+    // Known custom verbs are lowercased; verbs OpenAPI cannot describe are left out
+    let operations = spec["paths"]["/v1/users/{id}"].as_object().unwrap();
+    assert_eq!(operations.keys().collect::<Vec<_>>(), ["get"]);
+    let check = &spec["paths"]["/v1/users/{id}/check"]["head"];
+    assert_eq!(check["responses"]["200"]["content"]["application/json"]["schema"]["$ref"], "#/components/schemas/Profile");
+}
+
+/// Test that misspelled options are reported as warnings under the warn policy
+#[test]
+fn test_unknown_option_warnings() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("service.proto"), r#"
+syntax = "proto3";
+
+package example.v1;
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.htttp) = {
+            get: "/v1/users/{id}"
+        };
+    }
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#).unwrap();
+
+    let unknown_option_warnings = |policy| {
+        let config = ConfigBuilder::new()
+            .unknown_option_policy(policy)
+            .build_unchecked();
+        let (_, reports) = ProtoHttpCoordinator::with_config(config).validate_directory(temp_dir.path()).unwrap();
+        reports[0].1.warnings.iter()
+            .map(|warning| warning.message.clone())
+            .filter(|message| message.starts_with("Unknown option"))
+            .collect::<Vec<_>>()
+    };
+
+    assert!(unknown_option_warnings(UnknownOptionPolicy::Ignore).is_empty());
+    assert_eq!(
+        unknown_option_warnings(UnknownOptionPolicy::Warn),
+        vec!["Unknown option 'google.api.htttp' on method UserService.GetUser".to_string()]
+    );
+}
+
+/// Test that a rate limit option is attached to the route and documented
+#[test]
+fn test_rate_limit_metadata() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+    option (myapi.rate_limit) = "100/min";
+  }
+
+  rpc ListUsers(ListUsersRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users"
+    };
+    option (myapi.rate_limit) = "lots";
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message ListUsersRequest {
+  int32 page_size = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let get_user = result.routes.iter().find(|route| route.method_name == "GetUser").unwrap();
+    assert_eq!(get_user.rate_limit, Some(RateLimit { requests: 100, period: RateLimitPeriod::Minute }));
+    assert_eq!(get_user.rate_limit.unwrap().period.seconds(), 60);
+    assert_eq!(get_user.rate_limit.unwrap().to_string(), "100/min");
+    let list_users = result.routes.iter().find(|route| route.method_name == "ListUsers").unwrap();
+    assert_eq!(list_users.rate_limit, None);
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(controller.contains("/// Rate limit: 100 requests per minute."), "Missing rate limit docs:\n{}", controller);
+
+    let spec = PoemOpenApiGenerator::new().generate_openapi_spec(&result.proto_file, &result.routes);
+    assert_eq!(spec["paths"]["/v1/users/{id}"]["get"]["x-rate-limit"], serde_json::json!({ "requests": 100, "period": "minute" }));
+
+    let report = ValidationEngine::new().validate_proto_file(&result.proto_file).unwrap();
+    assert!(report.warnings.iter().any(|warning| warning.message.contains("Ignoring rate limit 'lots' on method UserService.ListUsers")),
+        "Missing rate limit warning: {:?}", report.warnings);
+}
+
+/// Test that common query parameters merge with explicit request fields by name
+#[test]
+fn test_query_parameters_merge_with_fields() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc ListUsers(ListUsersRequest) returns (ListUsersResponse) {
+        option (google.api.http) = {
+            get: "/v1/users"
+        };
+    }
+}
+
+message ListUsersRequest {
+    string limit = 1;
+}
+
+message ListUsersResponse {
+    repeated string ids = 1;
+}
+"#;
+
+    let mut config = ProtoHttpParserConfig::new();
+    config.extractor.common_query_params = vec!["limit".to_string(), "page".to_string(), "limit".to_string()];
+
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let query_parameters = &result.routes[0].query_parameters;
+    let limits: Vec<_> = query_parameters.iter().filter(|param| param.name == "limit").collect();
+    assert_eq!(limits.len(), 1, "Duplicate limit parameters: {:?}", query_parameters);
+    // The field's type wins over the integer inferred from the name
+    assert_eq!(limits[0].param_type, ParameterType::String);
+    assert_eq!(query_parameters.len(), 2);
+}
+
+/// Test parsing field behaviors into typed values and applying them to models
+#[test]
+fn test_field_behaviors() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+message User {
+    string id = 1 [(google.api.field_behavior) = OUTPUT_ONLY];
+    string name = 2 [(google.api.field_behavior) = REQUIRED];
+    string region = 3 [(google.api.field_behavior) = REQUIRED, (google.api.field_behavior) = IMMUTABLE];
+    string nickname = 4;
+    string token = 5 [(google.api.field_behavior) = REQUIRED, (google.api.field_behavior) = OUTPUT_ONLY];
+}
+"#;
+
+    let parser = NomProtoParser::new();
+    let proto_file = parser.parse_content(proto_content).expect("Failed to parse proto content");
+    let user = proto_file.find_message("User").unwrap();
+
+    assert_eq!(user.fields[0].behaviors, vec![FieldBehavior::OutputOnly]);
+    assert_eq!(user.fields[1].behaviors, vec![FieldBehavior::Required]);
+    assert_eq!(user.fields[2].behaviors, vec![FieldBehavior::Required, FieldBehavior::Immutable]);
+    assert!(user.fields[3].behaviors.is_empty());
+    assert!(user.fields[1].is_required());
+    assert!(user.fields[2].is_immutable());
+
+    let config = ConfigBuilder::new()
+        .model_variants(ModelVariantConfig::default())
+        .build()
+        .expect("Failed to build config");
+    let model = PoemOpenApiGenerator::with_config(config.generator).generate_model(user)
+        .expect("Failed to generate model");
+    assert!(model.content.contains("    /// name field\n    ///\n    /// Required.\n    pub name: String,"),
+        "Required field should be documented and non-optional:\n{}", model.content);
+    // Fields that are not REQUIRED may be omitted by clients
+    assert!(model.content.contains(
+        "    /// nickname field\n    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    #[oai(default)]\n    pub nickname: Option<String>,"
+    ), "Plain field should be optional:\n{}", model.content);
+    assert!(model.content.contains("    #[oai(read_only)]\n    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    #[oai(default)]\n    pub id: Option<String>,"),
+        "Output-only field should be read-only:\n{}", model.content);
+    assert!(model.content.contains("poem_openapi::Object"));
+    assert!(model.content.contains("/// Immutable: set on creation only."));
+
+    // Immutable fields can be set on creation but not updated
+    let create = model.content.split("pub struct CreateUser {").nth(1)
+        .and_then(|rest| rest.split('}').next())
+        .unwrap_or_else(|| panic!("Missing create variant:\n{}", model.content));
+    assert!(create.contains("pub region: String,"));
+    assert!(create.contains("pub nickname: Option<String>,"));
+    let update = model.content.split("pub struct UpdateUser {").nth(1)
+        .and_then(|rest| rest.split('}').next())
+        .unwrap_or_else(|| panic!("Missing update variant:\n{}", model.content));
+    assert!(!update.contains("pub region:"), "Immutable field should be omitted:\n{}", update);
+    assert!(update.contains("pub name: Option<String>,"));
+
+    let report = ValidationEngine::new().validate_proto_file(&proto_file).unwrap();
+    assert_eq!(report.warnings.len(), 1, "Unexpected warnings: {:?}", report.warnings);
+    assert!(report.warnings[0].message.contains("User.token is both REQUIRED and OUTPUT_ONLY"));
+}
+
+/// Test reading API metadata from file options
+#[test]
+fn test_api_metadata() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+option (myapi.title) = "User API";
+option (myapi.version) = "2.3.0";
+option (myapi.api_description) = "Manages \"users\"";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    assert_eq!(result.api_metadata, ApiMetadata {
+        title: "User API".to_string(),
+        version: "2.3.0".to_string(),
+        description: Some("Manages \"users\"".to_string()),
+        servers: Vec::new(),
+    });
+    assert!(!result.generated_files.contains_key(API_METADATA_FILENAME));
+
+    let spec = PoemOpenApiGenerator::new().generate_openapi_spec(&result.proto_file, &result.routes);
+    assert_eq!(spec["info"]["title"], "User API");
+    assert_eq!(spec["info"]["version"], "2.3.0");
+
+    // Emitted next to the registration helper
+    let config = ConfigBuilder::new()
+        .generate_registration_helper(true)
+        .build_unchecked();
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+    let metadata = &result.generated_files[API_METADATA_FILENAME].content;
+    assert!(metadata.contains(r#"pub const API_TITLE: &str = "User API";"#), "Missing title:\n{}", metadata);
+    assert!(metadata.contains(r#"pub const API_VERSION: &str = "2.3.0";"#));
+    assert!(metadata.contains(r#"pub const API_DESCRIPTION: &str = "Manages \"users\"";"#));
+    assert!(metadata.contains("poem_openapi::OpenApiService::new(apis, API_TITLE, API_VERSION)"));
+    assert!(metadata.contains(".description(API_DESCRIPTION)"));
+
+    // Without options the package names the API
+    let result = ProtoHttpCoordinator::new().process_content(&proto_content.replace("option (myapi.", "// option (myapi."))
+        .expect("Failed to process proto content");
+    assert_eq!(result.api_metadata.title, "example.v1");
+    assert_eq!(result.api_metadata.version, "1.0.0");
+    assert_eq!(result.api_metadata.description, None);
+}
+
+/// Test that batch processing reports progress once per file
+#[test]
+fn test_process_files_with_progress() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let paths: Vec<std::path::PathBuf> = ["first", "second", "third"].iter()
+        .map(|name| {
+            let path = temp_dir.path().join(format!("{}.proto", name));
+            let content = format!(
+                "syntax = \"proto3\";\n\npackage {}.v1;\n\nmessage Item {{\n  string id = 1;\n}}\n",
+                name
+            );
+            std::fs::write(&path, content).expect("Failed to write proto file");
+            path
+        })
+        .collect();
+
+    let mut calls = Vec::new();
+    let batch_result = ProtoHttpCoordinator::new()
+        .process_files_with_progress(&paths, &mut |current, total, file| {
+            calls.push((current, total, file.to_path_buf()));
+        })
+        .expect("Batch processing failed");
+
+    assert_eq!(batch_result.success_count(), 3);
+    assert_eq!(calls, vec![
+        (1, 3, paths[0].clone()),
+        (2, 3, paths[1].clone()),
+        (3, 3, paths[2].clone()),
+    ]);
+}
+
+/// Test that field constraints become poem-openapi validator attributes or a validate method
+#[test]
+fn test_constraint_validators() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+message Order {
+  int32 quantity = 1 [(maximum) = 100];
+  string code = 2 [(buf.validate.field).string = {min_len: 2, max_len: 8}];
+  double discount = 3 [(validate.rules).double.gt = 0, (validate.rules).double.lte = 0.5];
+  repeated string tags = 4 [(max_items) = 5];
+  float ratio = 5 [(exclusive_maximum) = 1];
+}
+"#;
+
+    let proto_file = NomProtoParser::new().parse_content(proto_content).expect("Failed to parse proto");
+    let order = proto_file.find_message("Order").unwrap();
+    assert_eq!(order.fields[0].constraints(), vec![FieldConstraint::Maximum(100.0)]);
+    assert_eq!(order.fields[1].constraints(), vec![FieldConstraint::MinLength(2), FieldConstraint::MaxLength(8)]);
+    assert_eq!(order.fields[2].constraints(), vec![FieldConstraint::ExclusiveMinimum(0.0), FieldConstraint::Maximum(0.5)]);
+    assert_eq!(order.fields[4].constraints(), vec![FieldConstraint::ExclusiveMaximum(1.0)]);
+
+    let model = PoemOpenApiGenerator::new().generate_model(order).expect("Failed to generate model");
+    assert!(model.content.contains("poem_openapi::Object"), "Validators need an Object derive:\n{}", model.content);
+    assert!(model.content.contains("#[oai(validator(maximum(value = \"100\")))]\n    pub quantity: i32,"), "Missing validator:\n{}", model.content);
+    assert!(model.content.contains("#[oai(validator(min_length = 2, max_length = 8))]"));
+    assert!(model.content.contains("#[oai(validator(minimum(value = \"0\", exclusive), maximum(value = \"0.5\")))]"));
+    assert!(model.content.contains("#[oai(validator(maximum(value = \"1\", exclusive)))]\n    pub ratio: f32,"));
+    assert!(model.content.contains("#[oai(validator(max_items = 5))]"));
+    assert!(!model.content.contains("pub fn validate"));
+
+    let config = ConfigBuilder::new()
+        .constraint_validation(ConstraintValidation::Method)
+        .build()
+        .expect("Failed to build config");
+    let model = PoemOpenApiGenerator::with_config(config.generator).generate_model(order)
+        .expect("Failed to generate model");
+    assert!(!model.content.contains("validator("), "Unexpected validator attribute:\n{}", model.content);
+    assert!(model.content.contains("pub fn validate(&self) -> Result<(), String>"));
+    assert!(model.content.contains("if (self.quantity as f64) > 100.0 {"), "Missing check:\n{}", model.content);
+    assert!(model.content.contains("return Err(\"`quantity` must be at most 100\".to_string());"));
+    assert!(model.content.contains("if (self.code.chars().count() as f64) < 2.0 {"));
+    assert!(model.content.contains("if (self.discount as f64) <= 0.0 {"));
+    assert!(model.content.contains("if (self.tags.len() as f64) > 5.0 {"));
+}
+
+/// Test that imports resolve relative to the importing file without configured include paths
+#[test]
+fn test_relative_import_resolution() {
+    let base_proto = r#"
+syntax = "proto3";
+
+package test.v1;
+
+message Book {
+    string id = 1;
+}
+"#;
+
+    let service_proto = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "common/base.proto";
+
+service BookService {
+    rpc GetBook(GetBookRequest) returns (Book) {
+        option (google.api.http) = {
+            get: "/v1/books/{id}"
+        };
+    }
+}
+
+message GetBookRequest {
+    string id = 1;
+}
+"#;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    std::fs::create_dir(temp_dir.path().join("common")).expect("Failed to create dir");
+    let service_path = temp_dir.path().join("service.proto");
+    std::fs::write(temp_dir.path().join("common/base.proto"), base_proto).expect("Failed to write proto file");
+    std::fs::write(&service_path, service_proto).expect("Failed to write proto file");
+
+    let result = ProtoHttpCoordinator::new().process_file(&service_path)
+        .expect("Failed to process service proto");
+    assert!(result.type_registry.resolve_type(&TypeReference::new("Book".to_string()), Some("test.v1")).is_some());
+
+    let parser = NomProtoParser::new();
+    let proto_file = parser.parse_file(&service_path).expect("Failed to parse service proto");
+    let imported = parser.parse_imported_files_from(&proto_file, Some(&service_path))
+        .expect("Failed to parse imports");
+    assert_eq!(imported.len(), 1);
+    assert_eq!(imported[0].0, temp_dir.path().join("common/base.proto"));
+    assert_eq!(imported[0].1.messages[0].name, "Book");
+
+    // Without the importing file's location the import is not found
+    assert!(parser.parse_imported_files(&proto_file).expect("Failed to parse imports").is_empty());
+}
+
+/// Test that imported files are parsed once, surface their parse errors and only resolve types
+#[test]
+fn test_imported_file_handling() {
+    let base_proto = r#"
+syntax = "proto3";
+
+package test.v1;
+
+message Book {
+    string id = 1;
+    Genre genre = 2;
+}
+
+enum Genre {
+    GENRE_FICTION = 1;
+}
+"#;
+
+    let service_proto = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "common/base.proto";
+import "./common/base.proto";
+
+service BookService {
+    rpc GetBook(GetBookRequest) returns (Book) {
+        option (google.api.http) = {
+            get: "/v1/books/{id}"
+        };
+    }
+}
+
+message GetBookRequest {
+    string id = 1;
+}
+"#;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    std::fs::create_dir(temp_dir.path().join("common")).expect("Failed to create dir");
+    let service_path = temp_dir.path().join("service.proto");
+    let base_path = temp_dir.path().join("common/base.proto");
+    std::fs::write(&base_path, base_proto).expect("Failed to write proto file");
+    std::fs::write(&service_path, service_proto).expect("Failed to write proto file");
+
+    // Both import strings name the same file, which is parsed once
+    let parser = NomProtoParser::new();
+    let proto_file = parser.parse_file(&service_path).expect("Failed to parse service proto");
+    let imported = parser.parse_imported_files_from(&proto_file, Some(&service_path))
+        .expect("Failed to parse imports");
+    assert_eq!(imported.len(), 1);
+
+    // Genre breaks the proto3 zero-value rule, but only the root file's definitions are validated
+    let result = ProtoHttpCoordinator::new().process_file(&service_path)
+        .expect("Failed to process service proto");
+    assert!(result.generated_files.contains_key("book_service_controller.rs"));
+    assert!(ProtoHttpCoordinator::new().process_file(&base_path).is_err());
+
+    // An imported file that fails to parse fails the file importing it
+    std::fs::write(&base_path, "syntax = \"proto3\";\n\nmessage Book {\n    string id = \n}\n")
+        .expect("Failed to write proto file");
+    match ProtoHttpCoordinator::new().process_file(&service_path) {
+        Err(ProtoHttpParserError::Parse(ParseError::InvalidSyntax { .. })) => {}
+        other => panic!("Expected the import's syntax error, got: {:?}", other),
+    }
+}
+
+/// Test that the prelude re-exports every generated public item and the models
+#[test]
+fn test_generated_prelude() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package shop.v1;
+
+service BookService {
+  rpc GetBook(GetBookRequest) returns (Book) {
+    option (google.api.http) = {
+      get: "/v1/books/{id}"
+    };
+  }
+}
+
+service ShelfService {
+  rpc GetShelf(GetShelfRequest) returns (Shelf) {
+    option (google.api.http) = {
+      get: "/v1/shelves/{id}"
+    };
+  }
+}
+
+message GetBookRequest {
+  string id = 1;
+}
+
+message Book {
+  string id = 1;
+  Genre genre = 2;
+}
+
+message GetShelfRequest {
+  string id = 1;
+}
+
+message Shelf {
+  string id = 1;
+}
+
+enum Genre {
+  GENRE_UNSPECIFIED = 0;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    assert!(!result.generated_files.contains_key(PRELUDE_FILENAME));
+
+    let config = ConfigBuilder::new()
+        .generate_prelude(true)
+        .generate_mocks(true)
+        .generate_tags_enum(true)
+        .generate_registration_helper(true)
+        .generate_error_module(true)
+        .build_unchecked();
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+    let prelude = &result.generated_files[PRELUDE_FILENAME].content;
+
+    for expected in [
+        "pub use super::book_service_controller::{ BookServiceController };",
+        "pub use super::book_service_service::{ BookServiceService };",
+        "pub use super::book_service_mock::{ MockBookService };",
+        "pub use super::shelf_service_controller::{ ShelfServiceController };",
+        "pub use super::shelf_service_service::{ ShelfServiceService };",
+        "pub use super::shelf_service_mock::{ MockShelfService };",
+        "pub use super::apis::{ AllControllers, register_apis };",
+        "pub use super::api_metadata::{ API_TITLE, API_VERSION, api_service };",
+        "pub use super::errors::{ ApiError };",
+        "pub use super::tags::{ Tags };",
+        "pub use super::{ GetBookRequest, Book, GetShelfRequest, Shelf, Genre };",
+    ] {
+        assert!(prelude.contains(expected), "Missing `{}` in prelude:\n{}", expected, prelude);
+    }
+}
+
+/// Test that a security scheme declared on a service protects its operations
+#[test]
+fn test_security_scheme() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service AccountService {
+  option (openapi.security) = "bearer";
+
+  rpc GetAccount(GetAccountRequest) returns (Account) {
+    option (google.api.http) = {
+      get: "/v1/accounts/{id}"
+    };
+  }
+  rpc GetAccountStatus(GetAccountRequest) returns (Account) {
+    option (openapi.security) = "api_key";
+    option (google.api.http) = {
+      get: "/v1/accounts/{id}/status"
+    };
+  }
+}
+
+service HealthService {
+  rpc Check(CheckRequest) returns (CheckResponse) {
+    option (google.api.http) = {
+      get: "/healthz"
+    };
+  }
+}
+
+message GetAccountRequest {
+  string id = 1;
+}
+
+message Account {
+  string id = 1;
+}
+
+message CheckRequest {}
+
+message CheckResponse {}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    let security: Vec<_> = result.routes.iter()
+        .map(|route| (route.method_name.as_str(), route.security))
+        .collect();
+    assert_eq!(security, vec![
+        ("GetAccount", Some(SecurityScheme::Bearer)),
+        ("GetAccountStatus", Some(SecurityScheme::ApiKey)),
+        ("Check", None),
+    ]);
+
+    let controller = &result.generated_files["account_service_controller.rs"].content;
+    assert!(controller.contains("#[derive(poem_openapi::SecurityScheme)]\n#[oai(ty = \"bearer\")]\npub struct AccountServiceBearerAuth(pub poem_openapi::auth::Bearer);"),
+        "Missing bearer scheme:\n{}", controller);
+    assert!(controller.contains("#[oai(ty = \"api_key\", key_name = \"X-API-Key\", key_in = \"header\")]\npub struct AccountServiceApiKeyAuth(pub poem_openapi::auth::ApiKey);"));
+    assert!(controller.contains("_auth: AccountServiceBearerAuth,"), "Operation should require the scheme:\n{}", controller);
+    assert!(controller.contains("_auth: AccountServiceApiKeyAuth,"));
+
+    let health = &result.generated_files["health_service_controller.rs"].content;
+    assert!(!health.contains("SecurityScheme") && !health.contains("_auth"));
+
+    let spec = PoemOpenApiGenerator::new().generate_openapi_spec(&result.proto_file, &result.routes);
+    assert_eq!(spec["components"]["securitySchemes"]["AccountServiceBearerAuth"]["scheme"], "bearer");
+    assert_eq!(spec["paths"]["/v1/accounts/{id}"]["get"]["security"][0]["AccountServiceBearerAuth"], serde_json::json!([]));
+    assert!(spec["paths"]["/healthz"]["get"].get("security").is_none());
+}
+
+/// Test that query parameters bound to required fields are required
+#[test]
+fn test_required_query_parameters() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service SearchService {
+    rpc Search(SearchRequest) returns (SearchResponse) {
+        option (google.api.http) = {
+            get: "/v1/search"
+        };
+    }
+}
+
+message SearchRequest {
+    string query = 1 [(google.api.field_behavior) = REQUIRED];
+    string filter = 2;
+}
+
+message SearchResponse {
+    repeated string ids = 1;
+}
+"#;
+
+    let mut config = ProtoHttpParserConfig::new();
+    config.extractor.common_query_params = vec!["query".to_string(), "filter".to_string()];
+
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let query_parameters = &result.routes[0].query_parameters;
+    assert_eq!(query_parameters, &vec![
+        QueryParameter::required("query".to_string(), ParameterType::String),
+        QueryParameter::optional("filter".to_string(), ParameterType::String),
+    ]);
+
+    let controller = &result.generated_files["search_service_controller.rs"].content;
+    assert!(controller.contains(" query: Query<String>,"), "Query should be required:\n{}", controller);
+    assert!(controller.contains("#[oai(default)] filter: Query<Option<String>>,"));
+}
+
+/// Test that path builders fill route parameters into the path template
+#[test]
+fn test_path_builders() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package user.v1;
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{user_id}"
+    };
+  }
+
+  rpc ListUsers(ListUsersRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users"
+    };
+  }
+}
+
+message GetUserRequest {
+  string user_id = 1;
+}
+
+message ListUsersRequest {
+  int32 page = 1;
+}
+
+message User {
+  string user_id = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    assert!(!result.generated_files.contains_key(PATHS_FILENAME));
+
+    let config = ConfigBuilder::new()
+        .generate_path_builders(true)
+        .build_unchecked();
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+    let paths = &result.generated_files[PATHS_FILENAME].content;
+
+    // `paths::get_user("42")` evaluates to `format!("/v1/users/{}", "42")`, i.e. `/v1/users/42`
+    assert!(paths.contains("pub fn get_user(user_id: &str) -> String"), "Missing get_user builder:\n{}", paths);
+    assert!(paths.contains(r#"format!("/v1/users/{}", user_id)"#), "Wrong get_user path:\n{}", paths);
+    assert!(paths.contains("pub fn list_users() -> String"), "Missing list_users builder:\n{}", paths);
+    assert!(paths.contains(r#""/v1/users".to_string()"#), "Wrong list_users path:\n{}", paths);
+}
+
+/// Test that a missing normal import is reported while a missing weak import is not
+#[test]
+fn test_missing_import_policy() {
+    let temp_dir = TempDir::new().unwrap();
+    let proto_path = temp_dir.path().join("user.proto");
+    std::fs::write(&proto_path, r#"
+syntax = "proto3";
+
+package user.v1;
+
+import "missing/common.proto";
+import weak "missing/optional.proto";
+import "google/protobuf/timestamp.proto";
+
+message User {
+  string id = 1;
+}
+"#).unwrap();
+
+    let (_, reports) = ProtoHttpCoordinator::new().validate_directory(temp_dir.path()).unwrap();
+    let messages: Vec<&str> = reports[0].1.warnings.iter().map(|warning| warning.message.as_str()).collect();
+    assert!(messages.contains(&"Import 'missing/common.proto' not found on the include paths"), "{:?}", messages);
+    assert_eq!(messages.iter().filter(|message| message.contains("not found")).count(), 1, "{:?}", messages);
+
+    let config = ConfigBuilder::new()
+        .missing_import_policy(MissingImportPolicy::Ignore)
+        .build_unchecked();
+    let (_, reports) = ProtoHttpCoordinator::with_config(config).validate_directory(temp_dir.path()).unwrap();
+    assert!(!reports[0].1.warnings.iter().any(|warning| warning.message.contains("not found")));
+
+    // Under the error policy only the normal import fails the parse
+    let config = ConfigBuilder::new()
+        .missing_import_policy(MissingImportPolicy::Error)
+        .build_unchecked();
+    match NomProtoParser::with_config(config.parser.clone()).parse_file(&proto_path) {
+        Err(ParseError::ImportNotFound { import_path }) => assert_eq!(import_path, "missing/common.proto"),
+        other => panic!("Expected a missing import error, got {:?}", other.map(|_| ())),
+    }
+
+    let weak_only_path = temp_dir.path().join("weak.proto");
+    std::fs::write(&weak_only_path, r#"
+syntax = "proto3";
+
+import weak "missing/optional.proto";
+
+message Weak {
+  string id = 1;
+}
+"#).unwrap();
+    assert!(NomProtoParser::with_config(config.parser).parse_file(&weak_only_path).is_ok());
+}
+
+/// Test that controllers import only the poem-openapi items their routes use
+#[test]
+fn test_minimal_controller_imports() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package user.v1;
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+}
+
+service HealthService {
+  rpc Check(CheckRequest) returns (CheckResponse) {
+    option (google.api.http) = {
+      post: "/v1/health"
+      body: "*"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+
+message CheckRequest {
+  string probe = 1;
+}
+
+message CheckResponse {
+  bool healthy = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let user_controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(user_controller.contains("use poem_openapi::{payload::Json, param::Path};"), "{}", user_controller);
+    assert!(!user_controller.contains("param::Query"), "{}", user_controller);
+
+    let health_controller = &result.generated_files["health_service_controller.rs"].content;
+    assert!(health_controller.contains("use poem_openapi::payload::Json;"), "{}", health_controller);
+    assert!(!health_controller.contains("param::Query"), "{}", health_controller);
+    assert!(!health_controller.contains("param::Path"), "{}", health_controller);
+}
+
+/// Test that configured naming suffixes rename controllers, traits and their modules
+#[test]
+fn test_naming_suffixes() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package user.v1;
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .naming(NamingConfig {
+            controller_suffix: "Handler".to_string(),
+            trait_suffix: "Api".to_string(),
+            controller_module_suffix: "handler".to_string(),
+            trait_module_suffix: "api".to_string(),
+        })
+        .generate_registration_helper(true)
+        .build()
+        .expect("Naming config should be valid");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let handler = &result.generated_files["user_service_handler.rs"].content;
+    assert!(handler.contains("pub struct UserServiceHandler<T: UserServiceApi>"), "{}", handler);
+    assert!(handler.contains("use super::user_service_api::UserServiceApi;"), "{}", handler);
+    assert!(!handler.contains("Controller"), "{}", handler);
+
+    let service_trait = &result.generated_files["user_service_api.rs"].content;
+    assert!(service_trait.contains("pub trait UserServiceApi"), "{}", service_trait);
+    assert!(!result.generated_files.contains_key("user_service_controller.rs"));
+
+    let registration = &result.generated_files[REGISTRATION_FILENAME].content;
+    assert!(registration.contains("use super::user_service_handler::UserServiceHandler;"), "{}", registration);
+
+    let clashing = ConfigBuilder::new()
+        .naming(NamingConfig {
+            controller_suffix: "Api".to_string(),
+            trait_suffix: "Api".to_string(),
+            ..NamingConfig::default()
+        })
+        .build();
+    assert!(clashing.is_err());
+}
+
+/// Test that routes sharing a path are merged into one CORS entry
+#[test]
+fn test_cors_config() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package user.v1;
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+
+  rpc UpdateUser(UpdateUserRequest) returns (User) {
+    option (google.api.http) = {
+      put: "/v1/users/{id}"
+      body: "*"
+    };
+  }
+
+  rpc ListUsers(ListUsersRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message UpdateUserRequest {
+  string id = 1;
+  string name = 2;
+}
+
+message ListUsersRequest {
+  int32 page = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    assert!(!result.generated_files.contains_key(CORS_FILENAME));
+
+    let config = ConfigBuilder::new()
+        .generate_cors_config(true)
+        .build_unchecked();
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+    let cors = &result.generated_files[CORS_FILENAME].content;
+
+    assert!(cors.contains("pub fn cors_config() -> &'static [CorsRoute]"), "{}", cors);
+    assert!(cors.contains(r#"path: "/v1/users/{id}",
+            methods: &["GET", "PUT"],
+            headers: &["content-type"],"#), "{}", cors);
+    assert!(cors.contains(r#"path: "/v1/users",
+            methods: &["GET"],
+            headers: &[],"#), "{}", cors);
+}
+
+/// Test that flat builds report protos producing the same output file instead of overwriting it
+#[test]
+fn test_build_integration_output_collision() {
+    let proto_template = r#"
+syntax = "proto3";
+
+package PACKAGE;
+
+import "google/api/annotations.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/PACKAGE/users/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let first_path = temp_dir.path().join("first.proto");
+    let second_path = temp_dir.path().join("second.proto");
+    std::fs::write(&first_path, proto_template.replace("PACKAGE", "first")).expect("Failed to write proto file");
+    std::fs::write(&second_path, proto_template.replace("PACKAGE", "second")).expect("Failed to write proto file");
+
+    let error = BuildIntegration::new()
+        .add_proto_file(&first_path)
+        .add_proto_file(&second_path)
+        .output_dir(temp_dir.path().join("output"))
+        .generate_to_memory()
+        .expect_err("Colliding output files should be reported");
+
+    let message = error.to_string();
+    assert!(message.contains("user_service_controller.rs"), "unexpected error: {}", message);
+    assert!(message.contains("first.proto") && message.contains("second.proto"), "unexpected error: {}", message);
+
+    // A single proto still builds
+    let in_memory = BuildIntegration::new()
+        .add_proto_file(&first_path)
+        .output_dir(temp_dir.path().join("output"))
+        .generate_to_memory()
+        .expect("Single proto should build");
+    assert!(in_memory.contains_key("user_service_controller.rs"));
+}
+
+/// Test that flat builds generate one registration helper and prelude for all protos
+#[test]
+fn test_build_integration_shared_files() {
+    let proto_template = r#"
+syntax = "proto3";
+
+package shop.v1;
+
+import "google/api/annotations.proto";
+
+service NAMEService {
+  rpc GetNAME(GetNAMERequest) returns (NAME) {
+    option (google.api.http) = {
+      get: "/v1/PATH/{id}"
+    };
+  }
+}
+
+message GetNAMERequest {
+  string id = 1;
+}
+
+message NAME {
+  string id = 1;
+}
+"#;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let book_path = temp_dir.path().join("book.proto");
+    let shelf_path = temp_dir.path().join("shelf.proto");
+    std::fs::write(&book_path, proto_template.replace("NAME", "Book").replace("PATH", "books")).expect("Failed to write proto file");
+    std::fs::write(&shelf_path, proto_template.replace("NAME", "Shelf").replace("PATH", "shelves")).expect("Failed to write proto file");
+
+    let config = ConfigBuilder::new()
+        .generate_registration_helper(true)
+        .generate_prelude(true)
+        .generate_tags_enum(true)
+        .generate_error_module(true)
+        .build_unchecked();
+    let in_memory = BuildIntegration::new()
+        .with_config(config.clone())
+        .add_proto_file(&book_path)
+        .add_proto_file(&shelf_path)
+        .output_dir(temp_dir.path().join("output"))
+        .generate_to_memory()
+        .expect("Shared files should not collide");
+
+    let apis = &in_memory[REGISTRATION_FILENAME];
+    assert!(apis.contains("pub fn register_apis<BookServiceImpl, ShelfServiceImpl>("), "Missing helper:\n{}", apis);
+    assert!(apis.contains("use super::book_service_controller::BookServiceController;"));
+    assert!(apis.contains("use super::shelf_service_controller::ShelfServiceController;"));
+
+    let prelude = &in_memory[PRELUDE_FILENAME];
+    for expected in [
+        "pub use super::book_service_controller::BookServiceController;",
+        "pub use super::shelf_service_controller::ShelfServiceController;",
+        "pub use super::apis::{register_apis, AllControllers};",
+        "pub use super::{Book, GetBookRequest, GetShelfRequest, Shelf};",
+    ] {
+        assert!(prelude.contains(expected), "Missing `{}` in prelude:\n{}", expected, prelude);
+    }
+
+    // The tags enum and errors module are shared by both files' services
+    let tags = &in_memory[TAGS_FILENAME];
+    assert!(tags.contains("    BookService,\n") && tags.contains("    ShelfService,\n"), "Missing tags:\n{}", tags);
+    assert!(in_memory.contains_key(ERRORS_FILENAME));
+
+    // With package modules, the shared files go into the package's directory
+    let mut nested_config = config;
+    nested_config.generator.package_to_module = true;
+    let in_memory = BuildIntegration::new()
+        .with_config(nested_config)
+        .add_proto_file(&book_path)
+        .add_proto_file(&shelf_path)
+        .output_dir(temp_dir.path().join("output"))
+        .generate_to_memory()
+        .expect("Shared files should not collide");
+    assert!(in_memory["shop/v1/apis.rs"].contains("pub fn register_apis<BookServiceImpl, ShelfServiceImpl>("));
+    assert!(in_memory["shop/v1/prelude.rs"].contains("pub use super::shelf_service_controller::ShelfServiceController;"));
+    assert!(in_memory["shop/v1/tags.rs"].contains("    ShelfService,\n"));
+    assert!(in_memory.contains_key("shop/v1/errors.rs"));
+    assert!(!in_memory.contains_key(REGISTRATION_FILENAME));
+}
+
+/// Test that models whose fields can all default implement `Default`
+#[test]
+fn test_model_default_generation() {
+    let proto_content = r#"
+syntax = "proto2";
+
+package example.v1;
+
+message ListUsersRequest {
+  optional int32 page_size = 1;
+  optional string page_token = 2;
+  repeated string tags = 3;
+  optional bool include_deleted = 4;
+}
+
+message SearchUsersRequest {
+  optional int32 page_size = 1 [default = 50];
+  optional string query = 2 [default = "all"];
+  optional double min_score = 3 [default = 0.5];
+  optional bool exact = 4;
+}
+
+message UpdateUserRequest {
+  optional User user = 1;
+  optional string etag = 2;
+}
+
+message User {
+  optional string id = 1;
+}
+"#;
+
+    let parser = NomProtoParser::new();
+    let proto_file = parser.parse_content(proto_content).expect("Failed to parse proto content");
+    let list = proto_file.find_message("ListUsersRequest").unwrap();
+
+    // Defaults are opt-in
+    let model = PoemOpenApiGenerator::new().generate_model(list).expect("Failed to generate model");
+    assert!(!model.content.contains("Default"));
+
+    let config = ConfigBuilder::new()
+        .generate_defaults(true)
+        .build_unchecked();
+    let generator = PoemOpenApiGenerator::with_config(config.generator);
+
+    let model = generator.generate_model(list).expect("Failed to generate model");
+    assert!(model.content.contains("poem_openapi::Object, Default)]"), "Missing Default derive:\n{}", model.content);
+    assert!(!model.content.contains("impl Default"));
+
+    let search = proto_file.find_message("SearchUsersRequest").unwrap();
+    let model = generator.generate_model(search).expect("Failed to generate model");
+    assert!(!model.content.contains(", Default)]"));
+    assert!(model.content.contains("impl Default for SearchUsersRequest"), "Missing Default impl:\n{}", model.content);
+    assert!(model.content.contains("page_size: 50,"));
+    assert!(model.content.contains("query: \"all\".to_string(),"));
+    assert!(model.content.contains("min_score: 0.5,"));
+    assert!(model.content.contains("exact: Default::default(),"));
+
+    let update = proto_file.find_message("UpdateUserRequest").unwrap();
+    let model = generator.generate_model(update).expect("Failed to generate model");
+    assert!(!model.content.contains(", Default)]") && !model.content.contains("impl Default"));
+    assert!(model.content.contains("Does not implement `Default`: `user` has no default value."), "Missing note:\n{}", model.content);
+}
+
+/// Test that configured handler attributes are emitted on the generated controller functions
+#[test]
+fn test_handler_attributes() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package user.v1;
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+  rpc DeleteUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      delete: "/v1/users/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .handler_attribute("#[tracing::instrument(skip(self))]")
+        .handler_attribute_for("^Delete", "allow(deprecated)")
+        .build()
+        .expect("Handler attribute config should be valid");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert_eq!(controller.matches("#[tracing::instrument(skip(self))]").count(), 2, "{}", controller);
+    assert_eq!(controller.matches("#[allow(deprecated)]").count(), 1, "{}", controller);
+    let delete = controller.find("async fn delete_user").unwrap();
+    assert!(controller[..delete].rfind("#[allow(deprecated)]").unwrap() > controller.find("async fn get_user").unwrap());
+
+    let invalid = ConfigBuilder::new()
+        .handler_attribute_for("(", "#[tracing::instrument]")
+        .build();
+    assert!(invalid.is_err(), "An invalid method pattern should be rejected");
+}
+
+/// Test that a path parameter bound to an enum of an imported package resolves to that enum
+#[test]
+fn test_cross_package_enum_path_parameter() {
+    let common_proto = r#"
+syntax = "proto3";
+
+package common.v1;
+
+enum Region {
+  REGION_UNSPECIFIED = 0;
+  REGION_EU = 1;
+}
+"#;
+
+    let service_proto = r#"
+syntax = "proto3";
+
+package shop.v1;
+
+import "google/api/annotations.proto";
+import "common.proto";
+
+service ShopService {
+  rpc GetRegionStats(GetRegionStatsRequest) returns (RegionStats) {
+    option (google.api.http) = {
+      get: "/v1/regions/{region}/stats"
+    };
+  }
+}
+
+message GetRegionStatsRequest {
+  common.v1.Region region = 1;
+}
+
+message RegionStats {
+  int64 shops = 1;
+}
+"#;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    std::fs::write(temp_dir.path().join("common.proto"), common_proto).expect("Failed to write proto file");
+    let service_path = temp_dir.path().join("service.proto");
+    std::fs::write(&service_path, service_proto).expect("Failed to write proto file");
+
+    let config = ConfigBuilder::new()
+        .add_include_path(temp_dir.path())
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+
+    let (all_valid, reports) = coordinator.validate_directory(temp_dir.path()).expect("Validation failed to run");
+    assert!(all_valid, "Qualified enum reference should resolve: {:?}", reports);
+
+    let result = coordinator.process_file(&service_path).expect("Failed to process service proto");
+    assert_eq!(result.routes[0].path_parameters[0].param_type, ParameterType::Enum("Region".to_string()));
+
+    let controller = &result.generated_files["shop_service_controller.rs"].content;
+    assert!(controller.contains("region: Path<Region>"), "Path parameter should use the enum:\n{}", controller);
+    assert!(controller.contains("use super::{Region, RegionStats};") || controller.contains("use super::{RegionStats, Region};"),
+        "Enum should be imported from the models module:\n{}", controller);
+}
+
+/// Test negotiating JSON or protobuf responses for annotated methods
+#[test]
+fn test_content_negotiation() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service ReportService {
+    rpc GetReport(GetReportRequest) returns (Report) {
+        option (content_negotiation) = true;
+        option (google.api.http) = {
+            get: "/v1/reports/{id}"
+        };
+    }
+
+    rpc GetSummary(GetReportRequest) returns (Summary) {
+        option (google.api.http) = {
+            get: "/v1/reports/{id}/summary"
+        };
+    }
+}
+
+message GetReportRequest {
+    string id = 1;
+}
+
+message Report {
+    string title = 1;
+    repeated int64 scores = 2;
+    map<string, string> labels = 3;
+    bytes attachment = 4;
+}
+
+message Summary {
+    string text = 1;
+}
+"#;
+
+    // The annotation has no effect unless negotiation is enabled
+    let coordinator = ProtoHttpCoordinator::new();
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+    let controller = &result.generated_files["report_service_controller.rs"].content;
+    assert!(controller.contains("-> poem_openapi::payload::Json<Report>"));
+    assert!(!controller.contains("NegotiatedResponse"));
+
+    let config = ConfigBuilder::new()
+        .content_negotiation(true)
+        .build_unchecked();
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let result = coordinator.process_content(proto_content).expect("Failed to process proto content");
+
+    let controller = &result.generated_files["report_service_controller.rs"].content;
+    assert!(controller.contains("#[oai(name = \"Accept\")] accept: poem_openapi::param::Header<Option<String>>"), "Missing Accept header:\n{}", controller);
+    assert!(controller.contains("-> GetReportNegotiatedResponse"));
+    assert!(controller.contains("Json(poem_openapi::payload::Json<Report>)"));
+    assert!(controller.contains("Protobuf(poem_openapi::payload::Binary<Vec<u8>>)"));
+    assert!(controller.contains("content_type = \"application/x-protobuf\""));
+    assert!(controller.contains("prost::Message::encode_to_vec(&result)"));
+    // Media ranges are weighed, so `application/x-protobuf;q=0` refuses protobuf
+    assert!(controller.contains("if accept.0.as_deref().is_some_and(prefers_protobuf) {"));
+    assert!(controller.contains("fn accept_weight(accept: &str, media_type: &str) -> Option<f32> {"));
+    assert!(controller.contains("protobuf > 0.0 && accept_weight(accept, \"application/json\")"));
+
+    // Methods without the annotation keep their plain JSON response
+    assert!(controller.contains("-> poem_openapi::payload::Json<Summary>"));
+    assert!(!controller.contains("GetSummaryNegotiatedResponse"));
+
+    // The models encoded as protobuf derive prost::Message
+    let generator = PoemOpenApiGenerator::with_config(ConfigBuilder::new().content_negotiation(true).build_unchecked().generator);
+    let report = generator.generate_model(result.proto_file.find_message("Report").unwrap())
+        .expect("Failed to generate model");
+    assert!(report.content.contains("#[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize, poem_openapi::Object, prost::Message)]"),
+        "Report should derive prost::Message:\n{}", report.content);
+    for field in [
+        "#[prost(string, tag = \"1\")]\n    pub title: String,",
+        "#[prost(int64, repeated, tag = \"2\")]\n    pub scores: Vec<i64>,",
+        "#[prost(map = \"string, string\", tag = \"3\")]\n    pub labels: std::collections::HashMap<String, String>,",
+        "#[prost(bytes = \"vec\", tag = \"4\")]\n    pub attachment: Vec<u8>,",
+    ] {
+        assert!(report.content.contains(field), "Missing {}:\n{}", field, report.content);
+    }
+
+    // Negotiated responses whose model has no protobuf encoding are rejected
+    let timestamped = proto_content.replace("bytes attachment = 4;", "google.protobuf.Timestamp created_at = 4;");
+    let error = ProtoHttpCoordinator::with_config(ConfigBuilder::new().content_negotiation(true).build_unchecked())
+        .process_content(&timestamped)
+        .expect_err("Report cannot be encoded as protobuf");
+    assert!(error.to_string().contains("ReportService.GetReport negotiates protobuf responses"), "Unexpected error: {}", error);
+    assert!(error.to_string().contains("`created_at` has no protobuf encoding"), "Unexpected error: {}", error);
+    let report = generator.generate_model(ProtoHttpCoordinator::new().process_content(&timestamped).unwrap().proto_file.find_message("Report").unwrap())
+        .expect("Failed to generate model");
+    assert!(report.content.contains("/// Does not implement `prost::Message`: `created_at` has no protobuf encoding."));
+    assert!(!report.content.contains("#[prost("));
+}
+
+/// Test declaring templated servers with file options
+#[test]
+fn test_api_servers() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+option (myapi.server) = {
+  url: "https://{region}.api.example.com/{version}"
+  description: "Regional endpoint"
+  variables: { name: "region" default: "us-east" description: "Deployment region" }
+  variables: { name: "version" default: "v1" }
+};
+option (myapi.server) = "http://localhost:3000";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User) {
+    option (google.api.http) = {
+      get: "/v1/users/{id}"
+    };
+  }
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  string id = 1;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .generate_registration_helper(true)
+        .build_unchecked();
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+    assert_eq!(result.api_metadata.servers, vec![
+        ApiServer {
+            url: "https://{region}.api.example.com/{version}".to_string(),
+            description: Some("Regional endpoint".to_string()),
+            variables: vec![
+                ServerVariable {
+                    name: "region".to_string(),
+                    default: "us-east".to_string(),
+                    description: Some("Deployment region".to_string()),
+                },
+                ServerVariable {
+                    name: "version".to_string(),
+                    default: "v1".to_string(),
+                    description: None,
+                },
+            ],
+        },
+        ApiServer {
+            url: "http://localhost:3000".to_string(),
+            description: None,
+            variables: Vec::new(),
+        },
+    ]);
+
+    let spec = PoemOpenApiGenerator::new().generate_openapi_spec(&result.proto_file, &result.routes);
+    assert_eq!(spec["servers"][0]["url"], "https://{region}.api.example.com/{version}");
+    assert_eq!(spec["servers"][0]["description"], "Regional endpoint");
+    assert_eq!(spec["servers"][0]["variables"]["region"]["default"], "us-east");
+    assert_eq!(spec["servers"][0]["variables"]["region"]["description"], "Deployment region");
+    assert_eq!(spec["servers"][0]["variables"]["version"]["default"], "v1");
+    assert_eq!(spec["servers"][1], serde_json::json!({ "url": "http://localhost:3000" }));
+
+    let metadata = &result.generated_files[API_METADATA_FILENAME].content;
+    assert!(metadata.contains(r#"pub const API_SERVERS: &[&str] = &["https://us-east.api.example.com/v1", "http://localhost:3000"];"#), "Missing servers:\n{}", metadata);
+    assert!(metadata.contains(r#".server(poem_openapi::ServerObject::new("https://us-east.api.example.com/v1").description("Regional endpoint"))"#));
+    assert!(metadata.contains(r#".server(poem_openapi::ServerObject::new("http://localhost:3000"))"#));
+}
+
+/// Test threading shared state through the handlers into the service traits
+#[test]
+fn test_shared_state() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{id}"
+        };
+    }
+
+    rpc CreateUser(User) returns (User) {
+        option (google.api.http) = {
+            post: "/v1/users"
+            body: "*"
+        };
+    }
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    assert!(!result.generated_files["user_service_controller.rs"].content.contains("Data<"));
+    assert!(!result.generated_files["user_service_service.rs"].content.contains("state"));
+
+    let config = ConfigBuilder::new()
+        .generate_shared_state(true)
+        .generate_mocks(true)
+        .generate_tests(true)
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert_eq!(controller.matches("state: poem::web::Data<&AppState>,").count(), 2, "Missing state extractor:\n{}", controller);
+    assert_eq!(controller.matches("state.0,").count(), 2);
+
+    let service = &result.generated_files["user_service_service.rs"].content;
+    assert_eq!(service.matches("state: &AppState,").count(), 2, "Missing state parameter:\n{}", service);
+    let mock = &result.generated_files["user_service_mock.rs"].content;
+    assert_eq!(mock.matches("_state: &AppState,").count(), 2);
+    let tests = &result.generated_files["user_service_tests.rs"].content;
+    assert!(tests.contains("poem::EndpointExt::data(poem::Route::new().nest(\"/\", api), <AppState>::default())"), "Missing test state:\n{}", tests);
+
+    // The state type is configurable
+    let config = ConfigBuilder::new()
+        .generate_shared_state(true)
+        .shared_state_type("crate::state::Shared")
+        .build_unchecked();
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+    assert!(result.generated_files["user_service_controller.rs"].content.contains("state: poem::web::Data<&crate::state::Shared>,"));
+
+    let error = ConfigBuilder::new()
+        .generate_shared_state(true)
+        .shared_state_type(" ")
+        .build()
+        .unwrap_err();
+    assert!(error.to_string().contains("generator.shared_state_type"));
+}
+
+/// Test that the registration helper nests controllers beyond the supported tuple arity
+#[test]
+fn test_registration_helper_nests_many_services() {
+    let mut proto_content = String::from("syntax = \"proto3\";\n\npackage test.v1;\n\nmessage Empty {}\n");
+    for index in 0..20 {
+        proto_content.push_str(&format!(r#"
+service Service{index} {{
+    rpc Ping(Empty) returns (Empty) {{
+        option (google.api.http) = {{
+            get: "/v1/service{index}/ping"
+        }};
+    }}
+}}
+"#));
+    }
+
+    let config = ConfigBuilder::new()
+        .generate_registration_helper(true)
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(&proto_content)
+        .expect("Failed to process proto content");
+
+    // 20 controllers exceed poem-openapi's 16-element tuples, so they are split in two
+    let apis = &result.generated_files[REGISTRATION_FILENAME].content;
+    let first: Vec<String> = (0..16).map(|index| format!("Service{index}Controller<Service{index}Impl>")).collect();
+    let second: Vec<String> = (16..20).map(|index| format!("Service{index}Controller<Service{index}Impl>")).collect();
+    assert!(
+        apis.contains(&format!(") -> (({}), ({}))", first.join(", "), second.join(", "))),
+        "Controllers should be nested:\n{}",
+        apis
+    );
+    assert!(apis.contains("        (Service16Controller::new(service16), Service17Controller::new(service17), Service18Controller::new(service18), Service19Controller::new(service19)),"));
+
+    // Groups nest again when they outnumber the arity themselves
+    let config = ConfigBuilder::new()
+        .generate_registration_helper(true)
+        .max_controller_tuple_arity(4)
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(&proto_content)
+        .expect("Failed to process proto content");
+    let apis = &result.generated_files[REGISTRATION_FILENAME].content;
+    assert!(
+        apis.contains("pub type AllControllers<Service0Impl, ") && apis.contains("> = (((Service0Controller<Service0Impl>, "),
+        "Controllers should be nested twice:\n{}",
+        apis
+    );
+    assert!(apis.contains("Service15Controller<Service15Impl>)), (Service16Controller<Service16Impl>, "));
+
+    let error = ConfigBuilder::new()
+        .max_controller_tuple_arity(17)
+        .build()
+        .unwrap_err();
+    assert!(error.to_string().contains("generator.max_controller_tuple_arity"));
+}
+
+/// Test that wrapper-typed fields become optional scalars
+#[test]
+fn test_wrapper_type_fields() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/protobuf/wrappers.proto";
+
+message Product {
+  google.protobuf.Int32Value stock = 1;
+  google.protobuf.StringValue nickname = 2;
+  repeated google.protobuf.DoubleValue prices = 3;
+}
+"#;
+
+    let proto_file = NomProtoParser::new().parse_content(proto_content).expect("Failed to parse proto");
+    let product = proto_file.find_message("Product").unwrap();
+    assert!(product.fields[0].field_type.to_type_reference().is_well_known_type());
+    assert_eq!(product.fields[0].field_type.to_type_reference().wrapped_scalar(), Some("int32"));
+
+    let model = PoemOpenApiGenerator::new().generate_model(product).expect("Failed to generate model");
+    assert!(model.content.contains("pub stock: Option<i32>,"), "Missing wrapper mapping:\n{}", model.content);
+    assert!(model.content.contains("pub nickname: Option<String>,"));
+    assert!(model.content.contains("pub prices: Vec<Option<f64>>,"));
+
+    let spec = PoemOpenApiGenerator::new().generate_openapi_spec(&proto_file, &[]);
+    assert_eq!(
+        spec["components"]["schemas"]["Product"]["properties"]["stock"],
+        serde_json::json!({ "type": "integer", "format": "int32", "nullable": true })
+    );
+}
+
+/// Test marking generated structs and enums `#[non_exhaustive]`
+#[test]
+fn test_non_exhaustive_types() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{id}"
+        };
+    }
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+
+    let proto_file = NomProtoParser::new().parse_content(proto_content).expect("Failed to parse proto");
+    let user = proto_file.find_message("User").unwrap();
+    let model = PoemOpenApiGenerator::new().generate_model(user).expect("Failed to generate model");
+    assert!(!model.content.contains("#[non_exhaustive]"));
+
+    let config = ConfigBuilder::new()
+        .non_exhaustive(true)
+        .generate_tags_enum(true)
+        .generate_error_module(true)
+        .build()
+        .expect("Invalid config");
+
+    let model = PoemOpenApiGenerator::with_config(config.generator.clone()).generate_model(user)
+        .expect("Failed to generate model");
+    assert!(model.content.contains("#[non_exhaustive]\npub struct User {"), "Missing attribute:\n{}", model.content);
+
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+    let tags = &result.generated_files[TAGS_FILENAME].content;
+    assert!(tags.contains("#[non_exhaustive]\npub enum Tags {"), "Missing attribute:\n{}", tags);
+    let errors = &result.generated_files[ERRORS_FILENAME].content;
+    assert!(errors.contains("#[non_exhaustive]\npub enum ApiError {"), "Missing attribute:\n{}", errors);
+}
+
+/// Test that external type mappings replace references to imported types
+#[test]
+fn test_external_type_mappings() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "third/party/thing.proto";
+
+service ThingService {
+    rpc CreateThing(third.party.Thing) returns (ThingReceipt) {
+        option (google.api.http) = {
+            post: "/v1/things"
+            body: "*"
+        };
+    }
+}
+
+message ThingReceipt {
+    string id = 1;
+    third.party.Thing thing = 2;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .external_type_mapping("third.party.Thing", "crate::external::Thing")
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config.clone()).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let controller = &result.generated_files["thing_service_controller.rs"].content;
+    assert!(controller.contains("body: Json<crate::external::Thing>"), "Body should use the mapped path:\n{}", controller);
+    assert!(controller.contains("use super::{ThingReceipt};"), "Mapped types should not be imported as models:\n{}", controller);
+    let service = &result.generated_files["thing_service_service.rs"].content;
+    assert!(service.contains("request: crate::external::Thing"), "Service trait should use the mapped path:\n{}", service);
+
+    let proto_file = NomProtoParser::new().parse_content(proto_content).expect("Failed to parse proto");
+    let model = PoemOpenApiGenerator::with_config(config.generator).generate_model(proto_file.find_message("ThingReceipt").unwrap())
+        .expect("Failed to generate model");
+    assert!(model.content.contains("pub thing: crate::external::Thing,"), "Field should use the mapped path:\n{}", model.content);
+}
+
+/// Test that handlers parse typed parameters themselves and answer 400 on failure
+#[test]
+fn test_validated_parameters() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service BookService {
+    rpc GetBook(GetBookRequest) returns (Book) {
+        option (google.api.http) = {
+            get: "/v1/books/{name}/copies/{copy_count}"
+        };
+    }
+}
+
+message GetBookRequest {
+    string name = 1;
+    int32 copy_count = 2;
+    int32 limit = 3;
+}
+
+message Book {
+    string name = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    let controller = &result.generated_files["book_service_controller.rs"].content;
+    assert!(controller.contains("copy_count: Path<i32>,"));
+    assert!(!controller.contains("ParseFromParameter"));
+
+    let config = ConfigBuilder::new()
+        .generate_error_module(true)
+        .validate_parameters(true)
+        .query_param_inference(QueryParamInference::AllMethods)
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let controller = &result.generated_files["book_service_controller.rs"].content;
+    assert!(controller.contains("copy_count: Path<String>,"), "Integer path parameter should arrive as a string:\n{}", controller);
+    assert!(controller.contains("name: Path<String>,"));
+    assert!(controller.contains(
+        "let copy_count = match <i32 as poem_openapi::types::ParseFromParameter>::parse_from_parameter(&copy_count.0) {"
+    ), "Missing parse:\n{}", controller);
+    assert!(controller.contains(
+        r#"Err(_) => return Err(ApiError::BadRequest(poem_openapi::payload::PlainText("invalid path parameter `copy_count`".to_string()))),"#
+    ));
+    // String parameters need no parsing
+    assert!(!controller.contains("let name = match"));
+    // Optional query parameters are parsed when present
+    assert!(controller.contains("limit: Query<Option<String>>,"), "Integer query parameter should arrive as a string:\n{}", controller);
+    assert!(controller.contains(
+        "let limit = match limit.0.as_deref().map(<i32 as poem_openapi::types::ParseFromParameter>::parse_from_parameter).transpose() {"
+    ));
+    // The parsed values reach the service unchanged
+    assert!(controller.contains("copy_count.0,"));
+
+    let error = ConfigBuilder::new()
+        .validate_parameters(true)
+        .build()
+        .unwrap_err();
+    assert!(error.to_string().contains("generator.validate_parameters"));
+}
+
+/// Test configuring the visibility of generated model fields
+#[test]
+fn test_field_visibility() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+message User {
+    string id = 1;
+    repeated string roles = 2;
+}
+"#;
+
+    let proto_file = NomProtoParser::new().parse_content(proto_content).expect("Failed to parse proto");
+    let user = proto_file.find_message("User").unwrap();
+    let generate = |visibility: FieldVisibility| {
+        let config = ConfigBuilder::new()
+            .field_visibility(visibility)
+            .build()
+            .expect("Invalid config");
+        PoemOpenApiGenerator::with_config(config.generator).generate_model(user)
+            .expect("Failed to generate model")
+            .content
+    };
+
+    let model = generate(FieldVisibility::Public);
+    assert!(model.contains("    pub id: String,"));
+
+    let model = generate(FieldVisibility::Crate);
+    assert!(model.contains("    pub(crate) id: String,"), "Fields should be crate-visible:\n{}", model);
+    assert!(model.contains("    pub(crate) roles: Vec<String>,"));
+    assert!(!model.contains("pub fn id("));
+
+    let model = generate(FieldVisibility::PrivateWithAccessors);
+    assert!(model.contains("    id: String,") && !model.contains("pub id"), "Fields should be private:\n{}", model);
+    assert!(model.contains("    pub fn id(&self) -> &String {\n        &self.id\n    }"), "Missing getter:\n{}", model);
+    assert!(model.contains("    pub fn roles(&self) -> &Vec<String> {"));
+    assert!(model.contains("    pub fn set_roles(&mut self, value: Vec<String>) {\n        self.roles = value;\n    }"), "Missing setter:\n{}", model);
+}
+
+/// Test that a service-level HTTP base path prefixes every method route
+#[test]
+fn test_service_http_base_path() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package library.v1;
+
+import "google/api/annotations.proto";
+
+service LibraryService {
+    option (google.api.http) = { base_path: "/v1/library/" };
+
+    rpc GetShelf(GetShelfRequest) returns (Shelf) {
+        option (google.api.http) = {
+            get: "/shelves/{shelf}"
+        };
+    }
+
+    rpc CreateShelf(CreateShelfRequest) returns (Shelf) {
+        option (google.api.http) = {
+            post: "/shelves"
+            body: "*"
+        };
+    }
+}
+
+message GetShelfRequest {
+    string shelf = 1;
+}
+
+message CreateShelfRequest {
+    string theme = 1;
+}
+
+message Shelf {
+    string name = 1;
+    string theme = 2;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let paths: Vec<&str> = result.routes.iter().map(|route| route.path_template.as_str()).collect();
+    assert_eq!(paths, vec!["/v1/library/shelves/{shelf}", "/v1/library/shelves"]);
+    assert_eq!(result.routes[0].path_parameters[0].name, "shelf");
+
+    let controller = &result.generated_files["library_service_controller.rs"].content;
+    assert!(controller.contains(r#"#[oai(path = "/v1/library/shelves/{shelf}", method = "get")]"#), "Route should be under the base path:\n{}", controller);
+    assert!(controller.contains(r#"#[oai(path = "/v1/library/shelves", method = "post")]"#));
+}
+
+/// Test generating service traits with native async methods instead of `#[async_trait]`
+#[test]
+fn test_native_async_trait() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{user_id}"
+        };
+    }
+}
+
+message GetUserRequest {
+    string user_id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .use_native_async_trait(true)
+        .generate_mocks(true)
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let service = &result.generated_files["user_service_service.rs"];
+    assert!(!service.content.contains("#[async_trait]"), "Trait should not use the macro:\n{}", service.content);
+    assert!(!service.content.contains("use async_trait::async_trait;"));
+    assert!(
+        service.content.contains("    fn get_user(") && !service.content.contains("async fn"),
+        "Trait methods should return futures directly:\n{}", service.content
+    );
+    assert!(service.content.contains(") -> impl std::future::Future<Output = Result<User, Box<dyn std::error::Error>>> + Send;"));
+    assert!(!service.dependencies.contains(&"async-trait".to_string()));
+
+    let mock = &result.generated_files["user_service_mock.rs"].content;
+    assert!(!mock.contains("async_trait"), "Mock should not use the macro:\n{}", mock);
+    assert!(mock.contains("    async fn get_user("));
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    let service = &result.generated_files["user_service_service.rs"].content;
+    assert!(service.contains("#[async_trait]\npub trait UserServiceService"));
+}
+
+/// Test that an explicit query parameter declaration replaces inference
+#[test]
+fn test_declared_query_params() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package library.v1;
+
+import "google/api/annotations.proto";
+
+service LibraryService {
+    rpc ListBooks(ListBooksRequest) returns (ListBooksResponse) {
+        option (google.api.http) = {
+            get: "/v1/books"
+        };
+        option (myapi.query_params) = "status, limit";
+    }
+}
+
+message ListBooksRequest {
+    string status = 1;
+    int32 limit = 2;
+    string filter = 3;
+    int32 page = 4;
+}
+
+message ListBooksResponse {
+    repeated string books = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let params: Vec<(&str, &ParameterType)> = result.routes[0].query_parameters.iter()
+        .map(|param| (param.name.as_str(), &param.param_type))
+        .collect();
+    assert_eq!(params, vec![("status", &ParameterType::String), ("limit", &ParameterType::Integer)]);
+}
+
+/// Test the generated `routes()` inventory of each controller
+#[test]
+fn test_route_inventory() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{user_id}"
+        };
+    }
+
+    rpc CreateUser(User) returns (User) {
+        option (google.api.http) = {
+            post: "/v1/users"
+            body: "*"
+        };
+    }
+}
+
+message GetUserRequest {
+    string user_id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    assert!(!result.generated_files.contains_key(ROUTE_INFO_FILENAME));
+    assert!(!result.generated_files["user_service_controller.rs"].content.contains("fn routes()"));
+
+    let config = ConfigBuilder::new()
+        .generate_route_inventory(true)
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+
+    let route_info = &result.generated_files[ROUTE_INFO_FILENAME].content;
+    assert!(route_info.contains("pub struct RouteInfo {"), "Missing RouteInfo type:\n{}", route_info);
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(controller.contains("use super::route_info::RouteInfo;"));
+    assert!(controller.contains("    pub fn routes() -> Vec<RouteInfo> {"), "Missing routes():\n{}", controller);
+    for route in &result.routes {
+        let entry = format!(
+            r#"RouteInfo {{ method: "{}", path: "{}", operation_id: "{}" }},"#,
+            route.http_method.as_str().to_uppercase(), route.path_template, route.operation_id()
+        );
+        assert!(controller.contains(&entry), "Missing entry {}:\n{}", entry, controller);
+    }
+    assert!(controller.contains(r#"RouteInfo { method: "GET", path: "/v1/users/{user_id}", operation_id: "UserService_GetUser" },"#));
+}
+
+/// Test that versions of a package generate into separate module directories
+#[test]
+fn test_package_to_module() {
+    let proto_content = |version: &str| format!(r#"
+syntax = "proto3";
+
+package foo.{version};
+
+import "google/api/annotations.proto";
+
+service UserService {{
+    rpc GetUser(GetUserRequest) returns (User) {{
+        option (google.api.http) = {{
+            get: "/{version}/users/{{user_id}}"
+        }};
+    }}
+}}
+
+message GetUserRequest {{
+    string user_id = 1;
+}}
+
+message User {{
+    string id = 1;
+}}
+"#);
+
+    let config = ConfigBuilder::new()
+        .package_to_module(true)
+        .models_module_path("crate::models")
+        .build()
+        .expect("Invalid config");
+    let coordinator = ProtoHttpCoordinator::with_config(config);
+    let v1 = coordinator.process_content(&proto_content("v1")).expect("Failed to process v1");
+    let v2 = coordinator.process_content(&proto_content("v2")).expect("Failed to process v2");
+
+    let controller = &v1.generated_files["foo/v1/user_service_controller.rs"].content;
+    assert!(controller.contains("use crate::models::foo::v1::{User};"), "Models should come from the package module:\n{}", controller);
+    assert!(controller.contains("use super::user_service_service::UserServiceService;"));
+    let service = &v2.generated_files["foo/v2/user_service_service.rs"].content;
+    assert!(service.contains("use crate::models::foo::v2::{User};"), "Models should come from the package module:\n{}", service);
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    coordinator.write_generated_code(&v1, temp_dir.path()).expect("Failed to write v1");
+    coordinator.write_generated_code(&v2, temp_dir.path()).expect("Failed to write v2");
+    for version in ["v1", "v2"] {
+        let written = std::fs::read_to_string(temp_dir.path().join("foo").join(version).join("user_service_controller.rs"))
+            .expect("Missing controller");
+        assert!(written.contains(&format!("\"/{}/users/{{user_id}}\"", version)));
+    }
+
+    // Relative model paths already resolve from the package module
+    let config = ConfigBuilder::new()
+        .package_to_module(true)
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(&proto_content("v1"))
+        .expect("Failed to process v1");
+    assert!(result.generated_files["foo/v1/user_service_controller.rs"].content.contains("use super::{User};"));
+
+    let result = ProtoHttpCoordinator::new().process_content(&proto_content("v1"))
+        .expect("Failed to process v1");
+    assert!(result.generated_files.contains_key("user_service_controller.rs"));
+}
+
+/// Test that editions files parse and generate like proto3 files
+#[test]
+fn test_editions_file() {
+    let proto_content = r#"
+edition = "2023";
+
+package test.v1;
+
+import "google/api/annotations.proto";
+
+option features.field_presence = EXPLICIT;
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{user_id}"
+        };
+    }
+}
+
+message GetUserRequest {
+    string user_id = 1;
+}
+
+message User {
+    string id = 1;
+    string nickname = 2 [features.field_presence = IMPLICIT];
+    repeated string roles = 3;
+}
+"#;
+
+    let proto_file = NomProtoParser::new().parse_content(proto_content).expect("Failed to parse editions file");
+    assert_eq!(proto_file.syntax, ProtocolVersion::Edition("2023".to_string()));
+    let user = proto_file.find_message("User").unwrap();
+    assert_eq!(user.fields.len(), 3);
+    assert_eq!(user.fields[0].label, FieldLabel::Optional);
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process editions file");
+    assert_eq!(result.proto_file.services.len(), 1);
+    assert_eq!(result.routes.len(), 1);
+    assert_eq!(result.routes[0].path_template, "/v1/users/{user_id}");
+    assert!(result.generated_files.contains_key("user_service_controller.rs"));
+}
+
+/// Test collecting the model types a service's routes import
+#[test]
+fn test_service_message_types() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "google/api/annotations.proto";
+import "google/protobuf/empty.proto";
+
+service UserService {
+    rpc CreateUser(CreateUserRequest) returns (User) {
+        option (google.api.http) = {
+            post: "/v1/users"
+            body: "*"
+        };
+    }
+
+    rpc GetUser(GetUserRequest) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{user_id}"
+        };
+    }
+
+    rpc DeleteUser(GetUserRequest) returns (google.protobuf.Empty) {
+        option (google.api.http) = {
+            delete: "/v1/users/{user_id}"
+        };
+    }
+}
+
+message CreateUserRequest {
+    string name = 1;
+}
+
+message GetUserRequest {
+    string user_id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+
+    let result = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect("Failed to process proto content");
+    let service = &result.proto_file.services[0];
+
+    let message_types = PoemOpenApiGenerator::new().message_types(service, &result.routes);
+    assert_eq!(message_types, vec!["CreateUserRequest".to_string(), "User".to_string()]);
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(controller.contains("use super::{CreateUserRequest, User};"), "Missing model import:\n{}", controller);
+}
+
+/// Test splitting a controller with many operations across files
+#[test]
+fn test_max_operations_per_file() {
+    let methods: String = ["Alpha", "Beta", "Gamma", "Delta", "Epsilon"].iter()
+        .map(|name| format!(r#"
+    rpc Get{name}(GetItemRequest) returns (Item) {{
+        option (google.api.http) = {{
+            get: "/v1/{path}/{{item_id}}"
+        }};
+    }}
+"#, name = name, path = name.to_lowercase()))
+        .collect();
+    let proto_content = format!(r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "google/api/annotations.proto";
+
+service ItemService {{{methods}}}
+
+message GetItemRequest {{
+    string item_id = 1;
+}}
+
+message Item {{
+    string id = 1;
+}}
+"#);
+
+    let config = ConfigBuilder::new()
+        .max_operations_per_file(2)
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(&proto_content)
+        .expect("Failed to process proto content");
+    assert!(!result.generated_files.contains_key("item_service_controller_4.rs"));
+
+    // The parts together serve every route, at most two each
+    let mut served = 0;
+    for number in 1..=3 {
+        let part = &result.generated_files[&format!("item_service_controller_{}.rs", number)].content;
+        assert!(part.contains(&format!("pub struct ItemServiceControllerPart{}<T: ItemServiceService> {{", number)), "Missing part struct:\n{}", part);
+        assert!(part.contains("    pub(super) service: Arc<T>,"));
+        let operations = part.matches("#[oai(path = ").count();
+        assert!(operations <= 2, "Too many operations in part {}:\n{}", number, part);
+        served += operations;
+    }
+    assert_eq!(served, result.routes.len());
+    for route in &result.routes {
+        let attribute = format!(r#"#[oai(path = "{}", method = "get")]"#, route.path_template);
+        assert!(result.generated_files.values().any(|file| file.content.contains(&attribute)), "Route {} not served", route.path_template);
+    }
+
+    // A single controller delegates to its parts
+    let controller = &result.generated_files["item_service_controller.rs"].content;
+    assert!(controller.contains("pub struct ItemServiceController<T: ItemServiceService> {"), "Missing controller:\n{}", controller);
+    assert!(controller.contains("use super::item_service_controller_3::ItemServiceControllerPart3;"));
+    assert!(controller.contains(
+        "type ItemServiceControllerParts<T> = (ItemServiceControllerPart1<T>, (ItemServiceControllerPart2<T>, ItemServiceControllerPart3<T>));"
+    ), "Parts should nest in pairs:\n{}", controller);
+    assert!(controller.contains("impl<T: ItemServiceService + Send + Sync + 'static> poem_openapi::OpenApi for ItemServiceController<T> {"));
+    assert!(!controller.contains("#[oai(path"));
+
+    // Services within the cap keep a single file
+    let config = ConfigBuilder::new()
+        .max_operations_per_file(5)
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(&proto_content)
+        .expect("Failed to process proto content");
+    assert!(!result.generated_files.contains_key("item_service_controller_1.rs"));
+    assert_eq!(result.generated_files["item_service_controller.rs"].content.matches("#[oai(path = ").count(), 5);
+}
+
+/// Test declaring custom request body media types on the generated payloads
+#[test]
+fn test_request_body_content_type() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+    rpc CreateUser(User) returns (User) {
+        option (google.api.http) = {
+            post: "/v1/users"
+            body: "*"
+        };
+        option (request_content_type) = "application/x-protobuf";
+    }
+
+    rpc PatchUser(PatchUserRequest) returns (User) {
+        option (google.api.http) = {
+            patch: "/v1/users/{user_id}"
+            body: "user"
+        };
+        option (request_content_type) = "application/merge-patch+json";
+    }
+
+    rpc ReplaceUser(PatchUserRequest) returns (User) {
+        option (google.api.http) = {
+            put: "/v1/users/{user_id}"
+            body: "user"
+        };
+    }
+}
+
+message PatchUserRequest {
+    string user_id = 1;
+    User user = 2;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+
+    let config = ConfigBuilder::new()
+        .generate_error_module(true)
+        .build()
+        .expect("Invalid config");
+    let result = ProtoHttpCoordinator::with_config(config).process_content(proto_content)
+        .expect("Failed to process proto content");
+    assert_eq!(result.routes[0].request_body.as_ref().unwrap().content_type, "application/x-protobuf");
+
+    let controller = &result.generated_files["user_service_controller.rs"].content;
+    assert!(controller.contains("body: CreateUserRequestBody,"), "Body should use the request enum:\n{}", controller);
+    assert!(controller.contains(
+        "    #[oai(content_type = \"application/x-protobuf\")]\n    Protobuf(poem_openapi::payload::Binary<Vec<u8>>),"
+    ), "Missing protobuf media type:\n{}", controller);
+    assert!(controller.contains("let CreateUserRequestBody::Protobuf(body) = body;"));
+    assert!(controller.contains("let body = match <User as prost::Message>::decode(body.0.as_slice()) {"));
+    assert!(controller.contains(
+        "    #[oai(content_type = \"application/merge-patch+json\")]\n    Body(poem_openapi::payload::Json<User>),"
+    ), "Missing JSON-based media type:\n{}", controller);
+    assert!(controller.contains("let PatchUserRequestBody::Body(body) = body;"));
+    // JSON bodies keep the plain payload
+    assert!(controller.contains("body: Json<User>,"));
+    assert!(!controller.contains("ReplaceUserRequestBody"));
+
+    let spec = PoemOpenApiGenerator::new().generate_openapi_spec(&result.proto_file, &result.routes);
+    assert!(spec["paths"]["/v1/users"]["post"]["requestBody"]["content"]["application/x-protobuf"].is_object());
+
+    // Without the error module a malformed protobuf body could not be answered with a 400
+    let error = ProtoHttpCoordinator::new().process_content(proto_content)
+        .expect_err("Protobuf bodies need the error module");
+    assert!(error.to_string().contains("UserService.CreateUser"), "Unexpected error: {}", error);
+    assert!(error.to_string().contains("generate_error_module"), "Unexpected error: {}", error);
+
+    let json_only = proto_content.replace("option (request_content_type) = \"application/x-protobuf\";", "");
+    let controller = &ProtoHttpCoordinator::new().process_content(&json_only)
+        .expect("Failed to process proto content")
+        .generated_files["user_service_controller.rs"].content;
+    assert!(controller.contains("let PatchUserRequestBody::Body(body) = body;"));
+    assert!(!controller.contains("decode("), "Request bodies must not be decoded by panicking:\n{}", controller);
+}
@@ -40,6 +40,30 @@ fn test_plugin_config_builder() {
     assert_eq!(config.settings.len(), 3);
 }
 
+#[test]
+fn test_plugin_config_typed_settings() {
+    let config = PluginConfigBuilder::new()
+        .number_setting("max_path_depth", 2)
+        .setting("name", "validator")
+        .build();
+    
+    assert_eq!(config.get_typed::<usize>("max_path_depth").unwrap(), Some(2));
+    assert_eq!(config.get_typed::<String>("name").unwrap(), Some("validator".to_string()));
+    assert_eq!(config.get_typed::<usize>("missing").unwrap(), None);
+    
+    let mut validator = RestApiValidator::new();
+    assert!(validator.initialize(&config).is_ok());
+    
+    // A non-numeric value is reported instead of silently falling back to the default
+    let config = PluginConfigBuilder::new()
+        .setting("max_path_depth", "deep")
+        .build();
+    
+    let error = validator.initialize(&config).unwrap_err();
+    assert!(matches!(&error, PluginError::ConfigurationError { message } if message.contains("max_path_depth")),
+        "Unexpected error: {}", error);
+}
+
 #[test]
 fn test_naming_convention_validator() {
     let validator = NamingConventionValidator::new();
@@ -112,6 +136,42 @@ fn test_coordinator_plugin_integration() {
     assert_eq!(coordinator.plugin_manager().plugins().len(), 1);
 }
 
+#[test]
+fn test_register_boxed_plugin_on_coordinator() {
+    let mut coordinator = ProtoHttpCoordinator::new();
+    
+    let result = coordinator.register_plugin(Box::new(NamingConventionValidator::new()));
+    assert!(result.is_ok());
+    assert!(coordinator.plugin_manager().get_plugin("naming_convention_validator").is_some());
+    
+    let bad_service = r#"
+syntax = "proto3";
+
+service user_service {
+    rpc GetUser(GetUserRequest) returns (User);
+}
+
+message GetUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+    
+    // The registered validator runs as part of processing and rejects the service name
+    match coordinator.process_content(bad_service) {
+        Err(ProtoHttpParserError::Validation(error)) => {
+            assert!(error.to_string().contains("user_service"), "Unexpected error: {}", error);
+        }
+        other => panic!("Expected a naming validation error, got: {:?}", other.map(|_| ())),
+    }
+    
+    let good_service = bad_service.replace("user_service", "UserService");
+    assert!(coordinator.process_content(&good_service).is_ok());
+}
+
 #[cfg(test)]
 mod integration_tests {
     use super::*;
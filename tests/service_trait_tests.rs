@@ -6,6 +6,93 @@
 use proto_http_parser::*;
 use proptest::prelude::*;
 
+// Helper functions for tests
+
+/// Convert string to snake_case
+fn to_snake_case(s: &str) -> String {
+    let mut result = String::new();
+    let mut prev_was_upper = false;
+    
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 && !prev_was_upper {
+                result.push('_');
+            }
+            result.push(c.to_lowercase().next().unwrap());
+            prev_was_upper = true;
+        } else {
+            result.push(c);
+            prev_was_upper = false;
+        }
+    }
+    
+    result
+}
+
+/// Convert string to PascalCase
+fn to_pascal_case(s: &str) -> String {
+    // If the string contains underscores, split on them
+    if s.contains('_') {
+        let words: Vec<&str> = s.split('_').collect();
+        words.iter()
+            .map(|word| capitalize_first(word))
+            .collect::<String>()
+    } else {
+        // If no underscores, assume it's already camelCase or PascalCase
+        // Just ensure the first letter is capitalized while preserving the rest
+        capitalize_first_preserve_case(s)
+    }
+}
+
+/// Capitalize first letter of a string
+fn capitalize_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
+    }
+}
+
+/// Capitalize first letter while preserving the rest of the string case
+fn capitalize_first_preserve_case(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+    }
+}
+
+/// Map Protocol Buffer types to Rust types
+fn map_proto_type_to_rust(proto_type: &str) -> String {
+    match proto_type {
+        "string" => "String".to_string(),
+        "int32" | "sint32" | "sfixed32" => "i32".to_string(),
+        "int64" | "sint64" | "sfixed64" => "i64".to_string(),
+        "uint32" | "fixed32" => "u32".to_string(),
+        "uint64" | "fixed64" => "u64".to_string(),
+        "double" => "f64".to_string(),
+        "float" => "f32".to_string(),
+        "bool" => "bool".to_string(),
+        "bytes" => "Vec<u8>".to_string(),
+        "google.protobuf.Timestamp" => "chrono::DateTime<chrono::Utc>".to_string(),
+        "google.protobuf.Duration" => "std::time::Duration".to_string(),
+        "google.protobuf.Empty" => "()".to_string(),
+        _ => proto_type.to_string(), // Custom types remain as-is
+    }
+}
+
+/// Map parameter types to Rust types
+fn map_parameter_type_to_rust(param_type: &ParameterType) -> String {
+    match param_type {
+        ParameterType::String => "String".to_string(),
+        ParameterType::Integer => "i32".to_string(),
+        ParameterType::Float => "f64".to_string(),
+        ParameterType::Boolean => "bool".to_string(),
+        ParameterType::Timestamp => "chrono::DateTime<chrono::Utc>".to_string(),
+        ParameterType::Enum(type_name) | ParameterType::Custom(type_name) => type_name.clone(),
+    }
+}
+
 /// Test that service trait generation maintains consistency with controller requirements
 #[cfg(test)]
 mod service_trait_property_tests {
@@ -178,6 +265,7 @@ mod service_trait_property_tests {
                     methods,
                     options: vec![],
                     comments: vec![],
+                    source: None,
                 }
             })
     }
@@ -211,6 +299,8 @@ mod service_trait_property_tests {
                     options: vec![],
                     comments: vec![],
                     http_annotation: None,
+                    idempotency_level: None,
+                    method_signature: vec![],
                 }
             })
     }
@@ -218,7 +308,7 @@ mod service_trait_property_tests {
     /// Generate valid type references
     fn valid_type_reference_generator() -> impl Strategy<Value = TypeReference> {
         "[a-zA-Z][a-zA-Z0-9_]*"
-            .prop_map(|name| TypeReference::new(name))
+            .prop_map(TypeReference::new)
     }
 
     /// Generate valid HTTP routes
@@ -247,6 +337,12 @@ mod service_trait_property_tests {
                 request_body: None,
                 input_type: TypeReference::new("Empty".to_string()),
                 response_type,
+                idempotency_level: None,
+                is_paginated: false,
+                deprecation: None,
+                segments: Vec::new(),
+                rate_limit: None,
+                security: None,
             }
         })
     }
@@ -317,6 +413,12 @@ mod service_trait_property_tests {
                 request_body: None,
                 input_type: method.input_type.clone(),
                 response_type: method.output_type.clone(),
+                idempotency_level: None,
+                is_paginated: false,
+                deprecation: None,
+                segments: Vec::new(),
+                rate_limit: None,
+                security: None,
             };
             routes.push(route);
         }
@@ -324,89 +426,3 @@ mod service_trait_property_tests {
         routes
     }
 }
-
-// Helper functions for tests
-
-/// Convert string to snake_case
-fn to_snake_case(s: &str) -> String {
-    let mut result = String::new();
-    let mut prev_was_upper = false;
-    
-    for (i, c) in s.chars().enumerate() {
-        if c.is_uppercase() {
-            if i > 0 && !prev_was_upper {
-                result.push('_');
-            }
-            result.push(c.to_lowercase().next().unwrap());
-            prev_was_upper = true;
-        } else {
-            result.push(c);
-            prev_was_upper = false;
-        }
-    }
-    
-    result
-}
-
-/// Convert string to PascalCase
-fn to_pascal_case(s: &str) -> String {
-    // If the string contains underscores, split on them
-    if s.contains('_') {
-        let words: Vec<&str> = s.split('_').collect();
-        words.iter()
-            .map(|word| capitalize_first(word))
-            .collect::<String>()
-    } else {
-        // If no underscores, assume it's already camelCase or PascalCase
-        // Just ensure the first letter is capitalized while preserving the rest
-        capitalize_first_preserve_case(s)
-    }
-}
-
-/// Capitalize first letter of a string
-fn capitalize_first(s: &str) -> String {
-    let mut chars = s.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
-    }
-}
-
-/// Capitalize first letter while preserving the rest of the string case
-fn capitalize_first_preserve_case(s: &str) -> String {
-    let mut chars = s.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-    }
-}
-
-/// Map Protocol Buffer types to Rust types
-fn map_proto_type_to_rust(proto_type: &str) -> String {
-    match proto_type {
-        "string" => "String".to_string(),
-        "int32" | "sint32" | "sfixed32" => "i32".to_string(),
-        "int64" | "sint64" | "sfixed64" => "i64".to_string(),
-        "uint32" | "fixed32" => "u32".to_string(),
-        "uint64" | "fixed64" => "u64".to_string(),
-        "double" => "f64".to_string(),
-        "float" => "f32".to_string(),
-        "bool" => "bool".to_string(),
-        "bytes" => "Vec<u8>".to_string(),
-        "google.protobuf.Timestamp" => "chrono::DateTime<chrono::Utc>".to_string(),
-        "google.protobuf.Duration" => "std::time::Duration".to_string(),
-        "google.protobuf.Empty" => "()".to_string(),
-        _ => proto_type.to_string(), // Custom types remain as-is
-    }
-}
-
-/// Map parameter types to Rust types
-fn map_parameter_type_to_rust(param_type: &ParameterType) -> String {
-    match param_type {
-        ParameterType::String => "String".to_string(),
-        ParameterType::Integer => "i32".to_string(),
-        ParameterType::Float => "f64".to_string(),
-        ParameterType::Boolean => "bool".to_string(),
-        ParameterType::Custom(type_name) => type_name.clone(),
-    }
-}
\ No newline at end of file
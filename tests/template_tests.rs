@@ -7,6 +7,62 @@ use proto_http_parser::*;
 use proptest::prelude::*;
 use std::collections::HashMap;
 
+// Helper functions for tests (re-implementing from templates.rs for testing)
+
+/// Convert string to snake_case
+fn to_snake_case(s: &str) -> String {
+    let mut result = String::new();
+    let mut prev_was_upper = false;
+    
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 && !prev_was_upper {
+                result.push('_');
+            }
+            result.push(c.to_lowercase().next().unwrap());
+            prev_was_upper = true;
+        } else {
+            result.push(c);
+            prev_was_upper = false;
+        }
+    }
+    
+    result
+}
+
+/// Convert string to camelCase
+fn to_camel_case(s: &str) -> String {
+    let words: Vec<&str> = s.split('_').collect();
+    let mut result = String::new();
+    
+    for (i, word) in words.iter().enumerate() {
+        if i == 0 {
+            result.push_str(&word.to_lowercase());
+        } else {
+            result.push_str(&capitalize_first(word));
+        }
+    }
+    
+    result
+}
+
+/// Convert string to PascalCase
+fn to_pascal_case(s: &str) -> String {
+    let words: Vec<&str> = s.split('_').collect();
+    words.iter()
+        .map(|word| capitalize_first(word))
+        .collect::<String>()
+}
+
+/// Capitalize first letter of a string
+fn capitalize_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
+    }
+}
+
 /// Test that template rendering is consistent and handles all valid inputs correctly
 #[cfg(test)]
 mod template_property_tests {
@@ -24,7 +80,7 @@ mod template_property_tests {
             routes in valid_routes_generator(),
             custom_data in valid_custom_data_generator()
         ) {
-            let mut engine = HandlebarsTemplateEngine::new();
+            let engine = HandlebarsTemplateEngine::new();
             
             // Create template context
             let mut context_data = custom_data;
@@ -55,7 +111,7 @@ mod template_property_tests {
                 
                 // Should contain service name in some form (except for imports template)
                 if template_name != "imports" {
-                    let service_name_variations = vec![
+                    let service_name_variations = [
                         service.name.clone(),
                         to_snake_case(&service.name),
                         to_pascal_case(&service.name),
@@ -179,6 +235,7 @@ mod template_property_tests {
                     methods,
                     options: vec![],
                     comments: vec![],
+                    source: None,
                 }
             })
     }
@@ -194,6 +251,8 @@ mod template_property_tests {
                     options: vec![],
                     comments: vec![],
                     http_annotation: None,
+                    idempotency_level: None,
+                    method_signature: vec![],
                 }
             })
     }
@@ -201,7 +260,7 @@ mod template_property_tests {
     /// Generate valid type references
     fn valid_type_reference_generator() -> impl Strategy<Value = TypeReference> {
         "[a-zA-Z][a-zA-Z0-9]*"
-            .prop_map(|name| TypeReference::new(name))
+            .prop_map(TypeReference::new)
     }
 
     /// Generate valid HTTP routes
@@ -230,6 +289,12 @@ mod template_property_tests {
                 request_body: None,
                 input_type: TypeReference::new("Empty".to_string()),
                 response_type,
+                idempotency_level: None,
+                is_paginated: false,
+                deprecation: None,
+                segments: Vec::new(),
+                rate_limit: None,
+                security: None,
             }
         })
     }
@@ -324,59 +389,3 @@ mod template_property_tests {
         ]
     }
 }
-
-// Helper functions for tests (re-implementing from templates.rs for testing)
-
-/// Convert string to snake_case
-fn to_snake_case(s: &str) -> String {
-    let mut result = String::new();
-    let mut prev_was_upper = false;
-    
-    for (i, c) in s.chars().enumerate() {
-        if c.is_uppercase() {
-            if i > 0 && !prev_was_upper {
-                result.push('_');
-            }
-            result.push(c.to_lowercase().next().unwrap());
-            prev_was_upper = true;
-        } else {
-            result.push(c);
-            prev_was_upper = false;
-        }
-    }
-    
-    result
-}
-
-/// Convert string to camelCase
-fn to_camel_case(s: &str) -> String {
-    let words: Vec<&str> = s.split('_').collect();
-    let mut result = String::new();
-    
-    for (i, word) in words.iter().enumerate() {
-        if i == 0 {
-            result.push_str(&word.to_lowercase());
-        } else {
-            result.push_str(&capitalize_first(word));
-        }
-    }
-    
-    result
-}
-
-/// Convert string to PascalCase
-fn to_pascal_case(s: &str) -> String {
-    let words: Vec<&str> = s.split('_').collect();
-    words.iter()
-        .map(|word| capitalize_first(word))
-        .collect::<String>()
-}
-
-/// Capitalize first letter of a string
-fn capitalize_first(s: &str) -> String {
-    let mut chars = s.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
-    }
-}
\ No newline at end of file
@@ -1,8 +1,9 @@
 //! Integration tests for the validation engine
 
 use proto_http_parser::*;
+use proto_http_parser::validation::ValidationConfig;
 use proto_http_parser::core::{
-    data::{ProtoFile, Service, RpcMethod, TypeReference, Message, Field, FieldType, FieldLabel, HttpRoute, HttpMethod},
+    data::{ProtoFile, ProtocolVersion, Service, RpcMethod, TypeReference, Message, Field, FieldType, FieldLabel, HttpRoute, HttpMethod, Enum, EnumValue},
     errors::ValidationError,
     Validator,
 };
@@ -59,12 +60,18 @@ fn test_validation_engine_with_valid_proto() {
                 label: FieldLabel::Optional,
                 options: vec![],
                 comments: vec![],
+                packed: false,
+                behaviors: vec![],
             }
         ],
         nested_messages: vec![],
         nested_enums: vec![],
         options: vec![],
         comments: vec![],
+        is_group: false,
+        reserved_ranges: vec![],
+        reserved_names: vec![],
+        source: None,
     };
     
     proto_file.messages.push(message);
@@ -149,6 +156,46 @@ fn test_http_route_validation() {
     assert_eq!(conflict_errors.len(), 1);
 }
 
+#[test]
+fn test_http_route_conflicts_ignore_parameter_names() {
+    let mut engine = ValidationEngine::new();
+    
+    let routes = vec![
+        HttpRoute::new(
+            "TestService".to_string(),
+            "GetById".to_string(),
+            HttpMethod::Get,
+            "/users/{id}/posts/{post_id}".to_string(),
+        ),
+        HttpRoute::new(
+            "TestService".to_string(),
+            "GetByName".to_string(),
+            HttpMethod::Get,
+            "/users/{name}/posts/{title=*}".to_string(), // Same structure, other names
+        ),
+        HttpRoute::new(
+            "TestService".to_string(),
+            "DeleteByName".to_string(),
+            HttpMethod::Delete,
+            "/users/{name}/posts/{title}".to_string(), // Other method, no conflict
+        ),
+    ];
+    
+    let result = engine.validate_http_routes_internal(&routes);
+    
+    assert!(!result.is_valid);
+    let conflicts: Vec<_> = result.errors.iter()
+        .filter_map(|e| match e {
+            ValidationError::ConflictingRoutes { route1, route2 } => Some((route1.as_str(), route2.as_str())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(conflicts, vec![(
+        "GET /users/{id}/posts/{post_id} (TestService.GetById)",
+        "GET /users/{name}/posts/{title=*} (TestService.GetByName)",
+    )]);
+}
+
 #[test]
 fn test_path_template_validation() {
     let engine = ValidationEngine::new();
@@ -182,6 +229,10 @@ fn test_error_suggestion_generation() {
         nested_enums: vec![],
         options: vec![],
         comments: vec![],
+        is_group: false,
+        reserved_ranges: vec![],
+        reserved_names: vec![],
+        source: None,
     };
     
     proto_file.messages.push(message);
@@ -227,4 +278,382 @@ fn test_validator_trait_implementation() {
     assert!(report.errors.is_empty());
     assert!(report.warnings.is_empty());
     assert!(report.suggestions.is_empty());
-}
\ No newline at end of file
+}
+#[test]
+fn test_proto3_enum_zero_value() {
+    let enum_with_values = |values: &[(&str, i32)]| Enum {
+        name: "Status".to_string(),
+        values: values.iter().map(|(name, number)| EnumValue {
+            name: name.to_string(),
+            number: *number,
+            options: Vec::new(),
+            comments: Vec::new(),
+        }).collect(),
+        options: Vec::new(),
+        comments: Vec::new(),
+        source: None,
+    };
+    let engine = ValidationEngine::new();
+    
+    // proto3 enums must start at 0
+    let mut proto_file = ProtoFile::new();
+    proto_file.enums.push(enum_with_values(&[("STATUS_ACTIVE", 1), ("STATUS_INACTIVE", 2)]));
+    
+    let report = engine.validate_proto_file(&proto_file).unwrap();
+    assert!(!report.is_valid);
+    assert!(report.errors.iter().any(|e| matches!(e, ValidationError::InvalidEnum { enum_name, .. } if enum_name == "Status")));
+    
+    // proto2 does not require a zero value
+    proto_file.syntax = ProtocolVersion::Proto2;
+    let report = engine.validate_proto_file(&proto_file).unwrap();
+    assert!(report.is_valid);
+    
+    // A zero value without the _UNSPECIFIED convention only warns
+    let mut proto_file = ProtoFile::new();
+    proto_file.enums.push(enum_with_values(&[("STATUS_ACTIVE", 0), ("STATUS_INACTIVE", 1)]));
+    
+    let report = engine.validate_proto_file(&proto_file).unwrap();
+    assert!(report.is_valid);
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].category, WarningCategory::Style);
+    
+    let mut proto_file = ProtoFile::new();
+    proto_file.enums.push(enum_with_values(&[("STATUS_UNSPECIFIED", 0), ("STATUS_ACTIVE", 1)]));
+    
+    let report = engine.validate_proto_file(&proto_file).unwrap();
+    assert!(report.is_valid);
+    assert!(report.warnings.is_empty());
+}
+
+#[test]
+fn test_warnings_as_errors() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+import "google/protobuf/timestamp.proto";
+
+message User {
+    string name = 1;
+}
+"#;
+    
+    // The unused import is only a warning by default
+    let coordinator = ProtoHttpCoordinator::new();
+    assert!(coordinator.process_content(proto_content).is_ok());
+    
+    let proto_file = NomProtoParser::new().parse_content(proto_content).unwrap();
+    let report = ValidationEngine::new().validate_proto_file(&proto_file).unwrap();
+    assert!(report.is_valid);
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].category, WarningCategory::Unused);
+    
+    let config = ValidationConfig {
+        warnings_as_errors: true,
+        ..ValidationConfig::default()
+    };
+    
+    let report = ValidationEngine::with_config(config.clone()).validate_proto_file(&proto_file).unwrap();
+    assert!(!report.is_valid);
+    assert!(report.warnings.is_empty());
+    assert!(matches!(&report.errors[0], ValidationError::PromotedWarning { message } if message.contains("google/protobuf/timestamp.proto")));
+    
+    let mut coordinator = ProtoHttpCoordinator::new();
+    coordinator.set_validation_config(config);
+    let result = coordinator.process_content(proto_content);
+    assert!(matches!(result, Err(ProtoHttpParserError::Validation(ValidationError::PromotedWarning { .. }))));
+}
+
+#[test]
+fn test_field_number_range() {
+    let engine = ValidationEngine::new();
+    let proto_file_with_number = |number: u32| {
+        let mut proto_file = ProtoFile::new();
+        proto_file.messages.push(Message {
+            name: "User".to_string(),
+            fields: vec![
+                Field {
+                    name: "id".to_string(),
+                    field_type: FieldType::String,
+                    number,
+                    label: FieldLabel::Optional,
+                    options: vec![],
+                    comments: vec![],
+                    packed: false,
+                    behaviors: vec![],
+                }
+            ],
+            nested_messages: vec![],
+            nested_enums: vec![],
+            options: vec![],
+            comments: vec![],
+            is_group: false,
+            reserved_ranges: vec![],
+            reserved_names: vec![],
+            source: None,
+        });
+        proto_file
+    };
+    
+    for number in [0, 19000, 19999, 536_870_912] {
+        let report = engine.validate_proto_file(&proto_file_with_number(number)).unwrap();
+        assert!(!report.is_valid, "Field number {} should be rejected", number);
+        assert!(report.errors.iter().any(|e| matches!(
+            e,
+            ValidationError::InvalidFieldNumber { message_name, field_name, number: n, .. }
+                if message_name == "User" && field_name == "id" && *n == number
+        )));
+    }
+    
+    for number in [1, 18999, 20000, 536_870_911] {
+        let report = engine.validate_proto_file(&proto_file_with_number(number)).unwrap();
+        assert!(report.is_valid, "Field number {} should be accepted: {:?}", number, report.errors);
+    }
+}
+
+#[test]
+fn test_reserved_field_numbers() {
+    let proto_content = r#"
+syntax = "proto3";
+
+message User {
+    reserved 100 to max;
+    string id = 1;
+    string nickname = 150;
+}
+"#;
+    
+    let proto_file = NomProtoParser::new().parse_content(proto_content).unwrap();
+    let report = ValidationEngine::new().validate_proto_file(&proto_file).unwrap();
+    
+    assert!(!report.is_valid);
+    assert_eq!(report.errors.len(), 1);
+    assert!(matches!(
+        &report.errors[0],
+        ValidationError::InvalidFieldNumber { field_name, number: 150, reason, .. }
+            if field_name == "nickname" && reason.contains("reserved 100 to max")
+    ));
+}
+
+#[test]
+fn test_missing_request_body_suggestion() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc CreateUser(CreateUserRequest) returns (User) {
+        option (google.api.http) = {
+            post: "/v1/orgs/{org_id}/users"
+        };
+    }
+    rpc ArchiveUser(ArchiveUserRequest) returns (User) {
+        option (google.api.http) = {
+            post: "/v1/users/{id}:archive"
+        };
+    }
+}
+
+message CreateUserRequest {
+    string org_id = 1;
+    string name = 2;
+    string email = 3;
+}
+
+message ArchiveUserRequest {
+    string id = 1;
+}
+
+message User {
+    string id = 1;
+}
+"#;
+    
+    let proto_file = NomProtoParser::new().parse_content(proto_content).unwrap();
+    let report = ValidationEngine::new().validate_proto_file(&proto_file).unwrap();
+    
+    // Only the request with fields outside the path is flagged
+    assert!(report.is_valid);
+    assert_eq!(report.warnings.len(), 1, "Unexpected warnings: {:?}", report.warnings);
+    assert!(report.warnings[0].message.contains("UserService.CreateUser"));
+    assert!(report.warnings[0].message.contains("(name, email)"));
+    
+    assert_eq!(report.suggestions.len(), 1);
+    assert_eq!(report.suggestions[0].issue_type, "MissingRequestBody");
+    assert_eq!(report.suggestions[0].suggested_fix.as_deref(), Some("body: \"*\""));
+}
+
+#[test]
+fn test_validation_report_merge() {
+    let valid_content = r#"
+syntax = "proto3";
+
+import "google/protobuf/timestamp.proto";
+
+message User {
+    string id = 1;
+}
+"#;
+    let invalid_content = r#"
+syntax = "proto3";
+
+message Order {
+    string id = 1;
+    string note = 19500;
+}
+"#;
+    
+    let engine = ValidationEngine::new();
+    let valid = engine.validate_proto_file(&NomProtoParser::new().parse_content(valid_content).unwrap()).unwrap();
+    let invalid = engine.validate_proto_file(&NomProtoParser::new().parse_content(invalid_content).unwrap()).unwrap();
+    assert!(valid.is_valid);
+    assert_eq!(valid.warnings.len(), 1);
+    assert!(!invalid.is_valid);
+    
+    let mut merged = valid.clone();
+    merged.merge(invalid.clone());
+    assert!(!merged.is_valid);
+    assert_eq!(merged.errors.len(), valid.errors.len() + invalid.errors.len());
+    assert_eq!(merged.warnings.len(), valid.warnings.len() + invalid.warnings.len());
+    assert_eq!(merged.suggestions.len(), valid.suggestions.len() + invalid.suggestions.len());
+    
+    // The coordinator merges the reports of a whole directory
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("user.proto"), valid_content).unwrap();
+    std::fs::write(temp_dir.path().join("order.proto"), invalid_content).unwrap();
+    let report = ProtoHttpCoordinator::new().validate_directory_merged(temp_dir.path()).unwrap();
+    assert!(!report.is_valid);
+    assert_eq!(report.errors.len(), invalid.errors.len());
+    assert_eq!(report.warnings.len(), 1);
+}
+
+#[test]
+fn test_enum_option_value_suggestion() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package test.v1;
+
+service UserService {
+    rpc GetUser(GetUserRequest) returns (User) {
+        option idempotency_level = IDEMPOTNT;
+    }
+    rpc ListUsers(GetUserRequest) returns (User) {
+        option idempotency_level = NO_SIDE_EFFECTS;
+    }
+}
+
+message GetUserRequest {
+    string id = 1 [(google.api.field_behavior) = REQUIRED, jstype = JS_STRNG];
+}
+
+message User {
+    string id = 1;
+}
+"#;
+    
+    let proto_file = NomProtoParser::new().parse_content(proto_content).unwrap();
+    let report = ValidationEngine::new().validate_proto_file(&proto_file).unwrap();
+    
+    assert!(!report.is_valid);
+    assert_eq!(report.errors.len(), 2, "Unexpected errors: {:?}", report.errors);
+    assert!(report.errors.iter().any(|error| matches!(error,
+        ValidationError::InvalidOptionValue { option, value, location }
+            if option == "idempotency_level" && value == "IDEMPOTNT" && location == "method UserService.GetUser")));
+    assert!(report.errors.iter().any(|error| matches!(error,
+        ValidationError::InvalidOptionValue { option, value, .. } if option == "jstype" && value == "JS_STRNG")));
+    
+    let fixes: Vec<_> = report.suggestions.iter()
+        .filter(|suggestion| suggestion.issue_type == "InvalidOptionValue")
+        .map(|suggestion| suggestion.suggested_fix.as_deref())
+        .collect();
+    assert_eq!(fixes, vec![Some("JS_STRING"), Some("IDEMPOTENT")]);
+}
+
+#[test]
+fn test_proto_file_validate() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+message User {
+    string id = 1;
+}
+
+message Account {
+    string id = 1;
+}
+
+message User {
+    string name = 1;
+}
+"#;
+    
+    let proto_file = NomProtoParser::new().parse_content(proto_content).unwrap();
+    let report = proto_file.validate(&ValidationConfig::default());
+    
+    assert!(!report.is_valid);
+    assert_eq!(report.errors.len(), 1, "Unexpected errors: {:?}", report.errors);
+    assert!(matches!(&report.errors[0], ValidationError::DuplicateDefinition { name, .. } if name == "User"));
+    
+    let proto_file = NomProtoParser::new().parse_content(&proto_content.replace("message User {\n    string name = 1;\n}", "")).unwrap();
+    assert!(proto_file.validate(&ValidationConfig::default()).is_valid);
+}
+
+#[test]
+fn test_require_http_annotations() {
+    let proto_content = r#"
+syntax = "proto3";
+
+package example.v1;
+
+import "google/api/annotations.proto";
+
+service UserService {
+    rpc GetUser(User) returns (User) {
+        option (google.api.http) = {
+            get: "/v1/users/{id}"
+        };
+    }
+
+    rpc SyncUsers(User) returns (User);
+
+    rpc PurgeUsers(User) returns (User);
+
+    // Only reachable over gRPC
+    // @no_http
+    rpc ReindexUsers(User) returns (User);
+
+    rpc AuditUsers(User) returns (User) {
+        option (myapi.http_excluded) = true;
+    }
+}
+
+service InternalService {
+    rpc Ping(User) returns (User);
+}
+
+message User {
+    string id = 1;
+}
+"#;
+    
+    let proto_file = NomProtoParser::new().parse_content(proto_content).unwrap();
+    
+    // The check is opt-in
+    let report = ValidationEngine::new().validate_proto_file(&proto_file).unwrap();
+    assert!(report.warnings.is_empty(), "Unexpected warnings: {:?}", report.warnings);
+    
+    let config = ValidationConfig {
+        require_http_annotations: true,
+        ..ValidationConfig::default()
+    };
+    let report = ValidationEngine::with_config(config).validate_proto_file(&proto_file).unwrap();
+    assert!(report.is_valid);
+    assert_eq!(report.warnings.len(), 1, "Unexpected warnings: {:?}", report.warnings);
+    assert!(report.warnings[0].message.contains("Service UserService exposes methods over HTTP, but SyncUsers, PurgeUsers have no HTTP annotation"),
+        "Unexpected warning: {}", report.warnings[0].message);
+}